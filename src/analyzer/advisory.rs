@@ -0,0 +1,520 @@
+//! Security advisory database.
+//!
+//! Advisories are YAML files, one per advisory, kept in a local directory
+//! or a git mirror. Each one names an affected module or provider and the
+//! versions it affects:
+//!
+//! ```yaml
+//! id: TFSEC-2024-0001
+//! kind: module                      # module or provider
+//! source: terraform-aws-modules/vpc/aws
+//! title: Default security group allows all egress
+//! severity: error                   # info, warning, error or critical
+//! affected: [">= 3.0.0, < 5.1.0"]   # optional, defaults to all versions
+//! patched: [">= 5.1.0"]
+//! unaffected: ["< 3.0.0"]
+//! aliases: [CVE-2024-12345]
+//! url: https://example.com/advisories/TFSEC-2024-0001
+//! description: |
+//!   Longer explanation of the issue.
+//! ```
+//!
+//! A version is vulnerable when it matches one of `affected` and none of
+//! `patched` or `unaffected`. A module or provider is reported when its
+//! version constraint allows at least one vulnerable version; references
+//! without a constraint allow every version.
+
+use crate::analyzer::deprecation::{module_deprecation_keys, parse_severity};
+use crate::config::AdvisoryOptions;
+use crate::error::{MonPhareError, Result};
+use crate::git::GitClient;
+use crate::rule_sources::collect_files;
+use crate::types::{
+    Constraint, Finding, FindingCategory, Location, ModuleRef, ProviderRef, VersionSet,
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Default registry hostname, stripped from provider sources.
+const DEFAULT_PROVIDER_REGISTRY: &str = "registry.terraform.io/";
+
+/// Kind of resource an advisory applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AdvisoryKind {
+    /// A Terraform module
+    Module,
+    /// A Terraform provider
+    Provider,
+}
+
+/// A single security advisory, as stored on disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Advisory {
+    /// Unique advisory identifier
+    pub id: String,
+
+    /// Whether `source` is a module or a provider
+    pub kind: AdvisoryKind,
+
+    /// Affected module or provider source (e.g., `hashicorp/aws`)
+    pub source: String,
+
+    /// One-line summary
+    #[serde(default)]
+    pub title: String,
+
+    /// Detailed description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Severity ("info", "warning", "error" or "critical")
+    #[serde(default = "default_severity")]
+    pub severity: String,
+
+    /// Affected version ranges (any of them; all versions when empty)
+    #[serde(default)]
+    pub affected: Vec<String>,
+
+    /// Versions containing the fix
+    #[serde(default)]
+    pub patched: Vec<String>,
+
+    /// Versions never affected by the issue
+    #[serde(default)]
+    pub unaffected: Vec<String>,
+
+    /// Other identifiers for the same issue (e.g., CVE numbers)
+    #[serde(default)]
+    pub aliases: Vec<String>,
+
+    /// Link to the full advisory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Publication date
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<NaiveDate>,
+
+    /// Date the advisory was withdrawn; withdrawn advisories are ignored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawn: Option<NaiveDate>,
+}
+
+fn default_severity() -> String {
+    "error".to_string()
+}
+
+/// An advisory with its vulnerable versions resolved.
+#[derive(Debug, Clone)]
+struct LoadedAdvisory {
+    advisory: Advisory,
+    vulnerable: VersionSet,
+    origin: String,
+}
+
+/// A collection of advisories loaded from disk.
+#[derive(Debug, Clone, Default)]
+pub struct AdvisoryDatabase {
+    advisories: Vec<LoadedAdvisory>,
+}
+
+impl AdvisoryDatabase {
+    /// Open the database described by the configuration.
+    ///
+    /// Relative paths are resolved against `base_dir`. Returns an empty
+    /// database when no advisory source is configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the git mirror cannot be cloned or an advisory
+    /// file is invalid.
+    pub async fn open(
+        options: &AdvisoryOptions,
+        base_dir: &Path,
+        git_client: &GitClient,
+    ) -> Result<Self> {
+        match (&options.git, &options.path) {
+            (Some(url), path) => {
+                tracing::info!(url = %url, git_ref = ?options.git_ref, "Fetching advisory database");
+                let root = git_client
+                    .clone_repository_at(url, options.git_ref.as_deref())
+                    .await?;
+                Self::load_dir(&root.join(path.as_deref().unwrap_or(".")))
+            }
+            (None, Some(path)) => Self::load_dir(&base_dir.join(path)),
+            (None, None) => Ok(Self::default()),
+        }
+    }
+
+    /// Load every advisory file in a directory, recursively.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory does not exist, holds no advisory
+    /// files, or an advisory is invalid or duplicated.
+    pub fn load_dir(dir: &Path) -> Result<Self> {
+        let files = collect_files(dir, ".", "advisories.path")?;
+
+        let mut seen = HashSet::new();
+        let mut advisories = Vec::new();
+        for file in files {
+            let loaded = load_advisory(&file)?;
+            if !seen.insert(loaded.advisory.id.clone()) {
+                return Err(crate::err!(ConfigValue {
+                    key: "advisories".to_string(),
+                    message: format!(
+                        "duplicate advisory id '{}' in {}",
+                        loaded.advisory.id,
+                        file.display()
+                    ),
+                }));
+            }
+            if loaded.advisory.withdrawn.is_some() {
+                tracing::debug!(id = %loaded.advisory.id, "Skipping withdrawn advisory");
+                continue;
+            }
+            advisories.push(loaded);
+        }
+
+        tracing::info!(
+            path = %dir.display(),
+            advisories = advisories.len(),
+            "Advisory database loaded"
+        );
+
+        Ok(Self { advisories })
+    }
+
+    /// Number of active advisories.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.advisories.len()
+    }
+
+    /// Returns true if the database holds no active advisory.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.advisories.is_empty()
+    }
+}
+
+/// Parse one advisory file and resolve its vulnerable versions.
+fn load_advisory(path: &Path) -> Result<LoadedAdvisory> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| MonPhareError::io(path, e, file!(), line!()))?;
+    let advisory: Advisory = serde_yaml::from_str(&content).map_err(|e| {
+        crate::err!(ConfigParse {
+            message: format!("Invalid advisory file '{}': {e}", path.display()),
+            source: Some(Box::new(e)),
+        })
+    })?;
+
+    if advisory.id.trim().is_empty() || advisory.source.trim().is_empty() {
+        return Err(crate::err!(ConfigValue {
+            key: "advisories".to_string(),
+            message: format!("'{}' must set 'id' and 'source'", path.display()),
+        }));
+    }
+
+    let parse_all = |field: &str, ranges: &[String]| -> Result<VersionSet> {
+        ranges.iter().try_fold(VersionSet::empty(), |set, range| {
+            let constraint = Constraint::parse(range).map_err(|e| {
+                crate::err!(ConfigValue {
+                    key: format!("advisories.{}.{field}", advisory.id),
+                    message: format!("invalid version range '{range}': {e}"),
+                })
+            })?;
+            Ok(set.union(&VersionSet::from(&constraint)))
+        })
+    };
+
+    let affected = if advisory.affected.is_empty() {
+        VersionSet::all()
+    } else {
+        parse_all("affected", &advisory.affected)?
+    };
+    let safe = parse_all("patched", &advisory.patched)?
+        .union(&parse_all("unaffected", &advisory.unaffected)?);
+
+    Ok(LoadedAdvisory {
+        vulnerable: affected.intersect(&safe.complement()),
+        origin: path.display().to_string(),
+        advisory,
+    })
+}
+
+/// Match advisories against every module and provider.
+pub(crate) fn advisory_findings(
+    database: &AdvisoryDatabase,
+    modules: &[ModuleRef],
+    providers: &[ProviderRef],
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for module in modules.iter().filter(|m| !m.source.is_local()) {
+        let keys = module_deprecation_keys(&module.source);
+        for loaded in database.advisories.iter().filter(|a| {
+            a.advisory.kind == AdvisoryKind::Module && keys.contains(&a.advisory.source)
+        }) {
+            if !allows_vulnerable(module.version_constraint.as_ref(), &loaded.vulnerable) {
+                continue;
+            }
+            findings.push(advisory_finding(
+                loaded,
+                Affected {
+                    label: "Module",
+                    name: &module.name,
+                    resource: format!("module.{}", module.name),
+                    source: module.source.canonical_id(),
+                    constraint: module.version_constraint.as_ref(),
+                    location: Location {
+                        file: module.file_path.clone(),
+                        line: module.line_number,
+                        column: None,
+                        repository: module.repository.clone(),
+                    },
+                },
+            ));
+        }
+    }
+
+    for provider in providers {
+        let source = provider.qualified_source();
+        for loaded in database.advisories.iter().filter(|a| {
+            a.advisory.kind == AdvisoryKind::Provider
+                && provider_key(&a.advisory.source) == provider_key(&source)
+        }) {
+            if !allows_vulnerable(provider.version_constraint.as_ref(), &loaded.vulnerable) {
+                continue;
+            }
+            findings.push(advisory_finding(
+                loaded,
+                Affected {
+                    label: "Provider",
+                    name: &provider.name,
+                    resource: format!("provider.{}", provider.name),
+                    source: source.clone(),
+                    constraint: provider.version_constraint.as_ref(),
+                    location: Location {
+                        file: provider.file_path.clone(),
+                        line: provider.line_number,
+                        column: None,
+                        repository: provider.repository.clone(),
+                    },
+                },
+            ));
+        }
+    }
+
+    tracing::debug!(findings = findings.len(), "Advisory matching complete");
+    findings
+}
+
+/// The module or provider an advisory matched.
+struct Affected<'a> {
+    label: &'static str,
+    name: &'a str,
+    resource: String,
+    source: String,
+    constraint: Option<&'a Constraint>,
+    location: Location,
+}
+
+fn advisory_finding(loaded: &LoadedAdvisory, affected: Affected<'_>) -> Finding {
+    let advisory = &loaded.advisory;
+
+    let allowed = match affected.constraint {
+        Some(c) if !c.is_unconstrained() => format!("version constraint '{}' allows", c.raw),
+        _ => "has no version constraint and allows".to_string(),
+    };
+    let title = if advisory.title.is_empty() {
+        String::new()
+    } else {
+        format!(": {}", advisory.title)
+    };
+
+    let mut description = Vec::new();
+    if let Some(text) = &advisory.description {
+        description.push(text.trim().to_string());
+    }
+    if !advisory.aliases.is_empty() {
+        description.push(format!("Aliases: {}", advisory.aliases.join(", ")));
+    }
+    if let Some(url) = &advisory.url {
+        description.push(format!("More information: {url}"));
+    }
+
+    let suggestion = if advisory.patched.is_empty() {
+        "No patched version is available; consider replacing this dependency".to_string()
+    } else {
+        format!(
+            "Require a patched version: {}",
+            advisory.patched.join(" or ")
+        )
+    };
+
+    Finding {
+        code: "security-advisory".to_string(),
+        severity: parse_severity(&advisory.severity),
+        message: format!(
+            "{} '{}' {allowed} versions affected by {}{title}",
+            affected.label, affected.name, advisory.id
+        ),
+        description: (!description.is_empty()).then(|| description.join("\n")),
+        location: Some(affected.location),
+        related_locations: vec![],
+        suggestion: Some(suggestion),
+        category: FindingCategory::Security,
+        suppressed_by: None,
+        resource: Some(affected.resource),
+        source: Some(affected.source),
+        origin: Some(loaded.origin.clone()),
+    }
+}
+
+/// Check if a constraint allows at least one vulnerable version.
+fn allows_vulnerable(constraint: Option<&Constraint>, vulnerable: &VersionSet) -> bool {
+    let allowed = constraint.map_or_else(VersionSet::all, VersionSet::from);
+    !allowed.intersect(vulnerable).is_empty()
+}
+
+/// Normalize a provider source for comparison.
+fn provider_key(source: &str) -> String {
+    let source = source.to_ascii_lowercase();
+    source
+        .strip_prefix(DEFAULT_PROVIDER_REGISTRY)
+        .map_or_else(|| source.clone(), str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ModuleSource, Severity};
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    const VPC_ADVISORY: &str = r#"
+id: TFSEC-2024-0001
+kind: module
+source: terraform-aws-modules/vpc/aws
+title: Default security group allows all egress
+severity: critical
+affected: [">= 3.0.0"]
+patched: [">= 5.1.0"]
+aliases: [CVE-2024-12345]
+url: https://example.com/TFSEC-2024-0001
+"#;
+
+    const AWS_ADVISORY: &str = r#"
+id: TFSEC-2024-0002
+kind: provider
+source: registry.terraform.io/hashicorp/aws
+title: Credentials logged at debug level
+severity: warning
+patched: ["~> 4.67.1", ">= 5.2.0"]
+"#;
+
+    fn database() -> (tempfile::TempDir, AdvisoryDatabase) {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("modules")).unwrap();
+        std::fs::write(dir.path().join("modules/vpc.yaml"), VPC_ADVISORY).unwrap();
+        std::fs::write(dir.path().join("aws.yml"), AWS_ADVISORY).unwrap();
+        let database = AdvisoryDatabase::load_dir(dir.path()).unwrap();
+        (dir, database)
+    }
+
+    fn module(version: Option<&str>) -> ModuleRef {
+        ModuleRef {
+            name: "vpc".to_string(),
+            source: ModuleSource::Registry {
+                hostname: "registry.terraform.io".to_string(),
+                namespace: "terraform-aws-modules".to_string(),
+                name: "vpc".to_string(),
+                provider: "aws".to_string(),
+                subdir: None,
+                params: Default::default(),
+            },
+            version_constraint: version.map(|v| Constraint::parse(v).unwrap()),
+            file_path: PathBuf::from("main.tf"),
+            line_number: 1,
+            repository: None,
+            attributes: HashMap::new(),
+            dependencies: Default::default(),
+        }
+    }
+
+    fn provider(version: &str) -> ProviderRef {
+        ProviderRef {
+            name: "aws".to_string(),
+            source: Some("hashicorp/aws".to_string()),
+            version_constraint: Some(Constraint::parse(version).unwrap()),
+            file_path: PathBuf::from("versions.tf"),
+            line_number: 4,
+            repository: None,
+        }
+    }
+
+    #[test]
+    fn test_module_matching_uses_exact_intersection() {
+        let (_dir, database) = database();
+        assert_eq!(database.len(), 2);
+
+        let affected = advisory_findings(&database, &[module(Some("~> 5.0.0"))], &[]);
+        assert_eq!(affected.len(), 1);
+        let finding = &affected[0];
+        assert_eq!(finding.code, "security-advisory");
+        assert_eq!(finding.category, FindingCategory::Security);
+        assert_eq!(finding.severity, Severity::Critical);
+        assert!(finding.message.contains("TFSEC-2024-0001"));
+        assert!(finding
+            .description
+            .as_deref()
+            .unwrap()
+            .contains("CVE-2024-12345"));
+        assert!(finding.origin.as_deref().unwrap().ends_with("vpc.yaml"));
+
+        // Entirely patched, or entirely before the affected range
+        assert!(advisory_findings(&database, &[module(Some("~> 5.1"))], &[]).is_empty());
+        assert!(advisory_findings(&database, &[module(Some("< 3.0.0"))], &[]).is_empty());
+
+        // No constraint allows every version
+        assert_eq!(advisory_findings(&database, &[module(None)], &[]).len(), 1);
+    }
+
+    #[test]
+    fn test_provider_matching_honours_patched_ranges() {
+        let (_dir, database) = database();
+
+        assert_eq!(
+            advisory_findings(&database, &[], &[provider("~> 4.67.0")]).len(),
+            1
+        );
+        assert!(advisory_findings(&database, &[], &[provider("~> 4.67.1")]).is_empty());
+        assert!(advisory_findings(&database, &[], &[provider(">= 5.2.0, < 6.0.0")]).is_empty());
+        assert_eq!(
+            advisory_findings(&database, &[], &[provider(">= 5.0.0, != 5.1.0")]).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_withdrawn_and_invalid_advisories() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("old.yaml"),
+            format!("{VPC_ADVISORY}withdrawn: 2024-06-01\n"),
+        )
+        .unwrap();
+        assert!(AdvisoryDatabase::load_dir(dir.path()).unwrap().is_empty());
+
+        std::fs::write(
+            dir.path().join("bad.yaml"),
+            "id: X-1\nkind: module\nsource: a/b/c\naffected: [\"not a version\"]\n",
+        )
+        .unwrap();
+        assert!(AdvisoryDatabase::load_dir(dir.path()).is_err());
+    }
+}
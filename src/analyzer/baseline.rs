@@ -0,0 +1,402 @@
+//! Baseline files of known findings.
+//!
+//! A baseline records the findings present at some point in time so that CI
+//! can fail only on findings introduced afterwards. Findings are fingerprinted
+//! by repository, file, resource, code and source, never by line number, so
+//! unrelated edits to a file don't invalidate the baseline. Files are taken
+//! relative to the scanned directory, so the baseline keeps matching when
+//! the scan runs from another working directory.
+//!
+//! # Example
+//!
+//! ```bash
+//! # Record today's findings
+//! monphare scan ./terraform --baseline baseline.json --write-baseline
+//!
+//! # Later: only findings missing from baseline.json affect the exit code
+//! monphare scan ./terraform --baseline baseline.json --strict
+//! ```
+
+use crate::error::{MonPhareError, Result};
+use crate::types::{BaselineEntry, BaselineResult, Finding, ScanResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Current baseline file format version.
+const BASELINE_VERSION: u32 = 1;
+
+/// A set of known findings, as stored on disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Baseline {
+    /// File format version
+    pub version: u32,
+
+    /// When the baseline was written
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated_at: Option<DateTime<Utc>>,
+
+    /// Known findings
+    pub entries: Vec<BaselineEntry>,
+}
+
+impl Baseline {
+    /// Build a baseline from the given findings.
+    ///
+    /// Suppressed findings are skipped: they are already accepted inline.
+    /// `roots` are the scanned directories (see [`ScanResult::roots`]).
+    #[must_use]
+    pub fn from_findings(findings: &[Finding], roots: &[PathBuf]) -> Self {
+        let mut entries: Vec<_> = findings
+            .iter()
+            .filter(|f| !f.is_suppressed())
+            .map(|f| fingerprint(f, roots))
+            .collect();
+        entries.sort();
+
+        Self {
+            version: BASELINE_VERSION,
+            generated_at: Some(Utc::now()),
+            entries,
+        }
+    }
+
+    /// Load a baseline from a JSON file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, is not a valid baseline,
+    /// or uses an unsupported format version.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Err(crate::err!(FileNotFound {
+                path: path.to_path_buf(),
+            }));
+        }
+
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| MonPhareError::io(path, e, file!(), line!()))?;
+        let baseline: Self = serde_json::from_str(&content).map_err(|e| {
+            crate::err!(ConfigParse {
+                message: format!("Invalid baseline file '{}': {e}", path.display()),
+                source: Some(Box::new(e)),
+            })
+        })?;
+
+        if baseline.version != BASELINE_VERSION {
+            return Err(crate::err!(ConfigValue {
+                key: "baseline.version".to_string(),
+                message: format!(
+                    "unsupported baseline version {} (expected {BASELINE_VERSION})",
+                    baseline.version
+                ),
+            }));
+        }
+
+        tracing::debug!(
+            path = %path.display(),
+            entries = baseline.entries.len(),
+            "Baseline loaded"
+        );
+
+        Ok(baseline)
+    }
+
+    /// Write the baseline to a JSON file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization or writing fails.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| {
+            crate::err!(ReportGeneration {
+                message: format!("Failed to serialize baseline: {e}"),
+            })
+        })?;
+
+        std::fs::write(path, json + "\n").map_err(|e| MonPhareError::io(path, e, file!(), line!()))
+    }
+
+    /// Move known findings out of the analysis and record fixed entries.
+    ///
+    /// Each baseline entry accounts for at most one finding, so a second
+    /// occurrence of the same issue in the same file is still reported as new.
+    pub fn apply(&self, result: &mut ScanResult) {
+        let mut remaining: HashMap<&BaselineEntry, usize> = HashMap::new();
+        for entry in &self.entries {
+            *remaining.entry(entry).or_insert(0) += 1;
+        }
+
+        let mut known = Vec::new();
+        let mut new = Vec::new();
+        for finding in std::mem::take(&mut result.analysis.findings) {
+            if finding.is_suppressed() {
+                new.push(finding);
+                continue;
+            }
+            match remaining.get_mut(&fingerprint(&finding, &result.roots)) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    known.push(finding);
+                }
+                _ => new.push(finding),
+            }
+        }
+
+        let summary = &mut result.analysis.summary;
+        for finding in &known {
+            decrement(
+                &mut summary.findings_by_severity,
+                &finding.severity.to_string(),
+            );
+            decrement(
+                &mut summary.findings_by_category,
+                &finding.category.to_string(),
+            );
+        }
+
+        let mut fixed: Vec<BaselineEntry> = remaining
+            .into_iter()
+            .flat_map(|(entry, count)| std::iter::repeat_n(entry.clone(), count))
+            .collect();
+        fixed.sort();
+
+        tracing::info!(
+            new = new.len(),
+            known = known.len(),
+            fixed = fixed.len(),
+            "Compared findings against baseline"
+        );
+
+        result.analysis.findings = new;
+        result.baseline = Some(BaselineResult { known, fixed });
+    }
+}
+
+/// Compute the stable baseline fingerprint of a finding.
+///
+/// `roots` are the scanned directories; files are fingerprinted relative
+/// to the root that contains them.
+#[must_use]
+pub fn fingerprint(finding: &Finding, roots: &[PathBuf]) -> BaselineEntry {
+    let (repository, file) = match &finding.location {
+        Some(location) => (
+            location.repository.clone(),
+            relative_file(&location.file, location.repository.as_deref(), roots),
+        ),
+        None => (None, String::new()),
+    };
+
+    BaselineEntry {
+        code: finding.code.clone(),
+        repository,
+        file,
+        resource: finding.resource.clone(),
+        source: finding.source.clone(),
+    }
+}
+
+/// Path of a file relative to its repository root, with `/` separators.
+///
+/// Files under one of the scan `roots` are relative to the innermost root
+/// containing them, however the root was spelled on the command line.
+/// Otherwise, as for cloned repositories whose absolute cache paths differ
+/// between machines, everything up to and including the repository
+/// directory is dropped.
+pub(crate) fn relative_file(path: &Path, repository: Option<&str>, roots: &[PathBuf]) -> String {
+    if !roots.is_empty() {
+        let absolute = absolute_path(path);
+        let relative = roots
+            .iter()
+            .filter_map(|root| absolute.strip_prefix(root).ok())
+            .min_by_key(|rel| rel.components().count());
+        if let Some(relative) = relative {
+            return join_components(relative);
+        }
+    }
+
+    let components: Vec<String> = path
+        .components()
+        .filter(|c| !matches!(c, std::path::Component::CurDir))
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+
+    let start = repository
+        .and_then(|repo| components.iter().position(|c| c == repo))
+        .map_or(0, |idx| idx + 1);
+
+    components[start..].join("/")
+}
+
+/// The absolute directory a scan of `path` is rooted at.
+pub(crate) fn scan_root(path: &Path) -> PathBuf {
+    let absolute = absolute_path(path);
+    if absolute.is_file() {
+        absolute
+            .parent()
+            .map_or(absolute.clone(), Path::to_path_buf)
+    } else {
+        absolute
+    }
+}
+
+/// Resolve symlinks where the path exists; otherwise resolve `.` and `..`
+/// against the working directory.
+fn absolute_path(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| {
+        let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+        crate::graph::normalize_path(&absolute)
+    })
+}
+
+fn join_components(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn decrement(counts: &mut HashMap<String, usize>, key: &str) {
+    if let Some(count) = counts.get_mut(key) {
+        *count = count.saturating_sub(1);
+        if *count == 0 {
+            counts.remove(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{FindingCategory, Location, Severity};
+    use std::path::PathBuf;
+
+    fn finding(code: &str, resource: &str, file: &str, line: usize) -> Finding {
+        Finding {
+            code: code.to_string(),
+            severity: Severity::Error,
+            message: format!("'{resource}' {code}"),
+            description: None,
+            location: Some(Location {
+                file: PathBuf::from(file),
+                line,
+                column: None,
+                repository: Some("infra".to_string()),
+            }),
+            related_locations: vec![],
+            suggestion: None,
+            category: FindingCategory::MissingConstraint,
+            suppressed_by: None,
+            resource: Some(resource.to_string()),
+            source: Some("hashicorp/aws".to_string()),
+            origin: None,
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_line_and_clone_prefix() {
+        let a = finding("missing-version", "provider.aws", "/tmp/a/infra/main.tf", 3);
+        let b = finding(
+            "missing-version",
+            "provider.aws",
+            "/home/ci/infra/main.tf",
+            40,
+        );
+
+        assert_eq!(fingerprint(&a, &[]), fingerprint(&b, &[]));
+        assert_eq!(fingerprint(&a, &[]).file, "main.tf");
+    }
+
+    #[test]
+    fn test_relative_file() {
+        assert_eq!(relative_file(Path::new("./main.tf"), None, &[]), "main.tf");
+        assert_eq!(
+            relative_file(Path::new("/cache/infra/envs/prod.tf"), Some("infra"), &[]),
+            "envs/prod.tf"
+        );
+    }
+
+    #[test]
+    fn test_relative_file_to_scan_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let infra = dir.path().join("infra");
+        std::fs::create_dir_all(infra.join("envs")).unwrap();
+        std::fs::write(infra.join("envs/prod.tf"), "").unwrap();
+
+        let roots = vec![scan_root(&dir.path().join("infra/../infra"))];
+        let spellings = [
+            infra.join("envs/prod.tf"),
+            infra.join("./envs/../envs/prod.tf"),
+        ];
+        for file in spellings {
+            assert_eq!(relative_file(&file, None, &roots), "envs/prod.tf");
+        }
+    }
+
+    #[test]
+    fn test_apply_splits_known_new_and_fixed() {
+        let baseline = Baseline::from_findings(
+            &[
+                finding("missing-version", "module.vpc", "infra/main.tf", 1),
+                finding("missing-version", "module.old", "infra/main.tf", 9),
+            ],
+            &[],
+        );
+
+        let mut result = ScanResult::default();
+        result.analysis.findings = vec![
+            finding("missing-version", "module.vpc", "infra/main.tf", 5),
+            finding("missing-version", "module.eks", "infra/main.tf", 12),
+        ];
+        result
+            .analysis
+            .summary
+            .findings_by_severity
+            .insert("ERROR".to_string(), 2);
+
+        baseline.apply(&mut result);
+
+        let outcome = result.baseline.as_ref().unwrap();
+        assert_eq!(result.analysis.findings.len(), 1);
+        assert_eq!(
+            result.analysis.findings[0].resource.as_deref(),
+            Some("module.eks")
+        );
+        assert_eq!(outcome.known.len(), 1);
+        assert_eq!(outcome.fixed.len(), 1);
+        assert_eq!(outcome.fixed[0].resource.as_deref(), Some("module.old"));
+        assert_eq!(result.analysis.summary.findings_by_severity["ERROR"], 1);
+    }
+
+    #[test]
+    fn test_duplicate_findings_need_duplicate_entries() {
+        let baseline =
+            Baseline::from_findings(&[finding("exact-version", "module.vpc", "main.tf", 1)], &[]);
+
+        let mut result = ScanResult::default();
+        result.analysis.findings = vec![
+            finding("exact-version", "module.vpc", "main.tf", 1),
+            finding("exact-version", "module.vpc", "main.tf", 1),
+        ];
+
+        baseline.apply(&mut result);
+
+        assert_eq!(result.analysis.findings.len(), 1);
+        assert_eq!(result.baseline.unwrap().known.len(), 1);
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("baseline.json");
+        let baseline =
+            Baseline::from_findings(&[finding("exact-version", "module.vpc", "main.tf", 1)], &[]);
+
+        baseline.save(&path).unwrap();
+        let loaded = Baseline::load(&path).unwrap();
+
+        assert_eq!(loaded.entries, baseline.entries);
+        assert!(Baseline::load(&dir.path().join("missing.json")).is_err());
+    }
+}
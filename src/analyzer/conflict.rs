@@ -4,14 +4,22 @@
 //! Terraform version constraints, including missing constraints, risky patterns,
 //! and overly broad constraints.
 
+use crate::analyzer::advisory::{self, AdvisoryDatabase};
+use crate::analyzer::cycles;
 use crate::analyzer::deprecation;
+use crate::analyzer::effective;
+use crate::analyzer::outdated::OutdatedChecker;
 use crate::analyzer::patterns::{PatternChecker, RiskyPattern};
+use crate::analyzer::runtime;
+use crate::analyzer::suppression;
 use crate::config::Config;
 use crate::error::Result;
+use crate::git::ChangeSet;
 use crate::graph::DependencyGraph;
+use crate::registry::RegistryIndex;
 use crate::types::{
-    AnalysisResult, AnalysisSummary, Finding, FindingCategory, Location, ModuleRef, ProviderRef,
-    RuntimeRef, Severity,
+    AnalysisResult, AnalysisSummary, Finding, FindingCategory, Location, ModuleRef, ModuleSource,
+    ProviderRef, RuntimeRef, Severity, Suppression,
 };
 use std::collections::HashMap;
 
@@ -42,7 +50,44 @@ use std::collections::HashMap;
 /// ## Phase 4: Deprecations
 ///
 /// Checks for deprecated module/provider versions based on configuration
-/// or inline CLI rules.
+/// or inline CLI rules, and modules matching `policies.blocked_modules`.
+///
+/// ## Phase 5: Security Advisories
+///
+/// Matches modules and providers against the advisory database, if one
+/// was loaded, and reports every constraint that allows a vulnerable version.
+///
+/// ## Phase 6: Outdated Versions
+///
+/// With registry data (see [`crate::registry`]), reports constraints that
+/// exclude the latest major version or whose newest allowed release is
+/// older than `max_age_months`, and records how many releases each
+/// reference is behind.
+///
+/// ## Phase 7: Circular Dependencies
+///
+/// Reports every cycle of module-to-module edges in the dependency graph,
+/// with the full path of modules and where each one is declared.
+///
+/// ## Phase 8: Effective Provider Constraints
+///
+/// Intersects the provider constraints of each root module and every module
+/// it calls, reports providers no version can satisfy along with the module
+/// chains responsible, and flags child modules that alone hold a provider
+/// to a deprecated range.
+///
+/// ## Phase 9: Suppressions
+///
+/// Applies inline `# monphare:ignore` directives. Suppressed findings are
+/// kept but excluded from counts; directives that match nothing are flagged
+/// as `stale-suppression`.
+///
+/// ## Phase 10: Changed Spans
+///
+/// With a [`ChangeSet`], keeps only findings whose block, or one of whose
+/// related blocks, overlaps a changed line. A block spans from its first
+/// line to the line before the next module, provider or runtime reference
+/// in the same file. Findings without a location are dropped.
 ///
 /// # Example
 ///
@@ -54,8 +99,12 @@ use std::collections::HashMap;
 /// let analyzer = Analyzer::new(&config);
 /// ```
 pub struct Analyzer {
-    _config: Config,
+    config: Config,
     pattern_checker: PatternChecker,
+    suppressions: Vec<Suppression>,
+    advisories: AdvisoryDatabase,
+    registry: RegistryIndex,
+    changes: Option<ChangeSet>,
 }
 
 impl Analyzer {
@@ -63,11 +112,43 @@ impl Analyzer {
     #[must_use]
     pub fn new(config: &Config) -> Self {
         Self {
-            _config: config.clone(),
+            config: config.clone(),
             pattern_checker: PatternChecker::new(config),
+            suppressions: Vec::new(),
+            advisories: AdvisoryDatabase::default(),
+            registry: RegistryIndex::default(),
+            changes: None,
         }
     }
 
+    /// Apply inline suppression directives collected by the parser.
+    #[must_use]
+    pub fn with_suppressions(mut self, suppressions: Vec<Suppression>) -> Self {
+        self.suppressions = suppressions;
+        self
+    }
+
+    /// Check modules and providers against a security advisory database.
+    #[must_use]
+    pub fn with_advisories(mut self, advisories: AdvisoryDatabase) -> Self {
+        self.advisories = advisories;
+        self
+    }
+
+    /// Compare constraints with the releases published in their registries.
+    #[must_use]
+    pub fn with_registry(mut self, registry: RegistryIndex) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Only report findings on lines changed since a git ref.
+    #[must_use]
+    pub fn with_changes(mut self, changes: ChangeSet) -> Self {
+        self.changes = Some(changes);
+        self
+    }
+
     /// Analyze modules and providers for policy violations and best practice issues.
     ///
     /// # Errors
@@ -75,7 +156,7 @@ impl Analyzer {
     /// Returns an error if analysis fails.
     pub fn analyze(
         &self,
-        _graph: &DependencyGraph,
+        graph: &DependencyGraph,
         modules: &[ModuleRef],
         providers: &[ProviderRef],
         runtimes: &[RuntimeRef],
@@ -110,7 +191,7 @@ impl Analyzer {
         findings.extend(broad);
 
         tracing::debug!("Checking deprecations");
-        let deprecation_analyzer = deprecation::DeprecationAnalyzer::new(&self._config);
+        let deprecation_analyzer = deprecation::DeprecationAnalyzer::new(&self.config);
         let deprecations = deprecation_analyzer.analyze(modules, providers, runtimes);
         tracing::debug!(
             deprecated_modules = deprecations.modules.len(),
@@ -118,6 +199,82 @@ impl Analyzer {
             deprecated_runtimes = deprecations.runtimes.len(),
             "Deprecation analysis complete"
         );
+        findings.extend(deprecation::deprecation_findings(&deprecations));
+
+        let blocked = self.check_blocked_modules(modules);
+        tracing::debug!(blocked_modules = blocked.len(), "Blocked modules found");
+        findings.extend(blocked);
+
+        if !self.advisories.is_empty() {
+            tracing::debug!(
+                advisories = self.advisories.len(),
+                "Checking security advisories"
+            );
+            findings.extend(advisory::advisory_findings(
+                &self.advisories,
+                modules,
+                providers,
+            ));
+        }
+
+        let versions = if self.registry.is_empty() {
+            Vec::new()
+        } else {
+            tracing::debug!(
+                packages = self.registry.len(),
+                "Checking for outdated versions"
+            );
+            let checker = OutdatedChecker::new(
+                &self.registry,
+                self.config.analysis.max_age_months,
+                chrono::Utc::now(),
+            );
+            let (outdated, versions) = checker.check(modules, providers);
+            findings.extend(outdated);
+            versions
+        };
+
+        tracing::debug!("Checking for circular dependencies");
+        let cycles = cycles::cycle_findings(graph);
+        tracing::debug!(cycles = cycles.len(), "Circular dependencies found");
+        findings.extend(cycles);
+
+        tracing::debug!("Computing effective provider constraints");
+        let effective = effective::effective_constraints(graph, modules, providers);
+        let effective = effective::effective_constraint_findings(&effective, &self.config);
+        tracing::debug!(
+            findings = effective.len(),
+            "Effective provider constraint issues found"
+        );
+        findings.extend(effective);
+
+        tracing::debug!("Checking runtime compatibility");
+        let runtime = runtime::runtime_findings(graph, modules, providers, runtimes, &self.config);
+        tracing::debug!(
+            findings = runtime.len(),
+            "Runtime compatibility issues found"
+        );
+        findings.extend(runtime);
+
+        tracing::debug!(
+            suppressions = self.suppressions.len(),
+            "Applying inline suppressions"
+        );
+        let stale = suppression::apply_suppressions(&mut findings, &self.suppressions);
+        if self.config.analysis.check_stale_suppressions {
+            tracing::debug!(stale_suppressions = stale.len(), "Stale suppressions found");
+            findings.extend(stale);
+        }
+
+        if let Some(changes) = &self.changes {
+            let total = findings.len();
+            retain_changed(&mut findings, changes, modules, providers, runtimes);
+            tracing::debug!(
+                kept = findings.len(),
+                dropped = total - findings.len(),
+                "Restricted findings to changed spans"
+            );
+        }
 
         // Build summary
         tracing::debug!("Building analysis summary");
@@ -149,6 +306,7 @@ impl Analyzer {
             summary,
             timestamp: Some(chrono::Utc::now()),
             deprecations,
+            versions,
         })
     }
 
@@ -166,6 +324,23 @@ impl Analyzer {
                 tracing::trace!(module = %module.name, "Local module, skipping constraint check");
                 continue;
             }
+            if let ModuleSource::Oci { tag, digest, .. } = &module.source {
+                // OCI artifacts are pinned by tag or digest, not `version`
+                findings.extend(oci_pinning_finding(
+                    &format!("Module '{}'", module.name),
+                    format!("module.{}", module.name),
+                    module.source.canonical_id(),
+                    tag.as_deref(),
+                    digest.as_deref(),
+                    Location {
+                        file: module.file_path.clone(),
+                        line: module.line_number,
+                        column: None,
+                        repository: module.repository.clone(),
+                    },
+                ));
+                continue;
+            }
             if module.version_constraint.is_none() && !module.source.is_local() {
                 findings.push(Finding {
                     code: "missing-version".to_string(),
@@ -187,12 +362,41 @@ impl Analyzer {
                         "Add a version constraint, e.g., version = \"~> 1.0\"".to_string(),
                     ),
                     category: FindingCategory::MissingConstraint,
+                    suppressed_by: None,
+                    resource: Some(format!("module.{}", module.name)),
+                    source: Some(module.source.canonical_id()),
+                    origin: None,
                 });
             }
         }
 
         // Check providers
         for provider in providers {
+            let location = Location {
+                file: provider.file_path.clone(),
+                line: provider.line_number,
+                column: None,
+                repository: provider.repository.clone(),
+            };
+            // Providers mirrored as OCI artifacts may be pinned by tag or digest instead
+            if let Some(ModuleSource::Oci { tag, digest, .. }) = provider
+                .source
+                .as_deref()
+                .filter(|s| s.starts_with("oci://"))
+                .and_then(|s| crate::parser::parse_module_source(s).ok())
+            {
+                if provider.version_constraint.is_none() || (tag.is_some() && digest.is_none()) {
+                    findings.extend(oci_pinning_finding(
+                        &format!("Provider '{}'", provider.name),
+                        format!("provider.{}", provider.name),
+                        provider.qualified_source(),
+                        tag.as_deref(),
+                        digest.as_deref(),
+                        location,
+                    ));
+                }
+                continue;
+            }
             if provider.version_constraint.is_none() {
                 findings.push(Finding {
                     code: "missing-version".to_string(),
@@ -203,17 +407,16 @@ impl Analyzer {
                          to incompatible versions. Always specify a version constraint."
                             .to_string(),
                     ),
-                    location: Some(Location {
-                        file: provider.file_path.clone(),
-                        line: provider.line_number,
-                        column: None,
-                        repository: provider.repository.clone(),
-                    }),
+                    location: Some(location),
                     related_locations: vec![],
                     suggestion: Some(
                         "Add a version constraint, e.g., version = \">= 4.0, < 6.0\"".to_string(),
                     ),
                     category: FindingCategory::MissingConstraint,
+                    suppressed_by: None,
+                    resource: Some(format!("provider.{}", provider.name)),
+                    source: Some(provider.qualified_source()),
+                    origin: None,
                 });
             }
         }
@@ -237,13 +440,18 @@ impl Analyzer {
             }
             if let Some(constraint) = &module.version_constraint {
                 for pattern in self.pattern_checker.check(&constraint.raw) {
-                    findings.push(self.pattern_to_finding(
-                        pattern,
-                        &module.name,
-                        &module.file_path,
-                        module.line_number,
-                        module.repository.as_deref(),
-                    ));
+                    findings.push(Finding {
+                        resource: Some(format!("module.{}", module.name)),
+                        source: Some(module.source.canonical_id()),
+                        origin: None,
+                        ..self.pattern_to_finding(
+                            pattern,
+                            &module.name,
+                            &module.file_path,
+                            module.line_number,
+                            module.repository.as_deref(),
+                        )
+                    });
                 }
             }
         }
@@ -252,13 +460,18 @@ impl Analyzer {
         for provider in providers {
             if let Some(constraint) = &provider.version_constraint {
                 for pattern in self.pattern_checker.check(&constraint.raw) {
-                    findings.push(self.pattern_to_finding(
-                        pattern,
-                        &provider.name,
-                        &provider.file_path,
-                        provider.line_number,
-                        provider.repository.as_deref(),
-                    ));
+                    findings.push(Finding {
+                        resource: Some(format!("provider.{}", provider.name)),
+                        source: Some(provider.qualified_source()),
+                        origin: None,
+                        ..self.pattern_to_finding(
+                            pattern,
+                            &provider.name,
+                            &provider.file_path,
+                            provider.line_number,
+                            provider.repository.as_deref(),
+                        )
+                    });
                 }
             }
         }
@@ -266,6 +479,69 @@ impl Analyzer {
         findings
     }
 
+    /// Check modules against the `policies.blocked_modules` glob patterns.
+    ///
+    /// Patterns are matched against the canonical source and every key a
+    /// deprecation rule could use for it (e.g., `oci://ghcr.io/acme/*`).
+    fn check_blocked_modules(&self, modules: &[ModuleRef]) -> Vec<Finding> {
+        let patterns: Vec<glob::Pattern> = self
+            .config
+            .policies
+            .blocked_modules
+            .iter()
+            .filter_map(|p| match glob::Pattern::new(p) {
+                Ok(pattern) => Some(pattern),
+                Err(e) => {
+                    tracing::warn!(pattern = %p, error = %e, "Ignoring invalid blocked module pattern");
+                    None
+                }
+            })
+            .collect();
+        if patterns.is_empty() {
+            return Vec::new();
+        }
+
+        let mut findings = Vec::new();
+        for module in modules.iter().filter(|m| !m.source.is_local()) {
+            let mut keys = deprecation::module_deprecation_keys(&module.source);
+            keys.push(module.source.canonical_id());
+            let Some(pattern) = patterns
+                .iter()
+                .find(|pattern| keys.iter().any(|key| pattern.matches(key)))
+            else {
+                continue;
+            };
+
+            findings.push(Finding {
+                code: "blocked-module".to_string(),
+                severity: Severity::Error,
+                message: format!(
+                    "Module '{}' uses blocked source '{}'",
+                    module.name,
+                    module.source.canonical_id()
+                ),
+                description: Some(format!(
+                    "The source matches the blocked module pattern '{}'.",
+                    pattern.as_str()
+                )),
+                location: Some(Location {
+                    file: module.file_path.clone(),
+                    line: module.line_number,
+                    column: None,
+                    repository: module.repository.clone(),
+                }),
+                related_locations: vec![],
+                suggestion: Some("Replace the module with an approved source".to_string()),
+                category: FindingCategory::BestPractice,
+                suppressed_by: None,
+                resource: Some(format!("module.{}", module.name)),
+                source: Some(module.source.canonical_id()),
+                origin: None,
+            });
+        }
+        findings
+    }
+
     /// Check for overly broad constraints.
     fn check_broad_constraints(
         &self,
@@ -306,6 +582,10 @@ impl Analyzer {
                                 .to_string(),
                         ),
                         category: FindingCategory::BroadConstraint,
+                        suppressed_by: None,
+                        resource: Some(format!("module.{}", module.name)),
+                        source: Some(module.source.canonical_id()),
+                        origin: None,
                     });
                 }
             }
@@ -338,6 +618,10 @@ impl Analyzer {
                             "Use a more specific constraint like '>= 4.0, < 6.0'".to_string(),
                         ),
                         category: FindingCategory::BroadConstraint,
+                        suppressed_by: None,
+                        resource: Some(format!("provider.{}", provider.name)),
+                        source: Some(provider.qualified_source()),
+                        origin: None,
                     });
                 }
             }
@@ -400,6 +684,10 @@ impl Analyzer {
             related_locations: vec![],
             suggestion: Some(suggestion.to_string()),
             category: FindingCategory::BestPractice,
+            suppressed_by: None,
+            resource: None,
+            source: None,
+            origin: None,
         }
     }
 
@@ -413,7 +701,13 @@ impl Analyzer {
         let mut findings_by_severity: HashMap<String, usize> = HashMap::new();
         let mut findings_by_category: HashMap<String, usize> = HashMap::new();
 
+        let mut suppressed = 0;
+
         for finding in findings {
+            if finding.is_suppressed() {
+                suppressed += 1;
+                continue;
+            }
             *findings_by_severity
                 .entry(finding.severity.to_string())
                 .or_insert(0) += 1;
@@ -436,10 +730,95 @@ impl Analyzer {
             unique_provider_sources: unique_provider_sources.len(),
             findings_by_severity,
             findings_by_category,
+            suppressed,
         }
     }
 }
 
+/// Pinning finding for an OCI artifact: `missing-version` without a tag or
+/// digest, `oci-tag-without-digest` when only a tag is given. Tags can be
+/// moved to another manifest, like a git branch; only a digest is immutable.
+fn oci_pinning_finding(
+    subject: &str,
+    resource: String,
+    source: String,
+    tag: Option<&str>,
+    digest: Option<&str>,
+    location: Location,
+) -> Option<Finding> {
+    let (code, severity, message, description, suggestion, category) = match (tag, digest) {
+        (_, Some(_)) => return None,
+        (None, None) => (
+            "missing-version",
+            Severity::Error,
+            format!("{subject} has no OCI tag or digest"),
+            "OCI sources without a tag or digest resolve to whatever \
+             the registry serves as latest. Pin a tag or, better, a digest.",
+            "Add a tag or digest, e.g., ?tag=1.0.0 or ?digest=sha256:...".to_string(),
+            FindingCategory::MissingConstraint,
+        ),
+        (Some(tag), None) => (
+            "oci-tag-without-digest",
+            Severity::Warning,
+            format!("{subject} is pinned to OCI tag '{tag}' without a digest"),
+            "OCI tags are mutable: the registry can point a tag at a different \
+             manifest, so the artifact may change without any edit to the configuration.",
+            format!("Pin the manifest digest, e.g., ?tag={tag}&digest=sha256:..."),
+            FindingCategory::BroadConstraint,
+        ),
+    };
+
+    Some(Finding {
+        code: code.to_string(),
+        severity,
+        message,
+        description: Some(description.to_string()),
+        location: Some(location),
+        related_locations: vec![],
+        suggestion: Some(suggestion),
+        category,
+        suppressed_by: None,
+        resource: Some(resource),
+        source: Some(source),
+        origin: None,
+    })
+}
+
+/// Drop findings that do not touch a changed line.
+fn retain_changed(
+    findings: &mut Vec<Finding>,
+    changes: &ChangeSet,
+    modules: &[ModuleRef],
+    providers: &[ProviderRef],
+    runtimes: &[RuntimeRef],
+) {
+    // First line of every block, by file
+    let mut starts: HashMap<&std::path::Path, Vec<usize>> = HashMap::new();
+    let blocks = modules
+        .iter()
+        .map(|m| (&m.file_path, m.line_number))
+        .chain(providers.iter().map(|p| (&p.file_path, p.line_number)))
+        .chain(runtimes.iter().map(|r| (&r.file_path, r.line_number)));
+    for (file, line) in blocks {
+        starts.entry(file.as_path()).or_default().push(line);
+    }
+
+    let touched = |location: &Location| {
+        let last = starts
+            .get(location.file.as_path())
+            .and_then(|lines| lines.iter().filter(|&&l| l > location.line).min())
+            .map_or(usize::MAX, |next| next - 1);
+        changes.covers(&location.file, location.line, last)
+    };
+    findings.retain(|finding| {
+        finding
+            .location
+            .iter()
+            .chain(&finding.related_locations)
+            .any(touched)
+    });
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -463,12 +842,15 @@ mod tests {
                 namespace: "terraform-aws-modules".to_string(),
                 name: source_name.to_string(),
                 provider: "aws".to_string(),
+                subdir: None,
+                params: Default::default(),
             },
             version_constraint: version.map(|v| Constraint::parse(v).unwrap()),
             file_path: PathBuf::from("main.tf"),
             line_number: 1,
             repository: Some(repo.to_string()),
             attributes: Default::default(),
+            dependencies: Default::default(),
         }
     }
 
@@ -505,6 +887,146 @@ mod tests {
         assert!(!missing.is_empty(), "Should detect missing constraint");
     }
 
+    #[test]
+    fn test_oci_pinning_and_blocked_modules() {
+        let oci =
+            |name: &str, repository: &str, tag: Option<&str>, digest: Option<&str>| ModuleRef {
+                name: name.to_string(),
+                source: ModuleSource::Oci {
+                    registry: "ghcr.io".to_string(),
+                    repository: repository.to_string(),
+                    tag: tag.map(str::to_string),
+                    digest: digest.map(str::to_string),
+                    subdir: None,
+                    params: Default::default(),
+                },
+                version_constraint: None,
+                file_path: PathBuf::from("main.tf"),
+                line_number: 1,
+                repository: None,
+                attributes: Default::default(),
+                dependencies: Default::default(),
+            };
+        let modules = vec![
+            oci("pinned", "acme/vpc", Some("1.0.0"), Some("sha256:abc")),
+            oci("by_digest", "acme/rds", None, Some("sha256:def")),
+            oci("tagged", "acme/iam", Some("1.0.0"), None),
+            oci("floating", "acme/eks", None, None),
+            oci("forbidden", "untrusted/vpc", None, Some("sha256:abc")),
+        ];
+        let graph = GraphBuilder::new().build(&modules, &[], &[]).unwrap();
+        let mut config = Config::default();
+        config.policies.blocked_modules = vec!["oci://ghcr.io/untrusted/*".to_string()];
+
+        let result = Analyzer::new(&config)
+            .analyze(&graph, &modules, &[], &[])
+            .unwrap();
+
+        let codes: Vec<_> = result
+            .findings
+            .iter()
+            .map(|f| (f.code.as_str(), f.resource.as_deref().unwrap_or_default()))
+            .collect();
+        assert_eq!(
+            codes,
+            [
+                ("oci-tag-without-digest", "module.tagged"),
+                ("missing-version", "module.floating"),
+                ("blocked-module", "module.forbidden"),
+            ]
+        );
+        let tagged = &result.findings[0];
+        assert_eq!(tagged.severity, Severity::Warning);
+        assert_eq!(
+            tagged.message,
+            "Module 'tagged' is pinned to OCI tag '1.0.0' without a digest"
+        );
+    }
+
+    #[test]
+    fn test_blocked_registry_and_git_modules() {
+        let module = |name: &str, source: &str| ModuleRef {
+            name: name.to_string(),
+            source: crate::parser::parse_module_source(source).unwrap(),
+            version_constraint: Some(Constraint::parse("~> 1.0").unwrap()),
+            file_path: PathBuf::from("main.tf"),
+            line_number: 1,
+            repository: None,
+            attributes: Default::default(),
+            dependencies: Default::default(),
+        };
+        let modules = vec![
+            module("vpc", "terraform-aws-modules/vpc/aws"),
+            module("legacy", "legacy-org/network/aws//modules/subnets"),
+            module(
+                "forked",
+                "git::https://github.com/untrusted/eks.git?ref=v1.0.0",
+            ),
+            module("local", "../modules/legacy-org"),
+        ];
+        let graph = GraphBuilder::new().build(&modules, &[], &[]).unwrap();
+        let mut config = Config::default();
+        config.policies.blocked_modules = vec![
+            "legacy-org/*".to_string(),
+            "github.com/untrusted/*".to_string(),
+        ];
+
+        let result = Analyzer::new(&config)
+            .analyze(&graph, &modules, &[], &[])
+            .unwrap();
+
+        let blocked: Vec<_> = result
+            .findings
+            .iter()
+            .filter(|f| f.code == "blocked-module")
+            .map(|f| (f.severity, f.resource.as_deref().unwrap_or_default()))
+            .collect();
+        assert_eq!(
+            blocked,
+            [
+                (Severity::Error, "module.legacy"),
+                (Severity::Error, "module.forked"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_oci_mirrored_provider_pinning() {
+        let provider = |name: &str, source: &str, version: Option<&str>| ProviderRef {
+            name: name.to_string(),
+            source: Some(source.to_string()),
+            version_constraint: version.map(|v| Constraint::parse(v).unwrap()),
+            file_path: PathBuf::from("versions.tf"),
+            line_number: 1,
+            repository: None,
+        };
+        let providers = vec![
+            provider("aws", "oci://ghcr.io/acme/aws?digest=sha256:abc", None),
+            provider("google", "oci://ghcr.io/acme/google?tag=5.0.0", None),
+            provider("azurerm", "oci://ghcr.io/acme/azurerm", Some("~> 3.0")),
+            provider("random", "oci://ghcr.io/acme/random", None),
+        ];
+        let graph = GraphBuilder::new().build(&[], &providers, &[]).unwrap();
+
+        let result = Analyzer::new(&Config::default())
+            .analyze(&graph, &[], &providers, &[])
+            .unwrap();
+
+        let codes: Vec<_> = result
+            .findings
+            .iter()
+            .filter(|f| f.category != FindingCategory::BestPractice)
+            .map(|f| (f.code.as_str(), f.resource.as_deref().unwrap_or_default()))
+            .collect();
+        assert_eq!(
+            codes,
+            [
+                ("oci-tag-without-digest", "provider.google"),
+                ("missing-version", "provider.random"),
+            ]
+        );
+    }
+
     #[test]
     fn test_detect_broad_constraint() {
         let modules = vec![create_module("vpc", "vpc", Some(">= 0.0.0"), "repo-a")];
@@ -553,8 +1075,10 @@ mod tests {
                 reason: "Legacy Terraform version, migrate to v0.13.1 or later".to_string(),
                 severity: Severity::Error.to_string(),
                 replacement: ">= 0.13.1".to_string(),
+                ..Default::default()
             }],
         )]);
+        config.deprecations.enforce_undated = true;
         let analyzer = Analyzer::new(&config);
 
         let result = analyzer
@@ -574,6 +1098,40 @@ mod tests {
         } else {
             panic!("Expected exact version");
         }
+
+        let finding = result
+            .findings
+            .iter()
+            .find(|f| f.code == "deprecated-runtime")
+            .expect("runtime deprecation should be reported as a finding");
+        assert_eq!(finding.severity, Severity::Error);
+        assert!(result.has_errors());
+    }
+
+    #[test]
+    fn test_suppressed_findings_excluded_from_summary() {
+        use crate::types::{Suppression, SuppressionScope};
+
+        let modules = vec![create_module("vpc", "vpc", None, "repo-a")];
+        let suppression = Suppression {
+            codes: vec!["missing-version".to_string()],
+            reason: Some("tracked upstream".to_string()),
+            file: PathBuf::from("main.tf"),
+            line: 1,
+            scope: SuppressionScope::File,
+            repository: Some("repo-a".to_string()),
+        };
+
+        let graph = GraphBuilder::new().build(&modules, &[], &[]).unwrap();
+        let config = Config::default();
+        let analyzer = Analyzer::new(&config).with_suppressions(vec![suppression]);
+
+        let result = analyzer.analyze(&graph, &modules, &[], &[]).unwrap();
+
+        assert!(result.findings.iter().any(|f| f.is_suppressed()));
+        assert!(!result.has_errors());
+        assert_eq!(result.summary.suppressed, 1);
+        assert!(!result.summary.findings_by_severity.contains_key("ERROR"));
     }
 
     #[test]
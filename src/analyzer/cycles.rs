@@ -0,0 +1,141 @@
+//! Circular dependency detection.
+//!
+//! Reports every dependency cycle between modules in the graph as a
+//! `circular-dependency` finding. Terraform refuses to plan a configuration
+//! whose modules depend on each other in a loop.
+
+use crate::graph::{format_cycle, DependencyGraph, GraphNode};
+use crate::types::{Finding, FindingCategory, Location, Severity};
+
+/// Build one finding per dependency cycle in `graph`.
+///
+/// The finding is located at the first module of the cycle; the other
+/// members are listed as related locations, in cycle order.
+pub(crate) fn cycle_findings(graph: &DependencyGraph) -> Vec<Finding> {
+    graph
+        .find_cycles()
+        .iter()
+        .map(|cycle| cycle_finding(graph, cycle))
+        .collect()
+}
+
+fn cycle_finding(graph: &DependencyGraph, cycle: &[String]) -> Finding {
+    let modules: Vec<_> = cycle
+        .iter()
+        .filter_map(|id| match graph.get_node(id) {
+            Some(GraphNode::Module(m)) => Some(m),
+            _ => None,
+        })
+        .collect();
+    let locations: Vec<Location> = modules
+        .iter()
+        .map(|m| Location {
+            file: m.file_path.clone(),
+            line: m.line_number,
+            column: None,
+            repository: m.repository.clone(),
+        })
+        .collect();
+
+    let mut names: Vec<String> = modules
+        .iter()
+        .map(|m| format!("module.{}", m.name))
+        .collect();
+    if let Some(first) = names.first().cloned() {
+        names.push(first);
+    }
+    let steps: Vec<String> = modules
+        .iter()
+        .zip(&locations)
+        .map(|(m, location)| format!("module.{} ({location})", m.name))
+        .collect();
+
+    let first = modules.first();
+    Finding {
+        code: "circular-dependency".to_string(),
+        severity: Severity::Error,
+        message: format!("Circular module dependency: {}", names.join(" -> ")),
+        description: Some(format!(
+            "These modules depend on each other in a loop, which Terraform cannot plan: {}. \
+             Node path: {}",
+            steps.join(" -> "),
+            format_cycle(cycle)
+        )),
+        location: locations.first().cloned(),
+        related_locations: locations.into_iter().skip(1).collect(),
+        suggestion: Some(
+            "Break the cycle by moving shared resources into a separate module or passing \
+             values in as variables"
+                .to_string(),
+        ),
+        category: FindingCategory::Dependency,
+        suppressed_by: None,
+        resource: first.map(|m| format!("module.{}", m.name)),
+        source: first.map(|m| m.source.canonical_id()),
+        origin: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::GraphBuilder;
+    use crate::types::{ModuleRef, ModuleSource};
+    use std::path::PathBuf;
+
+    fn local_module(name: &str, path: &str, line: usize) -> ModuleRef {
+        ModuleRef {
+            name: name.to_string(),
+            source: ModuleSource::Local {
+                path: path.to_string(),
+            },
+            version_constraint: None,
+            file_path: PathBuf::from(format!("{name}/main.tf")),
+            line_number: line,
+            repository: Some("infra".to_string()),
+            attributes: Default::default(),
+            dependencies: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_cycle_reported_with_path_and_locations() {
+        let modules = vec![
+            local_module("network", "../dns", 3),
+            local_module("dns", "../network", 7),
+            local_module("app", "../network", 1),
+        ];
+        let graph = GraphBuilder::new().build(&modules, &[], &[]).unwrap();
+
+        let findings = cycle_findings(&graph);
+        assert_eq!(findings.len(), 1);
+        let finding = &findings[0];
+        assert_eq!(finding.code, "circular-dependency");
+        assert_eq!(finding.category, FindingCategory::Dependency);
+        assert_eq!(
+            finding.message,
+            "Circular module dependency: module.network -> module.dns -> module.network"
+        );
+        assert_eq!(finding.location.as_ref().unwrap().line, 3);
+        assert_eq!(finding.related_locations.len(), 1);
+        assert_eq!(
+            finding.related_locations[0].file,
+            PathBuf::from("dns/main.tf")
+        );
+        assert!(finding
+            .description
+            .as_deref()
+            .unwrap()
+            .contains("module:infra:local://../dns:network"));
+    }
+
+    #[test]
+    fn test_no_findings_without_cycles() {
+        let modules = vec![
+            local_module("app", "../network", 1),
+            local_module("network", "./modules/vpc", 1),
+        ];
+        let graph = GraphBuilder::new().build(&modules, &[], &[]).unwrap();
+        assert!(cycle_findings(&graph).is_empty());
+    }
+}
@@ -1,12 +1,19 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
-use crate::types::{DeprecationResult, ModuleSource, RuntimeRef};
+use chrono::NaiveDate;
+
+use crate::config::DeprecationRef;
+use crate::types::{
+    DeprecationNotice, DeprecationPhase, DeprecationResult, Finding, FindingCategory, Location,
+    ModuleSource, RuntimeRef, Severity,
+};
 use crate::{Config, Constraint, ModuleRef, ProviderRef};
 
 const DEFAULT_TERRAFORM_REGISTRY: &str = "registry.terraform.io";
 
 pub struct DeprecationAnalyzer {
     config: Config,
+    today: NaiveDate,
 }
 
 impl DeprecationAnalyzer {
@@ -14,9 +21,18 @@ impl DeprecationAnalyzer {
     pub fn new(config: &Config) -> Self {
         Self {
             config: config.clone(),
+            today: chrono::Utc::now().date_naive(),
         }
     }
 
+    /// Evaluate retirement schedules as of the given date instead of today.
+    #[cfg(test)]
+    #[must_use]
+    pub fn at(mut self, today: NaiveDate) -> Self {
+        self.today = today;
+        self
+    }
+
     #[must_use]
     pub fn analyze(
         &self,
@@ -28,23 +44,67 @@ impl DeprecationAnalyzer {
             modules = modules.len(),
             providers = providers.len(),
             runtimes = runtimes.len(),
+            today = %self.today,
             "Starting deprecation analysis"
         );
+        let mut notices = Vec::new();
+
         let deprecated_runtimes = self.check_runtime_deprecations(runtimes);
         tracing::debug!(
             deprecated_runtimes = deprecated_runtimes.len(),
             "Runtime deprecation check complete"
         );
+        for (runtime, rule) in &deprecated_runtimes {
+            notices.push(self.notice(
+                format!("runtime.{}", runtime.name),
+                runtime.name.clone(),
+                rule,
+                Location {
+                    file: runtime.file_path.clone(),
+                    line: runtime.line_number,
+                    column: None,
+                    repository: runtime.repository.clone(),
+                },
+            ));
+        }
+
         let deprecated_modules = self.check_module_deprecations(modules);
         tracing::debug!(
             deprecated_modules = deprecated_modules.len(),
             "Module deprecation check complete"
         );
+        for (module, key, rule) in &deprecated_modules {
+            notices.push(self.notice(
+                format!("module.{}", module.name),
+                key.clone(),
+                rule,
+                Location {
+                    file: module.file_path.clone(),
+                    line: module.line_number,
+                    column: None,
+                    repository: module.repository.clone(),
+                },
+            ));
+        }
+
         let deprecated_providers = self.check_provider_deprecations(providers);
         tracing::debug!(
             deprecated_providers = deprecated_providers.len(),
             "Provider deprecation check complete"
         );
+        for (provider, rule) in &deprecated_providers {
+            notices.push(self.notice(
+                format!("provider.{}", provider.name),
+                provider.qualified_source(),
+                rule,
+                Location {
+                    file: provider.file_path.clone(),
+                    line: provider.line_number,
+                    column: None,
+                    repository: provider.repository.clone(),
+                },
+            ));
+        }
 
         tracing::debug!(
             total_deprecated =
@@ -53,48 +113,81 @@ impl DeprecationAnalyzer {
         );
 
         DeprecationResult {
-            runtimes: deprecated_runtimes,
-            modules: deprecated_modules,
-            providers: deprecated_providers,
+            runtimes: deprecated_runtimes.into_iter().map(|(r, _)| r).collect(),
+            modules: deprecated_modules.into_iter().map(|(m, _, _)| m).collect(),
+            providers: deprecated_providers.into_iter().map(|(p, _)| p).collect(),
             unique_module_sources: HashSet::new(),
             unique_provider_sources: HashSet::new(),
             unique_runtime_sources: HashSet::new(),
+            notices,
+        }
+    }
+
+    /// Where a rule is in its retirement schedule, or `None` if it has not
+    /// been announced yet. Rules without dates stay announced unless
+    /// `deprecations.enforce_undated` is set.
+    pub(crate) fn phase(&self, rule: &DeprecationRef) -> Option<DeprecationPhase> {
+        let future = |date: Option<NaiveDate>| date.is_some_and(|d| self.today < d);
+        let undated =
+            rule.announced.is_none() && rule.warn_from.is_none() && rule.enforce_from.is_none();
+
+        if undated && !self.config.deprecations.enforce_undated {
+            Some(DeprecationPhase::Announced)
+        } else if future(rule.announced) {
+            None
+        } else if future(rule.warn_from) {
+            Some(DeprecationPhase::Announced)
+        } else if future(rule.enforce_from) {
+            Some(DeprecationPhase::Warning)
+        } else {
+            Some(DeprecationPhase::Enforced)
         }
     }
 
-    fn check_runtime_deprecations(&self, runtimes: &[RuntimeRef]) -> Vec<RuntimeRef> {
+    fn notice(
+        &self,
+        resource: String,
+        source: String,
+        rule: &DeprecationRef,
+        location: Location,
+    ) -> DeprecationNotice {
+        DeprecationNotice {
+            resource,
+            source,
+            matched: rule
+                .version
+                .clone()
+                .or_else(|| rule.git_ref.clone())
+                .or_else(|| rule.oci_ref.clone())
+                .unwrap_or_default(),
+            reason: rule.reason.clone(),
+            replacement: rule.replacement.clone(),
+            severity: parse_severity(&rule.severity),
+            phase: self.phase(rule).unwrap_or(DeprecationPhase::Announced),
+            announced: rule.announced,
+            warn_from: rule.warn_from,
+            enforce_from: rule.enforce_from,
+            days_until_enforcement: rule
+                .enforce_from
+                .map(|d| d.signed_duration_since(self.today).num_days()),
+            origin: rule.origin.clone(),
+            location,
+        }
+    }
+
+    fn check_runtime_deprecations<'a>(
+        &'a self,
+        runtimes: &[RuntimeRef],
+    ) -> Vec<(RuntimeRef, &'a DeprecationRef)> {
         tracing::debug!(
             runtimes = runtimes.len(),
             deprecation_rules = self.config.deprecations.runtime.len(),
             "Checking runtime deprecations"
         );
-        let mut parsed_rules: HashMap<&str, Vec<Constraint>> = HashMap::new();
-        for (name, rules) in &self.config.deprecations.runtime {
-            let mut constraints = Vec::new();
-            for rule in rules {
-                if let Some(v) = &rule.version {
-                    if let Ok(c) = Constraint::parse(v) {
-                        constraints.push(c);
-                    } else {
-                        tracing::debug!(
-                            runtime_name = %name,
-                            version = %v,
-                            "Failed to parse deprecation constraint"
-                        );
-                    }
-                }
-            }
-            tracing::debug!(
-                runtime_name = %name,
-                constraints_count = constraints.len(),
-                "Parsed deprecation rules for runtime"
-            );
-            parsed_rules.insert(name.as_str(), constraints);
-        }
 
         let mut findings = Vec::new();
         for runtime in runtimes {
-            let Some(deprecations) = parsed_rules.get(runtime.name.as_str()) else {
+            let Some(rules) = self.config.deprecations.runtime.get(runtime.name.as_str()) else {
                 tracing::debug!(
                     runtime_name = %runtime.name,
                     "No deprecation rules found for runtime"
@@ -104,18 +197,27 @@ impl DeprecationAnalyzer {
             tracing::debug!(
                 runtime_name = %runtime.name,
                 version = %runtime.version.raw,
-                deprecation_rules_count = deprecations.len(),
+                deprecation_rules_count = rules.len(),
                 "Checking runtime against deprecation rules"
             );
-            for deprecated_constraint in deprecations {
-                if runtime.version.has_overlap_with(deprecated_constraint) {
+            for rule in rules.iter().filter(|r| self.phase(r).is_some()) {
+                let Some(v) = &rule.version else { continue };
+                let Ok(deprecated_constraint) = Constraint::parse(v) else {
+                    tracing::debug!(
+                        runtime_name = %runtime.name,
+                        version = %v,
+                        "Failed to parse deprecation constraint"
+                    );
+                    continue;
+                };
+                if runtime.version.has_overlap_with(&deprecated_constraint) {
                     tracing::debug!(
                         runtime_name = %runtime.name,
                         version = %runtime.version.raw,
                         deprecated_constraint = %deprecated_constraint.raw,
                         "Runtime matches deprecated version"
                     );
-                    findings.push(runtime.clone());
+                    findings.push((runtime.clone(), rule));
                     break;
                 }
             }
@@ -127,14 +229,17 @@ impl DeprecationAnalyzer {
         findings
     }
 
-    fn check_module_deprecations(&self, modules: &[ModuleRef]) -> Vec<ModuleRef> {
+    fn check_module_deprecations<'a>(
+        &'a self,
+        modules: &[ModuleRef],
+    ) -> Vec<(ModuleRef, String, &'a DeprecationRef)> {
         let mut findings = Vec::new();
         for module in modules {
             if module.source.is_local() {
                 tracing::trace!(module = %module.name, "Local module, skipping deprecation check");
                 continue;
             }
-            let mut deprecated = false;
+            let mut deprecated = None;
             for key in module_deprecation_keys(&module.source) {
                 tracing::debug!(
                     module_name = %module.name,
@@ -156,7 +261,20 @@ impl DeprecationAnalyzer {
                     "Found deprecation rules for module"
                 );
 
-                for rule in rules {
+                for rule in rules.iter().filter(|r| self.phase(r).is_some()) {
+                    // OCI artifacts: semver tags against `version`, tag or digest against `oci_ref`
+                    if let ModuleSource::Oci { tag, digest, .. } = &module.source {
+                        if oci_rule_matches(rule, tag.as_deref(), digest.as_deref()) {
+                            tracing::debug!(
+                                module_name = %module.name,
+                                "Module OCI tag or digest matches deprecation rule"
+                            );
+                            deprecated = Some((key.clone(), rule));
+                            break;
+                        }
+                        continue;
+                    }
+
                     // Registry-style semver rules
                     if let Some(v) = &rule.version {
                         if let (Some(module_constraint), Ok(deprecated_constraint)) =
@@ -173,7 +291,7 @@ impl DeprecationAnalyzer {
                                     module_name = %module.name,
                                     "Module matches deprecated version constraint"
                                 );
-                                deprecated = true;
+                                deprecated = Some((key.clone(), rule));
                                 break;
                             }
                         } else {
@@ -203,26 +321,29 @@ impl DeprecationAnalyzer {
                                     module_name = %module.name,
                                     "Module Git ref matches deprecated ref"
                                 );
-                                deprecated = true;
+                                deprecated = Some((key.clone(), rule));
                                 break;
                             }
                         }
                     }
                 }
 
-                if deprecated {
+                if deprecated.is_some() {
                     break;
                 }
             }
 
-            if deprecated {
-                findings.push(module.clone());
+            if let Some((key, rule)) = deprecated {
+                findings.push((module.clone(), key, rule));
             }
         }
         findings
     }
 
-    fn check_provider_deprecations(&self, providers: &[ProviderRef]) -> Vec<ProviderRef> {
+    fn check_provider_deprecations<'a>(
+        &'a self,
+        providers: &[ProviderRef],
+    ) -> Vec<(ProviderRef, &'a DeprecationRef)> {
         tracing::debug!(
             providers = providers.len(),
             deprecation_rules = self.config.deprecations.providers.len(),
@@ -246,35 +367,29 @@ impl DeprecationAnalyzer {
                 rules_count = rules.len(),
                 "Checking provider against deprecation rules"
             );
-            let mut deprecated = false;
-            for rule in rules {
-                if let Some(v) = &rule.version {
-                    if let (Some(provider_constraint), Ok(deprecated_constraint)) =
-                        (provider.version_constraint.as_ref(), Constraint::parse(v))
-                    {
-                        if provider_constraint.has_overlap_with(&deprecated_constraint) {
-                            tracing::debug!(
-                                provider_name = %provider.name,
-                                constraint = %provider_constraint.raw,
-                                deprecated_constraint = %deprecated_constraint.raw,
-                                "Provider matches deprecated version"
-                            );
-                            deprecated = true;
-                            break;
-                        }
-                    } else {
+            for rule in rules.iter().filter(|r| self.phase(r).is_some()) {
+                let Some(v) = &rule.version else { continue };
+                if let (Some(provider_constraint), Ok(deprecated_constraint)) =
+                    (provider.version_constraint.as_ref(), Constraint::parse(v))
+                {
+                    if provider_constraint.has_overlap_with(&deprecated_constraint) {
                         tracing::debug!(
                             provider_name = %provider.name,
-                            version = %v,
-                            "Failed to parse or compare deprecation constraint"
+                            constraint = %provider_constraint.raw,
+                            deprecated_constraint = %deprecated_constraint.raw,
+                            "Provider matches deprecated version"
                         );
+                        findings.push((provider.clone(), rule));
+                        break;
                     }
+                } else {
+                    tracing::debug!(
+                        provider_name = %provider.name,
+                        version = %v,
+                        "Failed to parse or compare deprecation constraint"
+                    );
                 }
             }
-
-            if deprecated {
-                findings.push(provider.clone());
-            }
         }
         tracing::debug!(
             findings = findings.len(),
@@ -284,7 +399,86 @@ impl DeprecationAnalyzer {
     }
 }
 
-fn module_deprecation_keys(source: &ModuleSource) -> Vec<String> {
+/// Turn deprecation notices into findings.
+///
+/// Enforced rules use their configured severity; rules in their warning
+/// period are warnings with a countdown, and announced rules are info.
+pub(crate) fn deprecation_findings(result: &DeprecationResult) -> Vec<Finding> {
+    result.notices.iter().map(notice_to_finding).collect()
+}
+
+fn notice_to_finding(notice: &DeprecationNotice) -> Finding {
+    let (kind, name) = notice
+        .resource
+        .split_once('.')
+        .unwrap_or(("resource", notice.resource.as_str()));
+    let label = match kind {
+        "module" => "Module",
+        "provider" => "Provider",
+        _ => "Runtime",
+    };
+
+    let (severity, schedule) = match (notice.phase, notice.enforce_from) {
+        (DeprecationPhase::Enforced, _) => (notice.severity, String::new()),
+        (DeprecationPhase::Warning, Some(date)) => (
+            Severity::Warning,
+            format!(
+                " (enforced in {} days, on {date})",
+                notice.days_until_enforcement.unwrap_or_default()
+            ),
+        ),
+        (DeprecationPhase::Warning, None) => (Severity::Warning, String::new()),
+        (DeprecationPhase::Announced, Some(date)) => (
+            Severity::Info,
+            format!(" (retirement scheduled for {date})"),
+        ),
+        (DeprecationPhase::Announced, None) => (Severity::Info, String::new()),
+    };
+
+    let matched = if notice.matched.is_empty() {
+        String::new()
+    } else {
+        format!(" '{}'", notice.matched)
+    };
+
+    Finding {
+        code: format!("deprecated-{kind}"),
+        severity,
+        message: format!(
+            "{label} '{name}' uses deprecated version{matched}{schedule}: {}",
+            notice.reason
+        ),
+        description: notice
+            .warn_from
+            .filter(|_| notice.phase == DeprecationPhase::Announced)
+            .map(|date| format!("Warnings for this deprecation start on {date}.")),
+        location: Some(notice.location.clone()),
+        related_locations: vec![],
+        suggestion: (!notice.replacement.is_empty())
+            .then(|| format!("Migrate to {}", notice.replacement)),
+        category: FindingCategory::Deprecated,
+        suppressed_by: None,
+        resource: Some(notice.resource.clone()),
+        source: Some(notice.source.clone()),
+        origin: notice.origin.clone(),
+    }
+}
+
+/// Parse a configured severity, defaulting to warning for unknown values.
+pub(crate) fn parse_severity(value: &str) -> Severity {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "critical" => Severity::Critical,
+        "error" => Severity::Error,
+        "info" => Severity::Info,
+        "warning" | "warn" => Severity::Warning,
+        other => {
+            tracing::warn!(severity = %other, "Unknown deprecation severity, using warning");
+            Severity::Warning
+        }
+    }
+}
+
+pub(crate) fn module_deprecation_keys(source: &ModuleSource) -> Vec<String> {
     tracing::debug!("looking for module deprecations for source: {:#?}", source);
     match source {
         ModuleSource::Git {
@@ -339,26 +533,75 @@ fn module_deprecation_keys(source: &ModuleSource) -> Vec<String> {
             namespace,
             name,
             provider,
+            subdir,
+            ..
         } => {
             // Accept both:
             // - "registry.terraform.io/namespace/name/provider" (canonical_id)
             // - "namespace/name/provider" (common shorthand)
-            let full = source.canonical_id();
-            if hostname == DEFAULT_TERRAFORM_REGISTRY {
+            let full = format!("{hostname}/{namespace}/{name}/{provider}");
+            let mut keys = if hostname == DEFAULT_TERRAFORM_REGISTRY {
                 vec![full, format!("{namespace}/{name}/{provider}")]
             } else {
                 vec![full]
+            };
+            // Rules for a submodule (`//subdir`) come first; rules for the
+            // package cover every submodule, since they share its version
+            if let Some(s) = subdir.as_deref().filter(|s| !s.is_empty()) {
+                let submodule: Vec<String> = keys.iter().map(|k| format!("{k}//{s}")).collect();
+                keys.splice(0..0, submodule);
+            }
+            keys
+        }
+        ModuleSource::Oci {
+            registry,
+            repository,
+            subdir,
+            ..
+        } => {
+            // Accept both "oci://registry/repository" and "registry/repository";
+            // the tag and digest are matched by the rule, not the key
+            let keys = [
+                format!("oci://{registry}/{repository}"),
+                format!("{registry}/{repository}"),
+            ];
+            match subdir.as_deref().filter(|s| !s.is_empty()) {
+                Some(subdir) => keys.iter().map(|k| format!("{k}//{subdir}")).collect(),
+                None => keys.to_vec(),
             }
         }
         _ => vec![source.canonical_id()],
     }
 }
 
+/// Whether a rule matches an OCI artifact's tag or digest.
+///
+/// `oci_ref` matches the tag or digest exactly; `version` matches tags that
+/// are semantic versions (with an optional `v` prefix).
+fn oci_rule_matches(rule: &DeprecationRef, tag: Option<&str>, digest: Option<&str>) -> bool {
+    if let Some(rule_ref) = &rule.oci_ref {
+        if tag == Some(rule_ref.as_str()) || digest == Some(rule_ref.as_str()) {
+            return true;
+        }
+    }
+
+    let (Some(v), Some(tag)) = (&rule.version, tag) else {
+        return false;
+    };
+    match (
+        Constraint::parse(v),
+        semver::Version::parse(tag.trim_start_matches('v')),
+    ) {
+        (Ok(deprecated), Ok(version)) => deprecated.is_satisfied_by(&version),
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
     use std::path::PathBuf;
 
-    use crate::config::DeprecationRef;
     use crate::types::RuntimeSource;
 
     use super::*;
@@ -376,6 +619,7 @@ mod tests {
                     reason: "Terraform < 0.13.0 is deprecated".to_string(),
                     severity: "error".to_string(),
                     replacement: ">= 0.13.0".to_string(),
+                    ..Default::default()
                 },
                 DeprecationRef {
                     version: Some(">= 0.13.0, < 0.14.0".to_string()),
@@ -383,6 +627,7 @@ mod tests {
                     reason: "Terraform >= 0.13.0, < 0.14.0 is deprecated".to_string(),
                     severity: "error".to_string(),
                     replacement: ">= 0.14.0".to_string(),
+                    ..Default::default()
                 },
             ],
         );
@@ -445,6 +690,7 @@ mod tests {
                 reason: "terraform-aws-vpc < 5.0.0 is deprecated".to_string(),
                 severity: "error".to_string(),
                 replacement: ">= 5.0.0".to_string(),
+                ..Default::default()
             }],
         );
         config.deprecations.modules.insert(
@@ -455,6 +701,7 @@ mod tests {
                 reason: "terraform-aws-eks < 20.0.0 is deprecated".to_string(),
                 severity: "error".to_string(),
                 replacement: ">= 20.0.0".to_string(),
+                ..Default::default()
             }],
         );
 
@@ -467,6 +714,7 @@ mod tests {
                 reason: "Tag 3.0.0 is deprecated".to_string(),
                 severity: "error".to_string(),
                 replacement: "refs/tags/3.0.1".to_string(),
+                ..Default::default()
             }],
         );
 
@@ -481,12 +729,15 @@ mod tests {
                     namespace: "terraform-aws-modules".to_string(),
                     name: "vpc".to_string(),
                     provider: "aws".to_string(),
+                    subdir: None,
+                    params: Default::default(),
                 },
                 version_constraint: Some(Constraint::parse("4.2.0").unwrap()),
                 file_path: PathBuf::from("network/main.tf"),
                 line_number: 10,
                 repository: Some("parent".to_string()),
                 attributes: Default::default(),
+                dependencies: Default::default(),
             },
             // Non-deprecated VPC (5.x)
             ModuleRef {
@@ -496,12 +747,15 @@ mod tests {
                     namespace: "terraform-aws-modules".to_string(),
                     name: "vpc".to_string(),
                     provider: "aws".to_string(),
+                    subdir: None,
+                    params: Default::default(),
                 },
                 version_constraint: Some(Constraint::parse("5.1.0").unwrap()),
                 file_path: PathBuf::from("network/main.tf"),
                 line_number: 40,
                 repository: Some("child-a".to_string()),
                 attributes: Default::default(),
+                dependencies: Default::default(),
             },
             // Deprecated EKS (19.x)
             ModuleRef {
@@ -511,12 +765,15 @@ mod tests {
                     namespace: "terraform-aws-modules".to_string(),
                     name: "eks".to_string(),
                     provider: "aws".to_string(),
+                    subdir: None,
+                    params: Default::default(),
                 },
                 version_constraint: Some(Constraint::parse("19.15.3").unwrap()),
                 file_path: PathBuf::from("cluster/main.tf"),
                 line_number: 12,
                 repository: Some("child-b".to_string()),
                 attributes: Default::default(),
+                dependencies: Default::default(),
             },
             // Non-deprecated EKS (20.x)
             ModuleRef {
@@ -526,12 +783,15 @@ mod tests {
                     namespace: "terraform-aws-modules".to_string(),
                     name: "eks".to_string(),
                     provider: "aws".to_string(),
+                    subdir: None,
+                    params: Default::default(),
                 },
                 version_constraint: Some(Constraint::parse("20.1.0").unwrap()),
                 file_path: PathBuf::from("cluster/main.tf"),
                 line_number: 55,
                 repository: Some("parent".to_string()),
                 attributes: Default::default(),
+                dependencies: Default::default(),
             },
             // Other terraform-aws-modules (no rules => non-deprecated)
             ModuleRef {
@@ -541,12 +801,15 @@ mod tests {
                     namespace: "terraform-aws-modules".to_string(),
                     name: "s3-bucket".to_string(),
                     provider: "aws".to_string(),
+                    subdir: None,
+                    params: Default::default(),
                 },
                 version_constraint: Some(Constraint::parse("4.0.0").unwrap()),
                 file_path: PathBuf::from("storage/main.tf"),
                 line_number: 8,
                 repository: Some("parent".to_string()),
                 attributes: Default::default(),
+                dependencies: Default::default(),
             },
             ModuleRef {
                 name: "ecs".to_string(),
@@ -555,12 +818,15 @@ mod tests {
                     namespace: "terraform-aws-modules".to_string(),
                     name: "ecs".to_string(),
                     provider: "aws".to_string(),
+                    subdir: None,
+                    params: Default::default(),
                 },
                 version_constraint: Some(Constraint::parse("5.12.0").unwrap()),
                 file_path: PathBuf::from("compute/main.tf"),
                 line_number: 20,
                 repository: Some("child-a".to_string()),
                 attributes: Default::default(),
+                dependencies: Default::default(),
             },
             // Git module with a branch named 3.0.0 and not a tag (should not be deprecated)
             ModuleRef {
@@ -571,12 +837,14 @@ mod tests {
                         .to_string(),
                     ref_: Some("3.0.0".to_string()),
                     subdir: Some("modules/resource-group".to_string()),
+                    params: Default::default(),
                 },
                 version_constraint: None,
                 file_path: PathBuf::from("azure/main.tf"),
                 line_number: 5,
                 repository: Some("parent".to_string()),
                 attributes: Default::default(),
+                dependencies: Default::default(),
             },
             // Git module with non-deprecated tag
             ModuleRef {
@@ -587,12 +855,14 @@ mod tests {
                         .to_string(),
                     ref_: Some("refs/tags/3.0.0".to_string()),
                     subdir: Some("".to_string()),
+                    params: Default::default(),
                 },
                 version_constraint: None,
                 file_path: PathBuf::from("azure/main.tf"),
                 line_number: 30,
                 repository: Some("child-b".to_string()),
                 attributes: Default::default(),
+                dependencies: Default::default(),
             },
         ];
 
@@ -614,4 +884,183 @@ mod tests {
             "ssh.dev.azure.com/v3/foo-bar/Terraform/mod-azurerm-resource-group?ref=refs/tags/3.0.0"
         ));
     }
+
+    #[test]
+    fn test_check_oci_module_deprecations() {
+        let mut config = Config::default();
+        config.deprecations.modules.insert(
+            "ghcr.io/acme/modules/vpc".to_string(),
+            vec![
+                DeprecationRef {
+                    version: Some("< 2.0.0".to_string()),
+                    reason: "VPC artifacts before 2.0 are unsupported".to_string(),
+                    severity: "error".to_string(),
+                    replacement: ">= 2.0.0".to_string(),
+                    ..Default::default()
+                },
+                DeprecationRef {
+                    oci_ref: Some("sha256:bad".to_string()),
+                    reason: "Artifact was built from a compromised pipeline".to_string(),
+                    severity: "critical".to_string(),
+                    replacement: "sha256:good".to_string(),
+                    ..Default::default()
+                },
+            ],
+        );
+        let oci = |name: &str, tag: Option<&str>, digest: Option<&str>| ModuleRef {
+            name: name.to_string(),
+            source: ModuleSource::Oci {
+                registry: "ghcr.io".to_string(),
+                repository: "acme/modules/vpc".to_string(),
+                tag: tag.map(str::to_string),
+                digest: digest.map(str::to_string),
+                subdir: None,
+                params: Default::default(),
+            },
+            version_constraint: None,
+            file_path: PathBuf::from("main.tf"),
+            line_number: 1,
+            repository: None,
+            attributes: Default::default(),
+            dependencies: Default::default(),
+        };
+
+        let modules = vec![
+            oci("old", Some("v1.4.0"), None),
+            oci("current", Some("2.1.0"), None),
+            oci("bad_digest", None, Some("sha256:bad")),
+            oci("good_digest", None, Some("sha256:good")),
+        ];
+        let result = DeprecationAnalyzer::new(&config).analyze(&modules, &[], &[]);
+
+        let names: Vec<&str> = result.modules.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["old", "bad_digest"]);
+        assert!(result.notices.iter().any(|n| n.matched == "sha256:bad"));
+        assert_eq!(
+            module_deprecation_keys(&modules[0].source),
+            ["oci://ghcr.io/acme/modules/vpc", "ghcr.io/acme/modules/vpc"]
+        );
+    }
+
+    #[test]
+    fn test_registry_submodule_keys() {
+        let source = crate::parser::parse_module_source(
+            "terraform-aws-modules/vpc/aws//modules/vpc-endpoints",
+        )
+        .unwrap();
+        assert_eq!(
+            module_deprecation_keys(&source),
+            [
+                "registry.terraform.io/terraform-aws-modules/vpc/aws//modules/vpc-endpoints",
+                "terraform-aws-modules/vpc/aws//modules/vpc-endpoints",
+                "registry.terraform.io/terraform-aws-modules/vpc/aws",
+                "terraform-aws-modules/vpc/aws",
+            ]
+        );
+    }
+
+    fn scheduled_config() -> Config {
+        let mut config = Config::default();
+        config.deprecations.providers.insert(
+            "hashicorp/aws".to_string(),
+            vec![DeprecationRef {
+                version: Some("< 5.0.0".to_string()),
+                reason: "AWS provider v4 is retired".to_string(),
+                severity: "error".to_string(),
+                replacement: ">= 5.0.0".to_string(),
+                announced: NaiveDate::from_ymd_opt(2025, 1, 1),
+                warn_from: NaiveDate::from_ymd_opt(2025, 3, 1),
+                enforce_from: NaiveDate::from_ymd_opt(2025, 6, 30),
+                ..Default::default()
+            }],
+        );
+        config
+    }
+
+    fn aws_provider() -> ProviderRef {
+        ProviderRef {
+            name: "aws".to_string(),
+            source: Some("hashicorp/aws".to_string()),
+            version_constraint: Some(Constraint::parse("~> 4.0").unwrap()),
+            file_path: PathBuf::from("versions.tf"),
+            line_number: 4,
+            repository: Some("infra".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_deprecation_schedule_phases() {
+        let config = scheduled_config();
+        let providers = vec![aws_provider()];
+        let at = |y, m, d| {
+            DeprecationAnalyzer::new(&config)
+                .at(NaiveDate::from_ymd_opt(y, m, d).unwrap())
+                .analyze(&[], &providers, &[])
+        };
+
+        // Not announced yet: invisible
+        let result = at(2024, 12, 1);
+        assert!(result.providers.is_empty());
+        assert!(deprecation_findings(&result).is_empty());
+
+        // Announced: info
+        let findings = deprecation_findings(&at(2025, 2, 1));
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Info);
+        assert!(findings[0]
+            .message
+            .contains("retirement scheduled for 2025-06-30"));
+
+        // Warning period: countdown
+        let result = at(2025, 6, 20);
+        assert_eq!(result.notices[0].phase, DeprecationPhase::Warning);
+        assert_eq!(result.notices[0].days_until_enforcement, Some(10));
+        let findings = deprecation_findings(&result);
+        assert_eq!(findings[0].severity, Severity::Warning);
+        assert!(findings[0]
+            .message
+            .contains("enforced in 10 days, on 2025-06-30"));
+
+        // Enforced: configured severity
+        let findings = deprecation_findings(&at(2025, 6, 30));
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert_eq!(findings[0].code, "deprecated-provider");
+        assert_eq!(findings[0].resource.as_deref(), Some("provider.aws"));
+        assert_eq!(
+            findings[0].suggestion.as_deref(),
+            Some("Migrate to >= 5.0.0")
+        );
+    }
+
+    #[test]
+    fn test_undated_rule_is_info_unless_enforced() {
+        let mut config = scheduled_config();
+        let rule = &mut config
+            .deprecations
+            .providers
+            .get_mut("hashicorp/aws")
+            .unwrap()[0];
+        rule.announced = None;
+        rule.warn_from = None;
+        rule.enforce_from = None;
+
+        let result = DeprecationAnalyzer::new(&config).analyze(&[], &[aws_provider()], &[]);
+
+        assert_eq!(result.notices[0].phase, DeprecationPhase::Announced);
+        assert!(result.timeline().is_empty());
+        assert_eq!(deprecation_findings(&result)[0].severity, Severity::Info);
+
+        config.deprecations.enforce_undated = true;
+        let result = DeprecationAnalyzer::new(&config).analyze(&[], &[aws_provider()], &[]);
+
+        assert_eq!(result.notices[0].phase, DeprecationPhase::Enforced);
+        assert_eq!(deprecation_findings(&result)[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_parse_severity() {
+        assert_eq!(parse_severity("Critical"), Severity::Critical);
+        assert_eq!(parse_severity("warn"), Severity::Warning);
+        assert_eq!(parse_severity("bogus"), Severity::Warning);
+    }
 }
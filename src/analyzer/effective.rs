@@ -0,0 +1,773 @@
+//! Effective provider constraints.
+//!
+//! Terraform intersects the `required_providers` constraints of a root
+//! module and of every module it calls, directly or transitively, and
+//! `terraform init` fails when no version satisfies all of them. This module
+//! computes that intersection for each root module of the scan, reports
+//! combinations that cannot be satisfied together with the modules
+//! responsible, and flags child modules that are the only reason a
+//! deprecated provider range is still selected.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::deprecation::{parse_severity, DeprecationAnalyzer};
+use crate::config::Config;
+use crate::graph::{declaring_dir, normalize_path, DependencyGraph, EdgeType, GraphNode};
+use crate::types::{
+    Constraint, DeprecationPhase, Finding, FindingCategory, Location, ModuleRef, ModuleSource,
+    ProviderRef, RuntimeRef, Severity, VersionSet,
+};
+
+/// One constraint on a provider within a root module's tree.
+#[derive(Debug, Clone)]
+pub struct ConstraintOrigin {
+    /// Module calls leading from the root to the module that declares the
+    /// constraint (e.g., `["module.eks", "module.kms"]`); empty for the root
+    pub chain: Vec<String>,
+    /// The declared constraint
+    pub constraint: Constraint,
+    /// Where the constraint is declared
+    pub location: Location,
+}
+
+impl ConstraintOrigin {
+    /// The module chain, as `root -> module.a -> module.b`.
+    #[must_use]
+    pub fn path(&self) -> String {
+        std::iter::once("root")
+            .chain(self.chain.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    }
+}
+
+/// The constraint Terraform applies to a provider for one root module.
+#[derive(Debug, Clone)]
+pub struct EffectiveConstraint {
+    /// Repository of the root module
+    pub repository: Option<String>,
+    /// Directory of the root module
+    pub root: PathBuf,
+    /// Provider source (e.g., "hashicorp/aws")
+    pub provider: String,
+    /// Every constraint in the module tree, root first
+    pub origins: Vec<ConstraintOrigin>,
+}
+
+impl EffectiveConstraint {
+    /// The intersection of all constraints, or `None` if nothing in the
+    /// module tree constrains the provider.
+    #[must_use]
+    pub fn constraint(&self) -> Option<Constraint> {
+        if self.origins.is_empty() {
+            return None;
+        }
+        Some(Constraint {
+            raw: self
+                .origins
+                .iter()
+                .map(|o| o.constraint.raw.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            ranges: self
+                .origins
+                .iter()
+                .flat_map(|o| o.constraint.ranges.iter().cloned())
+                .collect(),
+        })
+    }
+
+    /// Check if at least one version satisfies every constraint.
+    #[must_use]
+    pub fn is_satisfiable(&self) -> bool {
+        !self.versions().is_empty()
+    }
+
+    /// Human-readable name of the root module (e.g., `infra:envs/prod`).
+    #[must_use]
+    pub fn root_label(&self) -> String {
+        root_label(self.repository.as_ref(), &self.root)
+    }
+
+    fn versions(&self) -> VersionSet {
+        intersection(self.origins.iter())
+    }
+}
+
+/// A module directory: everything declared in one directory of one
+/// repository (or fetched package).
+pub(super) type Scope = (Option<String>, PathBuf);
+
+/// The kind of requirement collected along a module tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Requirement {
+    /// `required_providers` entries, keyed by provider source
+    Provider,
+    /// `required_version` constraints, keyed by runtime name
+    Runtime,
+}
+
+/// Compute the effective constraint of every provider for each root module.
+///
+/// Root modules are the directories of the scanned `modules` and
+/// `providers` that no other scanned directory calls as a local module.
+/// Their trees are followed through local module paths and through the
+/// remote modules attached to `graph` by module resolution.
+#[must_use]
+pub fn effective_constraints(
+    graph: &DependencyGraph,
+    modules: &[ModuleRef],
+    providers: &[ProviderRef],
+) -> Vec<EffectiveConstraint> {
+    let tree = ModuleTree::new(graph, modules, providers, &[]);
+
+    let mut result = Vec::new();
+    for (repository, root) in root_scopes(modules, providers, &[]) {
+        let origins = tree.origins(&(repository.clone(), root.clone()), Requirement::Provider);
+        result.extend(
+            origins
+                .into_iter()
+                .map(|(provider, origins)| EffectiveConstraint {
+                    repository: repository.clone(),
+                    root: root.clone(),
+                    provider,
+                    origins,
+                }),
+        );
+    }
+    tracing::debug!(
+        constraints = result.len(),
+        "Effective provider constraints computed"
+    );
+    result
+}
+
+/// Report unsatisfiable provider constraints and child modules that are the
+/// only reason a deprecated provider range is still allowed.
+pub(crate) fn effective_constraint_findings(
+    constraints: &[EffectiveConstraint],
+    config: &Config,
+) -> Vec<Finding> {
+    let deprecations = DeprecationAnalyzer::new(config);
+    let mut findings = Vec::new();
+    for effective in constraints {
+        if !effective.is_satisfiable() {
+            findings.push(unsatisfiable_finding(effective));
+            continue;
+        }
+        let Some(rules) = config.deprecations.providers.get(&effective.provider) else {
+            continue;
+        };
+        for rule in rules {
+            let Some(phase) = deprecations.phase(rule) else {
+                continue;
+            };
+            let Some(deprecated) = rule
+                .version
+                .as_deref()
+                .and_then(|v| Constraint::parse(v).ok())
+            else {
+                continue;
+            };
+            let severity = match phase {
+                DeprecationPhase::Enforced => parse_severity(&rule.severity),
+                DeprecationPhase::Warning => Severity::Warning,
+                DeprecationPhase::Announced => Severity::Info,
+            };
+            if let Some(origin) = sole_deprecated_pin(effective, &deprecated) {
+                findings.push(deprecated_pin_finding(
+                    effective,
+                    origin,
+                    &deprecated,
+                    severity,
+                    &rule.replacement,
+                    rule.origin.clone(),
+                ));
+            }
+        }
+    }
+    findings
+}
+
+/// The child module whose constraint alone restricts the provider to
+/// `deprecated` versions: with it, only deprecated versions remain; without
+/// it, a supported version would be selected.
+fn sole_deprecated_pin<'a>(
+    effective: &'a EffectiveConstraint,
+    deprecated: &Constraint,
+) -> Option<&'a ConstraintOrigin> {
+    let supported = VersionSet::from(deprecated).complement();
+    if !effective.versions().intersect(&supported).is_empty() {
+        return None;
+    }
+    let mut pins = effective
+        .origins
+        .iter()
+        .enumerate()
+        .filter(|(_, origin)| !origin.chain.is_empty())
+        .filter(|(index, _)| {
+            let others = effective
+                .origins
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| i != index)
+                .map(|(_, o)| o);
+            !intersection(others).intersect(&supported).is_empty()
+        });
+    match (pins.next(), pins.next()) {
+        (Some((_, origin)), None) => Some(origin),
+        _ => None,
+    }
+}
+
+/// A minimal set of constraints that cannot be satisfied together: the
+/// first constraint that empties the intersection, plus only those of the
+/// preceding ones needed to rule out every version.
+fn conflicting_origins(origins: &[ConstraintOrigin]) -> Vec<&ConstraintOrigin> {
+    let mut versions = VersionSet::all();
+    let Some(last) = origins.iter().position(|origin| {
+        versions = versions.intersect(&VersionSet::from(&origin.constraint));
+        versions.is_empty()
+    }) else {
+        return origins.iter().collect();
+    };
+
+    let mut picked: Vec<usize> = (0..=last).collect();
+    for index in (0..last).rev() {
+        let without: Vec<usize> = picked.iter().copied().filter(|&i| i != index).collect();
+        if intersection(without.iter().map(|&i| &origins[i])).is_empty() {
+            picked = without;
+        }
+    }
+    picked.into_iter().map(|i| &origins[i]).collect()
+}
+
+fn unsatisfiable_finding(effective: &EffectiveConstraint) -> Finding {
+    let conflict = conflicting_origins(&effective.origins);
+    let describe = |origin: &ConstraintOrigin| {
+        format!(
+            "'{}' from {} ({})",
+            origin.constraint.raw,
+            origin.path(),
+            origin.location
+        )
+    };
+    let all: Vec<String> = effective.origins.iter().map(describe).collect();
+    let culprits: Vec<String> = conflict
+        .iter()
+        .map(|o| format!("'{}' ({})", o.constraint.raw, o.path()))
+        .collect();
+
+    Finding {
+        code: "unsatisfiable-provider-constraint".to_string(),
+        severity: Severity::Error,
+        message: format!(
+            "No version of provider '{}' satisfies root module '{}': {}",
+            effective.provider,
+            effective.root_label(),
+            culprits.join(" conflicts with ")
+        ),
+        description: Some(format!(
+            "Terraform requires a single provider version that satisfies every constraint \
+             in the module tree, so `terraform init` will fail. Constraints: {}",
+            all.join("; ")
+        )),
+        location: conflict.first().map(|o| o.location.clone()),
+        related_locations: conflict
+            .iter()
+            .skip(1)
+            .map(|o| o.location.clone())
+            .collect(),
+        suggestion: Some(
+            "Align the provider constraints of the conflicting modules, or upgrade the \
+             module that pins an incompatible range"
+                .to_string(),
+        ),
+        category: FindingCategory::Dependency,
+        suppressed_by: None,
+        resource: conflict
+            .first()
+            .and_then(|o| o.chain.last().cloned())
+            .or_else(|| Some(format!("provider.{}", effective.provider))),
+        source: Some(effective.provider.clone()),
+        origin: None,
+    }
+}
+
+fn deprecated_pin_finding(
+    effective: &EffectiveConstraint,
+    origin: &ConstraintOrigin,
+    deprecated: &Constraint,
+    severity: Severity,
+    replacement: &str,
+    rule_origin: Option<String>,
+) -> Finding {
+    let effective_raw = effective.constraint().map(|c| c.raw).unwrap_or_default();
+    Finding {
+        code: "deprecated-provider-pinned".to_string(),
+        severity,
+        message: format!(
+            "{} is the only reason root module '{}' is held to deprecated versions '{}' of \
+             provider '{}'",
+            origin.path(),
+            effective.root_label(),
+            deprecated.raw,
+            effective.provider
+        ),
+        description: Some(format!(
+            "Its constraint '{}' excludes every supported version; the effective constraint \
+             is '{effective_raw}'. Without it, a version outside the deprecated range would \
+             be selected.",
+            origin.constraint.raw
+        )),
+        location: Some(origin.location.clone()),
+        related_locations: vec![],
+        suggestion: Some(if replacement.is_empty() {
+            "Upgrade the module or relax its provider constraint".to_string()
+        } else {
+            format!("Upgrade the module or relax its provider constraint to allow {replacement}")
+        }),
+        category: FindingCategory::Deprecated,
+        suppressed_by: None,
+        resource: origin.chain.last().cloned(),
+        source: Some(effective.provider.clone()),
+        origin: rule_origin,
+    }
+}
+
+/// The directories of the scanned references that no other scanned
+/// directory calls as a local module.
+pub(super) fn root_scopes(
+    modules: &[ModuleRef],
+    providers: &[ProviderRef],
+    runtimes: &[RuntimeRef],
+) -> BTreeSet<Scope> {
+    let called: HashSet<Scope> = modules
+        .iter()
+        .filter_map(|m| match &m.source {
+            ModuleSource::Local { path } => Some((
+                m.repository.clone(),
+                normalize_path(&declaring_dir(&m.file_path).join(path)),
+            )),
+            _ => None,
+        })
+        .collect();
+    modules
+        .iter()
+        .map(|m| scope_of(m.repository.as_ref(), &m.file_path))
+        .chain(
+            providers
+                .iter()
+                .map(|p| scope_of(p.repository.as_ref(), &p.file_path)),
+        )
+        .chain(
+            runtimes
+                .iter()
+                .map(|r| scope_of(r.repository.as_ref(), &r.file_path)),
+        )
+        .filter(|scope| !called.contains(scope))
+        .collect()
+}
+
+/// Human-readable name of a root module directory (e.g., `infra:envs/prod`).
+pub(super) fn root_label(repository: Option<&String>, root: &Path) -> String {
+    let dir = if root.as_os_str().is_empty() {
+        ".".to_string()
+    } else {
+        root.display().to_string()
+    };
+    match repository {
+        Some(repo) => format!("{repo}:{dir}"),
+        None => dir,
+    }
+}
+
+pub(super) fn intersection<'a>(origins: impl Iterator<Item = &'a ConstraintOrigin>) -> VersionSet {
+    origins.fold(VersionSet::all(), |set, origin| {
+        set.intersect(&VersionSet::from(&origin.constraint))
+    })
+}
+
+fn scope_of(repository: Option<&String>, file_path: &Path) -> Scope {
+    (repository.cloned(), declaring_dir(file_path))
+}
+
+/// A module block of the tree.
+struct TreeModule<'a> {
+    id: String,
+    name: &'a str,
+    source: &'a ModuleSource,
+    file_path: &'a Path,
+    repository: Option<&'a String>,
+}
+
+/// A provider or runtime requirement of the tree.
+struct TreeRequirement<'a> {
+    /// Provider source or runtime name
+    source: String,
+    constraint: Option<&'a Constraint>,
+    location: Location,
+}
+
+#[derive(Default)]
+struct ScopeContents<'a> {
+    modules: Vec<TreeModule<'a>>,
+    providers: Vec<TreeRequirement<'a>>,
+    runtimes: Vec<TreeRequirement<'a>>,
+}
+
+impl<'a> ScopeContents<'a> {
+    fn requirements(&self, kind: Requirement) -> &[TreeRequirement<'a>] {
+        match kind {
+            Requirement::Provider => &self.providers,
+            Requirement::Runtime => &self.runtimes,
+        }
+    }
+}
+
+/// Module blocks, provider requirements and runtime requirements grouped
+/// by module directory.
+///
+/// The graph keeps a single provider node per repository and source, so the
+/// scanned configuration is taken from the parsed references; only fetched
+/// packages, which have a single directory each, are read from the graph.
+pub(super) struct ModuleTree<'a> {
+    scopes: HashMap<Scope, ScopeContents<'a>>,
+    /// Outgoing edges by node ID
+    edges: HashMap<&'a str, Vec<(&'a EdgeType, &'a GraphNode)>>,
+    /// Repositories of the scan itself, as opposed to fetched packages
+    scanned: HashSet<Option<String>>,
+}
+
+impl<'a> ModuleTree<'a> {
+    pub(super) fn new(
+        graph: &'a DependencyGraph,
+        modules: &'a [ModuleRef],
+        providers: &'a [ProviderRef],
+        runtimes: &'a [RuntimeRef],
+    ) -> Self {
+        let scanned: HashSet<Option<String>> = modules
+            .iter()
+            .map(|m| m.repository.clone())
+            .chain(providers.iter().map(|p| p.repository.clone()))
+            .chain(runtimes.iter().map(|r| r.repository.clone()))
+            .collect();
+
+        let mut scopes: HashMap<Scope, ScopeContents<'_>> = HashMap::new();
+        for m in modules {
+            scopes
+                .entry(scope_of(m.repository.as_ref(), &m.file_path))
+                .or_default()
+                .modules
+                .push(TreeModule {
+                    id: graph.module_node_id(m),
+                    name: &m.name,
+                    source: &m.source,
+                    file_path: &m.file_path,
+                    repository: m.repository.as_ref(),
+                });
+        }
+        for p in providers {
+            scopes
+                .entry(scope_of(p.repository.as_ref(), &p.file_path))
+                .or_default()
+                .providers
+                .push(TreeRequirement {
+                    source: p.qualified_source(),
+                    constraint: p.version_constraint.as_ref(),
+                    location: location(&p.file_path, p.line_number, p.repository.as_ref()),
+                });
+        }
+        for r in runtimes {
+            scopes
+                .entry(scope_of(r.repository.as_ref(), &r.file_path))
+                .or_default()
+                .runtimes
+                .push(TreeRequirement {
+                    source: r.name.clone(),
+                    constraint: Some(&r.version),
+                    location: location(&r.file_path, r.line_number, r.repository.as_ref()),
+                });
+        }
+        for node in graph.nodes() {
+            match node {
+                GraphNode::Module(m) if !scanned.contains(&m.repository) => scopes
+                    .entry(scope_of(m.repository.as_ref(), &m.file_path))
+                    .or_default()
+                    .modules
+                    .push(TreeModule {
+                        id: m.id.clone(),
+                        name: &m.name,
+                        source: &m.source,
+                        file_path: &m.file_path,
+                        repository: m.repository.as_ref(),
+                    }),
+                GraphNode::Provider(p) if !scanned.contains(&p.repository) => scopes
+                    .entry(scope_of(p.repository.as_ref(), &p.file_path))
+                    .or_default()
+                    .providers
+                    .push(TreeRequirement {
+                        source: p.source.clone(),
+                        constraint: p.version_constraint.as_ref(),
+                        location: location(&p.file_path, p.line_number, p.repository.as_ref()),
+                    }),
+                GraphNode::Runtime(r) if !scanned.contains(&r.repository) => scopes
+                    .entry(scope_of(r.repository.as_ref(), &r.file_path))
+                    .or_default()
+                    .runtimes
+                    .push(TreeRequirement {
+                        source: r.name.clone(),
+                        constraint: Some(&r.version),
+                        location: location(&r.file_path, r.line_number, r.repository.as_ref()),
+                    }),
+                _ => {}
+            }
+        }
+
+        let mut edges: HashMap<&str, Vec<(&EdgeType, &GraphNode)>> = HashMap::new();
+        for (from, to, edge) in graph.edges() {
+            edges.entry(from.id()).or_default().push((edge, to));
+        }
+
+        Self {
+            scopes,
+            edges,
+            scanned,
+        }
+    }
+
+    /// Every constraint of one kind in the tree below a root directory,
+    /// keyed by provider source or runtime name, root first.
+    pub(super) fn origins(
+        &self,
+        root: &Scope,
+        kind: Requirement,
+    ) -> BTreeMap<String, Vec<ConstraintOrigin>> {
+        let mut origins = BTreeMap::new();
+        self.walk(root, kind, &mut Vec::new(), &mut Vec::new(), &mut origins);
+        origins
+    }
+
+    fn walk(
+        &self,
+        scope: &Scope,
+        kind: Requirement,
+        chain: &mut Vec<String>,
+        visiting: &mut Vec<Scope>,
+        origins: &mut BTreeMap<String, Vec<ConstraintOrigin>>,
+    ) {
+        // A cycle is reported separately; stop instead of looping
+        if visiting.contains(scope) {
+            return;
+        }
+        let Some(contents) = self.scopes.get(scope) else {
+            return;
+        };
+        visiting.push(scope.clone());
+
+        for requirement in contents.requirements(kind) {
+            let entry = origins.entry(requirement.source.clone()).or_default();
+            if let Some(constraint) = requirement.constraint {
+                entry.push(ConstraintOrigin {
+                    chain: chain.clone(),
+                    constraint: constraint.clone(),
+                    location: requirement.location.clone(),
+                });
+            }
+        }
+        for module in &contents.modules {
+            chain.push(format!("module.{}", module.name));
+            for child in self.children(module) {
+                self.walk(&child, kind, chain, visiting, origins);
+            }
+            chain.pop();
+        }
+        visiting.pop();
+    }
+
+    /// The module directories a module block calls.
+    fn children(&self, module: &TreeModule<'_>) -> BTreeSet<Scope> {
+        let mut children = BTreeSet::new();
+        if let ModuleSource::Local { path } = module.source {
+            children.insert((
+                module.repository.cloned(),
+                normalize_path(&declaring_dir(module.file_path).join(path)),
+            ));
+        }
+        // Fetched sources are attached by module resolution: a call edge to
+        // each module block of the package and a provider or runtime edge
+        // to each of its requirements
+        for (edge, to) in self.edges.get(module.id.as_str()).into_iter().flatten() {
+            match (edge, to) {
+                (EdgeType::ModuleCall, GraphNode::Module(m)) => {
+                    children.insert(scope_of(m.repository.as_ref(), &m.file_path));
+                }
+                (EdgeType::ModuleRequiresProvider(_), GraphNode::Provider(p))
+                    if !self.scanned.contains(&p.repository) =>
+                {
+                    children.insert(scope_of(p.repository.as_ref(), &p.file_path));
+                }
+                (EdgeType::RequiresRuntime, GraphNode::Runtime(r))
+                    if !self.scanned.contains(&r.repository) =>
+                {
+                    children.insert(scope_of(r.repository.as_ref(), &r.file_path));
+                }
+                _ => {}
+            }
+        }
+        children
+    }
+}
+
+pub(super) fn location(file: &Path, line: usize, repository: Option<&String>) -> Location {
+    Location {
+        file: file.to_path_buf(),
+        line,
+        column: None,
+        repository: repository.cloned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DeprecationRef;
+    use crate::graph::GraphBuilder;
+
+    fn local_module(name: &str, dir: &str, path: &str) -> ModuleRef {
+        ModuleRef {
+            name: name.to_string(),
+            source: ModuleSource::Local {
+                path: path.to_string(),
+            },
+            version_constraint: None,
+            file_path: PathBuf::from(dir).join("main.tf"),
+            line_number: 1,
+            repository: Some("infra".to_string()),
+            attributes: Default::default(),
+            dependencies: Default::default(),
+        }
+    }
+
+    fn aws(dir: &str, constraint: &str) -> ProviderRef {
+        ProviderRef {
+            name: "aws".to_string(),
+            source: Some("hashicorp/aws".to_string()),
+            version_constraint: Some(Constraint::parse(constraint).unwrap()),
+            file_path: PathBuf::from(dir).join("versions.tf"),
+            line_number: 3,
+            repository: Some("infra".to_string()),
+        }
+    }
+
+    fn analyze(
+        modules: &[ModuleRef],
+        providers: &[ProviderRef],
+    ) -> (Vec<EffectiveConstraint>, Vec<Finding>, Config) {
+        let graph = GraphBuilder::new().build(modules, providers, &[]).unwrap();
+        let constraints = effective_constraints(&graph, modules, providers);
+        let mut config = Config::default();
+        config.deprecations.enforce_undated = true;
+        config.deprecations.providers.insert(
+            "hashicorp/aws".to_string(),
+            vec![DeprecationRef {
+                version: Some("< 5.0.0".to_string()),
+                reason: "v4 is end of life".to_string(),
+                severity: "error".to_string(),
+                replacement: "5.x".to_string(),
+                ..Default::default()
+            }],
+        );
+        let findings = effective_constraint_findings(&constraints, &config);
+        (constraints, findings, config)
+    }
+
+    #[test]
+    fn test_intersection_across_nested_local_modules() {
+        let modules = vec![
+            local_module("network", "envs/prod", "../../modules/network"),
+            local_module("dns", "modules/network", "../dns"),
+        ];
+        let providers = vec![
+            aws("envs/prod", ">= 5.0.0"),
+            aws("modules/network", "~> 5.10"),
+            aws("modules/dns", "< 5.30.0"),
+        ];
+        let (constraints, findings, _) = analyze(&modules, &providers);
+
+        assert_eq!(constraints.len(), 1);
+        let effective = &constraints[0];
+        assert_eq!(effective.root_label(), "infra:envs/prod");
+        assert_eq!(effective.provider, "hashicorp/aws");
+        assert_eq!(
+            effective.constraint().unwrap().raw,
+            ">= 5.0.0, ~> 5.10, < 5.30.0"
+        );
+        assert_eq!(
+            effective.origins[2].path(),
+            "root -> module.network -> module.dns"
+        );
+        assert!(effective.is_satisfiable());
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_unsatisfiable_constraint_reports_conflicting_chain() {
+        let modules = vec![
+            local_module("network", "envs/prod", "../../modules/network"),
+            local_module("legacy", "modules/network", "../legacy"),
+        ];
+        let providers = vec![
+            aws("envs/prod", ">= 5.0.0"),
+            aws("modules/network", ">= 4.0.0"),
+            aws("modules/legacy", "~> 4.67"),
+        ];
+        let (_, findings, _) = analyze(&modules, &providers);
+
+        assert_eq!(findings.len(), 1);
+        let finding = &findings[0];
+        assert_eq!(finding.code, "unsatisfiable-provider-constraint");
+        assert_eq!(finding.severity, Severity::Error);
+        assert_eq!(
+            finding.message,
+            "No version of provider 'hashicorp/aws' satisfies root module 'infra:envs/prod': \
+             '>= 5.0.0' (root) conflicts with '~> 4.67' (root -> module.network -> module.legacy)"
+        );
+        assert_eq!(
+            finding.location.as_ref().unwrap().file,
+            PathBuf::from("envs/prod/versions.tf")
+        );
+        assert_eq!(
+            finding.related_locations[0].file,
+            PathBuf::from("modules/legacy/versions.tf")
+        );
+    }
+
+    #[test]
+    fn test_child_pinning_deprecated_range_is_flagged() {
+        let modules = vec![local_module("legacy", "envs/prod", "../../modules/legacy")];
+        let providers = vec![
+            aws("envs/prod", ">= 4.0.0"),
+            aws("modules/legacy", "~> 4.67"),
+        ];
+        let (_, findings, _) = analyze(&modules, &providers);
+
+        assert_eq!(findings.len(), 1);
+        let finding = &findings[0];
+        assert_eq!(finding.code, "deprecated-provider-pinned");
+        assert_eq!(finding.severity, Severity::Error);
+        assert_eq!(finding.resource.as_deref(), Some("module.legacy"));
+        assert!(finding
+            .message
+            .starts_with("root -> module.legacy is the only reason"));
+
+        // Not the sole reason when the root pins the deprecated range as well
+        let providers = vec![
+            aws("envs/prod", "< 5.0.0"),
+            aws("modules/legacy", "~> 4.67"),
+        ];
+        let (_, findings, _) = analyze(&modules, &providers);
+        assert!(findings.is_empty());
+    }
+}
@@ -18,6 +18,30 @@
 //!
 //! 5. **Risky Patterns**: Flags wildcards, pre-release versions, etc.
 //!
+//! 6. **Security Advisories**: Matches modules and providers against an
+//!    offline advisory database.
+//!
+//! 7. **Outdated Versions**: Compares constraints with the releases
+//!    published in module and provider registries.
+//!
+//! 8. **Suppressions**: Applies inline `# monphare:ignore` comments and
+//!    flags stale ones.
+//!
+//! 9. **Baselines**: Separates findings recorded in a baseline file from
+//!    new ones.
+//!
+//! 10. **Circular Dependencies**: Reports modules that depend on each
+//!     other in a loop.
+//!
+//! 11. **Effective Provider Constraints**: Intersects provider constraints
+//!     across each root module's tree and reports unsatisfiable ones.
+//!
+//! 12. **Runtime Compatibility**: Checks `required_version` constraints
+//!     along each root module's tree and against the approved runtimes.
+//!
+//! 13. **Changed Spans**: Keeps only findings on lines changed since a git
+//!     ref, for pull request pipelines.
+//!
 //! # Example
 //!
 //! ```rust,no_run
@@ -31,9 +55,20 @@
 //! // let result = analyzer.analyze(&graph, &modules, &providers)?;
 //! ```
 
+mod advisory;
+mod baseline;
 mod conflict;
+mod cycles;
 mod deprecation;
+mod effective;
+mod outdated;
 mod patterns;
+mod runtime;
+mod suppression;
 
+pub use advisory::{Advisory, AdvisoryDatabase, AdvisoryKind};
+pub use baseline::{fingerprint, Baseline};
+pub(crate) use baseline::{relative_file, scan_root};
 pub use conflict::Analyzer;
+pub use effective::{effective_constraints, ConstraintOrigin, EffectiveConstraint};
 pub use patterns::{PatternChecker, RiskyPattern};
@@ -0,0 +1,328 @@
+//! Outdated version detection.
+//!
+//! Compares registry module and provider constraints with the releases
+//! published in their registry (see [`crate::registry`]) and reports:
+//!
+//! - `outdated-major`: the constraint excludes every release of the latest
+//!   major version
+//! - `outdated-version`: newer releases exist and the newest release the
+//!   constraint allows is older than `analysis.max_age_months`
+//!
+//! Every reference with registry data also gets a [`VersionStatus`] telling
+//! how many releases it is behind.
+
+use crate::registry::{PackageAddress, PackageVersions, RegistryIndex};
+use crate::types::{
+    Constraint, Finding, FindingCategory, Location, ModuleRef, ProviderRef, Severity, VersionStatus,
+};
+use chrono::{DateTime, Months, Utc};
+
+/// Outdated checks for every reference with registry data.
+pub(crate) struct OutdatedChecker<'a> {
+    index: &'a RegistryIndex,
+    max_age_months: u32,
+    now: DateTime<Utc>,
+}
+
+/// A module or provider reference, as seen by the checks.
+struct Reference<'a> {
+    label: &'static str,
+    name: &'a str,
+    resource: String,
+    address: PackageAddress,
+    constraint: Option<&'a Constraint>,
+    location: Location,
+}
+
+impl<'a> OutdatedChecker<'a> {
+    pub(crate) fn new(index: &'a RegistryIndex, max_age_months: u32, now: DateTime<Utc>) -> Self {
+        Self {
+            index,
+            max_age_months,
+            now,
+        }
+    }
+
+    /// Check every reference, returning findings and version statuses.
+    pub(crate) fn check(
+        &self,
+        modules: &[ModuleRef],
+        providers: &[ProviderRef],
+    ) -> (Vec<Finding>, Vec<VersionStatus>) {
+        let module_refs = modules.iter().filter_map(|m| {
+            PackageAddress::for_module(&m.source).map(|address| Reference {
+                label: "Module",
+                name: &m.name,
+                resource: format!("module.{}", m.name),
+                address,
+                constraint: m.version_constraint.as_ref(),
+                location: Location {
+                    file: m.file_path.clone(),
+                    line: m.line_number,
+                    column: None,
+                    repository: m.repository.clone(),
+                },
+            })
+        });
+        let provider_refs = providers.iter().filter_map(|p| {
+            PackageAddress::for_provider(p).map(|address| Reference {
+                label: "Provider",
+                name: &p.name,
+                resource: format!("provider.{}", p.name),
+                address,
+                constraint: p.version_constraint.as_ref(),
+                location: Location {
+                    file: p.file_path.clone(),
+                    line: p.line_number,
+                    column: None,
+                    repository: p.repository.clone(),
+                },
+            })
+        });
+
+        let mut findings = Vec::new();
+        let mut statuses = Vec::new();
+        for reference in module_refs.chain(provider_refs) {
+            let Some(versions) = self.index.get(&reference.address) else {
+                continue;
+            };
+            let Some(latest) = versions.latest() else {
+                continue;
+            };
+
+            let newest = versions.newest_allowed(reference.constraint);
+            let releases_behind = newest.map_or(0, |v| versions.releases_after(v));
+            let published = newest.and_then(|v| versions.published.get(v)).copied();
+
+            findings.extend(self.check_major(&reference, versions, latest, releases_behind));
+            if let (Some(newest), Some(published)) = (newest, published) {
+                findings.extend(self.check_age(
+                    &reference,
+                    newest,
+                    published,
+                    latest,
+                    releases_behind,
+                ));
+            }
+
+            statuses.push(VersionStatus {
+                resource: reference.resource,
+                source: reference.address.to_string(),
+                constraint: reference.constraint.map(|c| c.raw.clone()),
+                newest_allowed: newest.map(ToString::to_string),
+                newest_allowed_published: published,
+                latest: latest.to_string(),
+                releases_behind,
+                location: reference.location,
+            });
+        }
+
+        tracing::debug!(
+            findings = findings.len(),
+            references = statuses.len(),
+            "Outdated version check complete"
+        );
+        (findings, statuses)
+    }
+
+    /// Report constraints excluding every release of the latest major.
+    fn check_major(
+        &self,
+        reference: &Reference<'_>,
+        versions: &PackageVersions,
+        latest: &semver::Version,
+        releases_behind: usize,
+    ) -> Option<Finding> {
+        let constraint = reference.constraint?;
+        let allows_latest_major = versions
+            .versions
+            .iter()
+            .filter(|v| v.major == latest.major && v.pre.is_empty())
+            .any(|v| constraint.is_satisfied_by(v));
+        if allows_latest_major {
+            return None;
+        }
+
+        Some(self.finding(
+            reference,
+            "outdated-major",
+            Severity::Warning,
+            format!(
+                "{} '{}' constraint '{}' excludes the latest major version {} (latest {latest})",
+                reference.label, reference.name, constraint.raw, latest.major
+            ),
+            releases_behind,
+            latest,
+        ))
+    }
+
+    /// Report an old newest-allowed release when newer releases exist.
+    fn check_age(
+        &self,
+        reference: &Reference<'_>,
+        newest: &semver::Version,
+        published: DateTime<Utc>,
+        latest: &semver::Version,
+        releases_behind: usize,
+    ) -> Option<Finding> {
+        if releases_behind == 0 {
+            return None;
+        }
+        let cutoff = self
+            .now
+            .checked_sub_months(Months::new(self.max_age_months))?;
+        if published >= cutoff {
+            return None;
+        }
+
+        let months = months_between(published, self.now);
+        Some(self.finding(
+            reference,
+            "outdated-version",
+            Severity::Info,
+            format!(
+                "{} '{}' newest allowed version {newest} was released {months} months ago (limit {})",
+                reference.label, reference.name, self.max_age_months
+            ),
+            releases_behind,
+            latest,
+        ))
+    }
+
+    fn finding(
+        &self,
+        reference: &Reference<'_>,
+        code: &str,
+        severity: Severity,
+        message: String,
+        releases_behind: usize,
+        latest: &semver::Version,
+    ) -> Finding {
+        Finding {
+            code: code.to_string(),
+            severity,
+            message,
+            description: Some(format!(
+                "{releases_behind} release(s) behind the latest version {latest}."
+            )),
+            location: Some(reference.location.clone()),
+            related_locations: vec![],
+            suggestion: Some(format!(
+                "Review the changelog and allow version {latest}, e.g., version = \"~> {}.{}\"",
+                latest.major, latest.minor
+            )),
+            category: FindingCategory::Outdated,
+            suppressed_by: None,
+            resource: Some(reference.resource.clone()),
+            source: Some(reference.address.to_string()),
+            origin: None,
+        }
+    }
+}
+
+/// Whole months from `from` to `to`.
+fn months_between(from: DateTime<Utc>, to: DateTime<Utc>) -> u32 {
+    let mut months = 0;
+    while from
+        .checked_add_months(Months::new(months + 1))
+        .is_some_and(|d| d <= to)
+    {
+        months += 1;
+    }
+    months
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::PackageKind;
+    use crate::types::ModuleSource;
+    use chrono::TimeZone;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn v(s: &str) -> semver::Version {
+        semver::Version::parse(s).unwrap()
+    }
+
+    fn vpc(version: &str) -> ModuleRef {
+        ModuleRef {
+            name: "vpc".to_string(),
+            source: ModuleSource::Registry {
+                hostname: "registry.terraform.io".to_string(),
+                namespace: "terraform-aws-modules".to_string(),
+                name: "vpc".to_string(),
+                provider: "aws".to_string(),
+                subdir: None,
+                params: Default::default(),
+            },
+            version_constraint: Some(Constraint::parse(version).unwrap()),
+            file_path: PathBuf::from("main.tf"),
+            line_number: 3,
+            repository: None,
+            attributes: HashMap::new(),
+            dependencies: Default::default(),
+        }
+    }
+
+    fn index() -> RegistryIndex {
+        let mut versions =
+            PackageVersions::new(vec![v("4.0.0"), v("4.1.0"), v("5.0.0"), v("5.1.0")]);
+        versions.published.insert(
+            v("4.1.0"),
+            Utc.with_ymd_and_hms(2023, 1, 10, 0, 0, 0).unwrap(),
+        );
+        versions.published.insert(
+            v("5.0.0"),
+            Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap(),
+        );
+
+        let mut index = RegistryIndex::default();
+        index.insert(
+            PackageAddress::new(
+                PackageKind::Module,
+                "registry.terraform.io",
+                "terraform-aws-modules/vpc/aws",
+            ),
+            versions,
+        );
+        index
+    }
+
+    #[test]
+    fn test_old_major_is_reported_with_releases_behind() {
+        let index = index();
+        let now = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let checker = OutdatedChecker::new(&index, 12, now);
+
+        let (findings, statuses) = checker.check(&[vpc("~> 4.0")], &[]);
+
+        let codes: Vec<_> = findings.iter().map(|f| f.code.as_str()).collect();
+        assert_eq!(codes, ["outdated-major", "outdated-version"]);
+        assert!(findings[0].message.contains("major version 5"));
+        assert!(findings[1].message.contains("16 months ago"));
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].newest_allowed.as_deref(), Some("4.1.0"));
+        assert_eq!(statuses[0].latest, "5.1.0");
+        assert_eq!(statuses[0].releases_behind, 2);
+    }
+
+    #[test]
+    fn test_recent_constraint_is_not_reported() {
+        let index = index();
+        let now = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let checker = OutdatedChecker::new(&index, 12, now);
+
+        let (findings, statuses) = checker.check(&[vpc("5.0.0")], &[]);
+
+        assert!(findings.is_empty());
+        assert_eq!(statuses[0].releases_behind, 1);
+    }
+
+    #[test]
+    fn test_months_between() {
+        let from = Utc.with_ymd_and_hms(2024, 1, 31, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2024, 3, 30, 0, 0, 0).unwrap();
+        assert_eq!(months_between(from, to), 1);
+    }
+}
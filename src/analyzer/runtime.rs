@@ -0,0 +1,256 @@
+//! Runtime compatibility analysis.
+//!
+//! Terraform and OpenTofu check the `required_version` of the root module
+//! and of every module it calls, so a configuration can only be applied
+//! with a version that satisfies all of them. This module intersects those
+//! constraints along each root module's tree, reports child modules that
+//! allow no version the root allows, and reports `required_version`
+//! constraints that allow no version of the organization's approved range
+//! (`policies.approved_runtimes`).
+
+use crate::analyzer::effective::{
+    intersection, location, root_label, root_scopes, ConstraintOrigin, ModuleTree, Requirement,
+};
+use crate::config::Config;
+use crate::graph::DependencyGraph;
+use crate::types::{
+    Constraint, Finding, FindingCategory, ModuleRef, ProviderRef, RuntimeRef, Severity, VersionSet,
+};
+
+/// Runtime compatibility findings for the scanned configuration.
+pub(crate) fn runtime_findings(
+    graph: &DependencyGraph,
+    modules: &[ModuleRef],
+    providers: &[ProviderRef],
+    runtimes: &[RuntimeRef],
+    config: &Config,
+) -> Vec<Finding> {
+    let tree = ModuleTree::new(graph, modules, providers, runtimes);
+
+    let mut findings = Vec::new();
+    for (repository, root) in root_scopes(modules, providers, runtimes) {
+        let label = root_label(repository.as_ref(), &root);
+        let origins = tree.origins(&(repository, root), Requirement::Runtime);
+        for (runtime, origins) in &origins {
+            let (root_origins, child_origins): (Vec<_>, Vec<_>) =
+                origins.iter().partition(|o| o.chain.is_empty());
+            if root_origins.is_empty() {
+                continue;
+            }
+            let allowed = intersection(root_origins.iter().copied());
+            for child in child_origins {
+                if VersionSet::from(&child.constraint)
+                    .intersect(&allowed)
+                    .is_empty()
+                {
+                    findings.push(incompatible_finding(runtime, &label, &root_origins, child));
+                }
+            }
+        }
+    }
+
+    for runtime in runtimes {
+        let Some(approved) = config
+            .policies
+            .approved_runtimes
+            .get(&runtime.name)
+            .and_then(|v| Constraint::parse(v).ok())
+        else {
+            continue;
+        };
+        if VersionSet::from(&runtime.version)
+            .intersect(&VersionSet::from(&approved))
+            .is_empty()
+        {
+            findings.push(unapproved_finding(runtime, &approved));
+        }
+    }
+    findings
+}
+
+fn incompatible_finding(
+    runtime: &str,
+    root: &str,
+    root_origins: &[&ConstraintOrigin],
+    child: &ConstraintOrigin,
+) -> Finding {
+    let root_constraints: Vec<&str> = root_origins
+        .iter()
+        .map(|o| o.constraint.raw.as_str())
+        .collect();
+    let module = child.chain.last().cloned().unwrap_or_default();
+
+    Finding {
+        code: "incompatible-runtime-constraint".to_string(),
+        severity: Severity::Error,
+        message: format!(
+            "Module '{}' requires {} '{}', which root module '{}' does not allow ('{}')",
+            module,
+            runtime,
+            child.constraint.raw,
+            root,
+            root_constraints.join(", ")
+        ),
+        description: Some(format!(
+            "{runtime} checks the required_version of every module in the configuration, \
+             so no {runtime} version can run root module '{root}'. Module chain: {}",
+            child.path()
+        )),
+        location: Some(child.location.clone()),
+        related_locations: root_origins.iter().map(|o| o.location.clone()).collect(),
+        suggestion: Some(format!(
+            "Widen the required_version of the root module, or use a version of '{module}' \
+             that supports the {runtime} versions the root allows"
+        )),
+        category: FindingCategory::Dependency,
+        suppressed_by: None,
+        resource: Some(module),
+        source: Some(runtime.to_string()),
+        origin: None,
+    }
+}
+
+fn unapproved_finding(runtime: &RuntimeRef, approved: &Constraint) -> Finding {
+    let scope = runtime
+        .repository
+        .clone()
+        .unwrap_or_else(|| runtime.file_path.display().to_string());
+
+    Finding {
+        code: "unapproved-runtime-version".to_string(),
+        severity: Severity::Error,
+        message: format!(
+            "'{}' requires {} '{}', which allows no approved version ('{}')",
+            scope, runtime.name, runtime.version.raw, approved.raw
+        ),
+        description: Some(format!(
+            "The organization approves {} versions '{}', but required_version rules all of \
+             them out, so this configuration cannot run on an approved {}.",
+            runtime.name, approved.raw, runtime.name
+        )),
+        location: Some(location(
+            &runtime.file_path,
+            runtime.line_number,
+            runtime.repository.as_ref(),
+        )),
+        related_locations: Vec::new(),
+        suggestion: Some(format!(
+            "Update required_version to include the approved range '{}'",
+            approved.raw
+        )),
+        category: FindingCategory::BestPractice,
+        suppressed_by: None,
+        resource: Some(runtime.name.clone()),
+        source: Some(runtime.name.clone()),
+        origin: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::GraphBuilder;
+    use crate::types::{ModuleSource, RuntimeSource};
+    use std::path::PathBuf;
+
+    fn local_module(name: &str, dir: &str, path: &str) -> ModuleRef {
+        ModuleRef {
+            name: name.to_string(),
+            source: ModuleSource::Local {
+                path: path.to_string(),
+            },
+            version_constraint: None,
+            file_path: PathBuf::from(dir).join("main.tf"),
+            line_number: 1,
+            repository: Some("infra".to_string()),
+            attributes: Default::default(),
+            dependencies: Default::default(),
+        }
+    }
+
+    fn terraform(dir: &str, constraint: &str) -> RuntimeRef {
+        RuntimeRef {
+            name: "terraform".to_string(),
+            version: Constraint::parse(constraint).unwrap(),
+            source: RuntimeSource::Terraform,
+            file_path: PathBuf::from(dir).join("versions.tf"),
+            line_number: 0,
+            repository: Some("infra".to_string()),
+        }
+    }
+
+    fn analyze(modules: &[ModuleRef], runtimes: &[RuntimeRef], config: &Config) -> Vec<Finding> {
+        let graph = GraphBuilder::new().build(modules, &[], runtimes).unwrap();
+        runtime_findings(&graph, modules, &[], runtimes, config)
+    }
+
+    #[test]
+    fn test_child_requires_newer_runtime_than_root() {
+        let modules = vec![
+            local_module("network", "envs/prod", "../../modules/network"),
+            local_module("dns", "modules/network", "../dns"),
+        ];
+        let runtimes = vec![
+            terraform("envs/prod", ">= 1.3.0, < 1.6.0"),
+            terraform("modules/network", ">= 1.0.0"),
+            terraform("modules/dns", ">= 1.7.0"),
+        ];
+        let findings = analyze(&modules, &runtimes, &Config::default());
+
+        assert_eq!(findings.len(), 1);
+        let finding = &findings[0];
+        assert_eq!(finding.code, "incompatible-runtime-constraint");
+        assert_eq!(finding.severity, Severity::Error);
+        assert_eq!(finding.resource.as_deref(), Some("module.dns"));
+        assert!(finding.message.contains("'>= 1.7.0'"));
+        assert!(finding.message.contains("'infra:envs/prod'"));
+        assert!(finding
+            .description
+            .as_deref()
+            .unwrap()
+            .contains("root -> module.network -> module.dns"));
+        assert_eq!(
+            finding.location.as_ref().unwrap().file,
+            PathBuf::from("modules/dns/versions.tf")
+        );
+        assert_eq!(
+            finding.related_locations[0].file,
+            PathBuf::from("envs/prod/versions.tf")
+        );
+    }
+
+    #[test]
+    fn test_unconstrained_root_allows_any_child_runtime() {
+        let modules = vec![local_module(
+            "network",
+            "envs/prod",
+            "../../modules/network",
+        )];
+        let runtimes = vec![terraform("modules/network", ">= 1.7.0")];
+
+        assert!(analyze(&modules, &runtimes, &Config::default()).is_empty());
+    }
+
+    #[test]
+    fn test_runtime_outside_approved_range() {
+        let runtimes = vec![
+            terraform("envs/prod", "~> 0.13.0"),
+            terraform("envs/dev", ">= 1.0.0"),
+        ];
+        let mut config = Config::default();
+        config
+            .policies
+            .approved_runtimes
+            .insert("terraform".to_string(), ">= 1.5.0, < 2.0.0".to_string());
+        let findings = analyze(&[], &runtimes, &config);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].code, "unapproved-runtime-version");
+        assert_eq!(findings[0].category, FindingCategory::BestPractice);
+        assert_eq!(
+            findings[0].message,
+            "'infra' requires terraform '~> 0.13.0', which allows no approved version \
+             ('>= 1.5.0, < 2.0.0')"
+        );
+    }
+}
@@ -0,0 +1,167 @@
+//! Inline suppression matching.
+//!
+//! Applies `# monphare:ignore` directives collected by the parser to the
+//! analysis findings. Suppressed findings are kept (marked with the directive
+//! that silenced them) so reports can still show them, and directives that no
+//! longer match anything are reported as stale.
+
+use crate::types::{Finding, FindingCategory, Location, Severity, Suppression, SuppressionScope};
+
+/// Mark findings silenced by a suppression and return findings for stale ones.
+///
+/// The first matching directive wins. Stale suppression findings are never
+/// themselves suppressible, otherwise a directive could keep itself alive.
+pub(crate) fn apply_suppressions(
+    findings: &mut [Finding],
+    suppressions: &[Suppression],
+) -> Vec<Finding> {
+    let mut used = vec![false; suppressions.len()];
+
+    for finding in findings.iter_mut() {
+        if let Some(idx) = suppressions.iter().position(|s| s.matches(finding)) {
+            tracing::debug!(
+                code = %finding.code,
+                file = %suppressions[idx].file.display(),
+                line = suppressions[idx].line,
+                "Finding suppressed"
+            );
+            finding.suppressed_by = Some(suppressions[idx].clone());
+            used[idx] = true;
+        }
+    }
+
+    suppressions
+        .iter()
+        .zip(used)
+        .filter(|(_, used)| !used)
+        .map(|(suppression, _)| stale_finding(suppression))
+        .collect()
+}
+
+/// Build the finding reported for a suppression that matched nothing.
+fn stale_finding(suppression: &Suppression) -> Finding {
+    let codes = if suppression.codes.is_empty() {
+        "all findings".to_string()
+    } else {
+        suppression
+            .codes
+            .iter()
+            .map(|c| format!("'{c}'"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let target = match suppression.scope {
+        SuppressionScope::File => "in this file".to_string(),
+        SuppressionScope::Lines { start, end } if start == end => format!("on line {start}"),
+        SuppressionScope::Lines { start, end } => format!("on lines {start}-{end}"),
+    };
+
+    Finding {
+        code: "stale-suppression".to_string(),
+        severity: Severity::Warning,
+        message: format!("Suppression of {codes} {target} no longer matches any finding"),
+        description: Some(match &suppression.reason {
+            Some(reason) => format!(
+                "The directive was added with reason \"{reason}\" but the issue it \
+                 silenced is gone. Stale suppressions hide future regressions."
+            ),
+            None => "The issue this directive silenced is gone. Stale suppressions \
+                     hide future regressions."
+                .to_string(),
+        }),
+        location: Some(Location {
+            file: suppression.file.clone(),
+            line: suppression.line,
+            column: None,
+            repository: suppression.repository.clone(),
+        }),
+        related_locations: vec![],
+        suggestion: Some("Remove the monphare:ignore comment".to_string()),
+        category: FindingCategory::Configuration,
+        suppressed_by: None,
+        resource: None,
+        source: None,
+        origin: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn finding(code: &str, line: usize) -> Finding {
+        Finding {
+            code: code.to_string(),
+            severity: Severity::Info,
+            message: format!("'vpc' {code}"),
+            description: None,
+            location: Some(Location {
+                file: PathBuf::from("main.tf"),
+                line,
+                column: None,
+                repository: Some("repo".to_string()),
+            }),
+            related_locations: vec![],
+            suggestion: None,
+            category: FindingCategory::BestPractice,
+            suppressed_by: None,
+            resource: Some("module.vpc".to_string()),
+            source: None,
+            origin: None,
+        }
+    }
+
+    fn suppression(codes: &[&str], scope: SuppressionScope) -> Suppression {
+        Suppression {
+            codes: codes.iter().map(|c| (*c).to_string()).collect(),
+            reason: Some("pinned for FIPS".to_string()),
+            file: PathBuf::from("main.tf"),
+            line: 1,
+            scope,
+            repository: Some("repo".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_suppresses_matching_code_in_span() {
+        let mut findings = vec![finding("exact-version", 3), finding("no-upper-bound", 3)];
+        let suppressions = vec![suppression(
+            &["exact-version"],
+            SuppressionScope::Lines { start: 2, end: 5 },
+        )];
+
+        let stale = apply_suppressions(&mut findings, &suppressions);
+
+        assert!(stale.is_empty());
+        assert!(findings[0].is_suppressed());
+        assert!(!findings[1].is_suppressed());
+    }
+
+    #[test]
+    fn test_file_scope_without_codes_suppresses_everything() {
+        let mut findings = vec![finding("exact-version", 3), finding("no-upper-bound", 40)];
+        let suppressions = vec![suppression(&[], SuppressionScope::File)];
+
+        apply_suppressions(&mut findings, &suppressions);
+
+        assert!(findings.iter().all(Finding::is_suppressed));
+    }
+
+    #[test]
+    fn test_reports_stale_suppression() {
+        let mut findings = vec![finding("exact-version", 10)];
+        let suppressions = vec![suppression(
+            &["exact-version"],
+            SuppressionScope::Lines { start: 2, end: 5 },
+        )];
+
+        let stale = apply_suppressions(&mut findings, &suppressions);
+
+        assert!(!findings[0].is_suppressed());
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].code, "stale-suppression");
+        assert_eq!(stale[0].location.as_ref().unwrap().line, 1);
+        assert!(stale[0].message.contains("'exact-version' on lines 2-5"));
+    }
+}
@@ -7,6 +7,8 @@
 //!
 //! - `scan`: Scan directories or repositories for Terraform files
 //! - `graph`: Generate dependency graph visualizations
+//! - `impact`: List who uses a module or provider source
+//! - `diff`: Compare two scans from snapshots or git refs
 //! - `init`: Create an example configuration file
 //! - `validate`: Validate a configuration file
 //!
@@ -22,9 +24,28 @@
 //! # Generate JSON report
 //! monphare scan ./terraform --format json --output report.json
 //!
+//! # In a pull request, report only findings on changed lines
+//! monphare scan . --changed-since origin/main
+//!
 //! # Generate dependency graph
 //! monphare graph ./terraform --format dot --output deps.dot
 //!
+//! # Graph one module source and its direct neighbours
+//! monphare graph ./terraform --filter terraform-aws-modules/vpc --depth 1
+//!
+//! # Who breaks if the VPC module drops support for 4.x?
+//! monphare impact terraform-aws-modules/vpc/aws ./terraform --candidate ">= 5.0"
+//!
+//! # Scan once, then graph the saved result without rescanning
+//! monphare scan ./terraform --snapshot scan.json
+//! monphare graph --from-snapshot scan.json --format mermaid
+//!
+//! # Rank shared modules by blast radius
+//! monphare graph ./terraform --stats=json
+//!
+//! # What does this branch change compared to main?
+//! monphare diff main HEAD --format markdown
+//!
 //! # Initialize configuration
 //! monphare init
 //!
@@ -32,7 +53,9 @@
 //! monphare validate monphare.yaml
 //! ```
 
-use crate::types::{GraphFormat, ReportFormat};
+use crate::error::Result;
+use crate::graph::{GraphFilter, NodeKind};
+use crate::types::{ClusterBy, GraphFormat, QueryFormat, ReportFormat};
 use clap::{Args, Parser, Subcommand};
 use std::path::PathBuf;
 
@@ -71,12 +94,20 @@ pub struct Cli {
 pub enum Commands {
     /// Scan directories or repositories for Terraform/OpenTofu files
     #[command(visible_alias = "s")]
-    Scan(ScanArgs),
+    Scan(Box<ScanArgs>),
 
     /// Generate dependency graph visualization
     #[command(visible_alias = "g")]
     Graph(GraphArgs),
 
+    /// List the modules and repositories that use a module or provider source
+    #[command(visible_alias = "i")]
+    Impact(ImpactArgs),
+
+    /// Compare two scans, each from a snapshot file or a git ref
+    #[command(visible_alias = "d")]
+    Diff(DiffArgs),
+
     /// Create an example configuration file
     Init,
 
@@ -134,6 +165,27 @@ pub struct ScanArgs {
     /// Skip confirmation prompt for large organizations
     #[arg(long)]
     pub yes: bool,
+
+    /// Report on a snapshot written by --snapshot instead of scanning
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["paths", "repositories", "github", "gitlab", "ado", "bitbucket"]
+    )]
+    pub from_snapshot: Option<PathBuf>,
+
+    /// Also save the scan result as a snapshot other commands can read
+    #[arg(long, value_name = "FILE", conflicts_with = "from_snapshot")]
+    pub snapshot: Option<PathBuf>,
+
+    /// Only report findings on Terraform files changed since this git ref
+    #[arg(
+        long,
+        value_name = "REF",
+        conflicts_with_all = ["from_snapshot", "repositories", "github", "gitlab", "ado", "bitbucket"]
+    )]
+    pub changed_since: Option<String>,
+
     /// Output format
     #[arg(short, long, default_value = "text", value_enum)]
     pub format: ReportFormat,
@@ -150,6 +202,34 @@ pub struct ScanArgs {
     #[arg(long)]
     pub continue_on_error: bool,
 
+    /// Baseline file of known findings; only new findings affect the exit code
+    #[arg(long, value_name = "FILE")]
+    pub baseline: Option<PathBuf>,
+
+    /// Write the current findings to the baseline file instead of reporting
+    #[arg(long, requires = "baseline")]
+    pub write_baseline: bool,
+
+    /// Directory of security advisories to check modules and providers against
+    #[arg(long, value_name = "DIR")]
+    pub advisory_db: Option<PathBuf>,
+
+    /// Query module and provider registries for newer releases
+    #[arg(long)]
+    pub outdated: bool,
+
+    /// Use cached registry responses only (implies --outdated)
+    #[arg(long)]
+    pub offline: bool,
+
+    /// Fetch remote module sources and add the modules they call to the graph
+    #[arg(long)]
+    pub resolve: bool,
+
+    /// Levels of remote modules to fetch (implies --resolve)
+    #[arg(long, value_name = "N")]
+    pub resolve_depth: Option<usize>,
+
     /// Maximum depth for recursive directory scanning
     #[arg(long, default_value = "100")]
     pub max_depth: usize,
@@ -171,9 +251,13 @@ pub struct ScanArgs {
 #[derive(Args, Debug)]
 pub struct GraphArgs {
     /// Paths to scan
-    #[arg(value_name = "PATH", required = true)]
+    #[arg(value_name = "PATH", required_unless_present = "from_snapshot")]
     pub paths: Vec<PathBuf>,
 
+    /// Read the graph from a snapshot written by `scan --snapshot` instead of scanning
+    #[arg(long, value_name = "FILE", conflicts_with = "paths")]
+    pub from_snapshot: Option<PathBuf>,
+
     /// Output format for the graph
     #[arg(short, long, default_value = "dot", value_enum)]
     pub format: GraphFormat,
@@ -183,7 +267,7 @@ pub struct GraphArgs {
     pub output: Option<PathBuf>,
 
     /// Include only modules (exclude providers)
-    #[arg(long)]
+    #[arg(long, conflicts_with = "providers_only")]
     pub modules_only: bool,
 
     /// Include only providers (exclude modules)
@@ -193,6 +277,138 @@ pub struct GraphArgs {
     /// Filter to specific module sources (partial match)
     #[arg(long, value_name = "FILTER")]
     pub filter: Option<String>,
+
+    /// Filter to sources matching a regular expression
+    #[arg(long, value_name = "REGEX", conflicts_with = "filter")]
+    pub filter_regex: Option<String>,
+
+    /// Include only nodes from this repository
+    #[arg(long, value_name = "NAME")]
+    pub repository: Option<String>,
+
+    /// Also include nodes within N hops of filtered nodes
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    pub depth: usize,
+
+    /// Highlight modules and edges that form a dependency cycle
+    #[arg(long)]
+    pub highlight_cycles: bool,
+
+    /// Group nodes by repository or VCS namespace (DOT and Mermaid)
+    #[arg(long, value_name = "GROUPING", value_enum)]
+    pub cluster_by: Option<ClusterBy>,
+
+    /// Print fan-in, centrality and blast-radius stats instead of the graph
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        value_enum,
+        num_args = 0..=1,
+        default_missing_value = "text"
+    )]
+    pub stats: Option<QueryFormat>,
+
+    /// Number of critical shared modules and central nodes to list with --stats
+    #[arg(long, value_name = "N", default_value_t = 10, requires = "stats")]
+    pub top: usize,
+
+    /// Fetch remote module sources and add the modules they call to the graph
+    #[arg(long)]
+    pub resolve: bool,
+
+    /// Levels of remote modules to fetch (implies --resolve)
+    #[arg(long, value_name = "N")]
+    pub resolve_depth: Option<usize>,
+}
+
+impl GraphArgs {
+    /// Build the graph filter described by these arguments.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `--filter-regex` is not a valid regular expression.
+    pub fn graph_filter(&self) -> Result<GraphFilter> {
+        let mut filter = GraphFilter::new().neighbourhood(self.depth);
+        if self.modules_only {
+            filter = filter.kinds([NodeKind::Module]);
+        } else if self.providers_only {
+            filter = filter.kinds([NodeKind::Provider]);
+        }
+        if let Some(needle) = &self.filter {
+            filter = filter.source_contains(needle.as_str());
+        }
+        if let Some(pattern) = &self.filter_regex {
+            filter = filter.source_regex(pattern)?;
+        }
+        if let Some(repository) = &self.repository {
+            filter = filter.repository(repository.as_str());
+        }
+        Ok(filter)
+    }
+}
+
+/// Arguments for the impact command.
+#[derive(Args, Debug)]
+pub struct ImpactArgs {
+    /// Module or provider source (e.g., terraform-aws-modules/vpc/aws or hashicorp/aws)
+    #[arg(value_name = "SOURCE")]
+    pub source: String,
+
+    /// Paths to scan
+    #[arg(value_name = "PATH")]
+    pub paths: Vec<PathBuf>,
+
+    /// Git repository URLs to clone and scan
+    #[arg(short, long = "repo", value_name = "URL")]
+    pub repositories: Vec<String>,
+
+    /// Query a snapshot written by `scan --snapshot` instead of scanning
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["paths", "repositories"])]
+    pub from_snapshot: Option<PathBuf>,
+
+    /// Version or constraint to check every consumer's constraint against
+    #[arg(long, value_name = "VERSION")]
+    pub candidate: Option<String>,
+
+    /// Output format for the report
+    #[arg(short, long, default_value = "text", value_enum)]
+    pub format: QueryFormat,
+
+    /// Output file path (stdout if not specified)
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Fetch remote module sources to include modules that use the source indirectly
+    #[arg(long)]
+    pub resolve: bool,
+
+    /// Levels of remote modules to fetch (implies --resolve)
+    #[arg(long, value_name = "N")]
+    pub resolve_depth: Option<usize>,
+}
+
+/// Arguments for the diff command.
+#[derive(Args, Debug)]
+pub struct DiffArgs {
+    /// Old scan: a snapshot file or a git ref of the repository at --path
+    #[arg(value_name = "OLD")]
+    pub old: String,
+
+    /// New scan: a snapshot file or a git ref of the repository at --path
+    #[arg(value_name = "NEW")]
+    pub new: String,
+
+    /// Local git repository to check out refs from
+    #[arg(long, value_name = "DIR", default_value = ".")]
+    pub path: PathBuf,
+
+    /// Output format for the report
+    #[arg(short, long, default_value = "text", value_enum)]
+    pub format: QueryFormat,
+
+    /// Output file path (stdout if not specified)
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
 }
 
 /// Arguments for the validate command.
@@ -266,6 +482,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_scan_with_baseline() {
+        let cli = Cli::parse_from([
+            "monphare",
+            "scan",
+            "./terraform",
+            "--baseline",
+            "baseline.json",
+            "--write-baseline",
+        ]);
+        match cli.command {
+            Commands::Scan(args) => {
+                assert_eq!(args.baseline, Some(PathBuf::from("baseline.json")));
+                assert!(args.write_baseline);
+            }
+            _ => panic!("Expected Scan command"),
+        }
+
+        let result = Cli::try_parse_from(["monphare", "scan", ".", "--write-baseline"]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_graph_command() {
         let cli = Cli::parse_from(["monphare", "graph", "./terraform", "--format", "mermaid"]);
@@ -277,6 +515,147 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_graph_stats_options() {
+        let cli = Cli::parse_from(["monphare", "graph", "./terraform", "--stats"]);
+        match cli.command {
+            Commands::Graph(args) => {
+                assert_eq!(args.stats, Some(QueryFormat::Text));
+                assert_eq!(args.top, 10);
+            }
+            _ => panic!("Expected Graph command"),
+        }
+
+        let cli = Cli::parse_from([
+            "monphare",
+            "graph",
+            "./terraform",
+            "--stats=json",
+            "--top",
+            "3",
+        ]);
+        match cli.command {
+            Commands::Graph(args) => {
+                assert_eq!(args.stats, Some(QueryFormat::Json));
+                assert_eq!(args.top, 3);
+            }
+            _ => panic!("Expected Graph command"),
+        }
+
+        let result = Cli::try_parse_from(["monphare", "graph", ".", "--top", "3"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_graph_filter_options() {
+        let cli = Cli::parse_from([
+            "monphare",
+            "graph",
+            "./terraform",
+            "--modules-only",
+            "--filter-regex",
+            "^registry\\.terraform\\.io/",
+            "--repository",
+            "network",
+            "--depth",
+            "2",
+        ]);
+        match cli.command {
+            Commands::Graph(args) => {
+                assert!(args.modules_only);
+                assert_eq!(args.repository.as_deref(), Some("network"));
+                assert_eq!(args.depth, 2);
+                let filter = args.graph_filter().unwrap();
+                assert_eq!(filter.neighbourhood_hops(), 2);
+            }
+            _ => panic!("Expected Graph command"),
+        }
+
+        let result = Cli::try_parse_from([
+            "monphare",
+            "graph",
+            ".",
+            "--modules-only",
+            "--providers-only",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_impact_command() {
+        let cli = Cli::parse_from([
+            "monphare",
+            "impact",
+            "terraform-aws-modules/vpc/aws",
+            "./terraform",
+            "--candidate",
+            "5.1.0",
+            "--format",
+            "markdown",
+        ]);
+        match cli.command {
+            Commands::Impact(args) => {
+                assert_eq!(args.source, "terraform-aws-modules/vpc/aws");
+                assert_eq!(args.paths, vec![PathBuf::from("./terraform")]);
+                assert_eq!(args.candidate.as_deref(), Some("5.1.0"));
+                assert_eq!(args.format, QueryFormat::Markdown);
+            }
+            _ => panic!("Expected Impact command"),
+        }
+    }
+
+    #[test]
+    fn test_diff_command() {
+        let cli = Cli::parse_from(["monphare", "diff", "main", "HEAD", "-f", "markdown"]);
+        match cli.command {
+            Commands::Diff(args) => {
+                assert_eq!(args.old, "main");
+                assert_eq!(args.new, "HEAD");
+                assert_eq!(args.path, PathBuf::from("."));
+                assert_eq!(args.format, QueryFormat::Markdown);
+            }
+            _ => panic!("Expected Diff command"),
+        }
+    }
+
+    #[test]
+    fn test_snapshot_options() {
+        let cli = Cli::parse_from(["monphare", "graph", "--from-snapshot", "scan.json"]);
+        match cli.command {
+            Commands::Graph(args) => {
+                assert!(args.paths.is_empty());
+                assert_eq!(args.from_snapshot, Some(PathBuf::from("scan.json")));
+            }
+            _ => panic!("Expected Graph command"),
+        }
+
+        assert!(Cli::try_parse_from(["monphare", "graph"]).is_err());
+        assert!(
+            Cli::try_parse_from(["monphare", "scan", ".", "--from-snapshot", "scan.json"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_changed_since_option() {
+        let cli = Cli::parse_from(["monphare", "scan", ".", "--changed-since", "origin/main"]);
+        match cli.command {
+            Commands::Scan(args) => {
+                assert_eq!(args.changed_since.as_deref(), Some("origin/main"));
+            }
+            _ => panic!("Expected Scan command"),
+        }
+
+        let result = Cli::try_parse_from([
+            "monphare",
+            "scan",
+            "--github",
+            "my-org",
+            "--changed-since",
+            "origin/main",
+        ]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_init_command() {
         let cli = Cli::parse_from(["monphare", "init"]);
@@ -24,6 +24,7 @@
 //!   check_prerelease: true
 //!   check_upper_bound: true
 //!   max_age_months: 12  # Flag modules older than this
+//!   check_stale_suppressions: true
 //!
 //! # Output options
 //! output:
@@ -43,9 +44,39 @@
 //!   allowed_providers:
 //!     - hashicorp/*
 //!   blocked_modules: []
+//!   approved_runtimes:
+//!     terraform: ">= 1.5.0, < 2.0.0"
+//!
+//! # Deprecation rules, optionally with a retirement schedule
+//! deprecations:
+//!   include:
+//!     - path: ./deprecations/*.yaml
+//!     - git: https://github.com/my-org/terraform-deprecations
+//!       ref: main
+//!   modules:
+//!     "terraform-aws-modules/eks/aws":
+//!       - version: "< 19.0.0"
+//!         reason: "EKS module v18 is being retired"
+//!         severity: error
+//!         replacement: ">= 19.0.0"
+//!         warn_from: 2025-03-01
+//!         enforce_from: 2025-06-30
+//!
+//! # Security advisory database (local directory or git mirror)
+//! advisories:
+//!   path: ./advisories
+//!
+//! # Provider guessed for non-registry modules whose required_providers are unknown
+//! graph:
+//!   provider_inference:
+//!     - source: "[/-]aws-"
+//!       provider: hashicorp/aws
+//!     - source: "github\\.com/acme/terraform-(\\w+)-"
+//!       provider: acme/$1
 //! ```
 
 use crate::error::Result;
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -87,6 +118,10 @@ pub struct AnalysisOptions {
     /// Maximum age in months before flagging as outdated.
     #[serde(default = "default_max_age")]
     pub max_age_months: u32,
+
+    /// Flag `# monphare:ignore` comments that no longer match any finding.
+    #[serde(default = "default_true")]
+    pub check_stale_suppressions: bool,
 }
 
 /// Output options.
@@ -314,6 +349,167 @@ impl GitOptions {
     }
 }
 
+/// Registry options for version lookups.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RegistryOptions {
+    /// Query module and provider registries for newer releases (default: false)
+    pub enabled: bool,
+
+    /// Only use cached registry responses, never the network (default: false)
+    pub offline: bool,
+
+    /// Cache directory path (default: ~/.cache/monphare/registry)
+    /// Supports environment variable expansion
+    pub cache_dir: Option<String>,
+
+    /// Time-to-live in hours for cached registry responses (default: 24)
+    #[serde(default = "default_cache_ttl")]
+    pub cache_ttl_hours: u64,
+
+    /// Private registry or Terraform Enterprise hosts (e.g., `tf.internal.example.com`)
+    ///
+    /// Module source URLs on these hosts are parsed as registry sources.
+    pub hosts: Vec<String>,
+
+    /// Terraform CLI config file to read `credentials` blocks from
+    /// (default: `TF_CLI_CONFIG_FILE`, then `~/.terraformrc` or `%APPDATA%/terraform.rc`)
+    /// Supports environment variable expansion
+    pub credentials_file: Option<String>,
+}
+
+impl Default for RegistryOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            offline: false,
+            cache_dir: None,
+            cache_ttl_hours: default_cache_ttl(),
+            hosts: Vec::new(),
+            credentials_file: None,
+        }
+    }
+}
+
+impl RegistryOptions {
+    /// Get the registry cache directory, with environment variable expansion.
+    #[must_use]
+    pub fn get_cache_dir(&self) -> std::path::PathBuf {
+        if let Some(dir) = &self.cache_dir {
+            std::path::PathBuf::from(expand_env_vars(dir))
+        } else {
+            dirs::cache_dir()
+                .unwrap_or_else(|| std::path::PathBuf::from("/tmp"))
+                .join("monphare")
+                .join("registry")
+        }
+    }
+
+    /// Get the Terraform CLI config file holding registry credentials, if any.
+    ///
+    /// Uses `credentials_file`, then `TF_CLI_CONFIG_FILE`, then the platform
+    /// default (`~/.terraformrc`, or `%APPDATA%/terraform.rc` on Windows).
+    #[must_use]
+    pub fn get_credentials_file(&self) -> Option<std::path::PathBuf> {
+        if let Some(file) = &self.credentials_file {
+            return Some(std::path::PathBuf::from(expand_env_vars(file)));
+        }
+        if let Ok(file) = std::env::var("TF_CLI_CONFIG_FILE") {
+            return Some(std::path::PathBuf::from(file));
+        }
+        if cfg!(windows) {
+            dirs::config_dir().map(|dir| dir.join("terraform.rc"))
+        } else {
+            dirs::home_dir().map(|dir| dir.join(".terraformrc"))
+        }
+    }
+}
+
+/// Transitive module resolution options.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResolveOptions {
+    /// Fetch remote module sources and add the modules and providers they
+    /// declare to the graph (default: false)
+    pub enabled: bool,
+
+    /// Levels of remote modules to fetch below the scanned configuration (default: 3)
+    #[serde(default = "default_resolve_depth")]
+    pub max_depth: usize,
+}
+
+impl Default for ResolveOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_depth: default_resolve_depth(),
+        }
+    }
+}
+
+fn default_resolve_depth() -> usize {
+    3
+}
+
+/// Dependency graph options.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GraphOptions {
+    /// Rules guessing the provider a non-registry module requires from its
+    /// source, tried in order. Ignored for modules whose `required_providers`
+    /// are known.
+    pub provider_inference: Vec<ProviderInference>,
+}
+
+impl Default for GraphOptions {
+    fn default() -> Self {
+        Self {
+            provider_inference: default_provider_inference(),
+        }
+    }
+}
+
+/// Map module sources matching a pattern to the provider they require.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProviderInference {
+    /// Regular expression matched against the canonical module source
+    /// (e.g., `github.com/acme/terraform-aws-vpc?ref=v1.0.0`)
+    pub source: String,
+    /// Provider source; `$1` or `$name` expand to the pattern's capture groups
+    pub provider: String,
+}
+
+impl ProviderInference {
+    /// Compile the source pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pattern is not a valid regular expression.
+    pub fn regex(&self) -> Result<regex::Regex> {
+        regex::Regex::new(&self.source).map_err(|e| {
+            crate::err!(ConfigValue {
+                key: "graph.provider_inference".to_string(),
+                message: format!("invalid source pattern '{}': {e}", self.source),
+            })
+        })
+    }
+}
+
+/// Built-in rules for the usual `terraform-<provider>-<name>` repository names.
+fn default_provider_inference() -> Vec<ProviderInference> {
+    [
+        ("aws", "hashicorp/aws"),
+        ("google", "hashicorp/google"),
+        ("azurerm", "hashicorp/azurerm"),
+    ]
+    .into_iter()
+    .map(|(name, provider)| ProviderInference {
+        source: format!("[/-]{name}-"),
+        provider: provider.to_string(),
+    })
+    .collect()
+}
+
 /// Policy rules.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
@@ -333,6 +529,11 @@ pub struct PoliciesOptions {
 
     /// Custom severity overrides for finding codes.
     pub severity_overrides: HashMap<String, String>,
+
+    /// Approved version range per runtime (`terraform` or `opentofu`).
+    ///
+    /// A `required_version` that allows no approved version is flagged.
+    pub approved_runtimes: HashMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -350,15 +551,77 @@ pub struct DeprecationsOptions {
 
     /// Runtime deprecation rules for Terraform and OpenTofu.
     pub runtime: HashMap<String, Vec<DeprecationRef>>,
+
+    /// Additional rule files, directories or git repositories.
+    ///
+    /// Later entries override earlier ones for the same key, and rules
+    /// written directly in this file override every include.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<DeprecationInclude>,
+
+    /// Report rules without a schedule at their configured severity.
+    ///
+    /// Off by default: undated rules are reported as info, so adding a rule
+    /// never changes the exit code until it is scheduled for enforcement.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub enforce_undated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default)]
+/// A source of deprecation rules outside the main configuration file.
+///
+/// Without `git`, `path` is a file, directory or glob relative to the
+/// configuration file. With `git`, the repository is cloned at `ref` and
+/// `path` selects files inside it (all YAML files when omitted).
+pub struct DeprecationInclude {
+    /// File, directory or glob pattern.
+    pub path: Option<String>,
+    /// Git repository URL holding rule files.
+    pub git: Option<String>,
+    /// Branch, tag or commit to check out (git includes only).
+    #[serde(rename = "ref")]
+    pub git_ref: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
+/// Location of the security advisory database.
+///
+/// The database is a directory of advisory files, one per advisory. It is
+/// read from `path` (relative to the configuration file), or from a git
+/// mirror cloned at `ref`, where `path` selects a subdirectory. No advisory
+/// checks run when neither is set.
+pub struct AdvisoryOptions {
+    /// Directory holding advisory files.
+    pub path: Option<String>,
+    /// Git repository mirroring the advisory database.
+    pub git: Option<String>,
+    /// Branch, tag or commit to check out (git databases only).
+    #[serde(rename = "ref")]
+    pub git_ref: Option<String>,
+}
+
+impl AdvisoryOptions {
+    /// Returns true if an advisory database is configured.
+    #[must_use]
+    pub fn is_configured(&self) -> bool {
+        self.path.is_some() || self.git.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default)]
 /// A single deprecation rule for a module or provider version.
 ///
 /// This struct represents a deprecated version or version range,
 /// the reason for deprecation, the severity (e.g., "error", "warning"),
 /// and a recommended replacement version or module.
+///
+/// Rules may carry a retirement schedule. Before `announced` the rule is
+/// ignored, from `announced` it is reported as info, from `warn_from` as a
+/// warning with a countdown, and from `enforce_from` at `severity`. Rules
+/// without dates are reported as info unless `enforce_undated` is set.
 pub struct DeprecationRef {
     /// A version or version range (e.g., "1.0.1", ">= 2.0.8, < 3.0.0", "~> 3.1").
     /// Can be git ref / SHA1 / tag / commit hash / etc.
@@ -366,12 +629,54 @@ pub struct DeprecationRef {
     pub version: Option<String>,
 
     pub git_ref: Option<String>,
+    /// An OCI tag or digest (e.g., "1.2.0", "sha256:...") matched against OCI module sources.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oci_ref: Option<String>,
     /// The reason for this deprecation (e.g., "Critical security vulnerability CVE-2023-1234").
     pub reason: String,
     /// Severity of the deprecation ("error", "warning", etc).
     pub severity: String,
     /// Suggested replacement (could be a module name or version constraint).
     pub replacement: String,
+    /// Date the deprecation was announced (YYYY-MM-DD).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub announced: Option<NaiveDate>,
+    /// Date from which matches are reported as warnings (YYYY-MM-DD).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warn_from: Option<NaiveDate>,
+    /// Date from which matches are reported at `severity` (YYYY-MM-DD).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enforce_from: Option<NaiveDate>,
+    /// Where this rule was loaded from, set when includes are resolved.
+    #[serde(skip)]
+    pub origin: Option<String>,
+}
+
+impl DeprecationRef {
+    /// Check that the schedule dates are in order.
+    pub(crate) fn validate_schedule(&self, key: &str) -> Result<()> {
+        let dates = [
+            ("announced", self.announced),
+            ("warn_from", self.warn_from),
+            ("enforce_from", self.enforce_from),
+        ];
+        let mut previous: Option<(&str, NaiveDate)> = None;
+        for (name, date) in dates {
+            let Some(date) = date else { continue };
+            if let Some((previous_name, previous_date)) = previous {
+                if date < previous_date {
+                    return Err(crate::err!(ConfigValue {
+                        key: format!("deprecations.{key}.{name}"),
+                        message: format!(
+                            "{name} ({date}) must not be before {previous_name} ({previous_date})"
+                        ),
+                    }));
+                }
+            }
+            previous = Some((name, date));
+        }
+        Ok(())
+    }
 }
 
 /// Main configuration structure with nested sections.
@@ -398,6 +703,18 @@ pub struct Config {
 
     /// Deprecation tracking options
     pub deprecations: DeprecationsOptions,
+
+    /// Security advisory database
+    pub advisories: AdvisoryOptions,
+
+    /// Registry version lookups
+    pub registry: RegistryOptions,
+
+    /// Transitive module resolution
+    pub resolve: ResolveOptions,
+
+    /// Dependency graph construction
+    pub graph: GraphOptions,
 }
 
 fn default_max_depth() -> usize {
@@ -431,6 +748,7 @@ impl Default for Config {
                 check_prerelease: true,
                 check_upper_bound: true,
                 max_age_months: default_max_age(),
+                check_stale_suppressions: true,
             },
             output: OutputOptions {
                 colored: true,
@@ -453,12 +771,19 @@ impl Default for Config {
                 allowed_providers: Vec::new(),
                 blocked_modules: Vec::new(),
                 severity_overrides: HashMap::new(),
+                approved_runtimes: HashMap::new(),
             },
             deprecations: DeprecationsOptions {
                 modules: HashMap::new(),
                 providers: HashMap::new(),
                 runtime: HashMap::new(),
+                include: Vec::new(),
+                enforce_undated: false,
             },
+            advisories: AdvisoryOptions::default(),
+            registry: RegistryOptions::default(),
+            resolve: ResolveOptions::default(),
+            graph: GraphOptions::default(),
         }
     }
 }
@@ -482,6 +807,56 @@ impl Config {
             })
         })?;
 
+        for (kind, rules) in [
+            ("modules", &config.deprecations.modules),
+            ("providers", &config.deprecations.providers),
+            ("runtime", &config.deprecations.runtime),
+        ] {
+            for (name, refs) in rules {
+                for rule in refs {
+                    rule.validate_schedule(&format!("{kind}.{name}"))?;
+                }
+            }
+        }
+        for (idx, include) in config.deprecations.include.iter().enumerate() {
+            if include.path.is_none() && include.git.is_none() {
+                return Err(crate::err!(ConfigValue {
+                    key: format!("deprecations.include[{idx}]"),
+                    message: "either 'path' or 'git' is required".to_string(),
+                }));
+            }
+            if include.git_ref.is_some() && include.git.is_none() {
+                return Err(crate::err!(ConfigValue {
+                    key: format!("deprecations.include[{idx}].ref"),
+                    message: "'ref' is only valid together with 'git'".to_string(),
+                }));
+            }
+        }
+
+        for (runtime, range) in &config.policies.approved_runtimes {
+            if let Err(e) = crate::types::Constraint::parse(range) {
+                return Err(crate::err!(ConfigValue {
+                    key: format!("policies.approved_runtimes.{runtime}"),
+                    message: e.to_string(),
+                }));
+            }
+        }
+        for (idx, rule) in config.graph.provider_inference.iter().enumerate() {
+            if let Err(e) = regex::Regex::new(&rule.source) {
+                return Err(crate::err!(ConfigValue {
+                    key: format!("graph.provider_inference[{idx}].source"),
+                    message: e.to_string(),
+                }));
+            }
+        }
+
+        if config.advisories.git_ref.is_some() && config.advisories.git.is_none() {
+            return Err(crate::err!(ConfigValue {
+                key: "advisories.ref".to_string(),
+                message: "'ref' is only valid together with 'git'".to_string(),
+            }));
+        }
+
         tracing::debug!(
             exclude_patterns = config.scan.exclude_patterns.len(),
             continue_on_error = config.scan.continue_on_error,
@@ -526,6 +901,9 @@ analysis:
   # Flag modules older than this many months
   max_age_months: 12
 
+  # Flag monphare:ignore comments that no longer match any finding
+  check_stale_suppressions: true
+
 # Output options
 output:
   # Use colored output in terminal
@@ -572,9 +950,10 @@ policies:
   #   - hashicorp/*
   #   - terraform-aws-modules/*
   
-  # Blocked module patterns
+  # Blocked module patterns (glob, matched against module sources)
   # blocked_modules:
   #   - deprecated-module/*
+  #   - oci://ghcr.io/untrusted/*
 
   # Severity overrides for specific finding codes
   # severity_overrides:
@@ -582,6 +961,14 @@ policies:
   #   wildcard-constraint: error  # Upgrade wildcard to error
 
 deprecations:
+  # Load shared rules from files, directories, globs or git repositories.
+  # Later includes override earlier ones; rules below override all includes.
+  # include:
+  #   - path: ./deprecations/*.yaml
+  #   - git: https://github.com/my-org/terraform-deprecations
+  #     ref: main
+  #     path: rules/
+
   # runtime:
     # terraform:
       # - version: "< 0.13.0"
@@ -612,6 +999,17 @@ deprecations:
     #     severity: warning
     #     replacement: "terraform-aws-modules/vpc/aws >= 5.0.0"
 
+    # Retirement schedule: info once announced, warning with a countdown
+    # from warn_from, configured severity from enforce_from
+    # "terraform-aws-modules/eks/aws":
+    #   - version: "< 19.0.0"
+    #     reason: "EKS module v18 is being retired"
+    #     severity: error
+    #     replacement: "terraform-aws-modules/eks/aws >= 19.0.0"
+    #     announced: 2025-01-15
+    #     warn_from: 2025-03-01
+    #     enforce_from: 2025-06-30
+
     # "terraform-aws-modules/security-group/aws":
     #   - version: "0.0.10"
     #     reason: "Known security issue CVE-2024-1234"
@@ -632,6 +1030,24 @@ deprecations:
     #       severity: warning
     #       replacement: ">= 4.50.0"
 
+# Registry lookups: report constraints that exclude the latest major
+# version or whose newest allowed release is older than max_age_months
+# registry:
+#   enabled: true
+#   offline: false        # only use cached responses
+#   cache_ttl_hours: 24
+#   # Private registries / Terraform Enterprise, found via service discovery.
+#   # Tokens come from TF_TOKEN_<host> or `credentials` blocks in ~/.terraformrc
+#   hosts:
+#     - tf.internal.example.com
+
+# Security advisories: one YAML file per advisory, read from a local
+# directory or a git mirror (see README for the file format)
+# advisories:
+#   path: ./advisories
+#   git: https://github.com/my-org/terraform-advisories
+#   ref: main
+
 "#
         .to_string()
     }
@@ -665,6 +1081,22 @@ deprecations:
             if args.strict {
                 self.scan.strict_mode = true;
             }
+            if args.outdated {
+                self.registry.enabled = true;
+            }
+            if args.offline {
+                self.registry.enabled = true;
+                self.registry.offline = true;
+            }
+            self.merge_resolve_args(args.resolve, args.resolve_depth);
+            if let Some(ref db) = args.advisory_db {
+                // relative to the working directory, not the config file
+                let db = std::path::absolute(db).unwrap_or_else(|_| db.clone());
+                self.advisories = AdvisoryOptions {
+                    path: Some(db.display().to_string()),
+                    ..AdvisoryOptions::default()
+                };
+            }
 
             // git options
             if let Some(ref branch) = args.branch {
@@ -686,6 +1118,23 @@ deprecations:
                 }
             }
         }
+
+        if let crate::cli::Commands::Graph(ref args) = cli.command {
+            self.merge_resolve_args(args.resolve, args.resolve_depth);
+        }
+        if let crate::cli::Commands::Impact(ref args) = cli.command {
+            self.merge_resolve_args(args.resolve, args.resolve_depth);
+        }
+    }
+
+    /// Enable transitive module resolution from `--resolve` and `--resolve-depth`.
+    fn merge_resolve_args(&mut self, resolve: bool, depth: Option<usize>) {
+        if resolve || depth.is_some() {
+            self.resolve.enabled = true;
+        }
+        if let Some(depth) = depth {
+            self.resolve.max_depth = depth;
+        }
     }
 
     /// Load VCS tokens from environment variables
@@ -858,6 +1307,50 @@ policies:
         );
     }
 
+    #[test]
+    fn test_deprecation_schedule() {
+        let yaml = r#"
+deprecations:
+  modules:
+    "terraform-aws-modules/eks/aws":
+      - version: "< 19.0.0"
+        reason: "v18 retired"
+        severity: error
+        replacement: ">= 19.0.0"
+        announced: 2025-01-15
+        warn_from: 2025-03-01
+        enforce_from: 2025-06-30
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        let rule = &config.deprecations.modules["terraform-aws-modules/eks/aws"][0];
+        assert_eq!(rule.announced, NaiveDate::from_ymd_opt(2025, 1, 15));
+        assert_eq!(rule.enforce_from, NaiveDate::from_ymd_opt(2025, 6, 30));
+
+        let out_of_order = yaml.replace("warn_from: 2025-03-01", "warn_from: 2025-09-01");
+        let err = Config::from_yaml(&out_of_order).unwrap_err();
+        assert!(err.to_string().contains("enforce_from"));
+    }
+
+    #[test]
+    fn test_config_provider_inference() {
+        assert_eq!(Config::default().graph.provider_inference.len(), 3);
+
+        let yaml = r#"
+graph:
+  provider_inference:
+    - source: "^github\\.com/acme/tf-(\\w+)-"
+      provider: acme/$1
+"#;
+        let config = Config::from_yaml(yaml).unwrap();
+        assert_eq!(config.graph.provider_inference.len(), 1);
+        assert_eq!(config.graph.provider_inference[0].provider, "acme/$1");
+
+        let err = Config::from_yaml(&yaml.replace(r"(\\w+)", r"(\\w+")).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("graph.provider_inference[0].source"));
+    }
+
     #[test]
     fn test_config_with_deprecations() {
         let yaml = r#"
@@ -0,0 +1,579 @@
+//! Comparing two scans.
+//!
+//! A [`ScanDiff`] lists what changed between an old and a new scan: module
+//! blocks and provider requirements that were added or removed, how the
+//! constraint of each remaining reference changed, and which findings were
+//! introduced or resolved. References are matched by repository, directory
+//! relative to the repository root and Terraform address, so the two scans
+//! may come from different checkouts of the same repositories.
+//!
+//! # Example
+//!
+//! ```bash
+//! # What does this branch change compared to main?
+//! monphare diff main HEAD --format markdown
+//!
+//! # Compare two saved snapshots
+//! monphare diff last-week.json today.json
+//! ```
+
+use crate::analyzer::{fingerprint, relative_file};
+use crate::error::Result;
+use crate::graph::NodeKind;
+use crate::types::{BaselineEntry, Constraint, Finding, QueryFormat, ScanResult, VersionSet};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+/// The differences between two scans.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanDiff {
+    /// Label of the old scan (e.g., a git ref or snapshot path)
+    pub old: String,
+    /// Label of the new scan
+    pub new: String,
+    /// References only in the new scan
+    pub added: Vec<Reference>,
+    /// References only in the old scan
+    pub removed: Vec<Reference>,
+    /// References in both scans whose source or constraint changed
+    pub changed: Vec<ReferenceChange>,
+    /// Findings only in the new scan
+    pub introduced: Vec<Finding>,
+    /// Findings only in the old scan
+    pub resolved: Vec<Finding>,
+}
+
+/// A module block or provider requirement.
+#[derive(Debug, Clone, Serialize)]
+pub struct Reference {
+    /// Module or provider
+    pub kind: NodeKind,
+    /// Repository name
+    pub repository: Option<String>,
+    /// File path relative to the repository root
+    pub file: String,
+    /// Line number
+    pub line: usize,
+    /// Terraform address (e.g., `module.vpc` or `provider.aws`)
+    pub resource: String,
+    /// Module source or qualified provider source
+    pub source: String,
+    /// Version constraint, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constraint: Option<String>,
+    #[serde(skip)]
+    parsed: Option<Constraint>,
+}
+
+/// A reference present in both scans that changed.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReferenceChange {
+    /// The reference as in the new scan
+    pub reference: Reference,
+    /// The source in the old scan, if it changed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_source: Option<String>,
+    /// The constraint in the old scan
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_constraint: Option<String>,
+    /// How the constraint changed
+    pub change: ConstraintChange,
+}
+
+/// How a reference's version constraint changed between two scans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConstraintChange {
+    /// Same constraint; only the source changed
+    Unchanged,
+    /// A constraint was added where there was none
+    Added,
+    /// The constraint was removed
+    Removed,
+    /// Allows a subset of the versions it allowed before
+    Tightened,
+    /// Allows a superset of the versions it allowed before
+    Loosened,
+    /// The lowest allowed version moved to a higher major version
+    MajorBump,
+    /// Allows a different, overlapping or disjoint, set of versions
+    Shifted,
+    /// Written differently but allows exactly the same versions
+    Rewritten,
+}
+
+impl ConstraintChange {
+    /// Classify the change from `old` to `new`.
+    #[must_use]
+    pub fn between(old: Option<&Constraint>, new: Option<&Constraint>) -> Self {
+        let (old, new) = match (old, new) {
+            (None, None) => return Self::Unchanged,
+            (None, Some(_)) => return Self::Added,
+            (Some(_), None) => return Self::Removed,
+            (Some(old), Some(new)) if old.raw == new.raw => return Self::Unchanged,
+            (Some(old), Some(new)) => (VersionSet::from(old), VersionSet::from(new)),
+        };
+
+        if let (Some(before), Some(after)) = (old.lowest(), new.lowest()) {
+            if after.major > before.major {
+                return Self::MajorBump;
+            }
+        }
+        let new_is_subset = new.intersect(&old.complement()).is_empty();
+        let old_is_subset = old.intersect(&new.complement()).is_empty();
+        match (new_is_subset, old_is_subset) {
+            (true, true) => Self::Rewritten,
+            (true, false) => Self::Tightened,
+            (false, true) => Self::Loosened,
+            (false, false) => Self::Shifted,
+        }
+    }
+
+    /// Verb describing the change, as in "loosens `hashicorp/aws`".
+    #[must_use]
+    pub const fn verb(self) -> &'static str {
+        match self {
+            Self::Unchanged => "changes the source of",
+            Self::Added => "adds a constraint to",
+            Self::Removed => "removes the constraint of",
+            Self::Tightened => "tightens",
+            Self::Loosened => "loosens",
+            Self::MajorBump => "bumps the major version of",
+            Self::Shifted => "changes",
+            Self::Rewritten => "rewrites",
+        }
+    }
+}
+
+/// Key matching a reference across scans: repository, directory and
+/// address. The address prefix distinguishes modules from providers.
+type ReferenceKey = (Option<String>, String, String);
+
+impl ScanDiff {
+    /// Compare two scans.
+    #[must_use]
+    pub fn between(
+        old_label: impl Into<String>,
+        old: &ScanResult,
+        new_label: impl Into<String>,
+        new: &ScanResult,
+    ) -> Self {
+        let before = references(old);
+        let mut after = references(new);
+
+        let mut removed = Vec::new();
+        let mut changed = Vec::new();
+        for (key, old_ref) in before {
+            let Some(new_ref) = after.remove(&key) else {
+                removed.push(old_ref);
+                continue;
+            };
+            let change =
+                ConstraintChange::between(old_ref.parsed.as_ref(), new_ref.parsed.as_ref());
+            let source_changed = old_ref.source != new_ref.source;
+            if change == ConstraintChange::Unchanged && !source_changed {
+                continue;
+            }
+            changed.push(ReferenceChange {
+                old_source: source_changed.then_some(old_ref.source),
+                old_constraint: old_ref.constraint,
+                change,
+                reference: new_ref,
+            });
+        }
+        let added = after.into_values().collect();
+
+        let (introduced, resolved) = finding_changes(old, new);
+        let diff = Self {
+            old: old_label.into(),
+            new: new_label.into(),
+            added,
+            removed,
+            changed,
+            introduced,
+            resolved,
+        };
+        tracing::debug!(
+            added = diff.added.len(),
+            removed = diff.removed.len(),
+            changed = diff.changed.len(),
+            introduced = diff.introduced.len(),
+            resolved = diff.resolved.len(),
+            "Scans compared"
+        );
+        diff
+    }
+
+    /// Check if nothing changed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+            && self.introduced.is_empty()
+            && self.resolved.is_empty()
+    }
+
+    /// Render the diff in the given format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if JSON serialization fails.
+    pub fn render(&self, format: QueryFormat) -> Result<String> {
+        match format {
+            QueryFormat::Text => Ok(self.to_text()),
+            QueryFormat::Json => Ok(serde_json::to_string_pretty(self)?),
+            QueryFormat::Markdown => Ok(self.to_markdown()),
+        }
+    }
+
+    fn to_text(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "Changes from {} to {}", self.old, self.new);
+        if self.is_empty() {
+            out.push_str("\nNo changes.\n");
+            return out;
+        }
+
+        let mut section = |title: &str, lines: Vec<String>| {
+            if !lines.is_empty() {
+                let _ = writeln!(out, "\n{title} ({})", lines.len());
+                for line in lines {
+                    let _ = writeln!(out, "  {line}");
+                }
+            }
+        };
+        section(
+            "Added",
+            self.added
+                .iter()
+                .map(|r| format!("+ {}", r.summary()))
+                .collect(),
+        );
+        section(
+            "Removed",
+            self.removed
+                .iter()
+                .map(|r| format!("- {}", r.summary()))
+                .collect(),
+        );
+        section(
+            "Changed",
+            self.changed
+                .iter()
+                .map(|c| format!("~ {}", c.sentence(|s| s.to_string())))
+                .collect(),
+        );
+        section(
+            "Findings introduced",
+            self.introduced
+                .iter()
+                .map(|f| format!("+ {}", finding_line(f)))
+                .collect(),
+        );
+        section(
+            "Findings resolved",
+            self.resolved
+                .iter()
+                .map(|f| format!("- {}", finding_line(f)))
+                .collect(),
+        );
+        out
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "## Changes from `{}` to `{}`\n", self.old, self.new);
+        if self.is_empty() {
+            out.push_str("No changes.\n");
+            return out;
+        }
+
+        let code = |s: &str| format!("`{s}`");
+        if !self.changed.is_empty() {
+            let _ = writeln!(out, "### Changed\n");
+            for change in &self.changed {
+                let mut sentence = change.sentence(code);
+                if let Some(first) = sentence.get(..1) {
+                    sentence.replace_range(..1, &first.to_uppercase());
+                }
+                let _ = writeln!(out, "- {sentence}");
+            }
+            out.push('\n');
+        }
+        for (title, references) in [("Added", &self.added), ("Removed", &self.removed)] {
+            if references.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out, "### {title}\n");
+            let _ = writeln!(out, "| Location | Resource | Source | Constraint |");
+            let _ = writeln!(out, "|----------|----------|--------|------------|");
+            for reference in references {
+                let _ = writeln!(
+                    out,
+                    "| `{}` | `{}` | `{}` | {} |",
+                    reference.location(),
+                    reference.resource,
+                    reference.source,
+                    reference
+                        .constraint
+                        .as_deref()
+                        .map_or_else(|| "_none_".to_string(), code)
+                );
+            }
+            out.push('\n');
+        }
+        for (title, findings) in [
+            ("Findings introduced", &self.introduced),
+            ("Findings resolved", &self.resolved),
+        ] {
+            if findings.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out, "### {title}\n");
+            let _ = writeln!(out, "| Severity | Code | Message |");
+            let _ = writeln!(out, "|----------|------|---------|");
+            for finding in findings {
+                let _ = writeln!(
+                    out,
+                    "| {} | `{}` | {} |",
+                    finding.severity,
+                    finding.code,
+                    finding.message.replace('|', "\\|")
+                );
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl Reference {
+    fn location(&self) -> String {
+        match &self.repository {
+            Some(repo) => format!("{repo}:{}:{}", self.file, self.line),
+            None => format!("{}:{}", self.file, self.line),
+        }
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "{} {} {} ({})",
+            self.resource,
+            self.source,
+            self.constraint.as_deref().unwrap_or("(no constraint)"),
+            self.location()
+        )
+    }
+}
+
+impl ReferenceChange {
+    /// One-sentence description, e.g. "loosens hashicorp/aws from ~> 5.0
+    /// to >= 5.0", with `quote` applied to sources and constraints.
+    fn sentence(&self, quote: impl Fn(&str) -> String) -> String {
+        let reference = &self.reference;
+        let subject = match &self.old_source {
+            Some(old) => format!(
+                "{} ({} to {})",
+                reference.resource,
+                quote(old),
+                quote(&reference.source)
+            ),
+            None => quote(&reference.source),
+        };
+        let constraint = |c: &Option<String>| {
+            c.as_deref()
+                .map_or_else(|| "no constraint".to_string(), &quote)
+        };
+        let versions = match self.change {
+            ConstraintChange::Unchanged => String::new(),
+            _ => format!(
+                " from {} to {}",
+                constraint(&self.old_constraint),
+                constraint(&reference.constraint)
+            ),
+        };
+        format!(
+            "{} {subject}{versions} in {}",
+            self.change.verb(),
+            reference.location()
+        )
+    }
+}
+
+fn finding_line(finding: &Finding) -> String {
+    match &finding.location {
+        Some(location) => format!(
+            "[{}] {}: {} ({location})",
+            finding.severity, finding.code, finding.message
+        ),
+        None => format!(
+            "[{}] {}: {}",
+            finding.severity, finding.code, finding.message
+        ),
+    }
+}
+
+/// Module blocks and provider requirements of a scan, by key.
+fn references(result: &ScanResult) -> BTreeMap<ReferenceKey, Reference> {
+    let mut references = BTreeMap::new();
+    let modules = result.modules.iter().map(|m| Reference {
+        kind: NodeKind::Module,
+        repository: m.repository.clone(),
+        file: relative_file(&m.file_path, m.repository.as_deref(), &result.roots),
+        line: m.line_number,
+        resource: format!("module.{}", m.name),
+        source: m.source.to_source_string(),
+        constraint: m.version_constraint.as_ref().map(|c| c.raw.clone()),
+        parsed: m.version_constraint.clone(),
+    });
+    let providers = result.providers.iter().map(|p| Reference {
+        kind: NodeKind::Provider,
+        repository: p.repository.clone(),
+        file: relative_file(&p.file_path, p.repository.as_deref(), &result.roots),
+        line: p.line_number,
+        resource: format!("provider.{}", p.name),
+        source: p.qualified_source(),
+        constraint: p.version_constraint.as_ref().map(|c| c.raw.clone()),
+        parsed: p.version_constraint.clone(),
+    });
+    for reference in modules.chain(providers) {
+        // Blocks may move between files of a module, so match by directory
+        let dir = reference
+            .file
+            .rsplit_once('/')
+            .map_or("", |(dir, _)| dir)
+            .to_string();
+        let key = (
+            reference.repository.clone(),
+            dir,
+            reference.resource.clone(),
+        );
+        references.entry(key).or_insert(reference);
+    }
+    references
+}
+
+/// Findings only in `new` and findings only in `old`, matched by their
+/// baseline fingerprint. Suppressed findings are ignored.
+fn finding_changes(old: &ScanResult, new: &ScanResult) -> (Vec<Finding>, Vec<Finding>) {
+    let unmatched = |from: &ScanResult, against: &ScanResult| {
+        let mut remaining: HashMap<BaselineEntry, usize> = HashMap::new();
+        for finding in against.analysis.active_findings() {
+            *remaining
+                .entry(fingerprint(finding, &against.roots))
+                .or_insert(0) += 1;
+        }
+        from.analysis
+            .active_findings()
+            .filter(
+                |finding| match remaining.get_mut(&fingerprint(finding, &from.roots)) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        false
+                    }
+                    _ => true,
+                },
+            )
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+    (unmatched(new, old), unmatched(old, new))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ProviderRef, Severity};
+    use std::path::PathBuf;
+
+    fn aws(root: &str, constraint: &str) -> ProviderRef {
+        ProviderRef {
+            name: "aws".to_string(),
+            source: Some("hashicorp/aws".to_string()),
+            version_constraint: Some(Constraint::parse(constraint).unwrap()),
+            file_path: PathBuf::from(root).join("infra/envs/prod/versions.tf"),
+            line_number: 3,
+            repository: Some("infra".to_string()),
+        }
+    }
+
+    fn change(old: &str, new: &str) -> ConstraintChange {
+        ConstraintChange::between(
+            Some(&Constraint::parse(old).unwrap()),
+            Some(&Constraint::parse(new).unwrap()),
+        )
+    }
+
+    #[test]
+    fn test_constraint_change_classification() {
+        assert_eq!(change("~> 5.0", ">= 5.0"), ConstraintChange::Loosened);
+        assert_eq!(change(">= 5.0", "~> 5.10"), ConstraintChange::Tightened);
+        assert_eq!(change("~> 4.0", "~> 5.0"), ConstraintChange::MajorBump);
+        assert_eq!(
+            change("~> 5.0", ">= 5.0, < 6.0"),
+            ConstraintChange::Rewritten
+        );
+        assert_eq!(
+            change(">= 5.1, < 5.5", ">= 5.3, < 5.9"),
+            ConstraintChange::Shifted
+        );
+        assert_eq!(
+            ConstraintChange::between(None, Some(&Constraint::parse("~> 5.0").unwrap())),
+            ConstraintChange::Added
+        );
+    }
+
+    #[test]
+    fn test_diff_matches_references_across_checkouts() {
+        let mut finding = Finding {
+            code: "no-upper-bound".to_string(),
+            severity: Severity::Warning,
+            message: "Provider 'aws' has no upper bound".to_string(),
+            description: None,
+            location: Some(crate::types::Location {
+                file: PathBuf::from("/tmp/b/infra/envs/prod/versions.tf"),
+                line: 3,
+                column: None,
+                repository: Some("infra".to_string()),
+            }),
+            related_locations: vec![],
+            suggestion: None,
+            category: crate::types::FindingCategory::BestPractice,
+            suppressed_by: None,
+            resource: Some("provider.aws".to_string()),
+            source: Some("hashicorp/aws".to_string()),
+            origin: None,
+        };
+        let old = ScanResult {
+            providers: vec![aws("/tmp/a", "~> 5.0")],
+            ..Default::default()
+        };
+        let mut new = ScanResult {
+            providers: vec![aws("/tmp/b", ">= 5.0")],
+            ..Default::default()
+        };
+        new.analysis.findings.push(finding.clone());
+
+        let diff = ScanDiff::between("main", &old, "HEAD", &new);
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].change, ConstraintChange::Loosened);
+        assert_eq!(diff.introduced.len(), 1);
+        assert!(diff.resolved.is_empty());
+
+        let markdown = diff.render(QueryFormat::Markdown).unwrap();
+        assert!(markdown.contains(
+            "- Loosens `hashicorp/aws` from `~> 5.0` to `>= 5.0` in infra:envs/prod/versions.tf:3"
+        ));
+
+        // The same finding in both scans, at different absolute paths, is not a change
+        finding.location.as_mut().unwrap().file =
+            PathBuf::from("/tmp/a/infra/envs/prod/versions.tf");
+        let mut old = old;
+        old.analysis.findings.push(finding);
+        let diff = ScanDiff::between("main", &old, "HEAD", &new);
+        assert!(diff.introduced.is_empty());
+    }
+}
@@ -0,0 +1,306 @@
+//! Terraform files changed in a local repository.
+//!
+//! Used by `scan --changed-since` so pull request pipelines only look up and
+//! report what the change touches.
+
+use crate::error::Result;
+use crate::parser::{LocalModuleCalls, TERRAFORM_EXTENSIONS};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Terraform files changed since a git ref, with the lines that changed.
+#[derive(Debug, Clone, Default)]
+pub struct ChangeSet {
+    /// The ref the working tree was compared against
+    pub base: String,
+    /// Changed files (absolute) and their changed line ranges, 1-based and
+    /// inclusive. Deleted files have no ranges.
+    files: BTreeMap<PathBuf, Vec<(usize, usize)>>,
+    /// Directories containing a changed file
+    directories: BTreeSet<PathBuf>,
+}
+
+impl ChangeSet {
+    /// Find the Terraform files changed between `git_ref` and the working
+    /// tree of the repository containing `path`.
+    ///
+    /// Like `git diff <ref>...`, the comparison starts from the merge base
+    /// of the ref and `HEAD`, so commits that landed on the ref after the
+    /// branch was created are not counted. Staged, unstaged and untracked
+    /// files are included.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` is not inside a git working tree or the
+    /// ref cannot be resolved.
+    pub fn since(path: &Path, git_ref: &str) -> Result<Self> {
+        let git_error = |e: git2::Error| {
+            crate::err!(Git {
+                message: format!("Failed to diff against '{git_ref}': {}", e.message()),
+            })
+        };
+
+        let repo = git2::Repository::discover(path).map_err(git_error)?;
+        let workdir = repo.workdir().ok_or_else(|| {
+            crate::err!(Git {
+                message: format!("'{}' is a bare repository", path.display()),
+            })
+        })?;
+        let root = workdir
+            .canonicalize()
+            .unwrap_or_else(|_| workdir.to_path_buf());
+
+        let base = repo
+            .revparse_single(git_ref)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(git_error)?;
+        let base = match repo.head().and_then(|head| head.peel_to_commit()) {
+            Ok(head) => {
+                let merge_base = repo.merge_base(base.id(), head.id()).map_err(git_error)?;
+                repo.find_commit(merge_base).map_err(git_error)?
+            }
+            Err(_) => base,
+        };
+        let tree = base.tree().map_err(git_error)?;
+
+        let mut options = git2::DiffOptions::new();
+        options
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .show_untracked_content(true)
+            .context_lines(0);
+        let diff = repo
+            .diff_tree_to_workdir_with_index(Some(&tree), Some(&mut options))
+            .map_err(git_error)?;
+
+        let mut changes = Self {
+            base: git_ref.to_string(),
+            ..Self::default()
+        };
+        for (idx, delta) in diff.deltas().enumerate() {
+            let Some(relative) = delta.new_file().path().or_else(|| delta.old_file().path()) else {
+                continue;
+            };
+            let name = relative.to_string_lossy();
+            if !TERRAFORM_EXTENSIONS.iter().any(|ext| name.ends_with(ext)) {
+                continue;
+            }
+
+            let mut ranges = Vec::new();
+            if let Some(patch) = git2::Patch::from_diff(&diff, idx).map_err(git_error)? {
+                for hunk in 0..patch.num_hunks() {
+                    let (hunk, _) = patch.hunk(hunk).map_err(git_error)?;
+                    let start = hunk.new_start() as usize;
+                    // Pure deletions are reported after the line they follow
+                    let end = start + (hunk.new_lines() as usize).saturating_sub(1);
+                    ranges.push((start.max(1), end.max(1)));
+                }
+            }
+            changes.insert(root.join(relative), ranges);
+        }
+
+        tracing::info!(
+            base = %git_ref,
+            files = changes.files.len(),
+            directories = changes.directories.len(),
+            "Found changed Terraform files"
+        );
+        Ok(changes)
+    }
+
+    fn insert(&mut self, file: PathBuf, ranges: Vec<(usize, usize)>) {
+        if let Some(dir) = file.parent() {
+            self.directories.insert(dir.to_path_buf());
+        }
+        self.files.entry(file).or_default().extend(ranges);
+    }
+
+    /// Add the changes of another repository, e.g. when several paths are
+    /// scanned.
+    pub fn merge(&mut self, other: Self) {
+        for (file, ranges) in other.files {
+            let existing = self.files.entry(file).or_default();
+            for range in ranges {
+                if !existing.contains(&range) {
+                    existing.push(range);
+                }
+            }
+        }
+        self.directories.extend(other.directories);
+        if self.base.is_empty() {
+            self.base = other.base;
+        }
+    }
+
+    /// Directories a scan of this change has to parse.
+    ///
+    /// These are the directories with a changed file, the modules that call
+    /// them directly or transitively (their dependents), and every module
+    /// those call, so that the module tree of each affected root is
+    /// complete. `calls` maps directories to the directories of their local
+    /// module calls (see [`crate::parser::HclParser::local_module_calls`]).
+    #[must_use]
+    pub fn affected_directories(&self, calls: &LocalModuleCalls) -> BTreeSet<PathBuf> {
+        let mut callers: BTreeMap<&Path, Vec<&Path>> = BTreeMap::new();
+        for (caller, callees) in calls {
+            for callee in callees {
+                callers.entry(callee).or_default().push(caller);
+            }
+        }
+
+        // Dependents of the changed directories
+        let mut affected = self.directories.clone();
+        let mut queue: Vec<PathBuf> = affected.iter().cloned().collect();
+        while let Some(dir) = queue.pop() {
+            for &caller in callers.get(dir.as_path()).into_iter().flatten() {
+                if affected.insert(caller.to_path_buf()) {
+                    queue.push(caller.to_path_buf());
+                }
+            }
+        }
+
+        // The modules they call
+        let mut queue: Vec<PathBuf> = affected.iter().cloned().collect();
+        while let Some(dir) = queue.pop() {
+            for callee in calls.get(&dir).into_iter().flatten() {
+                if affected.insert(callee.clone()) {
+                    queue.push(callee.clone());
+                }
+            }
+        }
+        affected
+    }
+
+    /// Check if no Terraform file changed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Changed files, as absolute paths.
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.files.keys().map(PathBuf::as_path)
+    }
+
+    /// Check if `file` is in a directory with a changed file.
+    #[must_use]
+    pub fn touches_directory(&self, file: &Path) -> bool {
+        absolute(file)
+            .parent()
+            .is_some_and(|dir| self.directories.contains(dir))
+    }
+
+    /// Check if any line from `first` to `last` (inclusive) of `file` changed.
+    #[must_use]
+    pub fn covers(&self, file: &Path, first: usize, last: usize) -> bool {
+        self.files.get(&absolute(file)).is_some_and(|ranges| {
+            ranges
+                .iter()
+                .any(|&(start, end)| start <= last && first <= end)
+        })
+    }
+}
+
+/// Absolute form of a scanned path, for comparison with paths from git.
+fn absolute(path: &Path) -> PathBuf {
+    path.canonicalize()
+        .or_else(|_| std::path::absolute(path))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit_all(repo: &git2::Repository) {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        let parents = repo.head().ok().map(|head| head.peel_to_commit().unwrap());
+        let parents: Vec<_> = parents.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "commit",
+            &tree,
+            &parents,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_changed_spans_since_ref() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        let prod = dir.path().join("envs/prod");
+        std::fs::create_dir_all(&prod).unwrap();
+        std::fs::write(
+            prod.join("main.tf"),
+            "module \"vpc\" {\n  source  = \"terraform-aws-modules/vpc/aws\"\n  version = \"~> 4.0\"\n}\n",
+        )
+        .unwrap();
+        std::fs::write(prod.join("README.md"), "docs\n").unwrap();
+        commit_all(&repo);
+
+        std::fs::write(
+            prod.join("main.tf"),
+            "module \"vpc\" {\n  source  = \"terraform-aws-modules/vpc/aws\"\n  version = \"~> 5.0\"\n}\n",
+        )
+        .unwrap();
+        std::fs::write(prod.join("README.md"), "more docs\n").unwrap();
+        let staging = dir.path().join("envs/staging");
+        std::fs::create_dir_all(&staging).unwrap();
+        std::fs::write(staging.join("versions.tf"), "terraform {}\n").unwrap();
+
+        let changes = ChangeSet::since(dir.path(), "HEAD").unwrap();
+        assert_eq!(changes.files().count(), 2);
+        assert!(changes.covers(&prod.join("main.tf"), 1, 4));
+        assert!(changes.covers(&prod.join("main.tf"), 3, 3));
+        assert!(!changes.covers(&prod.join("main.tf"), 1, 2));
+        assert!(changes.covers(&staging.join("versions.tf"), 1, 1));
+        assert!(!changes.covers(&prod.join("README.md"), 1, 1));
+        assert!(changes.touches_directory(&prod.join("outputs.tf")));
+        assert!(!changes.touches_directory(&dir.path().join("modules/vpc/main.tf")));
+    }
+
+    #[test]
+    fn test_affected_directories() {
+        let mut changes = ChangeSet::default();
+        changes.insert(PathBuf::from("/repo/modules/dns/main.tf"), vec![(1, 1)]);
+        let calls: LocalModuleCalls = [
+            ("/repo/envs/prod", &["/repo/modules/network"][..]),
+            (
+                "/repo/modules/network",
+                &["/repo/modules/dns", "/repo/modules/vpc"],
+            ),
+            ("/repo/envs/dev", &["/repo/modules/vpc"]),
+            ("/repo/modules/vpc", &[]),
+            ("/repo/modules/dns", &[]),
+        ]
+        .into_iter()
+        .map(|(dir, callees)| {
+            (
+                PathBuf::from(dir),
+                callees.iter().map(PathBuf::from).collect(),
+            )
+        })
+        .collect();
+
+        let affected: Vec<_> = changes.affected_directories(&calls).into_iter().collect();
+        assert_eq!(
+            affected,
+            [
+                "/repo/envs/prod",
+                "/repo/modules/dns",
+                "/repo/modules/network",
+                "/repo/modules/vpc"
+            ]
+            .map(PathBuf::from)
+        );
+    }
+}
@@ -10,7 +10,7 @@ use crate::git::providers::{
     AzureDevOpsProvider, BitbucketProvider, GitHubProvider, GitLabProvider, GitProvider,
 };
 use crate::git::ProviderType;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 /// Git client for cloning repositories from multiple providers.
@@ -66,22 +66,42 @@ impl GitClient {
     /// - Authentication fails
     /// - Cloning/fetching fails
     pub async fn clone_repository(&self, url: &str) -> Result<PathBuf> {
-        tracing::debug!(url = %url, cache_enabled = self.cache_manager.is_enabled(), "Starting repository clone");
+        self.clone_repository_at(url, self.config.git.branch.as_deref())
+            .await
+    }
+
+    /// Clone a repository at a specific branch, tag or commit and return the
+    /// local path.
+    ///
+    /// Behaves like [`clone_repository`](Self::clone_repository) but ignores
+    /// the configured branch. Each ref is cached separately.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the provider is not supported, authentication
+    /// fails, or cloning/fetching fails.
+    pub async fn clone_repository_at(&self, url: &str, branch: Option<&str>) -> Result<PathBuf> {
+        tracing::debug!(url = %url, branch = ?branch, cache_enabled = self.cache_manager.is_enabled(), "Starting repository clone");
 
         // Find the appropriate provider
         let provider = self.find_provider(url)?;
-        let branch = self.config.git.branch.as_deref();
         let token = self.get_token_for_url(url).ok();
 
+        // Branches other than the configured one get their own cache entry
+        let cache_key = match branch {
+            Some(b) if branch != self.config.git.branch.as_deref() => format!("{url}#{b}"),
+            _ => url.to_string(),
+        };
+
         // Check if we should use cache
         if self.cache_manager.is_enabled() {
             return self
-                .clone_with_cache(url, provider, branch, token.as_deref())
+                .clone_with_cache(url, &cache_key, provider, branch, token.as_deref())
                 .await;
         }
 
         // Fallback to non-cached clone
-        self.clone_without_cache(url, provider, branch, token.as_deref())
+        self.clone_without_cache(url, &cache_key, provider, branch, token.as_deref())
             .await
     }
 
@@ -89,6 +109,7 @@ impl GitClient {
     async fn clone_with_cache(
         &self,
         url: &str,
+        cache_key: &str,
         provider: &Arc<dyn GitProvider>,
         branch: Option<&str>,
         token: Option<&str>,
@@ -96,10 +117,10 @@ impl GitClient {
         // Ensure cache directory exists
         self.cache_manager.ensure_cache_dir().await?;
 
-        let cache_path = self.cache_manager.get_cache_path(url);
+        let cache_path = self.cache_manager.get_cache_path(cache_key);
 
         // Check if we have a cached version
-        if let Some(cache_entry) = self.cache_manager.get_cached(url).await {
+        if let Some(cache_entry) = self.cache_manager.get_cached(cache_key).await {
             // Check if cache is fresh enough to skip fetch entirely
             let now = std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
@@ -117,7 +138,7 @@ impl GitClient {
                     "Using fresh cache, skipping fetch"
                 );
                 // Update last accessed time
-                let _ = self.cache_manager.touch_cache_entry(url).await;
+                let _ = self.cache_manager.touch_cache_entry(cache_key).await;
                 return Ok(cache_path);
             }
 
@@ -140,7 +161,7 @@ impl GitClient {
                         );
                         // Refresh cache entry timestamps (both last_accessed and last_updated)
                         // This ensures subsequent runs within fresh_threshold won't re-fetch
-                        let _ = self.cache_manager.refresh_cache_entry(url).await;
+                        let _ = self.cache_manager.refresh_cache_entry(cache_key).await;
                     } else {
                         tracing::info!(
                             url = %url,
@@ -150,7 +171,7 @@ impl GitClient {
                         );
                         // Update cache entry with new SHA
                         self.cache_manager
-                            .update_cache_entry(url, &new_sha, branch)
+                            .update_cache_entry(cache_key, &new_sha, branch)
                             .await?;
                     }
                     return Ok(cache_path);
@@ -188,7 +209,7 @@ impl GitClient {
         // Get the HEAD SHA and create cache entry
         let head_sha = self.cache_manager.get_head_sha(&cache_path).await?;
         self.cache_manager
-            .update_cache_entry(url, &head_sha, branch)
+            .update_cache_entry(cache_key, &head_sha, branch)
             .await?;
 
         tracing::info!(
@@ -204,6 +225,7 @@ impl GitClient {
     async fn clone_without_cache(
         &self,
         url: &str,
+        cache_key: &str,
         provider: &Arc<dyn GitProvider>,
         branch: Option<&str>,
         token: Option<&str>,
@@ -215,7 +237,10 @@ impl GitClient {
         );
 
         // Generate a unique directory name
-        let repo_name = self.extract_repo_name(url);
+        let mut repo_name = self.extract_repo_name(url);
+        if let (true, Some(b)) = (cache_key != url, branch) {
+            repo_name = format!("{repo_name}-{}", b.replace('/', "-"));
+        }
         let target_path = self.temp_dir.join(&repo_name);
 
         // Remove existing directory if it exists
@@ -330,6 +355,67 @@ impl GitClient {
         token
     }
 
+    /// Check out a commit of a local repository into a temporary directory.
+    ///
+    /// The repository is cloned with `--shared`, so nothing is copied or
+    /// changed in it. The checkout directory has the same name as the
+    /// repository, so scans of it report the same repository name. Remove
+    /// the returned directory's parent when done.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `git_ref` does not name a commit of `repo`, or if
+    /// cloning or checking out fails.
+    pub async fn checkout_ref(&self, repo: &Path, git_ref: &str) -> Result<PathBuf> {
+        let sha = run_git(
+            repo,
+            &["rev-parse", "--verify", &format!("{git_ref}^{{commit}}")],
+        )
+        .await
+        .map_err(|_| {
+            crate::err!(Git {
+                message: format!(
+                    "'{git_ref}' is not a commit in repository '{}'",
+                    repo.display()
+                ),
+            })
+        })?;
+
+        let repo = std::path::absolute(repo).unwrap_or_else(|_| repo.to_path_buf());
+        let name = repo
+            .file_name()
+            .map_or_else(|| "repository".into(), |n| n.to_string_lossy().into_owned());
+        let parent = self.temp_dir.join(format!(
+            "monphare-ref-{}-{:x}",
+            std::process::id(),
+            md5_hash(&format!("{}@{sha}", repo.display()))
+        ));
+        if parent.exists() {
+            tokio::fs::remove_dir_all(&parent)
+                .await
+                .map_err(|e| MonPhareError::io(&parent, e, file!(), line!()))?;
+        }
+        let checkout = parent.join(name);
+
+        tracing::debug!(repo = %repo.display(), git_ref = %git_ref, sha = %sha, "Checking out ref");
+        let repo_arg = repo.to_string_lossy();
+        let checkout_arg = checkout.to_string_lossy();
+        run_git(
+            &self.temp_dir,
+            &[
+                "clone",
+                "--quiet",
+                "--shared",
+                "--no-checkout",
+                &repo_arg,
+                &checkout_arg,
+            ],
+        )
+        .await?;
+        run_git(&checkout, &["checkout", "--quiet", "--detach", &sha]).await?;
+        Ok(checkout)
+    }
+
     /// Clean up temporary directories.
     ///
     /// # Errors
@@ -345,6 +431,32 @@ impl GitClient {
     }
 }
 
+/// Run a git command in `dir` and return its trimmed standard output.
+async fn run_git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = tokio::process::Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .await
+        .map_err(|e| {
+            crate::err!(Git {
+                message: format!("Failed to run git {}: {e}", args.join(" ")),
+            })
+        })?;
+
+    if !output.status.success() {
+        return Err(crate::err!(Git {
+            message: format!(
+                "git {} failed: {}",
+                args.first().copied().unwrap_or_default(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        }));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
 /// Simple hash function for generating unique directory names.
 fn md5_hash(s: &str) -> u64 {
     use std::collections::hash_map::DefaultHasher;
@@ -8,6 +8,9 @@
 //! - Bitbucket
 //! - Azure DevOps
 //!
+//! It also finds the Terraform files changed in a local working tree
+//! ([`ChangeSet`]).
+//!
 //! # Architecture
 //!
 //! ```text
@@ -50,10 +53,12 @@
 //! ```
 
 mod cache;
+mod changes;
 mod client;
 mod providers;
 
 pub use cache::{CacheEntry, CacheManager, CacheResult};
+pub use changes::ChangeSet;
 pub use client::GitClient;
 pub use providers::{
     AzureDevOpsProvider, BitbucketProvider, GitHubProvider, GitLabProvider, GitProvider,
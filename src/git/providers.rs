@@ -2,7 +2,7 @@
 //!
 //! This module contains implementations for each supported Git provider.
 
-use crate::error::Result;
+use crate::error::{MonPhareError, Result};
 use async_trait::async_trait;
 use std::path::Path;
 
@@ -451,36 +451,67 @@ async fn clone_repository_impl(url: &str, target_path: &Path, branch: Option<&st
     let target_path = target_path.to_path_buf();
     let branch = branch.map(String::from);
 
-    // Run git2 operations in a blocking task
-    tokio::task::spawn_blocking(move || {
+    // Run git2 operations in a blocking task; shallow clone for performance
+    tokio::task::spawn_blocking(move || clone_at(&url, &target_path, branch.as_deref(), 1))
+        .await
+        .map_err(|e| {
+            crate::err!(Internal {
+                message: format!("Clone task failed: {e}"),
+            })
+        })?
+}
+
+/// Clone a repository at `git_ref`, fetching `depth` commits (0 for all).
+///
+/// `git_ref` is tried as a branch first. A tag or commit needs the full
+/// history, so when no branch matches the repository is cloned again
+/// without a depth limit and the ref is checked out detached.
+fn clone_at(url: &str, target_path: &Path, git_ref: Option<&str>, depth: i32) -> Result<()> {
+    let clone = |branch: Option<&str>, depth: i32| {
         let mut builder = git2::build::RepoBuilder::new();
         let mut fetch_options = git2::FetchOptions::new();
-
-        fetch_options.depth(1); // Shallow clone for performance
+        fetch_options.depth(depth);
         builder.fetch_options(fetch_options);
-
-        // Set branch if specified
-        if let Some(ref branch) = branch {
+        if let Some(branch) = branch {
             builder.branch(branch);
         }
+        builder.clone(url, target_path)
+    };
+    let clone_error = |e: git2::Error| {
+        crate::err!(GitClone {
+            url: url.to_string(),
+            message: e.message().to_string(),
+        })
+    };
 
-        tracing::debug!(url = %url, path = %target_path.display(), "Cloning repository");
+    tracing::debug!(url = %url, path = %target_path.display(), "Cloning repository");
 
-        builder.clone(&url, &target_path).map_err(|e| {
-            crate::err!(GitClone {
-                url: url.clone(),
-                message: e.message().to_string(),
-            })
-        })?;
-
-        Ok(())
-    })
-    .await
-    .map_err(|e| {
-        crate::err!(Internal {
-            message: format!("Clone task failed: {e}"),
-        })
-    })?
+    match (clone(git_ref, depth), git_ref) {
+        (Ok(_), _) => Ok(()),
+        (Err(e), Some(git_ref)) if e.code() == git2::ErrorCode::NotFound => {
+            tracing::debug!(url = %url, git_ref = %git_ref, "No such branch, resolving as tag or commit");
+            if target_path.exists() {
+                std::fs::remove_dir_all(target_path)
+                    .map_err(|e| MonPhareError::io(target_path, e, file!(), line!()))?;
+            }
+            let repo = clone(None, 0).map_err(clone_error)?;
+            checkout_detached(&repo, git_ref).map_err(clone_error)
+        }
+        (Err(e), _) => Err(clone_error(e)),
+    }
+}
+
+/// Check out a tag or commit of a cloned repository with a detached HEAD.
+fn checkout_detached(
+    repo: &git2::Repository,
+    git_ref: &str,
+) -> std::result::Result<(), git2::Error> {
+    let commit = repo.revparse_single(git_ref)?.peel_to_commit()?;
+    repo.checkout_tree(
+        commit.as_object(),
+        Some(git2::build::CheckoutBuilder::new().force()),
+    )?;
+    repo.set_head_detached(commit.id())
 }
 
 #[cfg(test)]
@@ -573,4 +604,50 @@ mod tests {
         assert!(azure.can_handle("https://dev.azure.com/org/project/_git/repo"));
         assert!(!azure.can_handle("https://github.com/org/repo"));
     }
+
+    #[test]
+    fn test_clone_pinned_to_tag_or_commit() {
+        let origin = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(origin.path()).unwrap();
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        let commit = |content: &str| {
+            std::fs::write(origin.path().join("rules.yaml"), content).unwrap();
+            let mut index = repo.index().unwrap();
+            index
+                .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+                .unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parents = repo.head().ok().map(|head| head.peel_to_commit().unwrap());
+            let parents: Vec<_> = parents.iter().collect();
+            repo.commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                content,
+                &tree,
+                &parents,
+            )
+            .unwrap()
+        };
+        let first = commit("v1");
+        let tagged = repo.find_object(first, None).unwrap();
+        repo.tag("v1.0.0", &tagged, &signature, "v1.0.0", false)
+            .unwrap();
+        commit("v2");
+
+        let url = format!("file://{}", origin.path().display());
+        let clones = tempfile::tempdir().unwrap();
+        let sha = first.to_string();
+        for (git_ref, expected) in [
+            (None, "v2"),
+            (Some("v1.0.0"), "v1"),
+            (Some(sha.as_str()), "v1"),
+        ] {
+            let target = clones.path().join(git_ref.unwrap_or("default"));
+            // The local transport cannot fetch shallow clones
+            clone_at(&url, &target, git_ref, 0).unwrap();
+            let content = std::fs::read_to_string(target.join("rules.yaml")).unwrap();
+            assert_eq!(content, expected, "{git_ref:?}");
+        }
+    }
 }
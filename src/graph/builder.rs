@@ -3,10 +3,17 @@
 //! This module provides the `GraphBuilder` which constructs a `DependencyGraph`
 //! from parsed module and provider references.
 
+use crate::config::{GraphOptions, ProviderInference};
 use crate::error::Result;
-use crate::graph::types::{DependencyGraph, EdgeType};
-use crate::types::{ModuleRef, ModuleSource, ProviderRef, RuntimeRef};
-use std::collections::HashMap;
+use crate::graph::types::{DependencyGraph, EdgeType, NodeId, RequirementOrigin};
+use crate::resolver::Resolution;
+use crate::types::{ModuleRef, ModuleSource, ProviderLock, ProviderRef, RuntimeRef};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+/// Module blocks keyed by repository and declaring directory, with their node IDs.
+type ModulesByDir<'a> = HashMap<(Option<&'a str>, PathBuf), Vec<(&'a String, &'a ModuleRef)>>;
 
 /// Builder for constructing dependency graphs.
 ///
@@ -21,9 +28,23 @@ use std::collections::HashMap;
 ///    - Deduplicate by canonical ID
 ///
 /// 2. **Edge Creation Phase**:
-///    - Link modules to their required providers
-///    - Link modules to other modules they reference
-///    - Detect local module references
+///    - Link modules to the providers passed in their `providers` map,
+///      or to the provider inferred from their source (skipped for
+///      modules fetched by resolution, and for providers missing from the
+///      lock file of the calling directory)
+///    - Link modules to the sibling modules they reference or list in
+///      `depends_on`
+///    - Link local module calls to the modules declared in the directory
+///      the source resolves to, relative to the declaring file
+///    - Link module directories to their `required_version` runtimes: the
+///      module blocks of a root directory, and the module blocks calling a
+///      child directory
+///
+/// 3. **Resolution Phase** (with [`with_resolution`](Self::with_resolution)):
+///    - Add the modules and providers of every fetched remote module
+///    - Link each calling module block to them, to the providers of its
+///      own configuration that the fetched module requires, and to the
+///      runtime versions the fetched module requires
 ///
 /// # Example
 ///
@@ -42,6 +63,14 @@ use std::collections::HashMap;
 pub struct GraphBuilder {
     /// Map from provider name to provider source for resolution
     provider_map: HashMap<String, String>,
+    /// Remote modules to attach below the modules calling them
+    resolution: Option<Resolution>,
+    /// Module source patterns and the provider they imply
+    inference: Vec<(Regex, String)>,
+    /// Lock files of the scanned configurations
+    locks: Vec<ProviderLock>,
+    /// Module blocks whose remote source was fetched
+    resolved_callers: HashSet<NodeId>,
 }
 
 impl Default for GraphBuilder {
@@ -51,14 +80,62 @@ impl Default for GraphBuilder {
 }
 
 impl GraphBuilder {
-    /// Create a new graph builder.
+    /// Create a new graph builder with the built-in provider inference rules.
     #[must_use]
     pub fn new() -> Self {
+        let inference = GraphOptions::default()
+            .provider_inference
+            .iter()
+            .map(|rule| {
+                let regex = rule.regex().expect("built-in inference rules are valid");
+                (regex, rule.provider.clone())
+            })
+            .collect();
         Self {
             provider_map: HashMap::new(),
+            resolution: None,
+            inference,
+            locks: Vec::new(),
+            resolved_callers: HashSet::new(),
         }
     }
 
+    /// Replace the rules used to infer the provider a module requires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a source pattern is not a valid regular expression.
+    pub fn with_provider_inference(mut self, rules: &[ProviderInference]) -> Result<Self> {
+        self.inference = rules
+            .iter()
+            .map(|rule| Ok((rule.regex()?, rule.provider.clone())))
+            .collect::<Result<_>>()?;
+        Ok(self)
+    }
+
+    /// Check inferred providers against the lock files of the scan.
+    ///
+    /// A lock file lists every provider its configuration installs, so a
+    /// provider inferred for a module block is dropped when the lock file
+    /// next to the block does not lock it.
+    #[must_use]
+    pub fn with_locks(mut self, locks: Vec<ProviderLock>) -> Self {
+        self.locks = locks;
+        self
+    }
+
+    /// Attach the modules and providers found by transitive resolution.
+    ///
+    /// Each fetched module becomes a set of nodes of its own, linked to the
+    /// module blocks calling it with `ModuleCall` edges (to its module
+    /// blocks) and `ModuleRequiresProvider` edges (to its provider
+    /// requirements).
+    #[must_use]
+    pub fn with_resolution(mut self, resolution: Resolution) -> Self {
+        self.resolution = Some(resolution);
+        self
+    }
+
     /// Build a dependency graph from modules and providers.
     ///
     /// # Errors
@@ -88,12 +165,13 @@ impl GraphBuilder {
 
         // Phase 2: Add all provider nodes
         tracing::debug!("Phase 2: Adding provider nodes");
-        let provider_ids: HashMap<String, String> = providers
+        let provider_nodes: Vec<(String, &ProviderRef)> = providers
             .iter()
-            .map(|p| {
-                let id = graph.add_provider(p);
-                (p.qualified_source(), id)
-            })
+            .map(|p| (graph.add_provider(p), p))
+            .collect();
+        let provider_ids: HashMap<String, String> = provider_nodes
+            .iter()
+            .map(|(id, p)| (p.qualified_source(), id.clone()))
             .collect();
         tracing::debug!(provider_nodes = provider_ids.len(), "Provider nodes added");
 
@@ -120,35 +198,160 @@ impl GraphBuilder {
 
         // Phase 4: Create edges
         tracing::debug!("Phase 4: Creating edges");
+        if let Some(resolution) = &self.resolution {
+            let resolved_callers = resolution
+                .calls
+                .iter()
+                .filter(|call| resolution.package(&call.package).is_some())
+                .map(|call| graph.module_node_id(&call.caller))
+                .collect();
+            self.resolved_callers = resolved_callers;
+        }
+        self.link_modules(&mut graph, &module_ids, &provider_nodes, &provider_ids);
+        self.link_runtimes(&mut graph, &module_ids, &runtime_ids);
+
+        // Phase 5: Link modules that share the same source (implicit dependency)
+        tracing::debug!("Phase 5: Linking modules with shared sources");
+        self.link_shared_sources(&mut graph, &module_ids);
+
+        // Phase 6: Attach the remote modules found by transitive resolution
+        if let Some(resolution) = &self.resolution {
+            tracing::debug!(
+                packages = resolution.packages.len(),
+                "Phase 6: Attaching resolved modules"
+            );
+            self.attach_resolution(&mut graph, resolution);
+        }
+
+        tracing::info!(
+            nodes = graph.node_count(),
+            edges = graph.edge_count(),
+            "Graph built successfully"
+        );
+
+        Ok(graph)
+    }
+
+    /// Create the edges between modules and providers declared together.
+    fn link_modules(
+        &self,
+        graph: &mut DependencyGraph,
+        module_ids: &[(String, &ModuleRef)],
+        provider_nodes: &[(String, &ProviderRef)],
+        provider_ids: &HashMap<String, String>,
+    ) {
+        // Index module blocks by the repository and directory declaring them
+        let mut modules_by_dir = ModulesByDir::new();
+        for (id, module) in module_ids {
+            modules_by_dir
+                .entry((
+                    module.repository.as_deref(),
+                    declaring_dir(&module.file_path),
+                ))
+                .or_default()
+                .push((id, module));
+        }
+
         let mut module_provider_edges = 0;
+        let mut module_dependency_edges = 0;
         let mut local_module_edges = 0;
-        for (module_id, module) in &module_ids {
-            // Link to providers based on module source
-            if let Some(provider_source) = self.infer_provider_for_module(module) {
-                if let Some(provider_id) = provider_ids.get(&provider_source) {
-                    graph.add_edge(module_id, provider_id, EdgeType::ModuleRequiresProvider);
-                    module_provider_edges += 1;
-                    tracing::debug!(
-                        module_id = %module_id,
-                        provider_id = %provider_id,
-                        provider_source = %provider_source,
-                        "Added module-provider edge"
-                    );
-                } else {
-                    tracing::debug!(
-                        module_id = %module_id,
-                        provider_source = %provider_source,
-                        "Provider source not found in provider map"
-                    );
+        for (module_id, module) in module_ids {
+            let dir = declaring_dir(&module.file_path);
+            let repository = module.repository.as_deref();
+
+            // Link to the provider configurations passed in explicitly, or
+            // fall back to inferring the provider from the module source
+            // unless resolution links the providers the module declares
+            if module.dependencies.providers.is_empty()
+                && !self.resolved_callers.contains(module_id.as_str())
+            {
+                let inferred = self
+                    .infer_provider_for_module(module)
+                    .filter(|source| self.lock_allows(module, &dir, source));
+                if let Some(provider_source) = inferred {
+                    if let Some(provider_id) = provider_ids.get(&provider_source) {
+                        graph.add_edge(
+                            module_id,
+                            provider_id,
+                            EdgeType::ModuleRequiresProvider(RequirementOrigin::Inferred),
+                        );
+                        module_provider_edges += 1;
+                        tracing::debug!(
+                            module_id = %module_id,
+                            provider_id = %provider_id,
+                            provider_source = %provider_source,
+                            "Added module-provider edge"
+                        );
+                    } else {
+                        tracing::debug!(
+                            module_id = %module_id,
+                            provider_source = %provider_source,
+                            "Provider source not found in provider map"
+                        );
+                    }
+                }
+            }
+            for address in module.dependencies.providers.values() {
+                let (local_name, alias) = match address.split_once('.') {
+                    Some((name, alias)) => (name, Some(alias)),
+                    None => (address.as_str(), None),
+                };
+                let provider_id = self.resolve_provider(
+                    provider_nodes,
+                    provider_ids,
+                    repository,
+                    &dir,
+                    local_name,
+                );
+                if let Some(provider_id) = provider_id {
+                    let edge_type = if alias.is_some() {
+                        EdgeType::ProviderAlias
+                    } else {
+                        EdgeType::ModuleRequiresProvider(RequirementOrigin::Declared)
+                    };
+                    if graph.add_edge(module_id, &provider_id, edge_type) {
+                        module_provider_edges += 1;
+                        tracing::debug!(
+                            module_id = %module_id,
+                            provider_id = %provider_id,
+                            address = %address,
+                            "Added module-provider edge from providers map"
+                        );
+                    }
                 }
             }
 
-            // Link local module references
+            // Link sibling modules whose outputs are referenced or that are
+            // listed in depends_on
+            let siblings = modules_by_dir
+                .get(&(repository, dir.clone()))
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            for name in module.dependencies.modules() {
+                for (other_id, other_module) in siblings {
+                    if other_module.name == name
+                        && graph.add_edge(module_id, other_id, EdgeType::ModuleDependsOn)
+                    {
+                        module_dependency_edges += 1;
+                        tracing::debug!(
+                            module_id = %module_id,
+                            other_id = %other_id,
+                            "Added module dependency edge"
+                        );
+                    }
+                }
+            }
+
+            // Link local module calls to the module blocks declared in the
+            // called directory
             if let ModuleSource::Local { path } = &module.source {
-                // Find other modules that might be the target of this local reference
-                for (other_id, other_module) in &module_ids {
-                    if self.is_local_module_match(path, other_module) {
-                        graph.add_edge(module_id, other_id, EdgeType::LocalModuleRef);
+                let target = normalize_path(&dir.join(path));
+                let called = modules_by_dir
+                    .get(&(repository, target))
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                for (other_id, _) in called {
+                    if graph.add_edge(module_id, other_id, EdgeType::LocalModuleRef) {
                         local_module_edges += 1;
                         tracing::debug!(
                             module_id = %module_id,
@@ -162,21 +365,58 @@ impl GraphBuilder {
         }
         tracing::debug!(
             module_provider_edges = module_provider_edges,
+            module_dependency_edges = module_dependency_edges,
             local_module_edges = local_module_edges,
             "Edge creation complete"
         );
+    }
 
-        // Phase 5: Link modules that share the same source (implicit dependency)
-        tracing::debug!("Phase 5: Linking modules with shared sources");
-        self.link_shared_sources(&mut graph, &module_ids);
+    /// Link module directories to the runtime versions they require.
+    ///
+    /// Directories are not nodes of their own: a root directory (one no
+    /// other scanned directory calls) is represented by the module blocks
+    /// it declares, and a child directory by the module blocks calling it.
+    fn link_runtimes(
+        &self,
+        graph: &mut DependencyGraph,
+        module_ids: &[(String, &ModuleRef)],
+        runtime_ids: &[(String, &RuntimeRef)],
+    ) {
+        let mut runtimes_by_dir: HashMap<(Option<&str>, PathBuf), Vec<&String>> = HashMap::new();
+        for (id, runtime) in runtime_ids {
+            runtimes_by_dir
+                .entry((
+                    runtime.repository.as_deref(),
+                    declaring_dir(&runtime.file_path),
+                ))
+                .or_default()
+                .push(id);
+        }
+        if runtimes_by_dir.is_empty() {
+            return;
+        }
 
-        tracing::info!(
-            nodes = graph.node_count(),
-            edges = graph.edge_count(),
-            "Graph built successfully"
-        );
+        let called: HashSet<(Option<&str>, PathBuf)> = module_ids
+            .iter()
+            .filter_map(|(_, m)| called_dir(m))
+            .collect();
 
-        Ok(graph)
+        let mut runtime_edges = 0;
+        for (module_id, module) in module_ids {
+            let own = (
+                module.repository.as_deref(),
+                declaring_dir(&module.file_path),
+            );
+            let dirs = [(!called.contains(&own)).then_some(own), called_dir(module)];
+            for dir in dirs.iter().flatten() {
+                for runtime_id in runtimes_by_dir.get(dir).into_iter().flatten() {
+                    if graph.add_edge(module_id, runtime_id, EdgeType::RequiresRuntime) {
+                        runtime_edges += 1;
+                    }
+                }
+            }
+        }
+        tracing::debug!(runtime_edges = runtime_edges, "Runtime edges created");
     }
 
     /// Build a map from provider local names to their sources.
@@ -193,102 +433,99 @@ impl GraphBuilder {
     }
 
     /// Infer which provider a module requires based on its source.
+    ///
+    /// Registry modules use the provider segment of their address; the first
+    /// inference rule matching the canonical source of any other remote
+    /// module wins. A provider of the scanned configuration with the same
+    /// local name as the inferred type takes precedence, so custom sources of
+    /// `hashicorp` providers still match.
     fn infer_provider_for_module(&self, module: &ModuleRef) -> Option<String> {
+        let source = module.source.canonical_id();
         let inferred = match &module.source {
-            ModuleSource::Registry { provider, .. } => {
-                tracing::debug!(
-                    module_name = %module.name,
-                    provider = %provider,
-                    "Inferring provider from registry module"
-                );
-                // Registry modules have an explicit provider
-                // Look up the full source from our provider map
-                self.provider_map
-                    .get(provider)
-                    .cloned()
-                    .or_else(|| Some(format!("hashicorp/{provider}")))
-            }
-            ModuleSource::Git { url, .. } => {
-                tracing::debug!(
-                    module_name = %module.name,
-                    url = %url,
-                    "Attempting to infer provider from Git URL"
-                );
-                // Try to infer provider from Git URL
-                // e.g., terraform-aws-modules implies AWS
-                if url.contains("-aws-") || url.contains("/aws-") {
-                    let provider = self
-                        .provider_map
-                        .get("aws")
-                        .cloned()
-                        .or_else(|| Some("hashicorp/aws".to_string()));
-                    tracing::debug!(
-                        module_name = %module.name,
-                        inferred_provider = %provider.as_ref().unwrap_or(&"none".to_string()),
-                        "Inferred AWS provider from Git URL"
-                    );
-                    return provider;
-                }
-                if url.contains("-google-") || url.contains("/google-") {
-                    let provider = self
-                        .provider_map
-                        .get("google")
-                        .cloned()
-                        .or_else(|| Some("hashicorp/google".to_string()));
-                    tracing::debug!(
-                        module_name = %module.name,
-                        inferred_provider = %provider.as_ref().unwrap_or(&"none".to_string()),
-                        "Inferred Google provider from Git URL"
-                    );
-                    return provider;
-                }
-                if url.contains("-azurerm-") || url.contains("/azurerm-") {
-                    let provider = self
-                        .provider_map
-                        .get("azurerm")
-                        .cloned()
-                        .or_else(|| Some("hashicorp/azurerm".to_string()));
-                    tracing::debug!(
-                        module_name = %module.name,
-                        inferred_provider = %provider.as_ref().unwrap_or(&"none".to_string()),
-                        "Inferred AzureRM provider from Git URL"
-                    );
-                    return provider;
-                }
-                tracing::debug!(
-                    module_name = %module.name,
-                    url = %url,
-                    "Could not infer provider from Git URL"
-                );
-                None
-            }
-            _ => {
-                tracing::debug!(
-                    module_name = %module.name,
-                    "Cannot infer provider for module source type"
-                );
-                None
-            }
+            // Local modules declare their providers in the scanned tree
+            ModuleSource::Local { .. } => return None,
+            ModuleSource::Registry { provider, .. } => Some(format!("hashicorp/{provider}")),
+            _ => self.inference.iter().find_map(|(pattern, provider)| {
+                pattern.captures(&source).map(|captures| {
+                    let mut expanded = String::new();
+                    captures.expand(provider, &mut expanded);
+                    expanded
+                })
+            }),
         };
-        if let Some(ref provider) = inferred {
+
+        let Some(inferred) = inferred else {
             tracing::debug!(
                 module_name = %module.name,
-                provider = %provider,
-                "Successfully inferred provider"
+                source = %source,
+                "Could not infer provider from module source"
             );
-        }
-        inferred
+            return None;
+        };
+        let local_name = inferred.rsplit('/').next().unwrap_or(&inferred);
+        let provider = self
+            .provider_map
+            .get(local_name)
+            .cloned()
+            .unwrap_or(inferred);
+        tracing::debug!(
+            module_name = %module.name,
+            provider = %provider,
+            "Successfully inferred provider"
+        );
+        Some(provider)
     }
 
-    /// Check if a local path might reference another module.
-    fn is_local_module_match(&self, path: &str, other_module: &ModuleRef) -> bool {
-        // Simplified matching: check if the path ends with the module name
-        // A more sophisticated implementation would resolve paths
-        let path_parts: Vec<&str> = path.split('/').collect();
-        if let Some(last_part) = path_parts.last() {
-            return *last_part == other_module.name;
+    /// Check that the lock file of a directory, if any, locks a provider.
+    fn lock_allows(&self, module: &ModuleRef, dir: &Path, provider_source: &str) -> bool {
+        let mut locks = self
+            .locks
+            .iter()
+            .filter(|lock| {
+                lock.repository == module.repository && declaring_dir(&lock.file_path) == dir
+            })
+            .peekable();
+        if locks.peek().is_none() {
+            return true;
         }
-        false
+        let allowed = locks.any(|lock| lock.locks(provider_source));
+        if !allowed {
+            tracing::debug!(
+                module_name = %module.name,
+                provider_source = %provider_source,
+                "Inferred provider is not in the lock file, ignoring"
+            );
+        }
+        allowed
+    }
+
+    /// Find the provider node a caller's provider local name refers to.
+    ///
+    /// Prefers a provider declared in the caller's directory, then one in
+    /// the caller's repository, then any provider with that local name.
+    fn resolve_provider(
+        &self,
+        provider_nodes: &[(String, &ProviderRef)],
+        provider_ids: &HashMap<String, String>,
+        repository: Option<&str>,
+        dir: &Path,
+        local_name: &str,
+    ) -> Option<String> {
+        let in_repository = |p: &&(String, &ProviderRef)| {
+            p.1.name == local_name && p.1.repository.as_deref() == repository
+        };
+        provider_nodes
+            .iter()
+            .filter(in_repository)
+            .find(|(_, p)| declaring_dir(&p.file_path) == dir)
+            .or_else(|| provider_nodes.iter().find(in_repository))
+            .map(|(id, _)| id.clone())
+            .or_else(|| {
+                self.provider_map
+                    .get(local_name)
+                    .and_then(|source| provider_ids.get(source))
+                    .cloned()
+            })
     }
 
     /// Link modules that share the same source.
@@ -319,6 +556,108 @@ impl GraphBuilder {
             }
         }
     }
+
+    /// Add the packages of a resolution to the graph and link them to their callers.
+    fn attach_resolution(&self, graph: &mut DependencyGraph, resolution: &Resolution) {
+        for package in &resolution.packages {
+            // Providers are resolved within the package, never against the
+            // scanned configuration
+            let mut builder = Self {
+                provider_map: HashMap::new(),
+                resolution: None,
+                inference: self.inference.clone(),
+                locks: Vec::new(),
+                resolved_callers: self.resolved_callers.clone(),
+            };
+            builder.build_provider_map(&package.providers);
+            let provider_nodes: Vec<(String, &ProviderRef)> = package
+                .providers
+                .iter()
+                .map(|p| (graph.add_provider(p), p))
+                .collect();
+            let provider_ids: HashMap<String, String> = provider_nodes
+                .iter()
+                .map(|(id, p)| (p.qualified_source(), id.clone()))
+                .collect();
+            let module_ids: Vec<(String, &ModuleRef)> = package
+                .modules
+                .iter()
+                .map(|m| (graph.add_module(m), m))
+                .collect();
+            for runtime in &package.runtimes {
+                graph.add_runtime(runtime);
+            }
+            builder.link_modules(graph, &module_ids, &provider_nodes, &provider_ids);
+        }
+
+        let declared = EdgeType::ModuleRequiresProvider(RequirementOrigin::Declared);
+        let mut call_edges = 0;
+        for call in &resolution.calls {
+            let Some(package) = resolution.package(&call.package) else {
+                continue;
+            };
+            let caller_id = graph.module_node_id(&call.caller);
+            for module in &package.modules {
+                let child_id = graph.module_node_id(module);
+                if graph.add_edge(&caller_id, &child_id, EdgeType::ModuleCall) {
+                    call_edges += 1;
+                }
+            }
+            for provider in &package.providers {
+                let provider_id = graph.provider_node_id(provider);
+                graph.add_edge(&caller_id, &provider_id, declared.clone());
+
+                // The requirement is met by the caller's own configuration
+                let configured_id = graph.provider_node_id(&ProviderRef {
+                    repository: call.caller.repository.clone(),
+                    ..provider.clone()
+                });
+                if graph.get_node(&configured_id).is_some() {
+                    graph.add_edge(&caller_id, &configured_id, declared.clone());
+                }
+            }
+            for runtime in &package.runtimes {
+                let runtime_id = graph.runtime_node_id(runtime);
+                graph.add_edge(&caller_id, &runtime_id, EdgeType::RequiresRuntime);
+            }
+        }
+        tracing::debug!(call_edges = call_edges, "Resolved modules attached");
+    }
+}
+
+/// The repository and directory a local module call resolves to.
+fn called_dir(module: &ModuleRef) -> Option<(Option<&str>, PathBuf)> {
+    match &module.source {
+        ModuleSource::Local { path } => Some((
+            module.repository.as_deref(),
+            normalize_path(&declaring_dir(&module.file_path).join(path)),
+        )),
+        _ => None,
+    }
+}
+
+/// The directory a file was declared in, normalized.
+pub(crate) fn declaring_dir(file_path: &Path) -> PathBuf {
+    normalize_path(file_path.parent().unwrap_or_else(|| Path::new("")))
+}
+
+/// Resolve `.` and `..` components lexically, without touching the filesystem.
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => normalized.push(".."),
+            },
+            other => normalized.push(other),
+        }
+    }
+    normalized
 }
 
 #[cfg(test)]
@@ -335,12 +674,15 @@ mod tests {
                 namespace: namespace.to_string(),
                 name: name.to_string(),
                 provider: provider.to_string(),
+                subdir: None,
+                params: Default::default(),
             },
             version_constraint: Some(Constraint::parse("~> 1.0").unwrap()),
             file_path: PathBuf::from("main.tf"),
             line_number: 1,
             repository: Some("test".to_string()),
             attributes: Default::default(),
+            dependencies: Default::default(),
         }
     }
 
@@ -391,10 +733,11 @@ mod tests {
                     path: "../modules/vpc".to_string(),
                 },
                 version_constraint: None,
-                file_path: PathBuf::from("main.tf"),
+                file_path: PathBuf::from("live/main.tf"),
                 line_number: 1,
                 repository: Some("test".to_string()),
                 attributes: Default::default(),
+                dependencies: Default::default(),
             },
             ModuleRef {
                 name: "vpc".to_string(),
@@ -403,12 +746,15 @@ mod tests {
                     namespace: "terraform-aws-modules".to_string(),
                     name: "vpc".to_string(),
                     provider: "aws".to_string(),
+                    subdir: None,
+                    params: Default::default(),
                 },
                 version_constraint: None,
                 file_path: PathBuf::from("modules/vpc/main.tf"),
                 line_number: 1,
                 repository: Some("test".to_string()),
                 attributes: Default::default(),
+                dependencies: Default::default(),
             },
         ];
 
@@ -420,6 +766,248 @@ mod tests {
         assert_eq!(graph.edge_count(), 1);
     }
 
+    #[test]
+    fn test_local_source_resolved_relative_to_declaring_file() {
+        let local = |name: &str, path: &str, file: &str| ModuleRef {
+            name: name.to_string(),
+            source: ModuleSource::Local {
+                path: path.to_string(),
+            },
+            version_constraint: None,
+            file_path: PathBuf::from(file),
+            line_number: 1,
+            repository: Some("test".to_string()),
+            attributes: Default::default(),
+            dependencies: Default::default(),
+        };
+        let modules = vec![
+            // Same last segment as the block name, but a different directory
+            local("network", "./modules/network", "main.tf"),
+            local("subnets", "../subnets", "modules/network/main.tf"),
+            local("network", "./network", "other/main.tf"),
+        ];
+
+        let graph = GraphBuilder::new().build(&modules, &[], &[]).unwrap();
+        let edges: Vec<_> = graph
+            .edges()
+            .map(|(from, to, edge)| (from.id().to_string(), to.id().to_string(), edge.clone()))
+            .collect();
+        assert_eq!(
+            edges,
+            vec![(
+                "module:test:local://./modules/network:network".to_string(),
+                "module:test:local://../subnets:subnets".to_string(),
+                EdgeType::LocalModuleRef
+            )]
+        );
+    }
+
+    #[test]
+    fn test_runtime_edges_from_root_and_calling_modules() {
+        let local = |name: &str, path: &str, file: &str| ModuleRef {
+            name: name.to_string(),
+            source: ModuleSource::Local {
+                path: path.to_string(),
+            },
+            version_constraint: None,
+            file_path: PathBuf::from(file),
+            line_number: 1,
+            repository: Some("test".to_string()),
+            attributes: Default::default(),
+            dependencies: Default::default(),
+        };
+        let terraform = |constraint: &str, file: &str| RuntimeRef {
+            name: "terraform".to_string(),
+            version: Constraint::parse(constraint).unwrap(),
+            source: crate::types::RuntimeSource::Terraform,
+            file_path: PathBuf::from(file),
+            line_number: 0,
+            repository: Some("test".to_string()),
+        };
+        let modules = vec![
+            local("network", "../modules/network", "live/main.tf"),
+            local("dns", "../dns", "modules/network/main.tf"),
+        ];
+        let runtimes = vec![
+            terraform(">= 1.5.0", "live/versions.tf"),
+            terraform(">= 1.3.0", "modules/network/versions.tf"),
+        ];
+
+        let graph = GraphBuilder::new().build(&modules, &[], &runtimes).unwrap();
+        let mut edges: Vec<_> = graph
+            .edges()
+            .filter(|(_, _, edge)| **edge == EdgeType::RequiresRuntime)
+            .map(|(from, to, _)| (from.id().to_string(), to.id().to_string()))
+            .collect();
+        edges.sort();
+        assert_eq!(
+            edges,
+            vec![
+                (
+                    "module:test:local://../modules/network:network".to_string(),
+                    "runtime:test:live/versions.tf:terraform".to_string()
+                ),
+                (
+                    "module:test:local://../modules/network:network".to_string(),
+                    "runtime:test:modules/network/versions.tf:terraform".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_references_and_depends_on_link_siblings() {
+        let mut eks = create_module("eks", "terraform-aws-modules", "aws");
+        eks.dependencies.references = vec!["vpc".to_string(), "missing".to_string()];
+        eks.dependencies.depends_on = vec!["iam".to_string()];
+        let mut elsewhere = create_module("vpc", "acme", "aws");
+        elsewhere.file_path = PathBuf::from("other/main.tf");
+        let modules = vec![
+            eks,
+            create_module("vpc", "terraform-aws-modules", "aws"),
+            create_module("iam", "terraform-aws-modules", "aws"),
+            elsewhere,
+        ];
+
+        let graph = GraphBuilder::new().build(&modules, &[], &[]).unwrap();
+        let mut targets: Vec<_> = graph
+            .edges()
+            .filter(|(_, _, edge)| **edge == EdgeType::ModuleDependsOn)
+            .map(|(from, to, _)| {
+                assert_eq!(from.display_name(), "eks");
+                to.source()
+            })
+            .collect();
+        targets.sort();
+        assert_eq!(
+            targets,
+            vec![
+                "registry.terraform.io/terraform-aws-modules/iam/aws",
+                "registry.terraform.io/terraform-aws-modules/vpc/aws",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_providers_map_links_aliased_configuration() {
+        let mut aliased = create_module("vpc", "terraform-aws-modules", "aws");
+        aliased
+            .dependencies
+            .providers
+            .insert("aws".to_string(), "aws.east".to_string());
+        let mut plain = create_module("dns", "acme", "google");
+        plain
+            .dependencies
+            .providers
+            .insert("aws".to_string(), "aws".to_string());
+        let providers = vec![
+            create_provider("aws", "hashicorp/aws"),
+            create_provider("google", "hashicorp/google"),
+        ];
+
+        let graph = GraphBuilder::new()
+            .build(&[aliased, plain], &providers, &[])
+            .unwrap();
+        let mut edges: Vec<_> = graph
+            .edges()
+            .map(|(from, to, edge)| (from.display_name(), to.display_name(), edge.clone()))
+            .collect();
+        edges.sort_by_key(|(from, _, _)| *from);
+        // The providers map replaces the provider inferred from the source
+        assert_eq!(
+            edges,
+            vec![
+                (
+                    "dns",
+                    "aws",
+                    EdgeType::ModuleRequiresProvider(RequirementOrigin::Declared)
+                ),
+                ("vpc", "aws", EdgeType::ProviderAlias),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolution_attaches_fetched_modules() {
+        use crate::resolver::{ResolvedCall, ResolvedPackage};
+
+        let key = "registry.terraform.io/terraform-aws-modules/eks/aws@20.1.0";
+        let caller = create_module("eks", "terraform-aws-modules", "aws");
+        let mut nested = create_module("kms", "terraform-aws-modules", "aws");
+        nested.repository = Some(key.to_string());
+        let mut inherited = create_provider("aws", "hashicorp/aws");
+        inherited.version_constraint = Some(Constraint::parse(">= 5.40").unwrap());
+        inherited.repository = Some(key.to_string());
+
+        let resolution = Resolution {
+            packages: vec![ResolvedPackage {
+                source: "registry.terraform.io/terraform-aws-modules/eks/aws".to_string(),
+                version: "20.1.0".to_string(),
+                depth: 1,
+                modules: vec![nested],
+                providers: vec![inherited],
+                runtimes: Vec::new(),
+            }],
+            calls: vec![ResolvedCall {
+                caller: caller.clone(),
+                package: key.to_string(),
+            }],
+            warnings: Vec::new(),
+        };
+
+        let graph = GraphBuilder::new()
+            .with_resolution(resolution)
+            .build(&[caller], &[create_provider("aws", "hashicorp/aws")], &[])
+            .unwrap();
+
+        // eks, kms, and one aws provider on each side
+        assert_eq!(graph.node_count(), 4);
+        let mut edges: Vec<_> = graph
+            .edges()
+            .map(|(from, to, edge)| {
+                (
+                    from.repository().unwrap().to_string(),
+                    to.display_name().to_string(),
+                    to.repository().unwrap().to_string(),
+                    edge.clone(),
+                )
+            })
+            .collect();
+        edges.sort_by(|a, b| (&a.0, &a.1, &a.2).cmp(&(&b.0, &b.1, &b.2)));
+        assert_eq!(
+            edges,
+            vec![
+                // kms infers the provider of its own package
+                (
+                    key.into(),
+                    "aws".into(),
+                    key.into(),
+                    EdgeType::ModuleRequiresProvider(RequirementOrigin::Inferred)
+                ),
+                // eks declares the requirement of the module it fetched,
+                // met by the scanned configuration's provider
+                (
+                    "test".into(),
+                    "aws".into(),
+                    key.into(),
+                    EdgeType::ModuleRequiresProvider(RequirementOrigin::Declared)
+                ),
+                (
+                    "test".into(),
+                    "aws".into(),
+                    "test".into(),
+                    EdgeType::ModuleRequiresProvider(RequirementOrigin::Declared)
+                ),
+                (
+                    "test".into(),
+                    "kms".into(),
+                    key.into(),
+                    EdgeType::ModuleCall
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn test_infer_provider_from_git_url() {
         let builder = GraphBuilder::new();
@@ -432,18 +1020,120 @@ mod tests {
                 url: "https://github.com/terraform-aws-modules/terraform-aws-vpc.git".to_string(),
                 ref_: None,
                 subdir: None,
+                params: Default::default(),
             },
             version_constraint: None,
             file_path: PathBuf::from("main.tf"),
             line_number: 1,
             repository: None,
             attributes: Default::default(),
+            dependencies: Default::default(),
         };
 
         let provider = builder.infer_provider_for_module(&module);
         assert_eq!(provider, Some("hashicorp/aws".to_string()));
     }
 
+    #[test]
+    fn test_configured_provider_inference() {
+        let module = |name: &str, host: &str| ModuleRef {
+            name: name.to_string(),
+            source: ModuleSource::Git {
+                host: host.to_string(),
+                url: format!("https://{host}.git"),
+                ref_: None,
+                subdir: None,
+                params: Default::default(),
+            },
+            version_constraint: None,
+            file_path: PathBuf::from("main.tf"),
+            line_number: 1,
+            repository: Some("test".to_string()),
+            attributes: Default::default(),
+            dependencies: Default::default(),
+        };
+        let rules = [ProviderInference {
+            source: r"^github\.com/acme/tf-(\w+)-".to_string(),
+            provider: "acme/$1".to_string(),
+        }];
+        let builder = GraphBuilder::new().with_provider_inference(&rules).unwrap();
+
+        let internal = module("cache", "github.com/acme/tf-internal-cache");
+        assert_eq!(
+            builder.infer_provider_for_module(&internal),
+            Some("acme/internal".to_string())
+        );
+        // Configured rules replace the built-in ones
+        let aws = module("vpc", "github.com/terraform-aws-modules/terraform-aws-vpc");
+        assert_eq!(builder.infer_provider_for_module(&aws), None);
+
+        let invalid = [ProviderInference {
+            source: "(".to_string(),
+            provider: "hashicorp/aws".to_string(),
+        }];
+        assert!(GraphBuilder::new()
+            .with_provider_inference(&invalid)
+            .is_err());
+    }
+
+    #[test]
+    fn test_registry_provider_wins_over_inference_rules() {
+        let rules = [ProviderInference {
+            source: r"/acme/".to_string(),
+            provider: "acme/internal".to_string(),
+        }];
+        let builder = GraphBuilder::new().with_provider_inference(&rules).unwrap();
+
+        let network = create_module("network", "acme", "google");
+        assert_eq!(
+            builder.infer_provider_for_module(&network),
+            Some("hashicorp/google".to_string())
+        );
+        // The built-in `terraform-aws-*` rule does not apply to registry addresses
+        let gke = create_module("gke", "terraform-aws-modules", "google");
+        assert_eq!(
+            GraphBuilder::new().infer_provider_for_module(&gke),
+            Some("hashicorp/google".to_string())
+        );
+    }
+
+    #[test]
+    fn test_lock_file_rejects_inferred_provider() {
+        let mut google = create_module("network", "terraform-google-modules", "google");
+        google.file_path = PathBuf::from("live/main.tf");
+        let mut aws = create_module("vpc", "terraform-aws-modules", "aws");
+        aws.file_path = PathBuf::from("live/main.tf");
+        let providers = vec![
+            create_provider("aws", "hashicorp/aws"),
+            create_provider("google", "hashicorp/google"),
+        ];
+        let lock = ProviderLock {
+            source: "registry.terraform.io/hashicorp/aws".to_string(),
+            version: "5.31.0".to_string(),
+            constraints: None,
+            file_path: PathBuf::from("live/.terraform.lock.hcl"),
+            line_number: 1,
+            repository: Some("test".to_string()),
+        };
+
+        let graph = GraphBuilder::new()
+            .with_locks(vec![lock])
+            .build(&[google, aws], &providers, &[])
+            .unwrap();
+        let edges: Vec<_> = graph
+            .edges()
+            .map(|(from, to, edge)| (from.display_name(), to.display_name(), edge.clone()))
+            .collect();
+        assert_eq!(
+            edges,
+            vec![(
+                "vpc",
+                "aws",
+                EdgeType::ModuleRequiresProvider(RequirementOrigin::Inferred)
+            )]
+        );
+    }
+
     #[test]
     fn test_multiple_repos_same_module() {
         let modules = vec![
@@ -454,12 +1144,15 @@ mod tests {
                     namespace: "terraform-aws-modules".to_string(),
                     name: "vpc".to_string(),
                     provider: "aws".to_string(),
+                    subdir: None,
+                    params: Default::default(),
                 },
                 version_constraint: Some(Constraint::parse("~> 5.0").unwrap()),
                 file_path: PathBuf::from("main.tf"),
                 line_number: 1,
                 repository: Some("repo-a".to_string()),
                 attributes: Default::default(),
+                dependencies: Default::default(),
             },
             ModuleRef {
                 name: "vpc".to_string(),
@@ -468,12 +1161,15 @@ mod tests {
                     namespace: "terraform-aws-modules".to_string(),
                     name: "vpc".to_string(),
                     provider: "aws".to_string(),
+                    subdir: None,
+                    params: Default::default(),
                 },
                 version_constraint: Some(Constraint::parse("~> 4.0").unwrap()),
                 file_path: PathBuf::from("main.tf"),
                 line_number: 1,
                 repository: Some("repo-b".to_string()),
                 attributes: Default::default(),
+                dependencies: Default::default(),
             },
         ];
 
@@ -4,9 +4,56 @@
 //! in various formats for visualization and analysis.
 
 use crate::error::Result;
-use crate::graph::types::{DependencyGraph, EdgeType, GraphNode};
-use crate::types::GraphFormat;
+use crate::graph::types::{DependencyGraph, EdgeType, GraphNode, NodeKind, RequirementOrigin};
+use crate::types::{ClusterBy, GraphFormat, ModuleSource};
 use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+
+/// Options for exporting a dependency graph.
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    /// Highlight the nodes and edges of dependency cycles
+    pub highlight_cycles: bool,
+    /// Group nodes into clusters (DOT and Mermaid only)
+    pub cluster_by: Option<ClusterBy>,
+}
+
+/// Nodes and edges to highlight as part of a cycle.
+#[derive(Debug, Default)]
+struct CycleHighlight {
+    cycles: Vec<Vec<String>>,
+    nodes: HashSet<String>,
+    edges: HashSet<(String, String)>,
+}
+
+impl CycleHighlight {
+    fn new(graph: &DependencyGraph, options: &ExportOptions) -> Self {
+        if !options.highlight_cycles {
+            return Self::default();
+        }
+
+        let cycles = graph.find_cycles();
+        let mut highlight = Self::default();
+        for cycle in &cycles {
+            for (i, from) in cycle.iter().enumerate() {
+                let to = &cycle[(i + 1) % cycle.len()];
+                highlight.nodes.insert(from.clone());
+                highlight.edges.insert((from.clone(), to.clone()));
+            }
+        }
+        highlight.cycles = cycles;
+        highlight
+    }
+
+    fn has_node(&self, id: &str) -> bool {
+        self.nodes.contains(id)
+    }
+
+    fn has_edge(&self, from: &str, to: &str) -> bool {
+        self.edges.contains(&(from.to_string(), to.to_string()))
+    }
+}
 
 /// Export the dependency graph to the specified format.
 ///
@@ -15,6 +62,15 @@ use serde::Serialize;
 /// - **DOT**: Graphviz DOT format for visualization
 /// - **JSON**: Structured JSON for programmatic access
 /// - **Mermaid**: Mermaid diagram syntax for documentation
+/// - **GraphML** and **GEXF**: XML with typed node and edge attributes, for
+///   Gephi and other graph analysis tools
+/// - **Cypher**: a script of idempotent `MERGE` statements for Neo4j
+///
+/// JSON, GraphML, GEXF and Cypher identify nodes by their graph ID (e.g.
+/// `module:infra:registry.terraform.io/terraform-aws-modules/vpc/aws:vpc`),
+/// so these exports of the same scan can be joined with each other. DOT and
+/// Mermaid replace the punctuation in IDs with `_` and are meant for viewing
+/// only.
 ///
 /// # Example
 ///
@@ -31,15 +87,53 @@ use serde::Serialize;
 ///
 /// Returns an error if serialization fails.
 pub fn export_graph(graph: &DependencyGraph, format: GraphFormat) -> Result<String> {
+    export_graph_with_options(graph, format, &ExportOptions::default())
+}
+
+/// Export the dependency graph with the given options.
+///
+/// With `highlight_cycles`, nodes and edges that form a dependency cycle
+/// are drawn in red (DOT, Mermaid), flagged with `in_cycle` and listed
+/// under `cycles` (JSON), or given an `in_cycle` attribute (GraphML, GEXF,
+/// Cypher).
+///
+/// With `cluster_by`, DOT and Mermaid group nodes by repository or VCS
+/// namespace. Registry modules and providers are drawn once, outside the
+/// clusters, with an edge from every cluster that uses them labelled with
+/// its number of consumers. Edges from a shared module to runtimes or other
+/// modules describe a single repository and are left out.
+///
+/// # Errors
+///
+/// Returns an error if serialization fails.
+pub fn export_graph_with_options(
+    graph: &DependencyGraph,
+    format: GraphFormat,
+    options: &ExportOptions,
+) -> Result<String> {
+    let highlight = CycleHighlight::new(graph, options);
     match format {
-        GraphFormat::Dot => export_dot(graph),
-        GraphFormat::Json => export_json(graph),
-        GraphFormat::Mermaid => export_mermaid(graph),
+        GraphFormat::Dot => match options.cluster_by {
+            Some(by) => Ok(export_dot_clustered(&ClusterLayout::new(
+                graph, by, &highlight,
+            ))),
+            None => export_dot(graph, &highlight),
+        },
+        GraphFormat::Json => export_json(graph, &highlight),
+        GraphFormat::Mermaid => match options.cluster_by {
+            Some(by) => Ok(export_mermaid_clustered(&ClusterLayout::new(
+                graph, by, &highlight,
+            ))),
+            None => export_mermaid(graph, &highlight),
+        },
+        GraphFormat::Graphml => Ok(export_graphml(graph, &highlight)),
+        GraphFormat::Gexf => Ok(export_gexf(graph, &highlight)),
+        GraphFormat::Cypher => Ok(export_cypher(graph, &highlight)),
     }
 }
 
 /// Export to Graphviz DOT format.
-fn export_dot(graph: &DependencyGraph) -> Result<String> {
+fn export_dot(graph: &DependencyGraph, highlight: &CycleHighlight) -> Result<String> {
     let mut dot = String::new();
     dot.push_str("digraph MonPhare {\n");
     dot.push_str("    rankdir=TB;\n");
@@ -63,8 +157,13 @@ fn export_dot(graph: &DependencyGraph) -> Result<String> {
                     .unwrap_or("no constraint")
             ));
             let node_id = escape_dot_id(&m.id);
+            let cycle_style = if highlight.has_node(&m.id) {
+                ", color=red, penwidth=2"
+            } else {
+                ""
+            };
             dot.push_str(&format!(
-                "        \"{node_id}\" [label=\"{label}\", fillcolor=lightblue, style=\"rounded,filled\"];\n"
+                "        \"{node_id}\" [label=\"{label}\", fillcolor=lightblue, style=\"rounded,filled\"{cycle_style}];\n"
             ));
         }
     }
@@ -93,18 +192,32 @@ fn export_dot(graph: &DependencyGraph) -> Result<String> {
     }
     dot.push_str("    }\n\n");
 
+    if graph.nodes().any(GraphNode::is_runtime) {
+        dot.push_str("    subgraph cluster_runtimes {\n");
+        dot.push_str("        label=\"Runtimes\";\n");
+        dot.push_str("        style=dashed;\n");
+        dot.push_str("        color=purple;\n");
+
+        for node in graph.nodes() {
+            if let GraphNode::Runtime(r) = node {
+                let label = escape_dot_string(&format!("{}\n{}", r.name, r.version.raw));
+                let node_id = escape_dot_id(&r.id);
+                dot.push_str(&format!(
+                    "        \"{node_id}\" [label=\"{label}\", fillcolor=lightyellow, style=\"filled\", shape=hexagon];\n"
+                ));
+            }
+        }
+        dot.push_str("    }\n\n");
+    }
+
     // Add edges
     for (from, to, edge_type) in graph.edges() {
         let from_id = escape_dot_id(from.id());
         let to_id = escape_dot_id(to.id());
-        let style = match edge_type {
-            EdgeType::ModuleDependsOn => "style=solid, color=blue",
-            EdgeType::ModuleRequiresProvider => "style=dashed, color=green",
-            EdgeType::ProviderAlias => "style=dotted, color=gray",
-            EdgeType::LocalModuleRef => "style=solid, color=orange",
-        };
+        let style = dot_edge_style(edge_type, highlight.has_edge(from.id(), to.id()));
+        let label = edge_label(edge_type);
         dot.push_str(&format!(
-            "    \"{from_id}\" -> \"{to_id}\" [{style}, label=\"{edge_type}\"];\n"
+            "    \"{from_id}\" -> \"{to_id}\" [{style}, label=\"{label}\"];\n"
         ));
     }
 
@@ -112,12 +225,52 @@ fn export_dot(graph: &DependencyGraph) -> Result<String> {
     Ok(dot)
 }
 
+/// DOT attributes of an edge.
+const fn dot_edge_style(edge_type: &EdgeType, in_cycle: bool) -> &'static str {
+    match edge_type {
+        _ if in_cycle => "style=bold, color=red",
+        EdgeType::ModuleDependsOn => "style=solid, color=blue",
+        EdgeType::ModuleRequiresProvider(RequirementOrigin::Declared) => {
+            "style=dashed, color=green"
+        }
+        EdgeType::ModuleRequiresProvider(RequirementOrigin::Inferred) => {
+            "style=dotted, color=green"
+        }
+        EdgeType::ProviderAlias => "style=dotted, color=gray",
+        EdgeType::LocalModuleRef => "style=solid, color=orange",
+        EdgeType::ModuleCall => "style=solid, color=darkgreen",
+        EdgeType::RequiresRuntime => "style=dashed, color=purple",
+    }
+}
+
+/// Mermaid arrow of an edge.
+const fn mermaid_arrow(edge_type: &EdgeType) -> &'static str {
+    match edge_type {
+        EdgeType::ModuleDependsOn => "-->",
+        EdgeType::ModuleRequiresProvider(_) => "-.->",
+        EdgeType::ProviderAlias => "-.-",
+        EdgeType::LocalModuleRef => "==>",
+        EdgeType::ModuleCall => "-->",
+        EdgeType::RequiresRuntime => "-.->",
+    }
+}
+
+/// Edge label, marking provider requirements inferred from the module source.
+fn edge_label(edge_type: &EdgeType) -> String {
+    match edge_type.requirement_origin() {
+        Some(RequirementOrigin::Inferred) => format!("{edge_type} (inferred)"),
+        _ => edge_type.to_string(),
+    }
+}
+
 /// Export to JSON format.
-fn export_json(graph: &DependencyGraph) -> Result<String> {
+fn export_json(graph: &DependencyGraph, highlight: &CycleHighlight) -> Result<String> {
     #[derive(Serialize)]
     struct JsonGraph {
         nodes: Vec<JsonNode>,
         edges: Vec<JsonEdge>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        cycles: Vec<Vec<String>>,
         metadata: JsonMetadata,
     }
 
@@ -131,6 +284,8 @@ fn export_json(graph: &DependencyGraph) -> Result<String> {
         version_constraint: Option<String>,
         file_path: String,
         repository: Option<String>,
+        #[serde(skip_serializing_if = "std::ops::Not::not")]
+        in_cycle: bool,
     }
 
     #[derive(Serialize)]
@@ -139,6 +294,8 @@ fn export_json(graph: &DependencyGraph) -> Result<String> {
         to: String,
         #[serde(rename = "type")]
         edge_type: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        origin: Option<RequirementOrigin>,
     }
 
     #[derive(Serialize)]
@@ -166,6 +323,7 @@ fn export_json(graph: &DependencyGraph) -> Result<String> {
                     version_constraint: m.version_constraint.as_ref().map(|c| c.raw.clone()),
                     file_path: m.file_path.to_string_lossy().to_string(),
                     repository: m.repository.clone(),
+                    in_cycle: highlight.has_node(&m.id),
                 });
             }
             GraphNode::Provider(p) => {
@@ -178,6 +336,7 @@ fn export_json(graph: &DependencyGraph) -> Result<String> {
                     version_constraint: p.version_constraint.as_ref().map(|c| c.raw.clone()),
                     file_path: p.file_path.to_string_lossy().to_string(),
                     repository: p.repository.clone(),
+                    in_cycle: false,
                 });
             }
             GraphNode::Runtime(r) => {
@@ -190,6 +349,7 @@ fn export_json(graph: &DependencyGraph) -> Result<String> {
                     version_constraint: None,
                     file_path: "".to_string(),
                     repository: None,
+                    in_cycle: false,
                 });
             }
         }
@@ -201,6 +361,7 @@ fn export_json(graph: &DependencyGraph) -> Result<String> {
             from: from.id().to_string(),
             to: to.id().to_string(),
             edge_type: edge_type.to_string(),
+            origin: edge_type.requirement_origin(),
         })
         .collect();
 
@@ -214,6 +375,7 @@ fn export_json(graph: &DependencyGraph) -> Result<String> {
         },
         nodes,
         edges,
+        cycles: highlight.cycles.clone(),
     };
 
     serde_json::to_string_pretty(&json_graph).map_err(|e| {
@@ -224,7 +386,7 @@ fn export_json(graph: &DependencyGraph) -> Result<String> {
 }
 
 /// Export to Mermaid diagram format.
-fn export_mermaid(graph: &DependencyGraph) -> Result<String> {
+fn export_mermaid(graph: &DependencyGraph, highlight: &CycleHighlight) -> Result<String> {
     let mut mermaid = String::new();
     mermaid.push_str("graph TD\n");
     mermaid.push_str("    %% MonPhare Dependency Graph\n\n");
@@ -252,17 +414,20 @@ fn export_mermaid(graph: &DependencyGraph) -> Result<String> {
 
     mermaid.push('\n');
 
-    // Add edges
-    for (from, to, edge_type) in graph.edges() {
+    // Add edges, remembering the index of cycle edges for `linkStyle`
+    let mut cycle_links = Vec::new();
+    for (index, (from, to, edge_type)) in graph.edges().enumerate() {
         let from_id = sanitize_mermaid_id(from.id());
         let to_id = sanitize_mermaid_id(to.id());
-        let arrow = match edge_type {
-            EdgeType::ModuleDependsOn => "-->",
-            EdgeType::ModuleRequiresProvider => "-.->",
-            EdgeType::ProviderAlias => "-.-",
-            EdgeType::LocalModuleRef => "==>",
+        let arrow = mermaid_arrow(edge_type);
+        let label = match edge_type.requirement_origin() {
+            Some(RequirementOrigin::Inferred) => "|inferred|",
+            _ => "",
         };
-        mermaid.push_str(&format!("    {from_id} {arrow} {to_id}\n"));
+        mermaid.push_str(&format!("    {from_id} {arrow}{label} {to_id}\n"));
+        if highlight.has_edge(from.id(), to.id()) {
+            cycle_links.push(index.to_string());
+        }
     }
 
     // Add styling
@@ -300,9 +465,700 @@ fn export_mermaid(graph: &DependencyGraph) -> Result<String> {
         mermaid.push_str(&format!("    class {} provider\n", provider_ids.join(",")));
     }
 
+    let cycle_ids: Vec<String> = graph
+        .nodes()
+        .filter(|n| highlight.has_node(n.id()))
+        .map(|n| sanitize_mermaid_id(n.id()))
+        .collect();
+    if !cycle_ids.is_empty() {
+        mermaid.push_str("    classDef cycle stroke:#d32f2f,stroke-width:3px\n");
+        mermaid.push_str(&format!("    class {} cycle\n", cycle_ids.join(",")));
+    }
+    if !cycle_links.is_empty() {
+        mermaid.push_str(&format!(
+            "    linkStyle {} stroke:#d32f2f,stroke-width:3px\n",
+            cycle_links.join(",")
+        ));
+    }
+
     Ok(mermaid)
 }
 
+/// A graph grouped into clusters, with registry modules and providers
+/// collapsed into shared nodes outside them.
+#[derive(Default)]
+struct ClusterLayout<'a> {
+    /// Top-level clusters, by name
+    clusters: BTreeMap<String, Cluster<'a>>,
+    /// Shared nodes, by ID
+    shared: BTreeMap<String, SharedNode>,
+    /// Edges between drawn nodes, by (from, to, type)
+    edges: BTreeMap<(String, String, String), AggregateEdge>,
+    /// Number of consumers in a cluster of a shared node, by (cluster, shared node)
+    uses: BTreeMap<(String, String), usize>,
+}
+
+#[derive(Default)]
+struct Cluster<'a> {
+    id: String,
+    nodes: Vec<&'a GraphNode>,
+    children: BTreeMap<String, Cluster<'a>>,
+}
+
+struct SharedNode {
+    kind: NodeKind,
+    label: String,
+    clusters: BTreeSet<String>,
+}
+
+struct AggregateEdge {
+    edge_type: EdgeType,
+    count: usize,
+    in_cycle: bool,
+}
+
+impl<'a> ClusterLayout<'a> {
+    fn new(graph: &'a DependencyGraph, by: ClusterBy, highlight: &CycleHighlight) -> Self {
+        // VCS metadata is only recorded on some nodes; apply it to the whole repository
+        let mut namespaces: HashMap<&str, Vec<String>> = HashMap::new();
+        if by == ClusterBy::Namespace {
+            for node in graph.nodes() {
+                let vcs = graph.get_vcs_metadata(&node.id().to_string());
+                if let (Some(repository), Some(vcs)) = (node.repository(), vcs) {
+                    if let Some((repo, namespace)) = vcs.components.split_last() {
+                        let mut path = vec![format!("{}:{}", vcs.platform(), namespace.join("/"))];
+                        if namespace.is_empty() {
+                            path[0] = format!("{}:{repo}", vcs.platform());
+                        } else {
+                            path.push(repo.clone());
+                        }
+                        namespaces.entry(repository).or_insert(path);
+                    }
+                }
+            }
+        }
+
+        let mut layout = Self::default();
+        let mut drawn_as: HashMap<&str, (String, bool)> = HashMap::new();
+        for node in graph.nodes() {
+            let repository = node.repository().unwrap_or("(no repository)");
+            let path = namespaces
+                .get(repository)
+                .cloned()
+                .unwrap_or_else(|| vec![repository.to_string()]);
+            let cluster = layout.cluster_mut(&path);
+
+            match shared_node(node) {
+                Some((id, label)) => {
+                    let cluster_id = cluster.id.clone();
+                    layout
+                        .shared
+                        .entry(id.clone())
+                        .or_insert_with(|| SharedNode {
+                            kind: node.kind(),
+                            label,
+                            clusters: BTreeSet::new(),
+                        })
+                        .clusters
+                        .insert(cluster_id.clone());
+                    *layout.uses.entry((cluster_id, id.clone())).or_insert(0) += 1;
+                    drawn_as.insert(node.id(), (id, true));
+                }
+                None => {
+                    cluster.nodes.push(node);
+                    drawn_as.insert(node.id(), (node.id().to_string(), false));
+                }
+            }
+        }
+
+        for (from, to, edge_type) in graph.edges() {
+            let (from_id, from_shared) = &drawn_as[from.id()];
+            let (to_id, to_shared) = &drawn_as[to.id()];
+            // Uses of shared nodes are drawn from the cluster instead. A shared
+            // node stands for every repository using it, so the only edges drawn
+            // from it are provider requirements and aliases, which hold for all of
+            // them; its runtimes and dependencies belong to a single repository.
+            let universal = *to_shared
+                && matches!(
+                    edge_type,
+                    EdgeType::ModuleRequiresProvider(_) | EdgeType::ProviderAlias
+                );
+            if from_id == to_id || (*to_shared && !from_shared) || (*from_shared && !universal) {
+                continue;
+            }
+            let edge = layout
+                .edges
+                .entry((from_id.clone(), to_id.clone(), edge_label(edge_type)))
+                .or_insert_with(|| AggregateEdge {
+                    edge_type: edge_type.clone(),
+                    count: 0,
+                    in_cycle: false,
+                });
+            edge.count += 1;
+            edge.in_cycle |= highlight.has_edge(from.id(), to.id());
+        }
+
+        tracing::debug!(
+            clusters = layout.clusters.len(),
+            shared = layout.shared.len(),
+            edges = layout.edges.len(),
+            "Grouped graph into clusters"
+        );
+        layout
+    }
+
+    fn cluster_mut(&mut self, path: &[String]) -> &mut Cluster<'a> {
+        let (first, rest) = path.split_first().expect("cluster path is never empty");
+        let mut id = format!("cluster_{}", sanitize_mermaid_id(first));
+        let mut cluster = self
+            .clusters
+            .entry(first.clone())
+            .or_insert_with(|| Cluster {
+                id: id.clone(),
+                ..Cluster::default()
+            });
+        for name in rest {
+            id.push('_');
+            id.push_str(&sanitize_mermaid_id(name));
+            cluster = cluster
+                .children
+                .entry(name.clone())
+                .or_insert_with(|| Cluster {
+                    id: id.clone(),
+                    ..Cluster::default()
+                });
+        }
+        cluster
+    }
+}
+
+/// ID and label of the shared node a node is drawn as, if any.
+fn shared_node(node: &GraphNode) -> Option<(String, String)> {
+    match node {
+        GraphNode::Module(m) if matches!(m.source, ModuleSource::Registry { .. }) => Some((
+            format!("shared:module:{}", m.source.canonical_id()),
+            m.source.to_source_string(),
+        )),
+        GraphNode::Provider(p) => Some((format!("shared:provider:{}", p.source), p.source.clone())),
+        _ => None,
+    }
+}
+
+/// Line width for an edge standing for `count` edges or consumers.
+fn edge_weight(count: usize) -> f64 {
+    (1.0 + (count as f64).log2()).min(6.0)
+}
+
+fn repositories_label(count: usize) -> String {
+    if count == 1 {
+        "1 repository".to_string()
+    } else {
+        format!("{count} repositories")
+    }
+}
+
+/// Export to Graphviz DOT format, grouped into clusters.
+fn export_dot_clustered(layout: &ClusterLayout<'_>) -> String {
+    fn write_cluster(
+        dot: &mut String,
+        cluster: &Cluster<'_>,
+        name: &str,
+        anchored: &HashSet<&str>,
+        depth: usize,
+    ) {
+        let indent = "    ".repeat(depth);
+        let _ = writeln!(dot, "{indent}subgraph {} {{", cluster.id);
+        let _ = writeln!(dot, "{indent}    label=\"{}\";", escape_dot_string(name));
+        let _ = writeln!(dot, "{indent}    style=dashed;");
+        let _ = writeln!(dot, "{indent}    color=gray40;");
+        if anchored.contains(cluster.id.as_str()) {
+            let _ = writeln!(
+                dot,
+                "{indent}    \"{}_anchor\" [shape=point, style=invis];",
+                cluster.id
+            );
+        }
+        for node in &cluster.nodes {
+            let node_id = escape_dot_id(node.id());
+            let label = match node {
+                GraphNode::Module(m) => format!(
+                    "{}\n{}",
+                    m.name,
+                    m.version_constraint
+                        .as_ref()
+                        .map_or("no constraint", |c| c.raw.as_str())
+                ),
+                GraphNode::Runtime(r) => format!("{}\n{}", r.name, r.version.raw),
+                GraphNode::Provider(p) => p.source.clone(),
+            };
+            let style = if node.is_runtime() {
+                "fillcolor=lightyellow, style=\"filled\", shape=hexagon"
+            } else {
+                "fillcolor=lightblue, style=\"rounded,filled\""
+            };
+            let _ = writeln!(
+                dot,
+                "{indent}    \"{node_id}\" [label=\"{}\", {style}];",
+                escape_dot_string(&label)
+            );
+        }
+        for (name, child) in &cluster.children {
+            write_cluster(dot, child, name, anchored, depth + 1);
+        }
+        let _ = writeln!(dot, "{indent}}}");
+    }
+
+    let anchored: HashSet<&str> = layout.uses.keys().map(|(c, _)| c.as_str()).collect();
+    let mut dot = String::new();
+    dot.push_str("digraph MonPhare {\n");
+    dot.push_str("    rankdir=TB;\n");
+    dot.push_str("    compound=true;\n");
+    dot.push_str("    node [shape=box, style=rounded];\n\n");
+
+    for (name, cluster) in &layout.clusters {
+        write_cluster(&mut dot, cluster, name, &anchored, 1);
+    }
+    dot.push('\n');
+
+    for (id, shared) in &layout.shared {
+        let label = format!(
+            "{}\n{}",
+            shared.label,
+            repositories_label(shared.clusters.len())
+        );
+        let style = match shared.kind {
+            NodeKind::Provider => "fillcolor=lightgreen, style=\"rounded,filled\", shape=ellipse",
+            _ => "fillcolor=lightblue, style=\"rounded,filled\", penwidth=2",
+        };
+        let _ = writeln!(
+            dot,
+            "    \"{}\" [label=\"{}\", {style}];",
+            escape_dot_id(id),
+            escape_dot_string(&label)
+        );
+    }
+    dot.push('\n');
+
+    for ((from, to, label), edge) in &layout.edges {
+        let style = dot_edge_style(&edge.edge_type, edge.in_cycle);
+        let label = if edge.count > 1 {
+            format!("{label} x{}", edge.count)
+        } else {
+            label.clone()
+        };
+        let _ = writeln!(
+            dot,
+            "    \"{}\" -> \"{}\" [{style}, label=\"{label}\", penwidth={:.1}];",
+            escape_dot_id(from),
+            escape_dot_id(to),
+            edge_weight(edge.count)
+        );
+    }
+    for ((cluster, shared), count) in &layout.uses {
+        let _ = writeln!(
+            dot,
+            "    \"{cluster}_anchor\" -> \"{}\" [ltail={cluster}, color=gray40, label=\"{count}\", penwidth={:.1}];",
+            escape_dot_id(shared),
+            edge_weight(*count)
+        );
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// Export to Mermaid diagram format, grouped into subgraphs.
+fn export_mermaid_clustered(layout: &ClusterLayout<'_>) -> String {
+    fn write_cluster(mermaid: &mut String, cluster: &Cluster<'_>, name: &str, depth: usize) {
+        let indent = "    ".repeat(depth);
+        let _ = writeln!(
+            mermaid,
+            "{indent}subgraph {}[\"{}\"]",
+            cluster.id,
+            escape_mermaid_string(name)
+        );
+        for node in &cluster.nodes {
+            let id = sanitize_mermaid_id(node.id());
+            let label = escape_mermaid_string(node.display_name());
+            let icon = if node.is_runtime() { "🚀" } else { "📦" };
+            let _ = writeln!(mermaid, "{indent}    {id}[\"{icon} {label}\"]");
+        }
+        for (name, child) in &cluster.children {
+            write_cluster(mermaid, child, name, depth + 1);
+        }
+        let _ = writeln!(mermaid, "{indent}end");
+    }
+
+    let mut mermaid = String::new();
+    mermaid.push_str("graph TD\n");
+    mermaid.push_str("    %% MonPhare Dependency Graph\n\n");
+
+    for (name, cluster) in &layout.clusters {
+        write_cluster(&mut mermaid, cluster, name, 1);
+    }
+    mermaid.push('\n');
+
+    for (id, shared) in &layout.shared {
+        let label = format!(
+            "{}<br/>{}",
+            escape_mermaid_string(&shared.label),
+            repositories_label(shared.clusters.len())
+        );
+        let id = sanitize_mermaid_id(id);
+        let _ = match shared.kind {
+            NodeKind::Provider => writeln!(mermaid, "    {id}((\"🔌 {label}\"))"),
+            _ => writeln!(mermaid, "    {id}[\"📦 {label}\"]"),
+        };
+    }
+    mermaid.push('\n');
+
+    // Edges, remembering the index and width of weighted and cycle edges
+    let mut link_styles = Vec::new();
+    let mut index = 0;
+    for ((from, to, _), edge) in &layout.edges {
+        let arrow = mermaid_arrow(&edge.edge_type);
+        let mut label = Vec::new();
+        if edge.edge_type.requirement_origin() == Some(RequirementOrigin::Inferred) {
+            label.push("inferred".to_string());
+        }
+        if edge.count > 1 {
+            label.push(format!("x{}", edge.count));
+        }
+        let label = if label.is_empty() {
+            String::new()
+        } else {
+            format!("|\"{}\"|", label.join(" "))
+        };
+        let _ = writeln!(
+            mermaid,
+            "    {} {arrow}{label} {}",
+            sanitize_mermaid_id(from),
+            sanitize_mermaid_id(to)
+        );
+        if edge.in_cycle {
+            link_styles.push(format!(
+                "    linkStyle {index} stroke:#d32f2f,stroke-width:3px"
+            ));
+        } else if edge.count > 1 {
+            link_styles.push(format!(
+                "    linkStyle {index} stroke-width:{:.1}px",
+                edge_weight(edge.count)
+            ));
+        }
+        index += 1;
+    }
+    for ((cluster, shared), count) in &layout.uses {
+        let _ = writeln!(
+            mermaid,
+            "    {cluster} -->|\"{count}\"| {}",
+            sanitize_mermaid_id(shared)
+        );
+        if *count > 1 {
+            link_styles.push(format!(
+                "    linkStyle {index} stroke-width:{:.1}px",
+                edge_weight(*count)
+            ));
+        }
+        index += 1;
+    }
+
+    mermaid.push_str("\n    %% Styling\n");
+    mermaid.push_str("    classDef shared fill:#e1f5fe,stroke:#01579b,stroke-width:2px\n");
+    let shared_ids: Vec<String> = layout
+        .shared
+        .keys()
+        .map(|id| sanitize_mermaid_id(id))
+        .collect();
+    if !shared_ids.is_empty() {
+        let _ = writeln!(mermaid, "    class {} shared", shared_ids.join(","));
+    }
+    for style in link_styles {
+        mermaid.push_str(&style);
+        mermaid.push('\n');
+    }
+    mermaid
+}
+
+/// Attributes shared by the GraphML, GEXF and Cypher exports.
+struct NodeRecord<'a> {
+    id: &'a str,
+    kind: &'static str,
+    name: &'a str,
+    source: String,
+    constraint: Option<&'a str>,
+    repository: Option<&'a str>,
+    file: String,
+    line: usize,
+    vcs: Option<&'a str>,
+    in_cycle: bool,
+}
+
+impl<'a> NodeRecord<'a> {
+    fn new(graph: &'a DependencyGraph, node: &'a GraphNode, highlight: &CycleHighlight) -> Self {
+        let (constraint, file, line) = match node {
+            GraphNode::Module(m) => (
+                m.version_constraint.as_ref().map(|c| c.raw.as_str()),
+                &m.file_path,
+                m.line_number,
+            ),
+            GraphNode::Provider(p) => (
+                p.version_constraint.as_ref().map(|c| c.raw.as_str()),
+                &p.file_path,
+                p.line_number,
+            ),
+            GraphNode::Runtime(r) => (Some(r.version.raw.as_str()), &r.file_path, r.line_number),
+        };
+        Self {
+            id: node.id(),
+            kind: match node.kind() {
+                NodeKind::Module => "module",
+                NodeKind::Provider => "provider",
+                NodeKind::Runtime => "runtime",
+            },
+            name: node.display_name(),
+            source: node.source(),
+            constraint,
+            repository: node.repository(),
+            file: file.to_string_lossy().to_string(),
+            line,
+            vcs: graph
+                .get_vcs_metadata(&node.id().to_string())
+                .map(|vcs| vcs.canonical.as_str()),
+            in_cycle: highlight.has_node(node.id()),
+        }
+    }
+
+    /// Optional string attributes, by name.
+    fn strings(&self) -> [(&'static str, Option<&str>); 6] {
+        [
+            ("kind", Some(self.kind)),
+            ("name", Some(self.name)),
+            ("source", Some(&self.source)),
+            ("constraint", self.constraint),
+            ("repository", self.repository),
+            ("file", Some(&self.file)),
+        ]
+    }
+}
+
+/// Export to GraphML.
+fn export_graphml(graph: &DependencyGraph, highlight: &CycleHighlight) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    for key in ["kind", "name", "source", "constraint", "repository", "file"] {
+        let _ = writeln!(
+            xml,
+            "  <key id=\"{key}\" for=\"node\" attr.name=\"{key}\" attr.type=\"string\"/>"
+        );
+    }
+    xml.push_str("  <key id=\"line\" for=\"node\" attr.name=\"line\" attr.type=\"int\"/>\n");
+    xml.push_str("  <key id=\"vcs\" for=\"node\" attr.name=\"vcs\" attr.type=\"string\"/>\n");
+    xml.push_str(
+        "  <key id=\"in_cycle\" for=\"node\" attr.name=\"in_cycle\" attr.type=\"boolean\">\
+         <default>false</default></key>\n",
+    );
+    xml.push_str("  <key id=\"type\" for=\"edge\" attr.name=\"type\" attr.type=\"string\"/>\n");
+    xml.push_str("  <key id=\"origin\" for=\"edge\" attr.name=\"origin\" attr.type=\"string\"/>\n");
+    xml.push_str("  <graph id=\"monphare\" edgedefault=\"directed\">\n");
+
+    for node in graph.nodes() {
+        let record = NodeRecord::new(graph, node, highlight);
+        let _ = writeln!(xml, "    <node id=\"{}\">", escape_xml(record.id));
+        for (key, value) in record.strings() {
+            if let Some(value) = value {
+                let _ = writeln!(
+                    xml,
+                    "      <data key=\"{key}\">{}</data>",
+                    escape_xml(value)
+                );
+            }
+        }
+        let _ = writeln!(xml, "      <data key=\"line\">{}</data>", record.line);
+        if let Some(vcs) = record.vcs {
+            let _ = writeln!(xml, "      <data key=\"vcs\">{}</data>", escape_xml(vcs));
+        }
+        if record.in_cycle {
+            xml.push_str("      <data key=\"in_cycle\">true</data>\n");
+        }
+        xml.push_str("    </node>\n");
+    }
+
+    for (index, (from, to, edge_type)) in graph.edges().enumerate() {
+        let origin = edge_type
+            .requirement_origin()
+            .map(|origin| format!("<data key=\"origin\">{origin}</data>"))
+            .unwrap_or_default();
+        let _ = writeln!(
+            xml,
+            "    <edge id=\"e{index}\" source=\"{}\" target=\"{}\"><data key=\"type\">{edge_type}</data>{origin}</edge>",
+            escape_xml(from.id()),
+            escape_xml(to.id())
+        );
+    }
+
+    xml.push_str("  </graph>\n</graphml>\n");
+    xml
+}
+
+/// Export to GEXF 1.3.
+fn export_gexf(graph: &DependencyGraph, highlight: &CycleHighlight) -> String {
+    const NODE_ATTRIBUTES: [(&str, &str); 9] = [
+        ("kind", "string"),
+        ("name", "string"),
+        ("source", "string"),
+        ("constraint", "string"),
+        ("repository", "string"),
+        ("file", "string"),
+        ("line", "integer"),
+        ("vcs", "string"),
+        ("in_cycle", "boolean"),
+    ];
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n");
+    xml.push_str("  <meta>\n    <creator>MonPhare</creator>\n  </meta>\n");
+    xml.push_str("  <graph defaultedgetype=\"directed\">\n");
+    xml.push_str("    <attributes class=\"node\">\n");
+    for (id, kind) in NODE_ATTRIBUTES {
+        let default = if id == "in_cycle" {
+            "<default>false</default>"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            xml,
+            "      <attribute id=\"{id}\" title=\"{id}\" type=\"{kind}\">{default}</attribute>"
+        );
+    }
+    xml.push_str("    </attributes>\n");
+    xml.push_str("    <attributes class=\"edge\">\n");
+    xml.push_str("      <attribute id=\"type\" title=\"type\" type=\"string\"></attribute>\n");
+    xml.push_str("      <attribute id=\"origin\" title=\"origin\" type=\"string\"></attribute>\n");
+    xml.push_str("    </attributes>\n");
+
+    xml.push_str("    <nodes>\n");
+    for node in graph.nodes() {
+        let record = NodeRecord::new(graph, node, highlight);
+        let _ = writeln!(
+            xml,
+            "      <node id=\"{}\" label=\"{}\">",
+            escape_xml(record.id),
+            escape_xml(record.name)
+        );
+        xml.push_str("        <attvalues>\n");
+        let line = record.line.to_string();
+        let values = record
+            .strings()
+            .into_iter()
+            .chain([("line", Some(line.as_str())), ("vcs", record.vcs)])
+            .chain([("in_cycle", record.in_cycle.then_some("true"))]);
+        for (id, value) in values {
+            if let Some(value) = value {
+                let _ = writeln!(
+                    xml,
+                    "          <attvalue for=\"{id}\" value=\"{}\"/>",
+                    escape_xml(value)
+                );
+            }
+        }
+        xml.push_str("        </attvalues>\n");
+        xml.push_str("      </node>\n");
+    }
+    xml.push_str("    </nodes>\n");
+
+    xml.push_str("    <edges>\n");
+    for (index, (from, to, edge_type)) in graph.edges().enumerate() {
+        let origin = edge_type
+            .requirement_origin()
+            .map(|origin| format!("<attvalue for=\"origin\" value=\"{origin}\"/>"))
+            .unwrap_or_default();
+        let _ = writeln!(
+            xml,
+            "      <edge id=\"e{index}\" source=\"{}\" target=\"{}\" label=\"{}\">\
+             <attvalues><attvalue for=\"type\" value=\"{edge_type}\"/>{origin}</attvalues></edge>",
+            escape_xml(from.id()),
+            escape_xml(to.id()),
+            edge_label(edge_type)
+        );
+    }
+    xml.push_str("    </edges>\n");
+    xml.push_str("  </graph>\n</gexf>\n");
+    xml
+}
+
+/// Export to a Cypher script for Neo4j.
+///
+/// Every node gets the `MonPhare` label plus one of `Module`, `Provider` or
+/// `Runtime`, keyed by a unique `id`. Statements use `MERGE`, so running
+/// the script again updates the graph instead of duplicating it.
+fn export_cypher(graph: &DependencyGraph, highlight: &CycleHighlight) -> String {
+    let mut cypher = String::new();
+    cypher.push_str("// MonPhare Dependency Graph\n");
+    cypher.push_str(
+        "CREATE CONSTRAINT monphare_node_id IF NOT EXISTS \
+         FOR (n:MonPhare) REQUIRE n.id IS UNIQUE;\n\n",
+    );
+
+    for node in graph.nodes() {
+        let record = NodeRecord::new(graph, node, highlight);
+        let label = match node.kind() {
+            NodeKind::Module => "Module",
+            NodeKind::Provider => "Provider",
+            NodeKind::Runtime => "Runtime",
+        };
+        let mut properties: Vec<String> = record
+            .strings()
+            .into_iter()
+            .filter_map(|(key, value)| value.map(|v| format!("{key}: {}", cypher_string(v))))
+            .collect();
+        properties.push(format!("line: {}", record.line));
+        if let Some(vcs) = record.vcs {
+            properties.push(format!("vcs: {}", cypher_string(vcs)));
+        }
+        properties.push(format!("in_cycle: {}", record.in_cycle));
+        let _ = writeln!(
+            cypher,
+            "MERGE (n:MonPhare {{id: {}}}) SET n:{label}, n += {{{}}};",
+            cypher_string(record.id),
+            properties.join(", ")
+        );
+    }
+
+    if graph.edge_count() > 0 {
+        cypher.push('\n');
+    }
+    for (from, to, edge_type) in graph.edges() {
+        let origin = edge_type
+            .requirement_origin()
+            .map(|origin| format!(" SET r.origin = '{origin}'"))
+            .unwrap_or_default();
+        let _ = writeln!(
+            cypher,
+            "MATCH (a:MonPhare {{id: {}}}), (b:MonPhare {{id: {}}}) MERGE (a)-[r:{}]->(b){origin};",
+            cypher_string(from.id()),
+            cypher_string(to.id()),
+            edge_type.to_string().to_uppercase()
+        );
+    }
+    cypher
+}
+
+/// Escape a string for use in XML text and attribute values.
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Quote a string as a Cypher string literal.
+fn cypher_string(s: &str) -> String {
+    format!("'{}'", s.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
 /// Escape a string for use in DOT labels.
 fn escape_dot_string(s: &str) -> String {
     s.replace('\\', "\\\\")
@@ -338,9 +1194,7 @@ mod tests {
 
     use super::*;
     use crate::graph::GraphBuilder;
-    use crate::types::{
-        Constraint, ModuleRef, ModuleSource, ProviderRef, RuntimeRef, RuntimeSource,
-    };
+    use crate::types::{Constraint, ModuleRef, ProviderRef, RuntimeRef, RuntimeSource};
 
     use std::path::PathBuf;
 
@@ -352,12 +1206,15 @@ mod tests {
                 namespace: "terraform-aws-modules".to_string(),
                 name: "vpc".to_string(),
                 provider: "aws".to_string(),
+                subdir: None,
+                params: Default::default(),
             },
             version_constraint: Some(Constraint::parse("~> 5.0").unwrap()),
             file_path: PathBuf::from("main.tf"),
             line_number: 1,
             repository: Some("test".to_string()),
             attributes: Default::default(),
+            dependencies: Default::default(),
         }];
 
         let providers = vec![ProviderRef {
@@ -386,17 +1243,40 @@ mod tests {
     #[test]
     fn test_export_dot() {
         let graph = create_test_graph();
-        let dot = export_dot(&graph).unwrap();
+        let dot = export_dot(&graph, &CycleHighlight::default()).unwrap();
 
         assert!(dot.contains("digraph MonPhare"));
         assert!(dot.contains("vpc"));
         assert!(dot.contains("hashicorp/aws"));
     }
 
+    #[test]
+    fn test_export_dot_declares_runtimes() {
+        let graph = create_test_graph();
+        let dot = export_dot(&graph, &CycleHighlight::default()).unwrap();
+
+        let runtime = graph.nodes().find(|n| n.is_runtime()).unwrap();
+        let runtime_id = escape_dot_id(runtime.id());
+        assert!(dot.contains(&format!(
+            "\"{runtime_id}\" [label=\"terraform\\n1.0.0\", fillcolor=lightyellow"
+        )));
+        assert!(dot.contains(&format!("-> \"{runtime_id}\"")));
+
+        // Every edge endpoint is declared as a node
+        for (from, to, _) in graph.edges() {
+            for id in [from.id(), to.id()] {
+                assert!(
+                    dot.contains(&format!("        \"{}\" [label=", escape_dot_id(id))),
+                    "{id} is not declared"
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_export_json() {
         let graph = create_test_graph();
-        let json = export_json(&graph).unwrap();
+        let json = export_json(&graph, &CycleHighlight::default()).unwrap();
 
         assert!(json.contains("\"nodes\""));
         assert!(json.contains("\"edges\""));
@@ -405,16 +1285,245 @@ mod tests {
         // Parse to verify it's valid JSON
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
         assert!(parsed["metadata"]["total_nodes"].as_u64().unwrap() > 0);
+        assert_eq!(parsed["edges"][0]["type"], "requires");
+        assert_eq!(parsed["edges"][0]["origin"], "inferred");
     }
 
     #[test]
     fn test_export_mermaid() {
         let graph = create_test_graph();
-        let mermaid = export_mermaid(&graph).unwrap();
+        let mermaid = export_mermaid(&graph, &CycleHighlight::default()).unwrap();
 
         assert!(mermaid.contains("graph TD"));
         assert!(mermaid.contains("📦")); // Module emoji
         assert!(mermaid.contains("🔌")); // Provider emoji
+        assert!(mermaid.contains("-.->|inferred|"));
+    }
+
+    #[test]
+    fn test_export_graphml_and_gexf() {
+        let graph = create_test_graph();
+        let module_id = graph
+            .nodes()
+            .find(|n| n.is_module())
+            .unwrap()
+            .id()
+            .to_string();
+
+        let graphml = export_graphml(&graph, &CycleHighlight::default());
+        assert!(graphml.contains("<graph id=\"monphare\" edgedefault=\"directed\">"));
+        assert!(graphml.contains(&format!("<node id=\"{module_id}\">")));
+        assert!(graphml.contains("<data key=\"constraint\">~&gt; 5.0</data>"));
+        assert!(graphml
+            .contains("<data key=\"type\">requires</data><data key=\"origin\">inferred</data>"));
+
+        let gexf = export_gexf(&graph, &CycleHighlight::default());
+        assert!(gexf.contains(&format!("<node id=\"{module_id}\" label=\"vpc\">")));
+        assert!(gexf.contains("<attvalue for=\"kind\" value=\"provider\"/>"));
+        assert!(gexf.contains("<attvalue for=\"line\" value=\"1\"/>"));
+        assert_eq!(
+            graphml.matches("<edge ").count(),
+            gexf.matches("<edge ").count()
+        );
+    }
+
+    #[test]
+    fn test_export_cypher() {
+        let graph = create_test_graph();
+        let cypher = export_cypher(&graph, &CycleHighlight::default());
+
+        assert!(cypher.contains("CREATE CONSTRAINT monphare_node_id IF NOT EXISTS"));
+        assert!(cypher.contains("SET n:Module, n += {kind: 'module', name: 'vpc'"));
+        assert!(cypher.contains("constraint: '>= 4.0'"));
+        assert!(cypher.contains("MERGE (a)-[r:REQUIRES]->(b) SET r.origin = 'inferred';"));
+        assert_eq!(cypher_string("it's"), "'it\\'s'");
+    }
+
+    fn create_cyclic_graph() -> DependencyGraph {
+        let local = |name: &str, path: &str| ModuleRef {
+            name: name.to_string(),
+            source: ModuleSource::Local {
+                path: path.to_string(),
+            },
+            version_constraint: None,
+            file_path: PathBuf::from(format!("{name}/main.tf")),
+            line_number: 1,
+            repository: Some("test".to_string()),
+            attributes: Default::default(),
+            dependencies: Default::default(),
+        };
+        let modules = vec![local("network", "../dns"), local("dns", "../network")];
+        GraphBuilder::new().build(&modules, &[], &[]).unwrap()
+    }
+
+    #[test]
+    fn test_highlight_cycles() {
+        let graph = create_cyclic_graph();
+        let options = ExportOptions {
+            highlight_cycles: true,
+            ..ExportOptions::default()
+        };
+
+        let dot = export_graph_with_options(&graph, GraphFormat::Dot, &options).unwrap();
+        assert_eq!(dot.matches("color=red, penwidth=2").count(), 2);
+        assert_eq!(dot.matches("style=bold, color=red").count(), 2);
+
+        let mermaid = export_graph_with_options(&graph, GraphFormat::Mermaid, &options).unwrap();
+        assert!(mermaid.contains("classDef cycle"));
+        assert!(mermaid.contains("linkStyle 0,1 stroke:#d32f2f"));
+
+        let json = export_graph_with_options(&graph, GraphFormat::Json, &options).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["cycles"][0].as_array().unwrap().len(), 2);
+        assert_eq!(parsed["nodes"][0]["in_cycle"], true);
+
+        let graphml = export_graph_with_options(&graph, GraphFormat::Graphml, &options).unwrap();
+        assert_eq!(
+            graphml
+                .matches("<data key=\"in_cycle\">true</data>")
+                .count(),
+            2
+        );
+
+        // Without the option, nothing is highlighted
+        let plain = export_graph(&graph, GraphFormat::Dot).unwrap();
+        assert!(!plain.contains("color=red"));
+    }
+
+    #[test]
+    fn test_cluster_by_namespace() {
+        let module = |name: &str, repository: &str, source: ModuleSource| ModuleRef {
+            name: name.to_string(),
+            source,
+            version_constraint: None,
+            file_path: PathBuf::from(format!("{repository}/main.tf")),
+            line_number: 1,
+            repository: Some(repository.to_string()),
+            attributes: Default::default(),
+            dependencies: Default::default(),
+        };
+        let vpc = || ModuleSource::Registry {
+            hostname: "registry.terraform.io".to_string(),
+            namespace: "terraform-aws-modules".to_string(),
+            name: "vpc".to_string(),
+            provider: "aws".to_string(),
+            subdir: None,
+            params: Default::default(),
+        };
+        let modules = vec![
+            module("vpc", "net", vpc()),
+            module("vpc_b", "net", vpc()),
+            module("vpc", "app", vpc()),
+            module(
+                "dns",
+                "net",
+                ModuleSource::Local {
+                    path: "./dns".to_string(),
+                },
+            ),
+        ];
+        let mut graph = GraphBuilder::new().build(&modules, &[], &[]).unwrap();
+        let ids: Vec<_> = graph.module_ids().into_iter().cloned().collect();
+        for id in ids {
+            let repository = graph
+                .get_node(&id)
+                .unwrap()
+                .repository()
+                .unwrap()
+                .to_string();
+            graph.set_vcs_metadata(
+                &id,
+                crate::vcs::VcsIdentifier::new("github", &["acme", &repository]),
+            );
+        }
+
+        let options = ExportOptions {
+            cluster_by: Some(ClusterBy::Namespace),
+            ..ExportOptions::default()
+        };
+        let dot = export_graph_with_options(&graph, GraphFormat::Dot, &options).unwrap();
+        assert!(dot.contains("subgraph cluster_github_acme {"));
+        assert!(dot.contains("        subgraph cluster_github_acme_net {"));
+        assert_eq!(
+            dot.matches("[label=\"terraform-aws-modules/vpc/aws\\n2 repositories\"")
+                .count(),
+            1
+        );
+        assert!(dot.contains("\"cluster_github_acme_net_anchor\" -> \"shared_module_registry_terraform_io_terraform_aws_modules_vpc_aws\" [ltail=cluster_github_acme_net, color=gray40, label=\"2\""));
+
+        let mermaid = export_graph_with_options(&graph, GraphFormat::Mermaid, &options).unwrap();
+        assert!(mermaid.contains("subgraph cluster_github_acme_app[\"app\"]"));
+        assert!(mermaid.contains("cluster_github_acme_app -->|\"1\"| shared_module_"));
+    }
+
+    #[test]
+    fn test_cluster_drops_repository_edges_from_shared_nodes() {
+        let module = |name: &str, repository: &str, module_name: &str| ModuleRef {
+            name: name.to_string(),
+            source: ModuleSource::Registry {
+                hostname: "registry.terraform.io".to_string(),
+                namespace: "terraform-aws-modules".to_string(),
+                name: module_name.to_string(),
+                provider: "aws".to_string(),
+                subdir: None,
+                params: Default::default(),
+            },
+            version_constraint: None,
+            file_path: PathBuf::from(format!("{repository}/main.tf")),
+            line_number: 1,
+            repository: Some(repository.to_string()),
+            attributes: Default::default(),
+            dependencies: Default::default(),
+        };
+        let runtime = |repository: &str| RuntimeRef {
+            name: "terraform".to_string(),
+            version: Constraint::parse(">= 1.5").unwrap(),
+            source: RuntimeSource::Terraform,
+            file_path: PathBuf::from(format!("{repository}/main.tf")),
+            line_number: 1,
+            repository: Some(repository.to_string()),
+        };
+        let modules = vec![
+            module("vpc", "net", "vpc"),
+            module("eks", "net", "eks"),
+            module("vpc", "app", "vpc"),
+            module("eks", "app", "eks"),
+        ];
+        let mut graph = GraphBuilder::new()
+            .build(&modules, &[], &[runtime("net"), runtime("app")])
+            .unwrap();
+        let ids: Vec<_> = graph.module_ids().into_iter().cloned().collect();
+        let (eks, vpc): (Vec<_>, Vec<_>) = ids
+            .iter()
+            .partition(|id| graph.get_node(id).unwrap().display_name() == "eks");
+        for (eks, vpc) in eks.iter().zip(&vpc) {
+            graph.add_edge(eks, vpc, EdgeType::ModuleDependsOn);
+        }
+        assert!(graph
+            .edges()
+            .any(|(_, _, edge_type)| *edge_type == EdgeType::RequiresRuntime));
+
+        let options = ExportOptions {
+            cluster_by: Some(ClusterBy::Repository),
+            ..ExportOptions::default()
+        };
+        let dot = export_graph_with_options(&graph, GraphFormat::Dot, &options).unwrap();
+        // Both repositories use the shared modules
+        assert!(dot.contains("\"cluster_net_anchor\" -> \"shared_module_registry_terraform_io_terraform_aws_modules_eks_aws\""));
+        assert!(dot.contains("\"cluster_app_anchor\" -> \"shared_module_registry_terraform_io_terraform_aws_modules_eks_aws\""));
+        // But no runtime or dependency of one of them hangs off a shared node
+        for line in dot.lines().filter(|l| l.contains(" -> ")) {
+            let from = line.trim().split(" -> ").next().unwrap();
+            assert!(!from.starts_with("\"shared_module_"), "{line}");
+        }
+
+        let mermaid = export_graph_with_options(&graph, GraphFormat::Mermaid, &options).unwrap();
+        for line in mermaid
+            .lines()
+            .filter(|l| l.contains("-->") || l.contains("-.->"))
+        {
+            assert!(!line.trim().starts_with("shared_module_"), "{line}");
+        }
     }
 
     #[test]
@@ -0,0 +1,241 @@
+//! Graph filtering.
+//!
+//! A `GraphFilter` describes which part of a `DependencyGraph` to keep:
+//! node kinds, a source substring or regex, a repository, and how many
+//! hops of neighbours to include around the nodes that match. Apply it
+//! with [`DependencyGraph::subgraph`](crate::graph::DependencyGraph::subgraph).
+
+use crate::error::Result;
+use crate::graph::types::{GraphNode, NodeKind};
+use regex::Regex;
+
+/// How node sources are matched.
+#[derive(Debug, Clone)]
+enum SourceMatcher {
+    /// Case-sensitive substring of the source
+    Contains(String),
+    /// Regular expression searched in the source
+    Regex(Regex),
+}
+
+/// Criteria for extracting a subgraph.
+///
+/// An empty filter keeps the whole graph.
+///
+/// ```rust
+/// use monphare::graph::{GraphFilter, NodeKind};
+///
+/// let filter = GraphFilter::new()
+///     .kinds([NodeKind::Module])
+///     .source_contains("terraform-aws-modules")
+///     .neighbourhood(1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GraphFilter {
+    /// Node kinds to keep (all kinds if empty)
+    kinds: Vec<NodeKind>,
+    /// Source criterion selecting the matched nodes
+    source: Option<SourceMatcher>,
+    /// Repository to keep nodes from
+    repository: Option<String>,
+    /// Hops of neighbours to include around matched nodes
+    hops: usize,
+}
+
+impl GraphFilter {
+    /// Create a filter that keeps everything.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep only nodes of the given kinds.
+    #[must_use]
+    pub fn kinds(mut self, kinds: impl IntoIterator<Item = NodeKind>) -> Self {
+        self.kinds = kinds.into_iter().collect();
+        self
+    }
+
+    /// Match nodes whose source contains `needle`.
+    #[must_use]
+    pub fn source_contains(mut self, needle: impl Into<String>) -> Self {
+        self.source = Some(SourceMatcher::Contains(needle.into()));
+        self
+    }
+
+    /// Match nodes whose source matches the regular expression `pattern`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pattern` is not a valid regular expression.
+    pub fn source_regex(mut self, pattern: &str) -> Result<Self> {
+        let regex = Regex::new(pattern).map_err(|e| {
+            crate::err!(ConfigValue {
+                key: "filter-regex".to_string(),
+                message: format!("invalid regular expression '{pattern}': {e}"),
+            })
+        })?;
+        self.source = Some(SourceMatcher::Regex(regex));
+        Ok(self)
+    }
+
+    /// Keep only nodes found in `repository`.
+    #[must_use]
+    pub fn repository(mut self, repository: impl Into<String>) -> Self {
+        self.repository = Some(repository.into());
+        self
+    }
+
+    /// Also keep nodes within `hops` edges of a matched node.
+    #[must_use]
+    pub const fn neighbourhood(mut self, hops: usize) -> Self {
+        self.hops = hops;
+        self
+    }
+
+    /// Number of neighbour hops to include around matched nodes.
+    #[must_use]
+    pub const fn neighbourhood_hops(&self) -> usize {
+        self.hops
+    }
+
+    /// Check the kind and repository criteria, which no node can bypass.
+    #[must_use]
+    pub fn admits(&self, node: &GraphNode) -> bool {
+        let kind_ok = self.kinds.is_empty() || self.kinds.contains(&node.kind());
+        let repository_ok = self
+            .repository
+            .as_deref()
+            .is_none_or(|repository| node.repository() == Some(repository));
+        kind_ok && repository_ok
+    }
+
+    /// Check the source criterion; true when no source criterion is set.
+    #[must_use]
+    pub fn matches_source(&self, node: &GraphNode) -> bool {
+        match &self.source {
+            None => true,
+            Some(SourceMatcher::Contains(needle)) => node.source().contains(needle.as_str()),
+            Some(SourceMatcher::Regex(regex)) => regex.is_match(&node.source()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DependencyGraph, GraphBuilder};
+    use crate::types::{Constraint, ModuleRef, ModuleSource, ProviderRef};
+    use std::path::PathBuf;
+
+    fn module(name: &str, namespace: &str, repository: &str) -> ModuleRef {
+        ModuleRef {
+            name: name.to_string(),
+            source: ModuleSource::Registry {
+                hostname: "registry.terraform.io".to_string(),
+                namespace: namespace.to_string(),
+                name: name.to_string(),
+                provider: "aws".to_string(),
+                subdir: None,
+                params: Default::default(),
+            },
+            version_constraint: Some(Constraint::parse("~> 5.0").unwrap()),
+            file_path: PathBuf::from("main.tf"),
+            line_number: 1,
+            repository: Some(repository.to_string()),
+            attributes: Default::default(),
+            dependencies: Default::default(),
+        }
+    }
+
+    fn provider(repository: &str) -> ProviderRef {
+        ProviderRef {
+            name: "aws".to_string(),
+            source: Some("hashicorp/aws".to_string()),
+            version_constraint: Some(Constraint::parse(">= 4.0").unwrap()),
+            file_path: PathBuf::from("versions.tf"),
+            line_number: 1,
+            repository: Some(repository.to_string()),
+        }
+    }
+
+    fn create_test_graph() -> DependencyGraph {
+        let modules = vec![
+            module("vpc", "terraform-aws-modules", "network"),
+            module("eks", "terraform-aws-modules", "platform"),
+            module("bucket", "acme", "platform"),
+        ];
+        let providers = vec![provider("network")];
+        GraphBuilder::new()
+            .build(&modules, &providers, &[])
+            .unwrap()
+    }
+
+    fn names(graph: &DependencyGraph) -> Vec<String> {
+        let mut names: Vec<String> = graph
+            .nodes()
+            .map(|n| format!("{}@{}", n.display_name(), n.repository().unwrap_or("-")))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_empty_filter_keeps_everything() {
+        let graph = create_test_graph();
+        let subgraph = graph.subgraph(&GraphFilter::new());
+        assert_eq!(subgraph.node_count(), graph.node_count());
+        assert_eq!(subgraph.edge_count(), graph.edge_count());
+    }
+
+    #[test]
+    fn test_kind_filter_drops_edges_to_removed_nodes() {
+        let graph = create_test_graph();
+        assert!(graph.edge_count() > 0);
+
+        let modules = graph.subgraph(&GraphFilter::new().kinds([NodeKind::Module]));
+        assert_eq!(modules.node_count(), 3);
+        assert_eq!(modules.edge_count(), 0);
+        assert_eq!(modules.provider_ids().len(), 0);
+
+        let providers = graph.subgraph(&GraphFilter::new().kinds([NodeKind::Provider]));
+        assert_eq!(providers.node_count(), 1);
+        assert_eq!(providers.module_ids().len(), 0);
+    }
+
+    #[test]
+    fn test_source_and_repository_filters() {
+        let graph = create_test_graph();
+
+        let aws = graph.subgraph(&GraphFilter::new().source_contains("terraform-aws-modules"));
+        assert_eq!(names(&aws), vec!["eks@platform", "vpc@network"]);
+
+        let regex = graph.subgraph(&GraphFilter::new().source_regex("^hashicorp/").unwrap());
+        assert_eq!(names(&regex), vec!["aws@network"]);
+
+        let platform = graph.subgraph(&GraphFilter::new().repository("platform"));
+        assert_eq!(names(&platform), vec!["bucket@platform", "eks@platform"]);
+
+        assert!(GraphFilter::new().source_regex("(unclosed").is_err());
+    }
+
+    #[test]
+    fn test_neighbourhood_includes_adjacent_nodes() {
+        let graph = create_test_graph();
+        let filter = GraphFilter::new().source_contains("/vpc/");
+
+        assert_eq!(names(&graph.subgraph(&filter)), vec!["vpc@network"]);
+
+        let one_hop = graph.subgraph(&filter.clone().neighbourhood(1));
+        assert_eq!(names(&one_hop), vec!["aws@network", "vpc@network"]);
+        assert_eq!(one_hop.edge_count(), 1);
+
+        let two_hops = graph.subgraph(&filter.clone().neighbourhood(2));
+        assert_eq!(two_hops.node_count(), 4);
+        assert_eq!(two_hops.edge_count(), 3);
+
+        // Kind criteria still apply to neighbours
+        let modules_only = graph.subgraph(&filter.kinds([NodeKind::Module]).neighbourhood(3));
+        assert_eq!(names(&modules_only), vec!["vpc@network"]);
+    }
+}
@@ -0,0 +1,482 @@
+//! Graph analytics.
+//!
+//! [`DependencyGraph::stats`] measures how much of an estate hangs off each
+//! module source: how many module blocks call it directly, how many modules
+//! depend on it transitively (its blast radius) and across how many
+//! repositories. It also computes betweenness centrality, lists providers no
+//! module requires, and ranks the shared modules with the largest blast
+//! radius, which is where upgrade planning starts.
+
+use crate::error::Result;
+use crate::graph::types::{DependencyGraph, GraphNode};
+use crate::types::QueryFormat;
+use petgraph::graph::NodeIndex;
+use petgraph::Direction;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+use std::fmt::Write;
+
+/// Analytics computed over a dependency graph.
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphStats {
+    /// Number of nodes in the graph
+    pub nodes: usize,
+    /// Number of edges in the graph
+    pub edges: usize,
+    /// Every module source, ranked by blast radius
+    pub modules: Vec<ModuleSourceStats>,
+    /// Module sources used by more than one module block or repository,
+    /// ranked by blast radius
+    pub critical_shared_modules: Vec<ModuleSourceStats>,
+    /// Nodes with the highest betweenness centrality
+    pub central_nodes: Vec<NodeCentrality>,
+    /// Providers that no module requires
+    pub orphaned_providers: Vec<OrphanedProvider>,
+}
+
+/// How widely one module source is used.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleSourceStats {
+    /// Canonical module source
+    pub source: String,
+    /// Module blocks that call the source
+    pub direct_consumers: usize,
+    /// Modules that depend on the source, directly or transitively
+    pub transitive_consumers: usize,
+    /// Distinct repositories among the transitive consumers
+    pub repositories: usize,
+    /// Highest betweenness centrality of a module block calling the source
+    pub betweenness: f64,
+}
+
+impl ModuleSourceStats {
+    /// Modules affected by a change to the source.
+    #[must_use]
+    pub const fn blast_radius(&self) -> usize {
+        self.transitive_consumers
+    }
+
+    fn is_shared(&self) -> bool {
+        self.direct_consumers > 1 || self.repositories > 1
+    }
+}
+
+/// Betweenness centrality of one node.
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeCentrality {
+    /// Node ID
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Source (canonical ID for modules)
+    pub source: String,
+    /// Repository name
+    pub repository: Option<String>,
+    /// Number of shortest paths between other nodes that pass through it
+    pub betweenness: f64,
+}
+
+/// A provider requirement no module edge points to.
+#[derive(Debug, Clone, Serialize)]
+pub struct OrphanedProvider {
+    /// Qualified provider source
+    pub source: String,
+    /// Repository name
+    pub repository: Option<String>,
+}
+
+impl DependencyGraph {
+    /// Compute analytics over the graph.
+    ///
+    /// `top` caps the critical shared modules and central nodes lists.
+    #[must_use]
+    pub fn stats(&self, top: usize) -> GraphStats {
+        let graph = self.inner();
+        let betweenness = betweenness_centrality(self);
+
+        // Module blocks grouped by canonical source
+        let mut by_source: BTreeMap<String, Vec<NodeIndex>> = BTreeMap::new();
+        for idx in graph.node_indices() {
+            if let GraphNode::Module(m) = &graph[idx] {
+                by_source
+                    .entry(m.source.canonical_id())
+                    .or_default()
+                    .push(idx);
+            }
+        }
+
+        let mut modules: Vec<ModuleSourceStats> = by_source
+            .into_iter()
+            .map(|(source, calls)| {
+                let consumers = transitive_consumers(self, &calls);
+                let repositories: BTreeSet<Option<&str>> =
+                    consumers.iter().map(|&i| graph[i].repository()).collect();
+                ModuleSourceStats {
+                    source,
+                    direct_consumers: calls.len(),
+                    transitive_consumers: consumers.len(),
+                    repositories: repositories.len(),
+                    betweenness: calls
+                        .iter()
+                        .map(|i| betweenness[i.index()])
+                        .fold(0.0, f64::max),
+                }
+            })
+            .collect();
+        modules.sort_by(|a, b| {
+            b.blast_radius()
+                .cmp(&a.blast_radius())
+                .then(b.repositories.cmp(&a.repositories))
+                .then(b.betweenness.total_cmp(&a.betweenness))
+                .then_with(|| a.source.cmp(&b.source))
+        });
+
+        let critical_shared_modules = modules
+            .iter()
+            .filter(|m| m.is_shared())
+            .take(top)
+            .cloned()
+            .collect();
+
+        let mut central_nodes: Vec<NodeCentrality> = graph
+            .node_indices()
+            .filter(|i| betweenness[i.index()] > 0.0)
+            .map(|i| {
+                let node = &graph[i];
+                NodeCentrality {
+                    id: node.id().to_string(),
+                    name: node.display_name().to_string(),
+                    source: node.source(),
+                    repository: node.repository().map(String::from),
+                    betweenness: betweenness[i.index()],
+                }
+            })
+            .collect();
+        central_nodes.sort_by(|a, b| {
+            b.betweenness
+                .total_cmp(&a.betweenness)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        central_nodes.truncate(top);
+
+        let mut orphaned_providers: Vec<OrphanedProvider> = graph
+            .node_indices()
+            .filter(|&i| {
+                graph[i].is_provider()
+                    && graph
+                        .neighbors_directed(i, Direction::Incoming)
+                        .next()
+                        .is_none()
+            })
+            .map(|i| OrphanedProvider {
+                source: graph[i].source(),
+                repository: graph[i].repository().map(String::from),
+            })
+            .collect();
+        orphaned_providers.sort_by(|a, b| {
+            (a.repository.as_deref(), &a.source).cmp(&(b.repository.as_deref(), &b.source))
+        });
+
+        tracing::debug!(
+            sources = modules.len(),
+            orphaned_providers = orphaned_providers.len(),
+            "Graph stats computed"
+        );
+        GraphStats {
+            nodes: self.node_count(),
+            edges: self.edge_count(),
+            modules,
+            critical_shared_modules,
+            central_nodes,
+            orphaned_providers,
+        }
+    }
+}
+
+/// The given module blocks plus every module that depends on them.
+fn transitive_consumers(graph: &DependencyGraph, calls: &[NodeIndex]) -> HashSet<NodeIndex> {
+    let graph = graph.inner();
+    let mut seen: HashSet<NodeIndex> = calls.iter().copied().collect();
+    let mut queue: VecDeque<NodeIndex> = calls.iter().copied().collect();
+    while let Some(idx) = queue.pop_front() {
+        for dependent in graph.neighbors_directed(idx, Direction::Incoming) {
+            if graph[dependent].is_module() && seen.insert(dependent) {
+                queue.push_back(dependent);
+            }
+        }
+    }
+    seen
+}
+
+/// Betweenness centrality of every node, indexed by node index.
+///
+/// Brandes' algorithm over the directed, unweighted graph.
+fn betweenness_centrality(graph: &DependencyGraph) -> Vec<f64> {
+    let graph = graph.inner();
+    let n = graph.node_count();
+    let mut centrality = vec![0.0; n];
+
+    for source in graph.node_indices() {
+        let mut stack = Vec::with_capacity(n);
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut paths = vec![0.0_f64; n];
+        let mut distance: Vec<Option<usize>> = vec![None; n];
+        paths[source.index()] = 1.0;
+        distance[source.index()] = Some(0);
+
+        let mut queue = VecDeque::from([source]);
+        while let Some(v) = queue.pop_front() {
+            stack.push(v.index());
+            let next_distance = distance[v.index()].map(|d| d + 1);
+            for w in graph.neighbors_directed(v, Direction::Outgoing) {
+                if distance[w.index()].is_none() {
+                    distance[w.index()] = next_distance;
+                    queue.push_back(w);
+                }
+                if distance[w.index()] == next_distance {
+                    paths[w.index()] += paths[v.index()];
+                    predecessors[w.index()].push(v.index());
+                }
+            }
+        }
+
+        let mut dependency = vec![0.0_f64; n];
+        while let Some(w) = stack.pop() {
+            for &v in &predecessors[w] {
+                dependency[v] += paths[v] / paths[w] * (1.0 + dependency[w]);
+            }
+            if w != source.index() {
+                centrality[w] += dependency[w];
+            }
+        }
+    }
+    centrality
+}
+
+impl GraphStats {
+    /// Render the stats in the given format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if JSON serialization fails.
+    pub fn render(&self, format: QueryFormat) -> Result<String> {
+        match format {
+            QueryFormat::Text => Ok(self.to_text()),
+            QueryFormat::Json => Ok(serde_json::to_string_pretty(self)?),
+            QueryFormat::Markdown => Ok(self.to_markdown()),
+        }
+    }
+
+    fn to_text(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "Graph: {} node(s), {} edge(s), {} module source(s)",
+            self.nodes,
+            self.edges,
+            self.modules.len()
+        );
+
+        let _ = writeln!(
+            out,
+            "\nMost critical shared modules ({})",
+            self.critical_shared_modules.len()
+        );
+        for (rank, module) in self.critical_shared_modules.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "  {:>2}. {}  blast radius {}  direct {}  repos {}  betweenness {:.1}",
+                rank + 1,
+                module.source,
+                module.blast_radius(),
+                module.direct_consumers,
+                module.repositories,
+                module.betweenness
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "\nBetweenness centrality ({})",
+            self.central_nodes.len()
+        );
+        for node in &self.central_nodes {
+            let _ = writeln!(
+                out,
+                "  {:.1}  {}  {}",
+                node.betweenness, node.name, node.source
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "\nOrphaned providers ({})",
+            self.orphaned_providers.len()
+        );
+        for provider in &self.orphaned_providers {
+            let _ = writeln!(
+                out,
+                "  {}  {}",
+                provider.repository.as_deref().unwrap_or("-"),
+                provider.source
+            );
+        }
+
+        let _ = writeln!(out, "\nModule sources ({})", self.modules.len());
+        for module in &self.modules {
+            let _ = writeln!(
+                out,
+                "  {}  direct {}  transitive {}  repos {}",
+                module.source,
+                module.direct_consumers,
+                module.transitive_consumers,
+                module.repositories
+            );
+        }
+        out
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# Dependency graph stats\n");
+        let _ = writeln!(
+            out,
+            "{} node(s), {} edge(s), {} module source(s).\n",
+            self.nodes,
+            self.edges,
+            self.modules.len()
+        );
+
+        let _ = writeln!(
+            out,
+            "## Most critical shared modules ({})\n",
+            self.critical_shared_modules.len()
+        );
+        let _ = writeln!(
+            out,
+            "| Rank | Source | Blast radius | Direct | Repos | Betweenness |"
+        );
+        let _ = writeln!(
+            out,
+            "|------|--------|--------------|--------|-------|-------------|"
+        );
+        for (rank, module) in self.critical_shared_modules.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "| {} | `{}` | {} | {} | {} | {:.1} |",
+                rank + 1,
+                module.source,
+                module.blast_radius(),
+                module.direct_consumers,
+                module.repositories,
+                module.betweenness
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "\n## Orphaned providers ({})\n",
+            self.orphaned_providers.len()
+        );
+        for provider in &self.orphaned_providers {
+            let _ = writeln!(
+                out,
+                "- `{}` ({})",
+                provider.source,
+                provider.repository.as_deref().unwrap_or("-")
+            );
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{EdgeType, RequirementOrigin};
+    use crate::types::{ModuleRef, ModuleSource, ProviderRef};
+    use std::path::PathBuf;
+
+    fn module(name: &str, source: &str, repository: &str) -> ModuleRef {
+        ModuleRef {
+            name: name.to_string(),
+            source: ModuleSource::Local {
+                path: source.to_string(),
+            },
+            version_constraint: None,
+            file_path: PathBuf::from("main.tf"),
+            line_number: 1,
+            repository: Some(repository.to_string()),
+            attributes: Default::default(),
+            dependencies: Default::default(),
+        }
+    }
+
+    fn provider(source: &str) -> ProviderRef {
+        ProviderRef {
+            name: source.rsplit('/').next().unwrap().to_string(),
+            source: Some(source.to_string()),
+            version_constraint: None,
+            file_path: PathBuf::from("versions.tf"),
+            line_number: 1,
+            repository: Some("infra".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_graph_stats() {
+        // app (infra) -> network (infra) -> vpc (infra)
+        // svc (platform) -> vpc (platform)
+        // vpc (infra) -> aws; random is required by nothing
+        let mut graph = DependencyGraph::new();
+        let app = graph.add_module(&module("app", "./app", "infra"));
+        let network = graph.add_module(&module("network", "./network", "infra"));
+        let vpc = graph.add_module(&module("vpc", "./vpc", "infra"));
+        let svc = graph.add_module(&module("svc", "./svc", "platform"));
+        let vpc2 = graph.add_module(&module("vpc", "./vpc", "platform"));
+        let aws = graph.add_provider(&provider("hashicorp/aws"));
+        graph.add_provider(&provider("hashicorp/random"));
+        graph.add_edge(&app, &network, EdgeType::LocalModuleRef);
+        graph.add_edge(&network, &vpc, EdgeType::LocalModuleRef);
+        graph.add_edge(&svc, &vpc2, EdgeType::LocalModuleRef);
+        graph.add_edge(
+            &vpc,
+            &aws,
+            EdgeType::ModuleRequiresProvider(RequirementOrigin::Declared),
+        );
+
+        let stats = graph.stats(10);
+        assert_eq!(stats.nodes, 7);
+        assert_eq!(stats.edges, 4);
+
+        let top = &stats.modules[0];
+        assert_eq!(top.source, "local://./vpc");
+        assert_eq!(top.direct_consumers, 2);
+        assert_eq!(top.transitive_consumers, 5);
+        assert_eq!(top.repositories, 2);
+        assert_eq!(stats.critical_shared_modules.len(), 1);
+        assert_eq!(stats.critical_shared_modules[0].source, "local://./vpc");
+
+        // network sits on app -> vpc and app -> aws, vpc on app -> aws and network -> aws
+        let network_stats = stats
+            .modules
+            .iter()
+            .find(|m| m.source == "local://./network")
+            .unwrap();
+        assert!((network_stats.betweenness - 2.0).abs() < f64::EPSILON);
+        assert!((top.betweenness - 2.0).abs() < f64::EPSILON);
+        assert_eq!(stats.central_nodes.len(), 2);
+
+        assert_eq!(stats.orphaned_providers.len(), 1);
+        assert_eq!(stats.orphaned_providers[0].source, "hashicorp/random");
+
+        let text = stats.render(QueryFormat::Text).unwrap();
+        assert!(text.contains("1. local://./vpc  blast radius 5"));
+        let json: serde_json::Value =
+            serde_json::from_str(&stats.render(QueryFormat::Json).unwrap()).unwrap();
+        assert_eq!(
+            json["critical_shared_modules"][0]["transitive_consumers"],
+            5
+        );
+    }
+}
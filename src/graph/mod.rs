@@ -31,7 +31,7 @@
 //!
 //! # Node Types
 //!
-//! The graph contains two types of nodes:
+//! The graph contains three types of nodes:
 //!
 //! 1. **Module Nodes**: Represent Terraform module blocks
 //!    - Store module name, source, version constraint
@@ -41,18 +41,34 @@
 //!    - Store provider name, source, version constraint
 //!    - Track where the requirement is defined
 //!
+//! 3. **Runtime Nodes**: Represent `required_version` constraints
+//!    - One per declaring file, storing the Terraform or OpenTofu constraint
+//!
 //! # Edge Types
 //!
 //! Edges represent relationships between nodes:
 //!
 //! 1. **ModuleDependsOn**: Module A depends on Module B
-//!    - Created when a module references another module's outputs
+//!    - Created when a module block uses `module.b.<output>` in an argument
+//!      or lists `module.b` in `depends_on`
 //!
 //! 2. **ModuleRequiresProvider**: Module requires a specific provider
-//!    - Created when a module uses resources from a provider
+//!    - Created from a `providers` map entry or the `required_providers` of
+//!      a fetched module (`declared`), or inferred from the module source
+//!      through `graph.provider_inference` (`inferred`)
+//!
+//! 3. **ProviderAlias**: Module is passed an aliased provider configuration
+//!    - Created for `providers = { aws = aws.east }`
+//!
+//! 4. **LocalModuleRef**: Module calls a local module
+//!    - Created from a local `source`, resolved relative to the declaring file
 //!
-//! 3. **ProviderAlias**: Provider A is an alias of Provider B
-//!    - Created for provider aliases
+//! 5. **ModuleCall**: Module calls a module declared in its remote source
+//!    - Created by transitive resolution (see [`crate::resolver`])
+//!
+//! 6. **RequiresRuntime**: Module directory requires a runtime version
+//!    - A root directory is represented by its module blocks, a child
+//!      directory by the module blocks calling it
 //!
 //! # Graph Operations
 //!
@@ -87,16 +103,45 @@
 //! let aws_modules = graph.modules_using_provider("hashicorp/aws");
 //! ```
 //!
-//! ## Detecting Cycles
+//! ## Filtering
 //!
-//! ```rust,ignore
+//! ```rust
+//! use monphare::graph::{DependencyGraph, GraphFilter, NodeKind};
+//! let graph = DependencyGraph::new();
+//! // Modules from terraform-aws-modules plus their direct neighbours
+//! let filter = GraphFilter::new()
+//!     .source_contains("terraform-aws-modules")
+//!     .neighbourhood(1);
+//! let subgraph = graph.subgraph(&filter);
+//!
+//! // Providers only; edges to removed nodes are dropped
+//! let providers = graph.subgraph(&GraphFilter::new().kinds([NodeKind::Provider]));
+//! ```
+//!
+//! ## Measuring Blast Radius
+//!
+//! ```rust
 //! use monphare::graph::DependencyGraph;
 //! let graph = DependencyGraph::new();
-//! if let Some(cycle) = graph.find_cycle() {
-//!     println!("Circular dependency detected: {:?}", cycle);
+//! // Shared module sources ranked by how many modules depend on them
+//! for module in graph.stats(10).critical_shared_modules {
+//!     println!("{}: {} dependent module(s)", module.source, module.blast_radius());
 //! }
 //! ```
 //!
+//! ## Detecting Cycles
+//!
+//! Cycles are searched along module-to-module edges only.
+//!
+//! ```rust
+//! use monphare::graph::{format_cycle, DependencyGraph};
+//! let graph = DependencyGraph::new();
+//! for cycle in graph.find_cycles() {
+//!     println!("Circular dependency detected: {}", format_cycle(&cycle));
+//! }
+//! assert!(graph.ensure_acyclic().is_ok());
+//! ```
+//!
 //! # Data Flow
 //!
 //! ```text
@@ -175,11 +220,19 @@
 
 mod builder;
 mod export;
+mod filter;
+mod metrics;
 mod types;
 
 pub use builder::GraphBuilder;
-pub use export::export_graph;
-pub use types::{DependencyGraph, EdgeType, GraphNode, NodeId};
+pub(crate) use builder::{declaring_dir, normalize_path};
+pub use export::{export_graph, export_graph_with_options, ExportOptions};
+pub use filter::GraphFilter;
+pub use metrics::{GraphStats, ModuleSourceStats, NodeCentrality, OrphanedProvider};
+pub use types::{
+    format_cycle, DependencyGraph, EdgeType, GraphNode, NodeId, NodeKind, RequirementOrigin,
+    MAX_CYCLES,
+};
 
 #[cfg(test)]
 mod tests {
@@ -195,12 +248,15 @@ mod tests {
                 namespace: "terraform-aws-modules".to_string(),
                 name: source.to_string(),
                 provider: "aws".to_string(),
+                subdir: None,
+                params: Default::default(),
             },
             version_constraint: Some(Constraint::parse("~> 5.0").unwrap()),
             file_path: PathBuf::from("main.tf"),
             line_number: 1,
             repository: Some("test-repo".to_string()),
             attributes: Default::default(),
+            dependencies: Default::default(),
         }
     }
 
@@ -230,6 +286,93 @@ mod tests {
         assert_eq!(graph.node_count(), 3);
     }
 
+    #[test]
+    fn test_find_cycles() {
+        let modules = vec![
+            create_test_module("a", "a"),
+            create_test_module("b", "b"),
+            create_test_module("c", "c"),
+            create_test_module("d", "d"),
+        ];
+        let providers = vec![create_test_provider("aws", "hashicorp/aws")];
+        let mut graph = GraphBuilder::new()
+            .build(&modules, &providers, &[])
+            .unwrap();
+        assert!(graph.find_cycle().is_none());
+        assert!(graph.ensure_acyclic().is_ok());
+
+        let ids: Vec<NodeId> = ["a", "b", "c", "d"]
+            .iter()
+            .map(|name| {
+                graph
+                    .module_ids()
+                    .into_iter()
+                    .find(|id| id.ends_with(&format!(":{name}")))
+                    .unwrap()
+                    .clone()
+            })
+            .collect();
+        graph.add_edge(&ids[0], &ids[1], EdgeType::ModuleDependsOn);
+        graph.add_edge(&ids[1], &ids[2], EdgeType::LocalModuleRef);
+        graph.add_edge(&ids[2], &ids[0], EdgeType::ModuleDependsOn);
+        graph.add_edge(&ids[3], &ids[3], EdgeType::ModuleDependsOn);
+
+        let cycles = graph.find_cycles();
+        assert_eq!(
+            cycles,
+            vec![
+                vec![ids[0].clone(), ids[1].clone(), ids[2].clone()],
+                vec![ids[3].clone()],
+            ]
+        );
+        assert!(matches!(
+            graph.ensure_acyclic(),
+            Err(crate::MonPhareError::CircularDependency { .. })
+        ));
+        assert_eq!(
+            format_cycle(&cycles[1]),
+            format!("{} -> {}", ids[3], ids[3])
+        );
+    }
+
+    #[test]
+    fn test_find_cycles_sharing_a_node() {
+        let names = ["a", "b", "c", "d", "e", "f"];
+        let modules: Vec<_> = names
+            .iter()
+            .map(|name| create_test_module(name, name))
+            .collect();
+        let mut graph = GraphBuilder::new().build(&modules, &[], &[]).unwrap();
+        let mut ids = graph.module_ids().into_iter().cloned().collect::<Vec<_>>();
+        ids.sort();
+
+        // a -> b -> a and a -> c -> a share module a
+        graph.add_edge(&ids[0], &ids[1], EdgeType::ModuleDependsOn);
+        graph.add_edge(&ids[1], &ids[0], EdgeType::ModuleDependsOn);
+        graph.add_edge(&ids[0], &ids[2], EdgeType::ModuleCall);
+        graph.add_edge(&ids[2], &ids[0], EdgeType::LocalModuleRef);
+        // A second edge between the same modules is not another cycle
+        graph.add_edge(&ids[2], &ids[0], EdgeType::ModuleDependsOn);
+
+        assert_eq!(
+            graph.find_cycles(),
+            vec![
+                vec![ids[0].clone(), ids[1].clone()],
+                vec![ids[0].clone(), ids[2].clone()],
+            ]
+        );
+
+        // Every module depends on every other one: enumeration is capped
+        for from in &ids {
+            for to in &ids {
+                if from != to {
+                    graph.add_edge(from, to, EdgeType::ModuleDependsOn);
+                }
+            }
+        }
+        assert_eq!(graph.find_cycles().len(), MAX_CYCLES);
+    }
+
     #[test]
     fn test_graph_export_dot() {
         let modules = vec![create_test_module("vpc", "vpc")];
@@ -267,4 +410,37 @@ mod tests {
         let mermaid = export_graph(&graph, GraphFormat::Mermaid).unwrap();
         assert!(mermaid.contains("graph"));
     }
+
+    #[test]
+    fn test_graph_serde_round_trip() {
+        let modules = vec![
+            create_test_module("vpc", "vpc"),
+            create_test_module("eks", "eks"),
+        ];
+        let providers = vec![create_test_provider("aws", "hashicorp/aws")];
+        let mut graph = GraphBuilder::new()
+            .build(&modules, &providers, &[])
+            .unwrap();
+        let vpc = graph.module_ids()[0].clone();
+        graph.set_vcs_metadata(&vpc, crate::vcs::VcsIdentifier::local());
+
+        let json = serde_json::to_string(&graph).unwrap();
+        let restored: DependencyGraph = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.node_count(), graph.node_count());
+        assert_eq!(restored.edge_count(), graph.edge_count());
+        assert_eq!(
+            restored.get_vcs_metadata(&vpc),
+            graph.get_vcs_metadata(&vpc)
+        );
+        assert_eq!(
+            restored.modules_using_provider("hashicorp/aws").len(),
+            graph.modules_using_provider("hashicorp/aws").len()
+        );
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+
+        let dangling =
+            r#"{"nodes": [], "edges": [{"from": "a", "to": "b", "type": "ModuleCall"}]}"#;
+        let err = serde_json::from_str::<DependencyGraph>(dangling).unwrap_err();
+        assert!(err.to_string().contains("unknown node 'a'"));
+    }
 }
@@ -5,13 +5,16 @@
 //! - `GraphNode`: Nodes in the graph (modules or providers)
 //! - `EdgeType`: Relationships between nodes
 //! - `NodeId`: Unique identifier for nodes
+//! - `NodeKind`: The kind of a node, used for filtering
 
+use crate::error::Result;
+use crate::graph::filter::GraphFilter;
 use crate::types::{Constraint, ModuleRef, ModuleSource, ProviderRef, RuntimeRef};
 use crate::vcs::VcsIdentifier;
 use petgraph::graph::{DiGraph, NodeIndex};
-use petgraph::visit::EdgeRef;
+use petgraph::visit::{EdgeFiltered, EdgeRef};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 
 /// Unique identifier for a node in the graph.
@@ -20,6 +23,35 @@ use std::path::PathBuf;
 /// or provider across all repositories being analyzed.
 pub type NodeId = String;
 
+/// Maximum number of cycles [`DependencyGraph::find_cycles`] reports.
+///
+/// The number of elementary cycles can grow exponentially with the size of
+/// a strongly connected component.
+pub const MAX_CYCLES: usize = 100;
+
+/// State of a cycle search from one start node (Johnson's algorithm).
+struct CycleSearch<'a> {
+    start: NodeIndex,
+    /// Nodes the search may visit
+    allowed: HashSet<NodeIndex>,
+    blocked: HashSet<NodeIndex>,
+    /// Nodes to unblock when the key is unblocked
+    blocked_by: HashMap<NodeIndex, HashSet<NodeIndex>>,
+    path: Vec<NodeIndex>,
+    cycles: &'a mut Vec<Vec<NodeId>>,
+}
+
+impl CycleSearch<'_> {
+    fn unblock(&mut self, idx: NodeIndex) {
+        self.blocked.remove(&idx);
+        for next in self.blocked_by.remove(&idx).unwrap_or_default() {
+            if self.blocked.contains(&next) {
+                self.unblock(next);
+            }
+        }
+    }
+}
+
 /// The dependency graph structure.
 ///
 /// This is the central data structure for representing relationships
@@ -38,6 +70,12 @@ pub type NodeId = String;
 /// └── vcs_metadata: HashMap<NodeId, VcsIdentifier>  // VCS markers
 /// ```
 ///
+/// # Serialization
+///
+/// The graph serializes as its nodes, its edges (by node ID) and its VCS
+/// metadata. Nodes and edges keep their order, so the lookup indices are
+/// rebuilt identically on deserialization.
+///
 /// # Thread Safety
 ///
 /// The graph is not thread-safe by default. For concurrent access,
@@ -159,9 +197,13 @@ impl DependencyGraph {
     /// Add a runtime node to the graph.
     ///
     /// Returns the node ID if the runtime was added, or the existing ID
-    /// if a runtime with the same name already exists.
+    /// if the same file already declared a runtime with that name.
     pub fn add_runtime(&mut self, runtime: &RuntimeRef) -> NodeId {
-        let node_id = format!("runtime:{}", runtime.name);
+        let node_id = self.runtime_node_id(runtime);
+
+        if let Some(&_existing) = self.node_index.get(&node_id) {
+            return node_id;
+        }
 
         let node = GraphNode::Runtime(RuntimeNode {
             id: node_id.clone(),
@@ -296,28 +338,8 @@ impl DependencyGraph {
     pub fn merge(&mut self, other: Self) {
         // Add all nodes from the other graph
         for node in other.inner.node_weights() {
-            match node {
-                GraphNode::Module(m) => {
-                    if !self.node_index.contains_key(&m.id) {
-                        let idx = self.inner.add_node(node.clone());
-                        self.node_index.insert(m.id.clone(), idx);
-                        self.modules.insert(m.id.clone(), idx);
-                    }
-                }
-                GraphNode::Provider(p) => {
-                    if !self.node_index.contains_key(&p.id) {
-                        let idx = self.inner.add_node(node.clone());
-                        self.node_index.insert(p.id.clone(), idx);
-                        self.providers.insert(p.id.clone(), idx);
-                    }
-                }
-                GraphNode::Runtime(r) => {
-                    if !self.node_index.contains_key(&r.id) {
-                        let idx = self.inner.add_node(node.clone());
-                        self.node_index.insert(r.id.clone(), idx);
-                        self.runtimes.insert(r.id.clone(), idx);
-                    }
-                }
+            if !self.node_index.contains_key(node.id()) {
+                self.insert_node(node.clone());
             }
         }
 
@@ -343,6 +365,188 @@ impl DependencyGraph {
         }
     }
 
+    /// Extract the subgraph selected by `filter`.
+    ///
+    /// Nodes that fail the kind or repository criteria are removed first.
+    /// Of the remaining nodes, those matching the source criterion are kept
+    /// along with every node within `filter`'s neighbourhood (following
+    /// edges in either direction). Edges are kept only when both endpoints
+    /// are kept.
+    ///
+    /// ```rust
+    /// use monphare::graph::{DependencyGraph, GraphFilter, NodeKind};
+    ///
+    /// let graph = DependencyGraph::new();
+    /// let modules = graph.subgraph(&GraphFilter::new().kinds([NodeKind::Module]));
+    /// assert_eq!(modules.node_count(), 0);
+    /// ```
+    #[must_use]
+    pub fn subgraph(&self, filter: &GraphFilter) -> Self {
+        let eligible = |idx: NodeIndex| filter.admits(&self.inner[idx]);
+
+        let mut keep: HashSet<NodeIndex> = self
+            .inner
+            .node_indices()
+            .filter(|&idx| eligible(idx) && filter.matches_source(&self.inner[idx]))
+            .collect();
+
+        // Grow the selection hop by hop, staying within eligible nodes
+        let mut frontier: Vec<NodeIndex> = keep.iter().copied().collect();
+        for _ in 0..filter.neighbourhood_hops() {
+            let mut next = Vec::new();
+            for idx in frontier {
+                for neighbor in self.inner.neighbors_undirected(idx) {
+                    if eligible(neighbor) && keep.insert(neighbor) {
+                        next.push(neighbor);
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
+
+        let mut subgraph = Self::new();
+        for idx in self.inner.node_indices().filter(|idx| keep.contains(idx)) {
+            subgraph.insert_node(self.inner[idx].clone());
+        }
+        for edge in self.inner.edge_references() {
+            if keep.contains(&edge.source()) && keep.contains(&edge.target()) {
+                subgraph.add_edge(
+                    &self.inner[edge.source()].id().to_string(),
+                    &self.inner[edge.target()].id().to_string(),
+                    edge.weight().clone(),
+                );
+            }
+        }
+        for (node_id, vcs_id) in &self.vcs_metadata {
+            if subgraph.node_index.contains_key(node_id) {
+                subgraph.set_vcs_metadata(node_id, vcs_id.clone());
+            }
+        }
+
+        tracing::debug!(
+            nodes = subgraph.node_count(),
+            edges = subgraph.edge_count(),
+            "Extracted subgraph"
+        );
+        subgraph
+    }
+
+    /// Find every dependency cycle between modules.
+    ///
+    /// Only module-to-module edges (`ModuleDependsOn`, `LocalModuleRef` and
+    /// `ModuleCall`) are followed. Every elementary cycle is reported, so two
+    /// loops that share a module are two cycles; enumeration stops after
+    /// [`MAX_CYCLES`]. A path lists node IDs in edge order, starting at the
+    /// lexicographically smallest node; the last node links back to the first.
+    #[must_use]
+    pub fn find_cycles(&self) -> Vec<Vec<NodeId>> {
+        let dependencies =
+            EdgeFiltered::from_fn(&self.inner, |edge| edge.weight().is_module_dependency());
+
+        // Johnson's algorithm: within each strongly connected component,
+        // find the cycles through each node that avoid the nodes before it
+        let mut cycles: Vec<Vec<NodeId>> = Vec::new();
+        'components: for mut component in petgraph::algo::tarjan_scc(&dependencies) {
+            component.sort_by(|a, b| self.inner[*a].id().cmp(self.inner[*b].id()));
+            for (rank, &start) in component.iter().enumerate() {
+                let mut search = CycleSearch {
+                    start,
+                    allowed: component[rank..].iter().copied().collect(),
+                    blocked: HashSet::new(),
+                    blocked_by: HashMap::new(),
+                    path: Vec::new(),
+                    cycles: &mut cycles,
+                };
+                self.circuit(start, &mut search);
+                if cycles.len() >= MAX_CYCLES {
+                    tracing::warn!(
+                        limit = MAX_CYCLES,
+                        "Too many dependency cycles, reporting the first ones only"
+                    );
+                    break 'components;
+                }
+            }
+        }
+        cycles.sort();
+
+        tracing::debug!(cycles = cycles.len(), "Cycle detection complete");
+        cycles
+    }
+
+    /// Find a dependency cycle between modules, if one exists.
+    ///
+    /// See [`Self::find_cycles`] for how cycles are reported.
+    #[must_use]
+    pub fn find_cycle(&self) -> Option<Vec<NodeId>> {
+        self.find_cycles().into_iter().next()
+    }
+
+    /// Check that no dependency cycle exists between modules.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CircularDependency` describing the first cycle found.
+    pub fn ensure_acyclic(&self) -> Result<()> {
+        match self.find_cycle() {
+            Some(cycle) => Err(crate::err!(CircularDependency {
+                cycle: format_cycle(&cycle),
+            })),
+            None => Ok(()),
+        }
+    }
+
+    /// Module dependencies of `idx` within `allowed`, without duplicates.
+    fn cycle_successors(&self, idx: NodeIndex, allowed: &HashSet<NodeIndex>) -> Vec<NodeIndex> {
+        let mut successors: Vec<NodeIndex> = self
+            .inner
+            .edges(idx)
+            .filter(|edge| edge.weight().is_module_dependency() && allowed.contains(&edge.target()))
+            .map(|edge| edge.target())
+            .collect();
+        successors.sort_by(|a, b| self.inner[*a].id().cmp(self.inner[*b].id()));
+        successors.dedup();
+        successors
+    }
+
+    /// Extend the search path with `idx`; true if a cycle was closed below it.
+    fn circuit(&self, idx: NodeIndex, search: &mut CycleSearch<'_>) -> bool {
+        let mut found = false;
+        search.path.push(idx);
+        search.blocked.insert(idx);
+
+        let successors = self.cycle_successors(idx, &search.allowed);
+        for &next in &successors {
+            if search.cycles.len() >= MAX_CYCLES {
+                break;
+            }
+            if next == search.start {
+                search.cycles.push(
+                    search
+                        .path
+                        .iter()
+                        .map(|i| self.inner[*i].id().to_string())
+                        .collect(),
+                );
+                found = true;
+            } else if !search.blocked.contains(&next) && self.circuit(next, search) {
+                found = true;
+            }
+        }
+
+        if found {
+            search.unblock(idx);
+        } else {
+            for next in successors {
+                search.blocked_by.entry(next).or_default().insert(idx);
+            }
+        }
+        search.path.pop();
+        found
+    }
+
     /// Get an iterator over all nodes.
     pub fn nodes(&self) -> impl Iterator<Item = &GraphNode> {
         self.inner.node_weights()
@@ -365,19 +569,127 @@ impl DependencyGraph {
         &self.inner
     }
 
+    /// Insert a node and index it by ID and kind.
+    fn insert_node(&mut self, node: GraphNode) {
+        let node_id = node.id().to_string();
+        let kind = node.kind();
+        let idx = self.inner.add_node(node);
+        self.node_index.insert(node_id.clone(), idx);
+        match kind {
+            NodeKind::Module => self.modules.insert(node_id, idx),
+            NodeKind::Provider => self.providers.insert(node_id, idx),
+            NodeKind::Runtime => self.runtimes.insert(node_id, idx),
+        };
+    }
+
     /// Generate a canonical node ID for a module.
-    fn module_node_id(&self, module: &ModuleRef) -> NodeId {
+    pub(crate) fn module_node_id(&self, module: &ModuleRef) -> NodeId {
         let source_id = module.source.canonical_id();
         let repo = module.repository.as_deref().unwrap_or("local");
         format!("module:{repo}:{source_id}:{}", module.name)
     }
 
     /// Generate a canonical node ID for a provider.
-    fn provider_node_id(&self, provider: &ProviderRef) -> NodeId {
+    pub(crate) fn provider_node_id(&self, provider: &ProviderRef) -> NodeId {
         let source = provider.qualified_source();
         let repo = provider.repository.as_deref().unwrap_or("local");
         format!("provider:{repo}:{source}")
     }
+
+    /// Generate a canonical node ID for a runtime requirement.
+    pub(crate) fn runtime_node_id(&self, runtime: &RuntimeRef) -> NodeId {
+        let repo = runtime.repository.as_deref().unwrap_or("local");
+        format!(
+            "runtime:{repo}:{}:{}",
+            runtime.file_path.display(),
+            runtime.name
+        )
+    }
+}
+
+/// Serialized form of a [`DependencyGraph`].
+#[derive(Serialize)]
+struct GraphRepr<'a> {
+    nodes: Vec<&'a GraphNode>,
+    edges: Vec<EdgeRepr<'a>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    vcs_metadata: BTreeMap<&'a NodeId, &'a VcsIdentifier>,
+}
+
+#[derive(Serialize)]
+struct EdgeRepr<'a> {
+    from: &'a str,
+    to: &'a str,
+    #[serde(rename = "type")]
+    edge_type: &'a EdgeType,
+}
+
+/// Deserialized form of a [`DependencyGraph`], validated before use.
+#[derive(Deserialize)]
+struct GraphData {
+    nodes: Vec<GraphNode>,
+    edges: Vec<EdgeData>,
+    #[serde(default)]
+    vcs_metadata: HashMap<NodeId, VcsIdentifier>,
+}
+
+#[derive(Deserialize)]
+struct EdgeData {
+    from: NodeId,
+    to: NodeId,
+    #[serde(rename = "type")]
+    edge_type: EdgeType,
+}
+
+impl Serialize for DependencyGraph {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        GraphRepr {
+            nodes: self.inner.node_weights().collect(),
+            edges: self
+                .edges()
+                .map(|(from, to, edge_type)| EdgeRepr {
+                    from: from.id(),
+                    to: to.id(),
+                    edge_type,
+                })
+                .collect(),
+            vcs_metadata: self.vcs_metadata.iter().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DependencyGraph {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let data = GraphData::deserialize(deserializer)?;
+        let mut graph = Self::new();
+        for node in data.nodes {
+            if graph.node_index.contains_key(node.id()) {
+                return Err(serde::de::Error::custom(format!(
+                    "duplicate node '{}'",
+                    node.id()
+                )));
+            }
+            graph.insert_node(node);
+        }
+        for edge in data.edges {
+            for id in [&edge.from, &edge.to] {
+                if !graph.node_index.contains_key(id) {
+                    return Err(serde::de::Error::custom(format!(
+                        "edge references unknown node '{id}'"
+                    )));
+                }
+            }
+            graph.add_edge(&edge.from, &edge.to, edge.edge_type);
+        }
+        graph.vcs_metadata = data.vcs_metadata;
+        Ok(graph)
+    }
 }
 
 /// A node in the dependency graph.
@@ -403,6 +715,36 @@ impl GraphNode {
         }
     }
 
+    /// Get the kind of this node.
+    #[must_use]
+    pub const fn kind(&self) -> NodeKind {
+        match self {
+            Self::Module(_) => NodeKind::Module,
+            Self::Provider(_) => NodeKind::Provider,
+            Self::Runtime(_) => NodeKind::Runtime,
+        }
+    }
+
+    /// Get the node's source (canonical ID for modules).
+    #[must_use]
+    pub fn source(&self) -> String {
+        match self {
+            Self::Module(m) => m.source.canonical_id(),
+            Self::Provider(p) => p.source.clone(),
+            Self::Runtime(r) => r.source.clone(),
+        }
+    }
+
+    /// Get the repository the node was found in.
+    #[must_use]
+    pub fn repository(&self) -> Option<&str> {
+        match self {
+            Self::Module(m) => m.repository.as_deref(),
+            Self::Provider(p) => p.repository.as_deref(),
+            Self::Runtime(r) => r.repository.as_deref(),
+        }
+    }
+
     /// Get the node's display name.
     #[must_use]
     pub fn display_name(&self) -> &str {
@@ -431,6 +773,18 @@ impl GraphNode {
     }
 }
 
+/// The kind of a node in the graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeKind {
+    /// A Terraform module
+    Module,
+    /// A Terraform provider
+    Provider,
+    /// A Terraform runtime
+    Runtime,
+}
+
 /// A module node in the graph.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModuleNode {
@@ -492,21 +846,77 @@ pub struct RuntimeNode {
 pub enum EdgeType {
     /// Module depends on another module
     ModuleDependsOn,
-    /// Module requires a provider
-    ModuleRequiresProvider,
-    /// Provider is an alias of another provider
+    /// Module requires a provider, as declared or inferred from its source
+    ModuleRequiresProvider(RequirementOrigin),
+    /// Module is passed an aliased provider configuration
     ProviderAlias,
     /// Module uses a local module
     LocalModuleRef,
+    /// Module calls a module found in its fetched remote source
+    ModuleCall,
+    /// Module directory requires a Terraform or OpenTofu version
+    RequiresRuntime,
+}
+
+/// How a module's provider requirement was established.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequirementOrigin {
+    /// Read from the providers map or the `required_providers` of the
+    /// fetched module
+    Declared,
+    /// Guessed from the module source
+    Inferred,
+}
+
+impl std::fmt::Display for RequirementOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Declared => write!(f, "declared"),
+            Self::Inferred => write!(f, "inferred"),
+        }
+    }
+}
+
+impl EdgeType {
+    /// How the requirement was established, for `ModuleRequiresProvider` edges.
+    #[must_use]
+    pub const fn requirement_origin(&self) -> Option<RequirementOrigin> {
+        match self {
+            Self::ModuleRequiresProvider(origin) => Some(*origin),
+            _ => None,
+        }
+    }
+
+    /// Check if this edge makes one module depend on another.
+    #[must_use]
+    pub const fn is_module_dependency(&self) -> bool {
+        matches!(
+            self,
+            Self::ModuleDependsOn | Self::LocalModuleRef | Self::ModuleCall
+        )
+    }
+}
+
+/// Render a cycle path as `a -> b -> a`.
+#[must_use]
+pub fn format_cycle(cycle: &[NodeId]) -> String {
+    let mut parts: Vec<&str> = cycle.iter().map(String::as_str).collect();
+    if let Some(first) = cycle.first() {
+        parts.push(first);
+    }
+    parts.join(" -> ")
 }
 
 impl std::fmt::Display for EdgeType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::ModuleDependsOn => write!(f, "depends_on"),
-            Self::ModuleRequiresProvider => write!(f, "requires"),
-            Self::ProviderAlias => write!(f, "alias_of"),
+            Self::ModuleRequiresProvider(_) => write!(f, "requires"),
+            Self::ProviderAlias => write!(f, "uses_alias"),
             Self::LocalModuleRef => write!(f, "local_ref"),
+            Self::ModuleCall => write!(f, "calls"),
+            Self::RequiresRuntime => write!(f, "requires_runtime"),
         }
     }
 }
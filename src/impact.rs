@@ -0,0 +1,536 @@
+//! Reverse-dependency impact queries.
+//!
+//! An [`ImpactQuery`] answers "who breaks if this module or provider
+//! changes?" for a scan: it lists every module block or provider
+//! requirement that uses a source, whether each one's constraint accepts a
+//! candidate version, and every module that depends on them transitively
+//! through the dependency graph.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use monphare::impact::ImpactQuery;
+//! use monphare::Constraint;
+//! # fn example(result: &monphare::ScanResult) -> monphare::Result<()> {
+//! let report = ImpactQuery::new("terraform-aws-modules/vpc/aws")
+//!     .with_candidate(Constraint::parse(">= 5.0")?)
+//!     .run(result);
+//! for consumer in report.consumers.iter().filter(|c| c.accepts == Some(false)) {
+//!     println!("{} breaks", consumer.resource);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::Result;
+use crate::graph::{DependencyGraph, GraphNode, NodeId};
+use crate::types::{Constraint, QueryFormat, ScanResult};
+use serde::Serialize;
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// A reverse-dependency query for one module or provider source.
+#[derive(Debug, Clone)]
+pub struct ImpactQuery {
+    source: String,
+    candidate: Option<Constraint>,
+}
+
+impl ImpactQuery {
+    /// Query the users of a module or provider source.
+    ///
+    /// The source matches when it is equal to a module's canonical source or
+    /// a provider's qualified source, ignoring case, the hostname, a Git
+    /// `?ref=` and a `//subdir`: `terraform-aws-modules/vpc/aws` matches
+    /// `registry.terraform.io/terraform-aws-modules/vpc/aws` and its
+    /// submodules, but `aws` or `vpc/aws` match nothing.
+    #[must_use]
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            candidate: None,
+        }
+    }
+
+    /// Check every consumer's constraint against a candidate version or
+    /// constraint. A consumer accepts it when at least one version satisfies
+    /// both, so an exact version checks whether that version is allowed.
+    #[must_use]
+    pub fn with_candidate(mut self, candidate: Constraint) -> Self {
+        self.candidate = Some(candidate);
+        self
+    }
+
+    /// Run the query against a scan.
+    #[must_use]
+    pub fn run(&self, result: &ScanResult) -> ImpactReport {
+        let graph = &result.graph;
+        let mut consumers = Vec::new();
+        let mut starts: Vec<(NodeId, String)> = Vec::new();
+
+        for module in &result.modules {
+            if !self.matches(&module.source.canonical_id()) {
+                continue;
+            }
+            let resource = format!("module.{}", module.name);
+            starts.push((graph.module_node_id(module), resource.clone()));
+            consumers.push(self.consumer(
+                resource,
+                module.source.to_source_string(),
+                module.version_constraint.as_ref(),
+                module.file_path.clone(),
+                module.line_number,
+                module.repository.clone(),
+            ));
+        }
+        for provider in &result.providers {
+            let source = provider.qualified_source();
+            if !self.matches(&source) {
+                continue;
+            }
+            let resource = format!("provider.{}", provider.name);
+            starts.push((graph.provider_node_id(provider), resource.clone()));
+            consumers.push(self.consumer(
+                resource,
+                source,
+                provider.version_constraint.as_ref(),
+                provider.file_path.clone(),
+                provider.line_number,
+                provider.repository.clone(),
+            ));
+        }
+
+        let dependents = transitive_dependents(graph, &starts);
+        tracing::debug!(
+            source = %self.source,
+            consumers = consumers.len(),
+            dependents = dependents.len(),
+            "Impact query complete"
+        );
+        ImpactReport {
+            source: self.source.clone(),
+            candidate: self.candidate.as_ref().map(|c| c.raw.clone()),
+            consumers,
+            dependents,
+        }
+    }
+
+    fn matches(&self, source: &str) -> bool {
+        let query = self.source.to_lowercase();
+        let source = source.to_lowercase();
+        // Only a leading segment that looks like a hostname may be left out
+        let equals = |s: &str| {
+            s == query
+                || s.split_once('/')
+                    .is_some_and(|(host, path)| host.contains('.') && path == query)
+        };
+        equals(&source) || equals(package_address(&source))
+    }
+
+    fn consumer(
+        &self,
+        resource: String,
+        source: String,
+        constraint: Option<&Constraint>,
+        file: PathBuf,
+        line: usize,
+        repository: Option<String>,
+    ) -> Consumer {
+        Consumer {
+            repository,
+            file,
+            line,
+            resource,
+            source,
+            constraint: constraint.map(|c| c.raw.clone()),
+            // No constraint accepts every version
+            accepts: self
+                .candidate
+                .as_ref()
+                .map(|candidate| constraint.is_none_or(|c| c.intersects(candidate))),
+        }
+    }
+}
+
+/// Strip the query string and `//subdir` from a source, leaving the address
+/// of the package it is fetched from.
+fn package_address(source: &str) -> &str {
+    let source = source.split('?').next().unwrap_or_default();
+    let path_start = source.find("://").map_or(0, |i| i + 3);
+    source[path_start..]
+        .find("//")
+        .map_or(source, |i| &source[..path_start + i])
+}
+
+/// Walk the graph against the direction of its edges from each consumer,
+/// recording every module reached and the consumer it was first reached
+/// from.
+fn transitive_dependents(graph: &DependencyGraph, starts: &[(NodeId, String)]) -> Vec<Dependent> {
+    let mut seen: HashSet<NodeId> = starts.iter().map(|(id, _)| id.clone()).collect();
+    let mut queue: VecDeque<(NodeId, &str, usize)> = starts
+        .iter()
+        .map(|(id, resource)| (id.clone(), resource.as_str(), 0))
+        .collect();
+    let mut dependents = Vec::new();
+
+    while let Some((id, via, depth)) = queue.pop_front() {
+        for node in graph.get_dependents(&id) {
+            let GraphNode::Module(module) = node else {
+                continue;
+            };
+            if !seen.insert(module.id.clone()) {
+                continue;
+            }
+            dependents.push(Dependent {
+                repository: module.repository.clone(),
+                file: module.file_path.clone(),
+                line: module.line_number,
+                module: format!("module.{}", module.name),
+                via: via.to_string(),
+                depth: depth + 1,
+            });
+            queue.push_back((module.id.clone(), via, depth + 1));
+        }
+    }
+    dependents
+}
+
+/// The outcome of an [`ImpactQuery`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ImpactReport {
+    /// The queried source
+    pub source: String,
+    /// The candidate version or constraint, if one was given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub candidate: Option<String>,
+    /// Module blocks and provider requirements using the source
+    pub consumers: Vec<Consumer>,
+    /// Modules that depend on a consumer, directly or transitively
+    pub dependents: Vec<Dependent>,
+}
+
+/// A module block or provider requirement that uses the queried source.
+#[derive(Debug, Clone, Serialize)]
+pub struct Consumer {
+    /// Repository name
+    pub repository: Option<String>,
+    /// File where declared
+    pub file: PathBuf,
+    /// Line number
+    pub line: usize,
+    /// Terraform address (e.g., `module.vpc` or `provider.aws`)
+    pub resource: String,
+    /// Source as declared
+    pub source: String,
+    /// Version constraint, if any
+    pub constraint: Option<String>,
+    /// Whether the constraint accepts the candidate; `None` without one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accepts: Option<bool>,
+}
+
+/// A module affected through its dependency on a consumer.
+#[derive(Debug, Clone, Serialize)]
+pub struct Dependent {
+    /// Repository name
+    pub repository: Option<String>,
+    /// File where declared
+    pub file: PathBuf,
+    /// Line number
+    pub line: usize,
+    /// Terraform address (e.g., `module.app`)
+    pub module: String,
+    /// The consumer it depends on
+    pub via: String,
+    /// Edges between this module and the consumer
+    pub depth: usize,
+}
+
+impl Consumer {
+    fn location(&self) -> String {
+        location(self.repository.as_deref(), &self.file, self.line)
+    }
+
+    fn verdict(&self) -> &'static str {
+        match self.accepts {
+            Some(true) => "accepts",
+            Some(false) => "rejects",
+            None => "-",
+        }
+    }
+}
+
+impl Dependent {
+    fn location(&self) -> String {
+        location(self.repository.as_deref(), &self.file, self.line)
+    }
+}
+
+fn location(repository: Option<&str>, file: &std::path::Path, line: usize) -> String {
+    match repository {
+        Some(repo) => format!("{repo}:{}:{line}", file.display()),
+        None => format!("{}:{line}", file.display()),
+    }
+}
+
+impl ImpactReport {
+    /// Consumers whose constraint excludes the candidate.
+    #[must_use]
+    pub fn rejecting(&self) -> usize {
+        self.consumers
+            .iter()
+            .filter(|c| c.accepts == Some(false))
+            .count()
+    }
+
+    /// Render the report in the given format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if JSON serialization fails.
+    pub fn render(&self, format: QueryFormat) -> Result<String> {
+        match format {
+            QueryFormat::Text => Ok(self.to_text()),
+            QueryFormat::Json => Ok(serde_json::to_string_pretty(self)?),
+            QueryFormat::Markdown => Ok(self.to_markdown()),
+        }
+    }
+
+    fn to_text(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "Impact of {}", self.source);
+        if let Some(candidate) = &self.candidate {
+            let _ = writeln!(
+                out,
+                "Candidate {candidate}: {} of {} consumer(s) reject it",
+                self.rejecting(),
+                self.consumers.len()
+            );
+        }
+
+        let _ = writeln!(out, "\nConsumers ({})", self.consumers.len());
+        for consumer in &self.consumers {
+            let _ = write!(
+                out,
+                "  {}  {}  {}",
+                consumer.location(),
+                consumer.resource,
+                consumer.constraint.as_deref().unwrap_or("(no constraint)")
+            );
+            if consumer.accepts.is_some() {
+                let _ = write!(out, "  {}", consumer.verdict());
+            }
+            out.push('\n');
+        }
+
+        let _ = writeln!(out, "\nTransitive dependents ({})", self.dependents.len());
+        for dependent in &self.dependents {
+            let _ = writeln!(
+                out,
+                "  {}  {}  via {} (depth {})",
+                dependent.location(),
+                dependent.module,
+                dependent.via,
+                dependent.depth
+            );
+        }
+        out
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# Impact of `{}`\n", self.source);
+        if let Some(candidate) = &self.candidate {
+            let _ = writeln!(
+                out,
+                "Candidate `{candidate}`: **{}** of {} consumer(s) reject it.\n",
+                self.rejecting(),
+                self.consumers.len()
+            );
+        }
+
+        let _ = writeln!(out, "## Consumers ({})\n", self.consumers.len());
+        let _ = writeln!(out, "| Location | Resource | Constraint | Candidate |");
+        let _ = writeln!(out, "|----------|----------|------------|-----------|");
+        for consumer in &self.consumers {
+            let _ = writeln!(
+                out,
+                "| `{}` | `{}` | {} | {} |",
+                consumer.location(),
+                consumer.resource,
+                consumer
+                    .constraint
+                    .as_deref()
+                    .map_or_else(|| "_none_".to_string(), |c| format!("`{c}`")),
+                consumer.verdict()
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "\n## Transitive dependents ({})\n",
+            self.dependents.len()
+        );
+        let _ = writeln!(out, "| Location | Module | Via | Depth |");
+        let _ = writeln!(out, "|----------|--------|-----|-------|");
+        for dependent in &self.dependents {
+            let _ = writeln!(
+                out,
+                "| `{}` | `{}` | `{}` | {} |",
+                dependent.location(),
+                dependent.module,
+                dependent.via,
+                dependent.depth
+            );
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::GraphBuilder;
+    use crate::types::{ModuleRef, ModuleSource, ProviderRef};
+
+    fn module(name: &str, dir: &str, source: ModuleSource, constraint: Option<&str>) -> ModuleRef {
+        ModuleRef {
+            name: name.to_string(),
+            source,
+            version_constraint: constraint.map(|c| Constraint::parse(c).unwrap()),
+            file_path: PathBuf::from(dir).join("main.tf"),
+            line_number: 1,
+            repository: Some("infra".to_string()),
+            attributes: Default::default(),
+            dependencies: Default::default(),
+        }
+    }
+
+    fn vpc() -> ModuleSource {
+        ModuleSource::Registry {
+            hostname: "registry.terraform.io".to_string(),
+            namespace: "terraform-aws-modules".to_string(),
+            name: "vpc".to_string(),
+            provider: "aws".to_string(),
+            subdir: None,
+            params: Default::default(),
+        }
+    }
+
+    fn scan() -> ScanResult {
+        let modules = vec![
+            module("vpc", "modules/network", vpc(), Some("~> 4.0")),
+            module("vpc", "envs/dev", vpc(), Some(">= 5.0")),
+            module(
+                "network",
+                "envs/prod",
+                ModuleSource::Local {
+                    path: "../../modules/network".to_string(),
+                },
+                None,
+            ),
+        ];
+        let providers = vec![ProviderRef {
+            name: "aws".to_string(),
+            source: Some("hashicorp/aws".to_string()),
+            version_constraint: None,
+            file_path: PathBuf::from("envs/dev/versions.tf"),
+            line_number: 2,
+            repository: Some("infra".to_string()),
+        }];
+        let graph = GraphBuilder::new()
+            .build(&modules, &providers, &[])
+            .unwrap();
+        ScanResult {
+            modules,
+            providers,
+            graph,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_consumers_and_candidate() {
+        let report = ImpactQuery::new("terraform-aws-modules/vpc/aws")
+            .with_candidate(Constraint::parse("5.1.0").unwrap())
+            .run(&scan());
+
+        assert_eq!(report.consumers.len(), 2);
+        assert_eq!(report.consumers[0].accepts, Some(false));
+        assert_eq!(report.consumers[1].accepts, Some(true));
+        assert_eq!(report.rejecting(), 1);
+
+        // The local call of modules/network is affected through module.vpc
+        assert_eq!(report.dependents.len(), 1);
+        assert_eq!(report.dependents[0].module, "module.network");
+        assert_eq!(report.dependents[0].via, "module.vpc");
+        assert_eq!(report.dependents[0].depth, 1);
+    }
+
+    #[test]
+    fn test_provider_source_and_formats() {
+        let report = ImpactQuery::new("hashicorp/aws").run(&scan());
+        assert_eq!(report.consumers.len(), 1);
+        assert_eq!(report.consumers[0].resource, "provider.aws");
+        assert_eq!(report.consumers[0].accepts, None);
+
+        let text = report.render(QueryFormat::Text).unwrap();
+        assert!(text.contains("infra:envs/dev/versions.tf:2  provider.aws  (no constraint)"));
+        let markdown = report.render(QueryFormat::Markdown).unwrap();
+        assert!(
+            markdown.contains("| `infra:envs/dev/versions.tf:2` | `provider.aws` | _none_ | - |")
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&report.render(QueryFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["consumers"][0]["resource"], "provider.aws");
+        assert!(json.get("candidate").is_none());
+    }
+
+    #[test]
+    fn test_unrelated_sources_do_not_match() {
+        let report = ImpactQuery::new("vpc/aws-extra").run(&scan());
+        assert!(report.consumers.is_empty());
+        assert!(report.dependents.is_empty());
+
+        // Partial addresses do not match every module of a provider
+        for query in ["aws", "vpc/aws"] {
+            assert!(ImpactQuery::new(query).run(&scan()).consumers.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_submodules_match_their_package() {
+        let mut result = scan();
+        let iam_user = ModuleSource::Registry {
+            hostname: "registry.terraform.io".to_string(),
+            namespace: "terraform-aws-modules".to_string(),
+            name: "iam".to_string(),
+            provider: "aws".to_string(),
+            subdir: Some("modules/iam-user".to_string()),
+            params: Default::default(),
+        };
+        let git = ModuleSource::Git {
+            host: "github.com/acme/terraform-modules".to_string(),
+            url: "https://github.com/acme/terraform-modules.git".to_string(),
+            ref_: Some("v1.2.0".to_string()),
+            subdir: Some("dns".to_string()),
+            params: Default::default(),
+        };
+        result
+            .modules
+            .push(module("deployer", "envs/dev", iam_user, Some("~> 5.0")));
+        result.modules.push(module("dns", "envs/dev", git, None));
+
+        let report = ImpactQuery::new("terraform-aws-modules/iam/aws").run(&result);
+        assert_eq!(report.consumers.len(), 1);
+        assert_eq!(report.consumers[0].resource, "module.deployer");
+        let report =
+            ImpactQuery::new("terraform-aws-modules/iam/aws//modules/iam-user").run(&result);
+        assert_eq!(report.consumers.len(), 1);
+
+        let report = ImpactQuery::new("github.com/acme/terraform-modules").run(&result);
+        assert_eq!(report.consumers.len(), 1);
+        assert_eq!(report.consumers[0].resource, "module.dns");
+    }
+}
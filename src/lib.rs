@@ -49,11 +49,17 @@
 pub mod analyzer;
 pub mod cli;
 pub mod config;
+pub mod diff;
 pub mod error;
 pub mod git;
 pub mod graph;
+pub mod impact;
 pub mod parser;
+pub mod registry;
 pub mod reporter;
+pub mod resolver;
+pub mod rule_sources;
+pub mod snapshot;
 pub mod types;
 pub mod vcs;
 pub mod vcs_clients;
@@ -66,6 +72,8 @@ pub use types::{
 };
 pub use vcs::VcsPlatform;
 
+use std::borrow::Cow;
+
 /// Main scanner orchestrator that coordinates all analysis operations.
 ///
 /// The `Scanner` is the primary entry point for using MonPhare as a library.
@@ -96,6 +104,9 @@ pub use vcs::VcsPlatform;
 pub struct Scanner {
     config: Config,
     git_client: git::GitClient,
+    advisories: analyzer::AdvisoryDatabase,
+    registry: Option<registry::RegistryClient>,
+    changes: Option<git::ChangeSet>,
 }
 
 impl Scanner {
@@ -103,7 +114,65 @@ impl Scanner {
     #[must_use]
     pub fn new(config: Config) -> Self {
         let git_client = git::GitClient::new(config.clone());
-        Self { config, git_client }
+        let registry = config
+            .registry
+            .enabled
+            .then(|| registry::RegistryClient::new(&config.registry));
+        Self {
+            config,
+            git_client,
+            advisories: analyzer::AdvisoryDatabase::default(),
+            registry,
+            changes: None,
+        }
+    }
+
+    /// Check scanned modules and providers against a security advisory database.
+    #[must_use]
+    pub fn with_advisories(mut self, advisories: analyzer::AdvisoryDatabase) -> Self {
+        self.advisories = advisories;
+        self
+    }
+
+    /// Look up registry modules and providers with this client.
+    #[must_use]
+    pub fn with_registry(mut self, client: registry::RegistryClient) -> Self {
+        self.registry = Some(client);
+        self
+    }
+
+    /// Only look up and report what changed since a git ref.
+    ///
+    /// Only the directories the change affects are parsed: those with a
+    /// changed file, the modules that call them and the modules those call
+    /// (see [`git::ChangeSet::affected_directories`]). Remote modules and
+    /// registry data are only fetched for references in directories with a
+    /// changed file, and only findings on changed lines are reported.
+    #[must_use]
+    pub fn with_changes(mut self, changes: git::ChangeSet) -> Self {
+        self.changes = Some(changes);
+        self
+    }
+
+    /// Save a scan result as a snapshot that [`Self::load_snapshot`] can read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot cannot be written.
+    pub fn save_snapshot(&self, result: &ScanResult, path: &std::path::Path) -> Result<()> {
+        snapshot::ScanSnapshot::new(result.clone()).save(path)?;
+        tracing::info!(path = %path.display(), "Snapshot written");
+        Ok(())
+    }
+
+    /// Load the scan result stored in a snapshot instead of scanning.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot cannot be read or uses an
+    /// unsupported format version.
+    pub fn load_snapshot(&self, path: &std::path::Path) -> Result<ScanResult> {
+        Ok(snapshot::ScanSnapshot::load(path)?.result)
     }
 
     /// Scan multiple local paths for Terraform/OpenTofu files.
@@ -125,24 +194,93 @@ impl Scanner {
         let mut all_runtimes = Vec::new();
         let mut all_files = Vec::new();
         let mut all_warnings = Vec::new();
+        let mut all_suppressions = Vec::new();
+        let mut all_locks = Vec::new();
+        let mut roots = Vec::new();
 
         for path in paths {
             tracing::debug!(path = %path.display(), "Scanning path");
-
-            let parsed = parser.parse_directory(&path).await?;
+            roots.push(analyzer::scan_root(&path));
+
+            let parsed = match &self.changes {
+                Some(changes) => {
+                    let calls = parser.local_module_calls(&path).await?;
+                    let affected = changes.affected_directories(&calls);
+                    tracing::debug!(
+                        directories = affected.len(),
+                        "Parsing directories affected by the change"
+                    );
+                    parser
+                        .parse_directory_where(&path, |dir| affected.contains(dir))
+                        .await?
+                }
+                None => parser.parse_directory(&path).await?,
+            };
             all_runtimes.extend(parsed.runtimes);
             all_modules.extend(parsed.modules);
             all_providers.extend(parsed.providers);
             all_files.extend(parsed.files);
             all_warnings.extend(parsed.warnings);
+            all_suppressions.extend(parsed.suppressions);
+            all_locks.extend(parsed.locks);
         }
 
-        // Build dependency graph
-        let graph_builder = graph::GraphBuilder::new();
+        // With a change set, only fetch what the change touches
+        let (changed_modules, changed_providers) = match &self.changes {
+            Some(changes) => (
+                Cow::Owned(
+                    all_modules
+                        .iter()
+                        .filter(|m| changes.touches_directory(&m.file_path))
+                        .cloned()
+                        .collect(),
+                ),
+                Cow::Owned(
+                    all_providers
+                        .iter()
+                        .filter(|p| changes.touches_directory(&p.file_path))
+                        .cloned()
+                        .collect(),
+                ),
+            ),
+            None => (
+                Cow::Borrowed(all_modules.as_slice()),
+                Cow::Borrowed(all_providers.as_slice()),
+            ),
+        };
+
+        // Build dependency graph, fetching remote modules first if asked to
+        let mut graph_builder = graph::GraphBuilder::new()
+            .with_provider_inference(&self.config.graph.provider_inference)?
+            .with_locks(all_locks.clone());
+        if self.config.resolve.enabled {
+            let mut resolver = resolver::ModuleResolver::new(&self.config, &self.git_client);
+            if let Some(client) = &self.registry {
+                resolver = resolver.with_registry(client.clone());
+            }
+            let mut resolution = resolver.resolve(&changed_modules).await;
+            all_warnings.append(&mut resolution.warnings);
+            graph_builder = graph_builder.with_resolution(resolution);
+        }
         let dependency_graph = graph_builder.build(&all_modules, &all_providers, &all_runtimes)?;
 
         // Run analysis
-        let analyzer = analyzer::Analyzer::new(&self.config);
+        let registry_index = match &self.registry {
+            Some(client) => {
+                let (index, warnings) = client.lookup(&changed_modules, &changed_providers).await;
+                all_warnings.extend(warnings);
+                index
+            }
+            None => registry::RegistryIndex::default(),
+        };
+
+        let mut analyzer = analyzer::Analyzer::new(&self.config)
+            .with_suppressions(all_suppressions)
+            .with_advisories(self.advisories.clone())
+            .with_registry(registry_index);
+        if let Some(changes) = &self.changes {
+            analyzer = analyzer.with_changes(changes.clone());
+        }
         let analysis = analyzer.analyze(
             &dependency_graph,
             &all_modules,
@@ -166,6 +304,9 @@ impl Scanner {
             graph: dependency_graph,
             analysis,
             warnings: all_warnings,
+            baseline: None,
+            locks: all_locks,
+            roots,
         })
     }
 
@@ -185,6 +326,26 @@ impl Scanner {
         self.scan_paths(vec![local_path]).await
     }
 
+    /// Scan a local Git repository as of a commit, branch or tag.
+    ///
+    /// The ref is checked out into a temporary directory that is removed
+    /// after the scan; the working tree of `repo` is not touched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ref cannot be checked out or scanning fails.
+    pub async fn scan_git_ref(&self, repo: &std::path::Path, git_ref: &str) -> Result<ScanResult> {
+        tracing::info!(repo = %repo.display(), git_ref = %git_ref, "Scanning git ref");
+        let checkout = self.git_client.checkout_ref(repo, git_ref).await?;
+        let result = self.scan_paths(vec![checkout.clone()]).await;
+        if let Some(parent) = checkout.parent() {
+            if let Err(e) = std::fs::remove_dir_all(parent) {
+                tracing::warn!(path = %parent.display(), error = %e, "Failed to remove checkout");
+            }
+        }
+        result
+    }
+
     /// Scan multiple repositories in parallel.
     ///
     /// # Errors
@@ -321,10 +482,17 @@ impl Scanner {
 
             match self.scan_repository(&repo.clone_url).await {
                 Ok(mut result) => {
-                    // Add VCS metadata to the graph
+                    // Add VCS metadata to the graph, with the full namespace from
+                    // the clone URL when it can be parsed
+                    let components =
+                        types::VcsIdentifier::from_url(&repo.clone_url, platform.as_str())
+                            .map_or_else(
+                                || repo.name.split('/').map(str::to_string).collect(),
+                                |id| id.components,
+                            );
                     let vcs_id = VcsIdentifier::new(
                         platform.as_str(),
-                        &repo.name.split('/').collect::<Vec<_>>(),
+                        &components.iter().map(String::as_str).collect::<Vec<_>>(),
                     );
 
                     // Mark all nodes from this repo with VCS identifier
@@ -3,8 +3,15 @@
 //! This binary provides the command-line interface for MonPhare.
 
 use clap::Parser;
+use monphare::analyzer::{AdvisoryDatabase, Baseline};
 use monphare::cli::{Cli, Commands};
-use monphare::{Config, Scanner, VcsPlatform};
+use monphare::diff::ScanDiff;
+use monphare::git::{ChangeSet, GitClient};
+use monphare::impact::ImpactQuery;
+use monphare::rule_sources;
+use monphare::types::ScanWarning;
+use monphare::{Config, Constraint, Scanner, VcsPlatform};
+use std::path::PathBuf;
 use std::process::ExitCode;
 use tracing_error::ErrorLayer;
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
@@ -91,7 +98,7 @@ fn init_logging(verbose: u8, quiet: bool) {
 async fn run(cli: Cli) -> anyhow::Result<ExitCode> {
     // Load configuration
     tracing::debug!("Loading configuration");
-    let mut config = load_config(&cli)?;
+    let (mut config, config_path) = load_config(&cli)?;
     tracing::debug!("Configuration loaded successfully");
 
     // merge CLI arguments into config
@@ -105,7 +112,9 @@ async fn run(cli: Cli) -> anyhow::Result<ExitCode> {
     match cli.command {
         Commands::Scan(mut args) => {
             tracing::debug!("Executing scan command");
-            let scanner = Scanner::new(config.clone());
+
+            let (mut scanner, include_warnings) =
+                build_scanner(&mut config, config_path.as_deref()).await?;
 
             // detect URLs passed as positional args and treat them as repositories
             let mut local_paths = Vec::new();
@@ -119,6 +128,22 @@ async fn run(cli: Cli) -> anyhow::Result<ExitCode> {
             }
             args.paths = local_paths;
 
+            // Restrict lookups and findings to what changed in the working tree
+            if let Some(git_ref) = &args.changed_since {
+                // Each path may be in a different repository
+                let mut changes = ChangeSet::default();
+                if args.paths.is_empty() {
+                    changes.merge(ChangeSet::since(std::path::Path::new("."), git_ref)?);
+                }
+                for path in &args.paths {
+                    changes.merge(ChangeSet::since(path, git_ref)?);
+                }
+                if changes.is_empty() {
+                    tracing::info!(base = %git_ref, "No Terraform files changed");
+                }
+                scanner = scanner.with_changes(changes);
+            }
+
             // Handle bulk VCS organization scanning
             let bulk_scan = args.github.is_some()
                 || args.gitlab.is_some()
@@ -126,7 +151,9 @@ async fn run(cli: Cli) -> anyhow::Result<ExitCode> {
                 || args.bitbucket.is_some();
             tracing::debug!(bulk_scan = bulk_scan, "Scan mode determined");
 
-            let result = if bulk_scan {
+            let mut result = if let Some(snapshot) = &args.from_snapshot {
+                scanner.load_snapshot(snapshot)?
+            } else if bulk_scan {
                 // Bulk organization scanning
                 let org_spec = if let Some(org) = &args.github {
                     (VcsPlatform::GitHub, org.clone())
@@ -150,6 +177,27 @@ async fn run(cli: Cli) -> anyhow::Result<ExitCode> {
                 // Single directory scanning
                 scanner.scan_paths(args.paths).await?
             };
+            result.warnings.extend(include_warnings);
+            if let Some(snapshot) = &args.snapshot {
+                scanner.save_snapshot(&result, snapshot)?;
+            }
+
+            // Record or compare against the baseline
+            if let Some(baseline_path) = &args.baseline {
+                if args.write_baseline {
+                    let baseline =
+                        Baseline::from_findings(&result.analysis.findings, &result.roots);
+                    baseline.save(baseline_path)?;
+                    eprintln!(
+                        "Wrote {} finding(s) to baseline {}",
+                        baseline.entries.len(),
+                        baseline_path.display()
+                    );
+                    return Ok(ExitCode::from(0));
+                }
+
+                Baseline::load(baseline_path)?.apply(&mut result);
+            }
 
             // Generate report
             let reporter = monphare::reporter::Reporter::new(&config);
@@ -176,11 +224,24 @@ async fn run(cli: Cli) -> anyhow::Result<ExitCode> {
         }
 
         Commands::Graph(args) => {
-            let scanner = Scanner::new(config);
-            let result = scanner.scan_paths(args.paths).await?;
+            let filter = args.graph_filter()?;
+            let (scanner, _) = build_scanner(&mut config, config_path.as_deref()).await?;
+            let result = match &args.from_snapshot {
+                Some(snapshot) => scanner.load_snapshot(snapshot)?,
+                None => scanner.scan_paths(args.paths).await?,
+            };
 
-            // Output graph in requested format
-            let graph_output = monphare::graph::export_graph(&result.graph, args.format)?;
+            // Output the filtered graph, or its stats, in requested format
+            let graph = result.graph.subgraph(&filter);
+            let graph_output = if let Some(stats_format) = args.stats {
+                graph.stats(args.top).render(stats_format)?
+            } else {
+                let options = monphare::graph::ExportOptions {
+                    highlight_cycles: args.highlight_cycles,
+                    cluster_by: args.cluster_by,
+                };
+                monphare::graph::export_graph_with_options(&graph, args.format, &options)?
+            };
 
             if let Some(output_path) = args.output {
                 std::fs::write(&output_path, &graph_output)?;
@@ -192,6 +253,57 @@ async fn run(cli: Cli) -> anyhow::Result<ExitCode> {
             Ok(ExitCode::from(0))
         }
 
+        Commands::Impact(args) => {
+            let mut query = ImpactQuery::new(&args.source);
+            if let Some(candidate) = &args.candidate {
+                query = query.with_candidate(Constraint::parse(candidate)?);
+            }
+
+            let (scanner, include_warnings) =
+                build_scanner(&mut config, config_path.as_deref()).await?;
+            let mut result = if let Some(snapshot) = &args.from_snapshot {
+                scanner.load_snapshot(snapshot)?
+            } else if args.repositories.is_empty() {
+                scanner.scan_paths(args.paths).await?
+            } else {
+                let urls: Vec<&str> = args.repositories.iter().map(String::as_str).collect();
+                let mut result = scanner.scan_repositories(&urls).await?;
+                if !args.paths.is_empty() {
+                    result.merge(scanner.scan_paths(args.paths).await?);
+                }
+                result
+            };
+            result.warnings.extend(include_warnings);
+
+            let report = query.run(&result);
+            let output = report.render(args.format)?;
+            if let Some(output_path) = args.output {
+                std::fs::write(&output_path, &output)?;
+                tracing::info!(path = %output_path.display(), "Impact report written");
+            } else {
+                println!("{output}");
+            }
+
+            Ok(ExitCode::from(0))
+        }
+
+        Commands::Diff(args) => {
+            let (scanner, _) = build_scanner(&mut config, config_path.as_deref()).await?;
+            let old = load_side(&scanner, &args.path, &args.old).await?;
+            let new = load_side(&scanner, &args.path, &args.new).await?;
+
+            let diff = ScanDiff::between(&args.old, &old, &args.new, &new);
+            let output = diff.render(args.format)?;
+            if let Some(output_path) = args.output {
+                std::fs::write(&output_path, &output)?;
+                tracing::info!(path = %output_path.display(), "Diff written");
+            } else {
+                println!("{output}");
+            }
+
+            Ok(ExitCode::from(0))
+        }
+
         Commands::Init => {
             // Generate example configuration file
             let example_config = Config::example_yaml();
@@ -226,14 +338,15 @@ async fn run(cli: Cli) -> anyhow::Result<ExitCode> {
     }
 }
 
-fn load_config(cli: &Cli) -> anyhow::Result<Config> {
+/// Load the configuration, returning it along with the file it came from.
+fn load_config(cli: &Cli) -> anyhow::Result<(Config, Option<PathBuf>)> {
     // Check for explicit config file
     if let Some(ref config_path) = cli.config {
         tracing::debug!(path = %config_path.display(), "Loading configuration from explicit path");
         let content = std::fs::read_to_string(config_path)?;
         let mut config = Config::from_yaml(&content)?;
         config.load_vcs_tokens_from_env();
-        return Ok(config);
+        return Ok((config, Some(config_path.clone())));
     }
 
     // Look for default config files
@@ -245,7 +358,7 @@ fn load_config(cli: &Cli) -> anyhow::Result<Config> {
             let content = std::fs::read_to_string(path)?;
             let mut config = Config::from_yaml(&content)?;
             config.load_vcs_tokens_from_env();
-            return Ok(config);
+            return Ok((config, Some(PathBuf::from(path))));
         }
     }
 
@@ -253,5 +366,38 @@ fn load_config(cli: &Cli) -> anyhow::Result<Config> {
     // Use default configuration
     let mut config = Config::default();
     config.load_vcs_tokens_from_env();
-    Ok(config)
+    Ok((config, None))
+}
+
+/// Build the scanner every command scans with: shared deprecation rules
+/// from `deprecations.include` are merged into `config` and the advisory
+/// database is opened, so all commands report the same findings.
+///
+/// Returns the scanner and the warnings raised while resolving includes.
+async fn build_scanner(
+    config: &mut Config,
+    config_path: Option<&std::path::Path>,
+) -> anyhow::Result<(Scanner, Vec<ScanWarning>)> {
+    let git_client = GitClient::new(config.clone());
+    let include_warnings = rule_sources::resolve_includes(config, config_path, &git_client).await?;
+    let base_dir = config_path
+        .and_then(std::path::Path::parent)
+        .unwrap_or_else(|| std::path::Path::new("."));
+    let advisories = AdvisoryDatabase::open(&config.advisories, base_dir, &git_client).await?;
+    let scanner = Scanner::new(config.clone()).with_advisories(advisories);
+    Ok((scanner, include_warnings))
+}
+
+/// Load one side of a diff: a snapshot if `side` names a file, otherwise a
+/// scan of `repo` checked out at the git ref `side`.
+async fn load_side(
+    scanner: &Scanner,
+    repo: &std::path::Path,
+    side: &str,
+) -> anyhow::Result<monphare::ScanResult> {
+    let snapshot = std::path::Path::new(side);
+    if snapshot.is_file() {
+        return Ok(scanner.load_snapshot(snapshot)?);
+    }
+    Ok(scanner.scan_git_ref(repo, side).await?)
 }
@@ -4,16 +4,29 @@
 
 use crate::config::Config;
 use crate::error::{ErrorCollector, MonPhareError, Result};
+use crate::parser::lock::{is_lock_file, parse_lock_file};
 use crate::parser::{Parser, SKIP_FILES, TERRAFORM_EXTENSIONS};
 use crate::types::{
-    Constraint, ModuleRef, ParsedHcl, ProviderRef, RuntimeRef, RuntimeSource, ScanWarning,
+    Constraint, ModuleDependencies, ModuleRef, ModuleSource, ParsedHcl, ProviderRef, RuntimeRef,
+    RuntimeSource, ScanWarning,
 };
 
-use hcl::{Block, Body, Expression};
+use hcl::expr::{Operation, TraversalOperator};
+use hcl::template::{Directive, Element};
+use hcl::{Block, Body, Expression, Template};
 use regex::Regex;
-use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
 use walkdir::WalkDir;
 
+/// Local module calls by directory, see [`HclParser::local_module_calls`].
+pub type LocalModuleCalls = BTreeMap<PathBuf, BTreeSet<PathBuf>>;
+
+/// A `source` attribute naming a local path.
+static LOCAL_SOURCE_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?m)^\s*source\s*=\s*"(\.\.?/[^"]*)""#).expect("Invalid regex"));
+
 /// HCL parser for Terraform/OpenTofu files.
 ///
 /// The parser walks directories, reads `.tf` files, and extracts
@@ -41,6 +54,23 @@ impl HclParser {
     /// Returns an error if the directory doesn't exist or if parsing fails
     /// for any file (unless `continue_on_error` is enabled in config).
     pub async fn parse_directory(&self, path: &Path) -> Result<ParsedHcl> {
+        self.parse_directory_where(path, |_| true).await
+    }
+
+    /// Parse the Terraform files under `path` whose directory `keep` accepts.
+    ///
+    /// Like [`parse_directory`](Self::parse_directory), but files in other
+    /// directories are not read. `keep` is called with canonical paths.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory doesn't exist or if parsing fails
+    /// for any file (unless `continue_on_error` is enabled in config).
+    pub async fn parse_directory_where(
+        &self,
+        path: &Path,
+        keep: impl Fn(&Path) -> bool,
+    ) -> Result<ParsedHcl> {
         if !path.exists() {
             return Err(crate::err!(DirectoryNotFound {
                 path: path.to_path_buf(),
@@ -50,8 +80,18 @@ impl HclParser {
         let mut result = ParsedHcl::default();
         let mut error_collector = ErrorCollector::new();
 
-        // Determine repository name from path
-        let repository = path.file_name().and_then(|n| n.to_str()).map(String::from);
+        // Determine repository name from path; `.` and `..` are named after
+        // the directory they resolve to
+        let repository = path
+            .file_name()
+            .map(std::ffi::OsStr::to_os_string)
+            .or_else(|| {
+                std::fs::canonicalize(path)
+                    .ok()?
+                    .file_name()
+                    .map(Into::into)
+            })
+            .and_then(|n| n.into_string().ok());
 
         // Walk directory tree
         for entry in WalkDir::new(path)
@@ -75,7 +115,11 @@ impl HclParser {
             }
 
             // Check file extension
-            if !self.is_terraform_file(file_path) {
+            if !self.is_terraform_file(file_path) && !is_lock_file(file_path) {
+                continue;
+            }
+            if !keep(&canonical_dir(file_path)) {
+                tracing::trace!(file = %file_path.display(), "Directory not selected, skipping");
                 continue;
             }
 
@@ -113,7 +157,108 @@ impl HclParser {
         Ok(result)
     }
 
-    /// Parse a single Terraform file.
+    /// Local module calls of every directory under `path`.
+    ///
+    /// Maps each directory to the directories its `source = "./..."` and
+    /// `source = "../..."` module calls point at, all as canonical paths.
+    /// Only those `source` lines are read, which is much cheaper than
+    /// parsing; `scan --changed-since` uses it to find the modules a change
+    /// affects before parsing them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory doesn't exist.
+    pub async fn local_module_calls(&self, path: &Path) -> Result<LocalModuleCalls> {
+        if !path.exists() {
+            return Err(crate::err!(DirectoryNotFound {
+                path: path.to_path_buf(),
+            }));
+        }
+
+        let mut calls = LocalModuleCalls::new();
+        for entry in WalkDir::new(path)
+            .follow_links(true)
+            .into_iter()
+            .filter_entry(|e| !self.should_skip(e.path()))
+            .filter_map(std::result::Result::ok)
+        {
+            let file_path = entry.path();
+            if file_path.is_dir() || !self.is_terraform_file(file_path) {
+                continue;
+            }
+            let content = match tokio::fs::read_to_string(file_path).await {
+                Ok(content) => content,
+                Err(e) => {
+                    tracing::warn!(file = %file_path.display(), error = %e, "Failed to read file");
+                    continue;
+                }
+            };
+
+            let dir = canonical_dir(file_path);
+            let callees = calls.entry(dir.clone()).or_default();
+            for cap in LOCAL_SOURCE_PATTERN.captures_iter(&content) {
+                callees.insert(crate::graph::normalize_path(&dir.join(&cap[1])));
+            }
+        }
+        Ok(calls)
+    }
+
+    /// Parse the Terraform files directly inside `path` as a single module.
+    ///
+    /// Unlike [`parse_directory`](Self::parse_directory), subdirectories are
+    /// not walked and exclude patterns do not apply: a Terraform module is
+    /// exactly the files of one directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be read or a file fails to
+    /// parse (unless `continue_on_error` is enabled in config).
+    pub async fn parse_module_directory(
+        &self,
+        path: &Path,
+        repository: Option<&str>,
+    ) -> Result<ParsedHcl> {
+        let mut entries = tokio::fs::read_dir(path).await.map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                crate::err!(DirectoryNotFound {
+                    path: path.to_path_buf(),
+                })
+            } else {
+                MonPhareError::io(path, e, file!(), line!())
+            }
+        })?;
+
+        let mut files = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| MonPhareError::io(path, e, file!(), line!()))?
+        {
+            let file_path = entry.path();
+            if file_path.is_file() && self.is_terraform_file(&file_path) {
+                files.push(file_path);
+            }
+        }
+        files.sort();
+
+        let mut result = ParsedHcl::default();
+        for file_path in files {
+            match self.parse_file(&file_path, repository).await {
+                Ok(parsed) => result.merge(parsed),
+                Err(e) if self.config.scan.continue_on_error && e.is_recoverable() => {
+                    tracing::warn!(
+                        file = %file_path.display(),
+                        "failed to parse file, continuing: {}",
+                        e
+                    );
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(result)
+    }
+
+    /// Parse a single Terraform file or dependency lock file.
     ///
     /// # Errors
     ///
@@ -123,6 +268,12 @@ impl HclParser {
             .await
             .map_err(|e| MonPhareError::io(path, e, file!(), line!()))?;
 
+        if is_lock_file(path) {
+            return Ok(ParsedHcl {
+                locks: parse_lock_file(&content, path, repository)?,
+                ..ParsedHcl::default()
+            });
+        }
         self.parse_content(&content, path, repository)
     }
 
@@ -130,8 +281,8 @@ impl HclParser {
     fn should_skip(&self, path: &Path) -> bool {
         // Check against skip patterns
         if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
-            // Skip hidden files/directories
-            if file_name.starts_with('.') {
+            // Skip hidden files/directories, except dependency lock files
+            if file_name.starts_with('.') && !is_lock_file(path) {
                 tracing::debug!(path = %path.display(), reason = "hidden file/directory", "Skipping path");
                 return true;
             }
@@ -181,7 +332,12 @@ impl Parser for HclParser {
                     error = %e,
                     "hcl-rs parse failed, falling back to regex extraction"
                 );
-                return parse_content_regex(content, file_path, repository);
+                return parse_content_regex(
+                    content,
+                    file_path,
+                    repository,
+                    &self.config.registry.hosts,
+                );
             }
         };
 
@@ -191,6 +347,8 @@ impl Parser for HclParser {
             runtimes: Vec::new(),
             files: vec![file_path.to_path_buf()],
             warnings: Vec::new(),
+            suppressions: Vec::new(),
+            locks: Vec::new(),
         };
 
         // Process all blocks
@@ -198,7 +356,12 @@ impl Parser for HclParser {
             if let hcl::Structure::Block(block) = structure {
                 match block.identifier.as_str() {
                     "module" => {
-                        let parse_result = parse_module_block(&block, file_path, repository)?;
+                        let parse_result = parse_module_block(
+                            &block,
+                            file_path,
+                            repository,
+                            &self.config.registry.hosts,
+                        )?;
                         result.warnings.extend(parse_result.warnings);
                         if let Some(module_ref) = parse_result.module {
                             result.modules.push(module_ref);
@@ -230,10 +393,44 @@ impl Parser for HclParser {
             }
         }
 
+        attach_source_info(&mut result, content, file_path, repository);
+
         Ok(result)
     }
 }
 
+/// Canonical path of the directory containing `file`.
+fn canonical_dir(file: &Path) -> PathBuf {
+    let dir = file.parent().unwrap_or(file);
+    dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf())
+}
+
+/// Fill in line numbers and suppression directives from the raw file text.
+///
+/// `hcl-rs` drops span information, so both are recovered lexically.
+fn attach_source_info(
+    result: &mut ParsedHcl,
+    content: &str,
+    file_path: &Path,
+    repository: Option<&str>,
+) {
+    for module in &mut result.modules {
+        module.line_number = super::span::module_line(content, &module.name).unwrap_or(0);
+    }
+    for provider in &mut result.providers {
+        provider.line_number =
+            super::span::required_provider_line(content, &provider.name).unwrap_or(0);
+    }
+    for runtime in &mut result.runtimes {
+        runtime.line_number = super::span::attribute_line(content, "required_version").unwrap_or(0);
+    }
+
+    let (suppressions, warnings) =
+        super::suppression::parse_suppressions(content, file_path, repository);
+    result.suppressions.extend(suppressions);
+    result.warnings.extend(warnings);
+}
+
 /// Result of parsing a module block - module reference and any warnings.
 struct ModuleParseResult {
     module: Option<ModuleRef>,
@@ -248,6 +445,7 @@ fn parse_module_block(
     block: &Block,
     file_path: &Path,
     repository: Option<&str>,
+    registry_hosts: &[String],
 ) -> Result<ModuleParseResult> {
     let mut warnings = Vec::new();
 
@@ -274,8 +472,27 @@ fn parse_module_block(
         }
     };
 
-    // Parse the source
-    let source = super::parse_module_source(&source_str)?;
+    // Parse the source - keep the module and warn if malformed
+    let source = match super::parse_module_source_with_registries(&source_str, registry_hosts) {
+        Ok(source) => source,
+        Err(e) => {
+            tracing::warn!(
+                module = %name,
+                source = %source_str,
+                file = %file_path.display(),
+                error = %e,
+                "Malformed module source"
+            );
+            warnings.push(ScanWarning {
+                code: "invalid-module-source".to_string(),
+                message: format!("Module '{name}' has a malformed source: {e}"),
+                file: file_path.to_path_buf(),
+                line: None,
+                repository: repository.map(String::from),
+            });
+            ModuleSource::Unknown(source_str.clone())
+        }
+    };
 
     // Extract version constraint - skip and warn if unparseable
     let version_constraint = match get_string_attribute(&block.body, "version") {
@@ -324,9 +541,10 @@ fn parse_module_block(
             source,
             version_constraint,
             file_path: file_path.to_path_buf(),
-            line_number: 0, // HCL-rs doesn't provide line numbers easily
+            line_number: 0, // Filled in by `attach_source_info`
             repository: repository.map(String::from),
             attributes,
+            dependencies: module_dependencies(&block.body),
         }),
         warnings,
     })
@@ -507,6 +725,165 @@ fn parse_provider_requirement(expr: &Expression) -> Result<(Option<String>, Opti
     }
 }
 
+/// Extract what a module block depends on from its arguments.
+///
+/// `module.<name>` references in any argument other than `source` and
+/// `version` are collected, along with the modules listed in `depends_on`
+/// and the `providers` map (`aws = aws.east` becomes `"aws" => "aws.east"`).
+fn module_dependencies(body: &Body) -> ModuleDependencies {
+    let mut dependencies = ModuleDependencies::default();
+    let mut references = BTreeSet::new();
+
+    for attr in body.attributes() {
+        match attr.key.as_str() {
+            "source" | "version" => {}
+            "depends_on" => {
+                if let Expression::Array(items) = &attr.expr {
+                    dependencies.depends_on = items
+                        .iter()
+                        .filter_map(traversal_address)
+                        .filter_map(|address| {
+                            let name = address.strip_prefix("module.")?;
+                            Some(name.split('.').next().unwrap_or(name).to_string())
+                        })
+                        .collect();
+                }
+            }
+            "providers" => {
+                if let Expression::Object(object) = &attr.expr {
+                    for (key, value) in object {
+                        let key = match key {
+                            hcl::ObjectKey::Identifier(id) => Some(id.as_str().to_string()),
+                            hcl::ObjectKey::Expression(expr) => traversal_address(expr),
+                            _ => None,
+                        };
+                        if let (Some(key), Some(value)) = (key, traversal_address(value)) {
+                            dependencies.providers.insert(key, value);
+                        }
+                    }
+                }
+            }
+            _ => collect_module_refs(&attr.expr, &mut references),
+        }
+    }
+
+    dependencies.references = references.into_iter().collect();
+    dependencies
+}
+
+/// Collect the names of modules referenced as `module.<name>` in `expr`.
+fn collect_module_refs(expr: &Expression, names: &mut BTreeSet<String>) {
+    match expr {
+        Expression::Traversal(traversal) => {
+            if let (Expression::Variable(root), Some(TraversalOperator::GetAttr(name))) =
+                (&traversal.expr, traversal.operators.first())
+            {
+                if root.as_str() == "module" {
+                    names.insert(name.as_str().to_string());
+                }
+            }
+            collect_module_refs(&traversal.expr, names);
+            for operator in &traversal.operators {
+                if let TraversalOperator::Index(index) = operator {
+                    collect_module_refs(index, names);
+                }
+            }
+        }
+        Expression::Array(items) => {
+            for item in items {
+                collect_module_refs(item, names);
+            }
+        }
+        Expression::Object(object) => {
+            for value in object.values() {
+                collect_module_refs(value, names);
+            }
+        }
+        Expression::FuncCall(call) => {
+            for arg in &call.args {
+                collect_module_refs(arg, names);
+            }
+        }
+        Expression::Parenthesis(inner) => collect_module_refs(inner, names),
+        Expression::Conditional(cond) => {
+            collect_module_refs(&cond.cond_expr, names);
+            collect_module_refs(&cond.true_expr, names);
+            collect_module_refs(&cond.false_expr, names);
+        }
+        Expression::Operation(operation) => match operation.as_ref() {
+            Operation::Unary(op) => collect_module_refs(&op.expr, names),
+            Operation::Binary(op) => {
+                collect_module_refs(&op.lhs_expr, names);
+                collect_module_refs(&op.rhs_expr, names);
+            }
+        },
+        Expression::ForExpr(for_expr) => {
+            collect_module_refs(&for_expr.collection_expr, names);
+            if let Some(key_expr) = &for_expr.key_expr {
+                collect_module_refs(key_expr, names);
+            }
+            collect_module_refs(&for_expr.value_expr, names);
+            if let Some(cond_expr) = &for_expr.cond_expr {
+                collect_module_refs(cond_expr, names);
+            }
+        }
+        Expression::TemplateExpr(template) => {
+            if let Ok(template) = Template::from_expr(template) {
+                collect_template_module_refs(&template, names);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Collect `module.<name>` references from the interpolations and
+/// directives of a string template.
+fn collect_template_module_refs(template: &Template, names: &mut BTreeSet<String>) {
+    for element in template.elements() {
+        match element {
+            Element::Interpolation(interpolation) => {
+                collect_module_refs(&interpolation.expr, names);
+            }
+            Element::Directive(Directive::If(directive)) => {
+                collect_module_refs(&directive.cond_expr, names);
+                collect_template_module_refs(&directive.true_template, names);
+                if let Some(false_template) = &directive.false_template {
+                    collect_template_module_refs(false_template, names);
+                }
+            }
+            Element::Directive(Directive::For(directive)) => {
+                collect_module_refs(&directive.collection_expr, names);
+                collect_template_module_refs(&directive.template, names);
+            }
+            Element::Literal(_) => {}
+        }
+    }
+}
+
+/// Render a plain reference like `aws.east` or `module.vpc` as a string.
+///
+/// Returns `None` for anything other than a variable followed by
+/// attribute accesses.
+fn traversal_address(expr: &Expression) -> Option<String> {
+    match expr {
+        Expression::Variable(variable) => Some(variable.as_str().to_string()),
+        Expression::Traversal(traversal) => {
+            let mut address = traversal_address(&traversal.expr)?;
+            for operator in &traversal.operators {
+                match operator {
+                    TraversalOperator::GetAttr(name) => {
+                        address.push('.');
+                        address.push_str(name.as_str());
+                    }
+                    _ => return None,
+                }
+            }
+            Some(address)
+        }
+        _ => None,
+    }
+}
+
 /// Get a string attribute from a body.
 fn get_string_attribute(body: &Body, key: &str) -> Option<String> {
     body.attributes()
@@ -544,6 +921,7 @@ fn parse_content_regex(
     content: &str,
     file_path: &Path,
     repository: Option<&str>,
+    registry_hosts: &[String],
 ) -> Result<ParsedHcl> {
     let mut result = ParsedHcl {
         modules: Vec::new(),
@@ -551,6 +929,8 @@ fn parse_content_regex(
         runtimes: Vec::new(),
         files: vec![file_path.to_path_buf()],
         warnings: Vec::new(),
+        suppressions: Vec::new(),
+        locks: Vec::new(),
     };
 
     // extract module blocks: module "name" { ... source = "..." ... version = "..." ... }
@@ -561,7 +941,17 @@ fn parse_content_regex(
     for cap in module_re.captures_iter(content) {
         let name = cap[1].to_string();
         let source_str = cap[2].to_string();
-        let source = super::parse_module_source(&source_str)?;
+        let source = super::parse_module_source_with_registries(&source_str, registry_hosts)
+            .unwrap_or_else(|e| {
+                result.warnings.push(ScanWarning {
+                    code: "invalid-module-source".to_string(),
+                    message: format!("Module '{name}' has a malformed source: {e}"),
+                    file: file_path.to_path_buf(),
+                    line: None,
+                    repository: repository.map(String::from),
+                });
+                ModuleSource::Unknown(source_str.clone())
+            });
 
         let version_constraint = cap.get(3).and_then(|m| Constraint::parse(m.as_str()).ok());
 
@@ -573,6 +963,7 @@ fn parse_content_regex(
             line_number: 0,
             repository: repository.map(String::from),
             attributes: std::collections::HashMap::new(),
+            dependencies: Default::default(),
         });
     }
 
@@ -615,6 +1006,8 @@ fn parse_content_regex(
         }
     }
 
+    attach_source_info(&mut result, content, file_path, repository);
+
     tracing::info!(
         file = %file_path.display(),
         modules = result.modules.len(),
@@ -629,7 +1022,6 @@ fn parse_content_regex(
 mod tests {
 
     use super::*;
-    use crate::types::ModuleSource;
     use crate::VersionRange;
 
     fn create_test_parser() -> HclParser {
@@ -678,6 +1070,26 @@ module "example" {
         assert!(matches!(module.source, ModuleSource::Git { .. }));
     }
 
+    #[test]
+    fn test_parse_malformed_module_source_warns() {
+        let parser = create_test_parser();
+        let content = r#"
+module "broken" {
+  source = "foo::https://example.com/module.zip"
+}
+"#;
+
+        let result = parser
+            .parse_content(content, Path::new("test.tf"), None)
+            .unwrap();
+
+        assert_eq!(result.modules.len(), 1);
+        assert!(matches!(result.modules[0].source, ModuleSource::Unknown(_)));
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].code, "invalid-module-source");
+        assert!(result.warnings[0].message.contains("broken"));
+    }
+
     #[test]
     fn test_parse_local_module() {
         let parser = create_test_parser();
@@ -696,6 +1108,38 @@ module "local" {
         assert!(matches!(module.source, ModuleSource::Local { .. }));
     }
 
+    #[test]
+    fn test_parse_module_dependencies() {
+        let parser = create_test_parser();
+        let content = r#"
+module "eks" {
+  source     = "terraform-aws-modules/eks/aws"
+  vpc_id     = module.vpc.vpc_id
+  subnet_ids = concat(module.vpc.private_subnets, [for s in module.extra.ids : s])
+  name       = "cluster-${module.naming.suffix}"
+  depends_on = [module.iam, aws_iam_role.node]
+  providers = {
+    aws      = aws.east
+    aws.peer = aws
+  }
+}
+"#;
+
+        let result = parser
+            .parse_content(content, Path::new("test.tf"), None)
+            .unwrap();
+
+        let dependencies = &result.modules[0].dependencies;
+        assert_eq!(dependencies.references, vec!["extra", "naming", "vpc"]);
+        assert_eq!(dependencies.depends_on, vec!["iam"]);
+        assert_eq!(dependencies.providers["aws"], "aws.east");
+        assert_eq!(dependencies.providers["aws.peer"], "aws");
+        assert_eq!(
+            dependencies.modules(),
+            vec!["extra", "iam", "naming", "vpc"]
+        );
+    }
+
     #[test]
     fn test_parse_required_providers() {
         let parser = create_test_parser();
@@ -789,6 +1233,39 @@ module "no_version" {
         assert!(result.providers.is_empty());
     }
 
+    #[test]
+    fn test_parse_line_numbers_and_suppressions() {
+        let parser = create_test_parser();
+        let content = r#"
+terraform {
+  required_version = ">= 1.5"
+  required_providers {
+    aws = { source = "hashicorp/aws", version = "5.0.0" } # monphare:ignore exact-version
+  }
+}
+
+# monphare:ignore exact-version reason="pinned for FIPS"
+module "vpc" {
+  source  = "terraform-aws-modules/vpc/aws"
+  version = "5.1.2"
+}
+"#;
+
+        let result = parser
+            .parse_content(content, Path::new("test.tf"), None)
+            .unwrap();
+
+        assert_eq!(result.runtimes[0].line_number, 3);
+        assert_eq!(result.providers[0].line_number, 5);
+        assert_eq!(result.modules[0].line_number, 10);
+
+        assert_eq!(result.suppressions.len(), 2);
+        assert_eq!(
+            result.suppressions[1].reason.as_deref(),
+            Some("pinned for FIPS")
+        );
+    }
+
     #[test]
     fn test_is_terraform_file() {
         let parser = create_test_parser();
@@ -809,6 +1286,7 @@ module "no_version" {
         assert!(parser.should_skip(Path::new(".terragrunt-cache")));
         assert!(!parser.should_skip(Path::new("modules")));
         assert!(!parser.should_skip(Path::new("main.tf")));
+        assert!(!parser.should_skip(Path::new(".terraform.lock.hcl")));
     }
 
     #[test]
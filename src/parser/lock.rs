@@ -0,0 +1,122 @@
+//! Dependency lock file parser.
+//!
+//! `terraform init` records the provider versions it selected in
+//! `.terraform.lock.hcl`. Lock files only cover providers; module versions
+//! are never locked.
+
+use crate::error::Result;
+use crate::types::ProviderLock;
+
+use hcl::Body;
+use std::path::Path;
+
+/// Name of the dependency lock file Terraform and OpenTofu write.
+pub const LOCK_FILE: &str = ".terraform.lock.hcl";
+
+/// Check if a path is a dependency lock file.
+#[must_use]
+pub fn is_lock_file(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == LOCK_FILE)
+}
+
+/// Parse the `provider` blocks of a dependency lock file.
+///
+/// Blocks without a `version` are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the content is not valid HCL.
+pub fn parse_lock_file(
+    content: &str,
+    file_path: &Path,
+    repository: Option<&str>,
+) -> Result<Vec<ProviderLock>> {
+    let body: Body = hcl::from_str(content).map_err(|e| {
+        crate::err!(HclParse {
+            file: file_path.to_path_buf(),
+            message: format!("Invalid dependency lock file: {e}"),
+            line: None,
+            column: None,
+        })
+    })?;
+
+    let mut locks = Vec::new();
+    for block in body.blocks() {
+        if block.identifier.as_str() != "provider" {
+            continue;
+        }
+        let Some(source) = block.labels.first().map(|l| l.as_str().to_string()) else {
+            continue;
+        };
+        let string_attribute = |key: &str| {
+            block
+                .body
+                .attributes()
+                .find(|attr| attr.key.as_str() == key)
+                .and_then(|attr| match &attr.expr {
+                    hcl::Expression::String(s) => Some(s.clone()),
+                    _ => None,
+                })
+        };
+        let Some(version) = string_attribute("version") else {
+            tracing::debug!(file = %file_path.display(), provider = %source, "Lock entry has no version, skipping");
+            continue;
+        };
+
+        locks.push(ProviderLock {
+            line_number: super::span::block_line(content, "provider", &source).unwrap_or(0),
+            source,
+            version,
+            constraints: string_attribute("constraints"),
+            file_path: file_path.to_path_buf(),
+            repository: repository.map(String::from),
+        });
+    }
+
+    Ok(locks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_parse_lock_file() {
+        let content = r#"
+# This file is maintained automatically by "terraform init".
+
+provider "registry.terraform.io/hashicorp/aws" {
+  version     = "5.31.0"
+  constraints = ">= 4.0, < 6.0"
+  hashes = [
+    "h1:abc=",
+  ]
+}
+
+provider "registry.terraform.io/hashicorp/random" {
+  version = "3.6.0"
+}
+"#;
+        let path = PathBuf::from("envs/prod/.terraform.lock.hcl");
+        let locks = parse_lock_file(content, &path, Some("infra")).unwrap();
+
+        assert_eq!(locks.len(), 2);
+        assert_eq!(locks[0].source, "registry.terraform.io/hashicorp/aws");
+        assert_eq!(locks[0].version, "5.31.0");
+        assert_eq!(locks[0].constraints.as_deref(), Some(">= 4.0, < 6.0"));
+        assert_eq!(locks[0].line_number, 4);
+        assert_eq!(locks[0].repository.as_deref(), Some("infra"));
+        assert_eq!(locks[1].constraints, None);
+        assert!(locks[0].locks("hashicorp/aws"));
+        assert!(!locks[1].locks("hashicorp/aws"));
+        assert!(is_lock_file(&path));
+        assert!(!is_lock_file(&PathBuf::from("main.tf")));
+    }
+
+    #[test]
+    fn test_parse_invalid_lock_file() {
+        let path = PathBuf::from(".terraform.lock.hcl");
+        assert!(parse_lock_file("provider \"x\" {", &path, None).is_err());
+    }
+}
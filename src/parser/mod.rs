@@ -8,6 +8,8 @@
 //! - `module` blocks with source and version attributes
 //! - `terraform.required_providers` blocks
 //! - `terraform.required_version` constraints
+//! - `provider` entries of `.terraform.lock.hcl` dependency lock files
+//! - `# monphare:ignore` suppression comments
 //!
 //! # Example
 //!
@@ -27,10 +29,14 @@
 //! ```
 
 mod hcl;
+mod lock;
 mod source;
+mod span;
+mod suppression;
 
-pub use hcl::HclParser;
-pub use source::parse_module_source;
+pub use hcl::{HclParser, LocalModuleCalls};
+pub use lock::{is_lock_file, parse_lock_file, LOCK_FILE};
+pub use source::{parse_module_source, parse_module_source_with_registries};
 
 use crate::types::ParsedHcl;
 
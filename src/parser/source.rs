@@ -1,65 +1,66 @@
 //! Module source parsing utilities.
 //!
 //! This module handles parsing of Terraform module source strings into
-//! structured `ModuleSource` types.
+//! structured `ModuleSource` types, following the go-getter grammar used by
+//! Terraform and OpenTofu.
 //!
 //! # Supported Source Types
 //!
 //! - **Registry**: `namespace/name/provider` or `hostname/namespace/name/provider`
-//! - **Git**: `git::https://...` or `git@github.com:...`
+//! - **Git**: `git::https://...`, `git@github.com:...`, `github.com/...`, `bitbucket.org/...`
+//! - **Mercurial**: `hg::https://...`
 //! - **HTTP**: `https://...` (archive downloads)
-//! - **S3**: `s3::https://...` or `s3://bucket/key`
-//! - **GCS**: `gcs::https://...`
+//! - **S3**: `s3::https://...`, `bucket.s3.amazonaws.com/...` or `s3://bucket/key`
+//! - **GCS**: `gcs::https://...` or `www.googleapis.com/storage/...`
+//! - **OCI**: `oci://registry/repository?tag=...` or `?digest=...`
 //! - **Local**: `./path` or `../path`
+//!
+//! Every remote form accepts a `//subdir` suffix and query arguments, either
+//! `url//subdir?query` or `url?query//subdir`. Query arguments are kept in
+//! the source's `params` (see [`ModuleSource`]).
 
 use crate::error::Result;
 use crate::types::ModuleSource;
 use regex::Regex;
+use std::collections::BTreeMap;
 use std::sync::LazyLock;
 
 /// Default Terraform registry hostname.
 const DEFAULT_REGISTRY: &str = "registry.terraform.io";
 
-// Regex patterns for parsing sources
-static REGISTRY_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
-    // Matches: namespace/name/provider or hostname/namespace/name/provider
-    Regex::new(r"^(?:([a-zA-Z0-9.-]+)/)?([a-zA-Z0-9_-]+)/([a-zA-Z0-9_-]+)/([a-zA-Z0-9_-]+)$")
-        .expect("Invalid regex")
-});
+/// Registry hosts recognised in URL form without configuration.
+const KNOWN_REGISTRY_HOSTS: &[&str] = &[
+    DEFAULT_REGISTRY,
+    "app.terraform.io",
+    "registry.opentofu.org",
+];
 
-static GIT_URL_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
-    // Matches git:: prefix URLs with optional ref and subdir
-    // git::https://github.com/example/module.git?ref=v1.0.0//modules/vpc
-    // Capture groups: 1=url, 2=ref, 3=subdir
-    Regex::new(r"^git::(.+?\.git)(?:\?ref=([^/]+))?(?://(.+))?$").expect("Invalid regex")
-});
-
-static GIT_SSH_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
-    // Matches git@host:path format
-    Regex::new(r"^git@([^:]+):(.+?)(?:\.git)?(?:\?ref=([^/]+))?(?://(.+))?$")
-        .expect("Invalid regex")
-});
+/// Forced getters (`getter::url`) supported by go-getter.
+const FORCED_GETTERS: &[&str] = &["git", "hg", "s3", "gcs", "http", "https"];
 
-static GITHUB_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
-    // Matches github.com URLs without git:: prefix
+// Regex patterns for parsing sources
+static REGISTRY_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    // Matches: namespace/name/provider or hostname[:port]/namespace/name/provider
     Regex::new(
-        r"^(?:https?://)?github\.com/([^/]+)/([^/?]+)(?:\.git)?(?:\?ref=([^/]+))?(?://(.+))?$",
+        r"^(?:([a-zA-Z0-9.-]+(?::[0-9]+)?)/)?([a-zA-Z0-9_-]+)/([a-zA-Z0-9_-]+)/([a-zA-Z0-9_-]+)$",
     )
     .expect("Invalid regex")
 });
 
-static S3_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
-    // Matches s3:: prefix or s3:// URLs
-    Regex::new(
-        r"^s3::https://s3(?:-([a-z0-9-]+))?\.amazonaws\.com/([^/]+)/(.+)$|^s3://([^/]+)/(.+)$",
-    )
-    .expect("Invalid regex")
+static FORCED_GETTER_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    // Matches: getter::rest (e.g., git::https://..., s3::https://...)
+    Regex::new(r"^([A-Za-z0-9]+)::(.+)$").expect("Invalid regex")
+});
+
+static SCP_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    // Matches SCP-style git addresses: user@host:path
+    Regex::new(r"^([A-Za-z0-9_.-]+)@([^:/]+):(.+)$").expect("Invalid regex")
 });
 
-static GCS_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
-    // Matches gcs:: prefix URLs
-    Regex::new(r"^gcs::https://(?:www\.)?googleapis\.com/storage/v1/([^/]+)/(.+)$")
-        .expect("Invalid regex")
+static S3_HOST_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    // Matches S3 endpoints, virtual-hosted (bucket.s3...) or path style (s3...)
+    // Capture groups: 1=bucket, 2=region
+    Regex::new(r"^(?:(.+)\.)?s3(?:[.-]([a-z0-9-]+))?\.amazonaws\.com$").expect("Invalid regex")
 });
 
 /// Parse a module source string into a structured `ModuleSource`.
@@ -81,13 +82,51 @@ static GCS_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
 /// // Local source
 /// let source = parse_module_source("../modules/vpc").unwrap();
 /// assert!(matches!(source, ModuleSource::Local { .. }));
+///
+/// // Malformed sources are errors
+/// assert!(parse_module_source("foo::https://example.com").is_err());
 /// ```
 ///
 /// # Errors
 ///
-/// Returns an error if the source string cannot be parsed.
+/// Returns an error if the source string is malformed or not a source
+/// format Terraform understands.
 pub fn parse_module_source(source: &str) -> Result<ModuleSource> {
+    parse_module_source_with_registries(source, &[])
+}
+
+/// Parse a module source string, treating URLs on `registry_hosts` as
+/// registry sources.
+///
+/// `https://` URLs are registry sources only when their host is a known
+/// public registry or one of `registry_hosts` (e.g., a private registry or
+/// Terraform Enterprise); any other URL is an HTTP archive.
+///
+/// ```rust
+/// use monphare::parser::parse_module_source_with_registries;
+/// use monphare::types::ModuleSource;
+///
+/// let hosts = vec!["tf.internal.example.com".to_string()];
+/// let source = parse_module_source_with_registries(
+///     "https://tf.internal.example.com/acme/vpc/aws",
+///     &hosts,
+/// )
+/// .unwrap();
+/// assert!(matches!(source, ModuleSource::Registry { .. }));
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the source string is malformed or not a source
+/// format Terraform understands.
+pub fn parse_module_source_with_registries(
+    source: &str,
+    registry_hosts: &[String],
+) -> Result<ModuleSource> {
     let source = source.trim();
+    if source.is_empty() {
+        return Err(malformed(source, "source is empty"));
+    }
 
     // Check for local paths first
     if is_local_path(source) {
@@ -96,30 +135,55 @@ pub fn parse_module_source(source: &str) -> Result<ModuleSource> {
         });
     }
 
-    // Check for Git sources
-    if let Some(git_source) = try_parse_git_source(source) {
-        return Ok(git_source);
+    // OCI artifacts use a URL scheme rather than a forced getter
+    if source.starts_with("oci://") {
+        return parse_oci_source(source);
     }
 
-    // Check for S3 sources
-    if let Some(s3_source) = try_parse_s3_source(source) {
-        return Ok(s3_source);
+    // Forced getters: git::, hg::, s3::, gcs::, http::
+    if let Some(caps) = FORCED_GETTER_PATTERN.captures(source) {
+        let getter = caps[1].to_ascii_lowercase();
+        let rest = caps.get(2).map_or("", |m| m.as_str());
+        return match getter.as_str() {
+            "git" => parse_git_source(source, rest),
+            "hg" => parse_hg_source(source, rest),
+            "s3" => parse_s3_source(source, rest),
+            "gcs" => parse_gcs_source(source, rest),
+            "http" | "https" => parse_http_source(source, rest, &[]),
+            other => Err(malformed(
+                source,
+                &format!(
+                    "unsupported getter '{other}::' (expected one of {})",
+                    FORCED_GETTERS.join(", ")
+                ),
+            )),
+        };
     }
 
-    // Check for GCS sources
-    if let Some(gcs_source) = try_parse_gcs_source(source) {
-        return Ok(gcs_source);
+    // Detectors: forms whose getter is implied by the address
+    if SCP_PATTERN.is_match(source) {
+        return parse_git_source(source, source);
+    }
+    if let Some(git_source) = try_parse_hosted_git(source) {
+        return git_source;
+    }
+    if source.starts_with("s3://") {
+        return parse_s3_source(source, source);
+    }
+    if !source.contains("://") {
+        let host = source.split(['/', '?']).next().unwrap_or_default();
+        if S3_HOST_PATTERN.is_match(host) {
+            return parse_s3_source(source, &format!("https://{source}"));
+        }
+        if host == "www.googleapis.com" {
+            return parse_gcs_source(source, &format!("https://{source}"));
+        }
     }
 
     // Check for HTTP sources
     if source.starts_with("http://") || source.starts_with("https://") {
         // Could be a registry with custom hostname or HTTP archive
-        if let Some(registry_source) = try_parse_registry_url(source) {
-            return Ok(registry_source);
-        }
-        return Ok(ModuleSource::Http {
-            url: source.to_string(),
-        });
+        return parse_http_source(source, source, registry_hosts);
     }
 
     // Try registry format (namespace/name/provider)
@@ -127,9 +191,7 @@ pub fn parse_module_source(source: &str) -> Result<ModuleSource> {
         return Ok(registry_source);
     }
 
-    // Unknown source format
-    tracing::warn!(source = %source, "Unknown module source format");
-    Ok(ModuleSource::Unknown(source.to_string()))
+    Err(malformed(source, "not a recognised module source format"))
 }
 
 /// Check if a path is a local file path.
@@ -138,7 +200,65 @@ fn is_local_path(source: &str) -> bool {
         || source.starts_with("../")
         || source.starts_with('/')
         || source.starts_with('~')
-        || (source.len() >= 2 && source.chars().nth(1) == Some(':')) // Windows paths
+        || (source.len() >= 2 && source.chars().nth(1) == Some(':') && !source.contains("::"))
+    // Windows paths
+}
+
+/// Build a `ModuleSourceParse` error.
+fn malformed(source: &str, message: &str) -> crate::MonPhareError {
+    crate::err!(ModuleSourceParse {
+        module_source: source.to_string(),
+        message: message.to_string(),
+    })
+}
+
+/// A source address split into its go-getter parts.
+struct SourceParts<'a> {
+    /// Address without subdirectory and query
+    base: &'a str,
+    subdir: Option<String>,
+    params: BTreeMap<String, String>,
+}
+
+/// Split `base//subdir?query` (or `base?query//subdir`) into parts.
+///
+/// The subdirectory separator is the first `//` after the scheme's `://`.
+fn split_source(address: &str) -> SourceParts<'_> {
+    let scheme_end = address.find("://").map_or(0, |i| i + 3);
+    let query_start = address.find('?');
+    let subdir_start = address[scheme_end..].find("//").map(|i| i + scheme_end);
+
+    let (base, subdir, query) = match (subdir_start, query_start) {
+        // base//subdir?query
+        (Some(d), Some(q)) if d < q => (
+            &address[..d],
+            Some(&address[d + 2..q]),
+            Some(&address[q + 1..]),
+        ),
+        // base?query//subdir
+        (Some(d), Some(q)) => (
+            &address[..q],
+            Some(&address[d + 2..]),
+            Some(&address[q + 1..d]),
+        ),
+        (Some(d), None) => (&address[..d], Some(&address[d + 2..]), None),
+        (None, Some(q)) => (&address[..q], None, Some(&address[q + 1..])),
+        (None, None) => (address, None, None),
+    };
+
+    let params = query
+        .map(|q| {
+            url::form_urlencoded::parse(q.as_bytes())
+                .map(|(k, v)| (k.into_owned(), v.into_owned()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    SourceParts {
+        base,
+        subdir: subdir.filter(|s| !s.is_empty()).map(str::to_string),
+        params,
+    }
 }
 
 /// Normalize a Git repository URL into a canonical `host/path` identifier.
@@ -161,143 +281,296 @@ fn canonical_git_host_from_url(url: &str) -> Option<String> {
     }
 }
 
-/// Try to parse a Git source.
-fn try_parse_git_source(source: &str) -> Option<ModuleSource> {
-    // git:: prefix
-    if let Some(caps) = GIT_URL_PATTERN.captures(source) {
-        let url = caps.get(1)?.as_str().to_string();
-        let ref_ = caps.get(2).map(|m| m.as_str().to_string());
-        let subdir = caps.get(3).map(|m| m.as_str().to_string());
-
-        let host = canonical_git_host_from_url(&url).unwrap_or_else(|| url.clone());
-        return Some(ModuleSource::Git {
-            host,
-            url,
-            ref_,
-            subdir,
-        });
-    }
+/// Parse a Git source from its address (without the `git::` prefix).
+fn parse_git_source(source: &str, address: &str) -> Result<ModuleSource> {
+    let SourceParts {
+        base,
+        subdir,
+        mut params,
+    } = split_source(address);
+    let ref_ = params.remove("ref");
 
     // git@host:path format
-    if let Some(caps) = GIT_SSH_PATTERN.captures(source) {
-        let host = caps.get(1)?.as_str();
-        let path = caps.get(2)?.as_str();
-
-        tracing::debug!(host = host, path = path, "Parsed Git SSH source");
-        let url = format!("ssh://git@{host}/{path}");
-        let ref_ = caps.get(3).map(|m| m.as_str().to_string());
-        let subdir = caps.get(4).map(|m| m.as_str().to_string());
-
-        tracing::debug!(
-            url = &url,
-            ref_ = &ref_,
-            subdir = subdir.as_deref().unwrap_or_default(),
-            "Parsed Git SSH source"
-        );
-        let canonical_host = format!(
-            "{}/{}",
-            host,
-            path.trim_start_matches('/').trim_end_matches(".git")
-        );
-        return Some(ModuleSource::Git {
-            host: canonical_host,
+    if let Some(caps) = SCP_PATTERN.captures(base) {
+        let user = &caps[1];
+        let host = &caps[2];
+        let path = caps[3].trim_start_matches('/');
+        let path = path.strip_suffix(".git").unwrap_or(path);
+        if path.is_empty() {
+            return Err(malformed(source, "git address has no repository path"));
+        }
+
+        let url = format!("ssh://{user}@{host}/{path}");
+        tracing::debug!(url = %url, ref_ = ?ref_, subdir = ?subdir, "Parsed Git SSH source");
+        return Ok(ModuleSource::Git {
+            host: format!("{host}/{path}"),
             url,
             ref_,
             subdir,
+            params,
         });
     }
 
-    // GitHub shorthand
-    if let Some(caps) = GITHUB_PATTERN.captures(source) {
-        let owner = caps.get(1)?.as_str();
-        let repo = caps.get(2)?.as_str();
-        let url = format!("https://github.com/{owner}/{repo}.git");
-        let ref_ = caps.get(3).map(|m| m.as_str().to_string());
-        let subdir = caps.get(4).map(|m| m.as_str().to_string());
-        return Some(ModuleSource::Git {
-            host: format!("github.com/{owner}/{repo}"),
-            url,
-            ref_,
-            subdir,
-        });
+    let host = url::Url::parse(base)
+        .ok()
+        .filter(|url| url.has_host())
+        .and_then(|_| canonical_git_host_from_url(base))
+        .ok_or_else(|| malformed(source, "invalid git repository URL"))?;
+    Ok(ModuleSource::Git {
+        host,
+        url: base.to_string(),
+        ref_,
+        subdir,
+        params,
+    })
+}
+
+/// Parse GitHub and Bitbucket shorthands (`github.com/owner/repo[/subdir]`).
+///
+/// Returns `None` if the source is not a shorthand.
+fn try_parse_hosted_git(source: &str) -> Option<Result<ModuleSource>> {
+    // A scheme is only accepted for the bare repository URL
+    let (shorthand, has_scheme) = match source
+        .strip_prefix("https://")
+        .or_else(|| source.strip_prefix("http://"))
+    {
+        Some(rest) => (rest, true),
+        None => (source, false),
+    };
+    let host = ["github.com", "bitbucket.org"]
+        .into_iter()
+        .find(|host| shorthand.starts_with(&format!("{host}/")))?;
+
+    let SourceParts {
+        base,
+        subdir,
+        mut params,
+    } = split_source(shorthand);
+    let segments: Vec<&str> = base.split('/').filter(|s| !s.is_empty()).collect();
+    if has_scheme && segments.len() != 3 {
+        return None;
+    }
+    if segments.len() < 3 {
+        return Some(Err(malformed(
+            source,
+            &format!("{host} sources need an owner and a repository"),
+        )));
     }
 
-    None
+    let owner = segments[1];
+    let repo = segments[2].strip_suffix(".git").unwrap_or(segments[2]);
+    // Extra path segments are a subdirectory (go-getter detector behaviour)
+    let subdir = match (segments.len() > 3, subdir) {
+        (true, Some(s)) => Some(format!("{}/{s}", segments[3..].join("/"))),
+        (true, None) => Some(segments[3..].join("/")),
+        (false, s) => s,
+    };
+
+    Some(Ok(ModuleSource::Git {
+        host: format!("{host}/{owner}/{repo}"),
+        url: format!("https://{host}/{owner}/{repo}.git"),
+        ref_: params.remove("ref"),
+        subdir,
+        params,
+    }))
 }
 
-/// Try to parse an S3 source.
-fn try_parse_s3_source(source: &str) -> Option<ModuleSource> {
-    if let Some(caps) = S3_PATTERN.captures(source) {
-        // s3:: format
-        if let (Some(bucket), Some(key)) = (caps.get(2), caps.get(3)) {
-            let region = caps.get(1).map(|m| m.as_str().to_string());
-            return Some(ModuleSource::S3 {
-                bucket: bucket.as_str().to_string(),
-                key: key.as_str().to_string(),
-                region,
-            });
-        }
-        // s3:// format
-        if let (Some(bucket), Some(key)) = (caps.get(4), caps.get(5)) {
-            return Some(ModuleSource::S3 {
-                bucket: bucket.as_str().to_string(),
-                key: key.as_str().to_string(),
-                region: None,
-            });
-        }
+/// Parse a Mercurial source from its address (without the `hg::` prefix).
+fn parse_hg_source(source: &str, address: &str) -> Result<ModuleSource> {
+    let SourceParts {
+        base,
+        subdir,
+        mut params,
+    } = split_source(address);
+    if !url::Url::parse(base).is_ok_and(|url| url.has_host() || url.scheme() == "file") {
+        return Err(malformed(source, "invalid Mercurial repository URL"));
     }
-    None
+
+    Ok(ModuleSource::Hg {
+        url: base.to_string(),
+        rev: params.remove("rev"),
+        subdir,
+        params,
+    })
 }
 
-/// Try to parse a GCS source.
-fn try_parse_gcs_source(source: &str) -> Option<ModuleSource> {
-    if let Some(caps) = GCS_PATTERN.captures(source) {
-        let bucket = caps.get(1)?.as_str().to_string();
-        let path = caps.get(2)?.as_str().to_string();
-        return Some(ModuleSource::Gcs { bucket, path });
+/// Parse an HTTP source, or a registry URL on a registry host.
+fn parse_http_source(
+    source: &str,
+    address: &str,
+    registry_hosts: &[String],
+) -> Result<ModuleSource> {
+    let SourceParts {
+        base,
+        subdir,
+        params,
+    } = split_source(address);
+    let valid = url::Url::parse(base)
+        .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.has_host());
+    if !valid {
+        return Err(malformed(source, "invalid HTTP URL"));
     }
-    None
+
+    if let Some(registry_source) =
+        try_parse_registry_url(base, subdir.as_deref(), &params, registry_hosts)
+    {
+        return Ok(registry_source);
+    }
+
+    Ok(ModuleSource::Http {
+        url: base.to_string(),
+        subdir,
+        params,
+    })
+}
+
+/// Parse an S3 source from an `https://` endpoint URL or `s3://bucket/key`.
+fn parse_s3_source(source: &str, address: &str) -> Result<ModuleSource> {
+    let SourceParts {
+        base,
+        subdir,
+        params,
+    } = split_source(address);
+    let url = url::Url::parse(base).map_err(|_| malformed(source, "invalid S3 URL"))?;
+    let host = url.host_str().unwrap_or_default();
+    let path = url.path().trim_start_matches('/');
+
+    let (bucket, key, region) = if url.scheme() == "s3" {
+        (host.to_string(), path.to_string(), None)
+    } else {
+        let caps = S3_HOST_PATTERN
+            .captures(host)
+            .ok_or_else(|| malformed(source, "not an Amazon S3 endpoint"))?;
+        let region = caps.get(2).map(|m| m.as_str().to_string());
+        match caps.get(1) {
+            // Virtual-hosted style: bucket.s3.amazonaws.com/key
+            Some(bucket) => (bucket.as_str().to_string(), path.to_string(), region),
+            // Path style: s3.amazonaws.com/bucket/key
+            None => {
+                let (bucket, key) = path.split_once('/').unwrap_or((path, ""));
+                (bucket.to_string(), key.to_string(), region)
+            }
+        }
+    };
+    if bucket.is_empty() || key.is_empty() {
+        return Err(malformed(
+            source,
+            "S3 sources need a bucket and an object key",
+        ));
+    }
+
+    Ok(ModuleSource::S3 {
+        bucket,
+        key,
+        region,
+        subdir,
+        params,
+    })
+}
+
+/// Parse a GCS source (`https://www.googleapis.com/storage/v1/bucket/path`).
+fn parse_gcs_source(source: &str, address: &str) -> Result<ModuleSource> {
+    let SourceParts {
+        base,
+        subdir,
+        params,
+    } = split_source(address);
+    let url = url::Url::parse(base).map_err(|_| malformed(source, "invalid GCS URL"))?;
+    let object = url
+        .host_str()
+        .filter(|host| host.ends_with("googleapis.com"))
+        .and_then(|_| url.path().strip_prefix("/storage/v1/"))
+        .and_then(|rest| rest.split_once('/'))
+        .filter(|(bucket, path)| !bucket.is_empty() && !path.is_empty());
+    let Some((bucket, path)) = object else {
+        return Err(malformed(
+            source,
+            "GCS sources look like gcs::https://www.googleapis.com/storage/v1/BUCKET/PATH",
+        ));
+    };
+
+    Ok(ModuleSource::Gcs {
+        bucket: bucket.to_string(),
+        path: path.to_string(),
+        subdir,
+        params,
+    })
+}
+
+/// Parse an OCI artifact source.
+///
+/// Format: `oci://registry/repository[//subdir][?tag=...|?digest=...]`.
+fn parse_oci_source(source: &str) -> Result<ModuleSource> {
+    let SourceParts {
+        base,
+        subdir,
+        mut params,
+    } = split_source(source);
+    let location = base.trim_start_matches("oci://");
+    let (registry, repository) = location
+        .split_once('/')
+        .map(|(registry, repository)| (registry, repository.trim_end_matches('/')))
+        .filter(|(registry, repository)| !registry.is_empty() && !repository.is_empty())
+        .ok_or_else(|| malformed(source, "OCI sources need a registry and a repository"))?;
+
+    Ok(ModuleSource::Oci {
+        registry: registry.to_ascii_lowercase(),
+        repository: repository.to_string(),
+        tag: params.remove("tag"),
+        digest: params.remove("digest"),
+        subdir,
+        params,
+    })
 }
 
 /// Try to parse a Terraform Registry source.
 fn try_parse_registry_source(source: &str) -> Option<ModuleSource> {
-    if let Some(caps) = REGISTRY_PATTERN.captures(source) {
-        // Four-part: hostname/namespace/name/provider
-        // Three-part: namespace/name/provider (uses default registry)
-        let (hostname, namespace, name, provider) = if caps.get(1).is_some() {
-            (
-                caps.get(1)?.as_str().to_string(),
-                caps.get(2)?.as_str().to_string(),
-                caps.get(3)?.as_str().to_string(),
-                caps.get(4)?.as_str().to_string(),
-            )
-        } else {
-            (
-                DEFAULT_REGISTRY.to_string(),
-                caps.get(2)?.as_str().to_string(),
-                caps.get(3)?.as_str().to_string(),
-                caps.get(4)?.as_str().to_string(),
-            )
-        };
+    let SourceParts {
+        base,
+        subdir,
+        params,
+    } = split_source(source);
+    let caps = REGISTRY_PATTERN.captures(base)?;
+    // Four-part: hostname/namespace/name/provider
+    // Three-part: namespace/name/provider (uses default registry)
+    let hostname = caps
+        .get(1)
+        .map_or(DEFAULT_REGISTRY, |m| m.as_str())
+        .to_string();
 
-        return Some(ModuleSource::Registry {
-            hostname,
-            namespace,
-            name,
-            provider,
-        });
-    }
-    None
+    Some(ModuleSource::Registry {
+        hostname,
+        namespace: caps[2].to_string(),
+        name: caps[3].to_string(),
+        provider: caps[4].to_string(),
+        subdir,
+        params,
+    })
 }
 
 /// Try to parse a registry URL (e.g., https://registry.terraform.io/...).
-fn try_parse_registry_url(source: &str) -> Option<ModuleSource> {
-    // Parse URL and check if it looks like a registry
-    let url = url::Url::parse(source).ok()?;
-    let host = url.host_str()?;
+///
+/// `url` is the address without its `//subdir` and query, which are passed
+/// separately.
+fn try_parse_registry_url(
+    url: &str,
+    subdir: Option<&str>,
+    params: &BTreeMap<String, String>,
+    registry_hosts: &[String],
+) -> Option<ModuleSource> {
+    let url = url::Url::parse(url).ok()?;
+    let host = match url.port() {
+        Some(port) => format!("{}:{port}", url.host_str()?),
+        None => url.host_str()?.to_string(),
+    };
 
-    // Check if it's a known registry host
-    if host.contains("registry") || host == "app.terraform.io" {
+    let is_registry = KNOWN_REGISTRY_HOSTS
+        .iter()
+        .any(|known| host.eq_ignore_ascii_case(known))
+        || registry_hosts
+            .iter()
+            .any(|configured| host.eq_ignore_ascii_case(configured));
+    if is_registry {
         let path_segments: Vec<&str> = url.path().trim_matches('/').split('/').collect();
 
         // Expected format: /modules/namespace/name/provider
@@ -306,10 +579,12 @@ fn try_parse_registry_url(source: &str) -> Option<ModuleSource> {
             let offset = if path_segments[0] == "modules" { 1 } else { 0 };
             if path_segments.len() >= offset + 3 {
                 return Some(ModuleSource::Registry {
-                    hostname: host.to_string(),
+                    hostname: host,
                     namespace: path_segments[offset].to_string(),
                     name: path_segments[offset + 1].to_string(),
                     provider: path_segments[offset + 2].to_string(),
+                    subdir: subdir.map(str::to_string),
+                    params: params.clone(),
                 });
             }
         }
@@ -331,11 +606,15 @@ mod tests {
                 namespace,
                 name,
                 provider,
+                subdir,
+                params,
             } => {
                 assert_eq!(hostname, "registry.terraform.io");
                 assert_eq!(namespace, "hashicorp");
                 assert_eq!(name, "consul");
                 assert_eq!(provider, "aws");
+                assert!(subdir.is_none());
+                assert!(params.is_empty());
             }
             _ => panic!("Expected Registry source"),
         }
@@ -366,11 +645,13 @@ mod tests {
                 url,
                 ref_,
                 subdir,
+                params,
             } => {
                 assert_eq!(url, "https://github.com/example/module.git");
                 assert_eq!(host, "github.com/example/module");
                 assert!(ref_.is_none());
                 assert!(subdir.is_none());
+                assert!(params.is_empty());
             }
             _ => panic!("Expected Git source"),
         }
@@ -449,13 +730,122 @@ mod tests {
     fn test_parse_http_source() {
         let source = parse_module_source("https://example.com/module.zip").unwrap();
         match source {
-            ModuleSource::Http { url } => {
+            ModuleSource::Http { url, .. } => {
                 assert_eq!(url, "https://example.com/module.zip");
             }
             _ => panic!("Expected Http source"),
         }
     }
 
+    #[test]
+    fn test_parse_registry_url_only_on_registry_hosts() {
+        let url = "https://tf.internal.example.com/modules/acme/vpc/aws";
+        assert!(matches!(
+            parse_module_source(url).unwrap(),
+            ModuleSource::Http { .. }
+        ));
+        assert!(matches!(
+            parse_module_source("https://registry.example.com/acme/vpc/aws.zip").unwrap(),
+            ModuleSource::Http { .. }
+        ));
+
+        let hosts = vec!["TF.internal.example.com".to_string()];
+        match parse_module_source_with_registries(url, &hosts).unwrap() {
+            ModuleSource::Registry {
+                hostname,
+                namespace,
+                name,
+                provider,
+                ..
+            } => {
+                assert_eq!(hostname, "tf.internal.example.com");
+                assert_eq!(namespace, "acme");
+                assert_eq!(name, "vpc");
+                assert_eq!(provider, "aws");
+            }
+            other => panic!("Expected Registry source, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_registry_source_with_subdir() {
+        let registry = |hostname: &str, subdir: &str| ModuleSource::Registry {
+            hostname: hostname.to_string(),
+            namespace: "terraform-aws-modules".to_string(),
+            name: "vpc".to_string(),
+            provider: "aws".to_string(),
+            subdir: Some(subdir.to_string()),
+            params: BTreeMap::new(),
+        };
+
+        let source =
+            parse_module_source("terraform-aws-modules/vpc/aws//modules/vpc-endpoints").unwrap();
+        assert_eq!(
+            source,
+            registry("registry.terraform.io", "modules/vpc-endpoints")
+        );
+        assert_eq!(
+            source.canonical_id(),
+            "registry.terraform.io/terraform-aws-modules/vpc/aws//modules/vpc-endpoints"
+        );
+
+        let source =
+            parse_module_source("app.terraform.io/terraform-aws-modules/vpc/aws//sub").unwrap();
+        assert_eq!(source, registry("app.terraform.io", "sub"));
+
+        let source = parse_module_source(
+            "https://registry.terraform.io/modules/terraform-aws-modules/vpc/aws//modules/vpc-endpoints",
+        )
+        .unwrap();
+        assert_eq!(
+            source,
+            registry("registry.terraform.io", "modules/vpc-endpoints")
+        );
+    }
+
+    #[test]
+    fn test_parse_registry_source_with_port() {
+        let source = parse_module_source("tf.internal.example.com:8443/acme/vpc/aws").unwrap();
+        match source {
+            ModuleSource::Registry { hostname, .. } => {
+                assert_eq!(hostname, "tf.internal.example.com:8443");
+            }
+            other => panic!("Expected Registry source, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_oci_source() {
+        let source =
+            parse_module_source("oci://ghcr.io/acme/modules/vpc?tag=1.2.0//modules/subnets")
+                .unwrap();
+        assert_eq!(
+            source,
+            ModuleSource::Oci {
+                registry: "ghcr.io".to_string(),
+                repository: "acme/modules/vpc".to_string(),
+                tag: Some("1.2.0".to_string()),
+                digest: None,
+                subdir: Some("modules/subnets".to_string()),
+                params: BTreeMap::new(),
+            }
+        );
+        assert_eq!(
+            source.canonical_id(),
+            "oci://ghcr.io/acme/modules/vpc:1.2.0//modules/subnets"
+        );
+
+        let pinned =
+            parse_module_source("oci://registry.example.com:5000/vpc//sub?digest=sha256:abc123")
+                .unwrap();
+        assert_eq!(
+            pinned.canonical_id(),
+            "oci://registry.example.com:5000/vpc@sha256:abc123//sub"
+        );
+
+        assert!(parse_module_source("oci://ghcr.io").is_err());
+    }
+
     #[test]
     fn test_parse_s3_source() {
         let source = parse_module_source("s3://my-bucket/modules/vpc.zip").unwrap();
@@ -481,6 +871,186 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_bitbucket_shorthand_with_subdir() {
+        let source = parse_module_source("bitbucket.org/acme/network/modules/vpc?ref=v2").unwrap();
+        assert_eq!(
+            source,
+            ModuleSource::Git {
+                host: "bitbucket.org/acme/network".to_string(),
+                url: "https://bitbucket.org/acme/network.git".to_string(),
+                ref_: Some("v2".to_string()),
+                subdir: Some("modules/vpc".to_string()),
+                params: BTreeMap::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_git_keeps_query_params() {
+        let source = parse_module_source(
+            "git::ssh://git@example.com/infra/vpc.git//modules/vpc?ref=v1.2.0&depth=1&sshkey=a2V5",
+        )
+        .unwrap();
+        match source {
+            ModuleSource::Git {
+                host,
+                ref_,
+                subdir,
+                params,
+                ..
+            } => {
+                assert_eq!(host, "example.com/infra/vpc");
+                assert_eq!(ref_.as_deref(), Some("v1.2.0"));
+                assert_eq!(subdir.as_deref(), Some("modules/vpc"));
+                assert_eq!(params.get("depth").map(String::as_str), Some("1"));
+                assert_eq!(params.get("sshkey").map(String::as_str), Some("a2V5"));
+            }
+            other => panic!("Expected Git source, got {other:?}"),
+        }
+
+        // The query may also come before the subdirectory
+        let reordered =
+            parse_module_source("git::https://example.com/vpc.git?ref=v1&depth=1//modules/vpc")
+                .unwrap();
+        assert_eq!(
+            reordered,
+            parse_module_source("git::https://example.com/vpc.git//modules/vpc?depth=1&ref=v1")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_hg_source() {
+        let source =
+            parse_module_source("hg::https://hg.example.com/infra/vpc//modules?rev=stable")
+                .unwrap();
+        assert_eq!(
+            source,
+            ModuleSource::Hg {
+                url: "https://hg.example.com/infra/vpc".to_string(),
+                rev: Some("stable".to_string()),
+                subdir: Some("modules".to_string()),
+                params: BTreeMap::new(),
+            }
+        );
+        assert_eq!(
+            source.canonical_id(),
+            "hg::https://hg.example.com/infra/vpc?rev=stable//modules"
+        );
+    }
+
+    #[test]
+    fn test_parse_s3_forms() {
+        let source = parse_module_source(
+            "s3::https://s3-eu-west-1.amazonaws.com/my-bucket/vpc.zip?version=3HL4kqtJ",
+        )
+        .unwrap();
+        match &source {
+            ModuleSource::S3 {
+                bucket,
+                key,
+                region,
+                params,
+                ..
+            } => {
+                assert_eq!(bucket, "my-bucket");
+                assert_eq!(key, "vpc.zip");
+                assert_eq!(region.as_deref(), Some("eu-west-1"));
+                assert_eq!(params.get("version").map(String::as_str), Some("3HL4kqtJ"));
+            }
+            other => panic!("Expected S3 source, got {other:?}"),
+        }
+        assert_eq!(
+            source.canonical_id(),
+            "s3://my-bucket/vpc.zip?version=3HL4kqtJ"
+        );
+
+        let virtual_hosted = parse_module_source("my-bucket.s3.amazonaws.com/vpc.zip").unwrap();
+        assert_eq!(virtual_hosted.canonical_id(), "s3://my-bucket/vpc.zip");
+    }
+
+    #[test]
+    fn test_parse_gcs_source() {
+        let source =
+            parse_module_source("gcs::https://www.googleapis.com/storage/v1/modules/vpc.zip")
+                .unwrap();
+        assert_eq!(source.canonical_id(), "gcs://modules/vpc.zip");
+        assert_eq!(
+            parse_module_source("www.googleapis.com/storage/v1/modules/vpc.zip").unwrap(),
+            source
+        );
+    }
+
+    #[test]
+    fn test_parse_http_archive() {
+        let source =
+            parse_module_source("https://example.com/vpc-module?archive=zip//modules/vpc").unwrap();
+        assert_eq!(
+            source,
+            ModuleSource::Http {
+                url: "https://example.com/vpc-module".to_string(),
+                subdir: Some("modules/vpc".to_string()),
+                params: BTreeMap::from([("archive".to_string(), "zip".to_string())]),
+            }
+        );
+    }
+
+    #[test]
+    fn test_source_string_round_trip() {
+        let sources = [
+            "hashicorp/consul/aws",
+            "app.terraform.io/my-org/vpc/aws",
+            "terraform-aws-modules/vpc/aws//modules/vpc-endpoints",
+            "app.terraform.io/acme/vpc/aws//sub",
+            "https://app.terraform.io/acme/vpc/aws//sub",
+            "../modules/vpc",
+            "git::https://github.com/example/module.git?ref=v1.0.0//modules/vpc",
+            "git::ssh://git@example.com/infra/vpc.git?depth=1&sshkey=a2V5%2Bkey%3D",
+            "git@github.com:example/module.git?ref=main",
+            "github.com/example/module/modules/vpc",
+            "bitbucket.org/acme/network?ref=v2",
+            "hg::https://hg.example.com/infra/vpc?rev=stable",
+            "https://example.com/vpc-module?archive=tar.gz",
+            "s3::https://s3-eu-west-1.amazonaws.com/bucket/vpc.zip?version=3HL4kqtJ",
+            "s3://bucket/modules/vpc.zip",
+            "gcs::https://www.googleapis.com/storage/v1/bucket/vpc.zip//modules",
+            "oci://ghcr.io/acme/vpc?tag=1.2.0&digest=sha256:abc123",
+        ];
+
+        for source in sources {
+            let parsed = parse_module_source(source).unwrap();
+            let rendered = parsed.to_source_string();
+            assert_eq!(
+                parse_module_source(&rendered).unwrap(),
+                parsed,
+                "{source} rendered as {rendered}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_malformed_sources_are_errors() {
+        let sources = [
+            "",
+            "foo::https://example.com/module.zip",
+            "git::not a url",
+            "hg::example",
+            "s3::https://example.com/bucket/key",
+            "s3://bucket-only",
+            "gcs::https://www.googleapis.com/storage/v1/bucket",
+            "github.com/example",
+            "just-a-name",
+        ];
+
+        for source in sources {
+            assert!(
+                parse_module_source(source).is_err(),
+                "{source:?} should not parse"
+            );
+        }
+    }
+
     #[test]
     fn test_canonical_id_registry() {
         let source = ModuleSource::Registry {
@@ -488,6 +1058,8 @@ mod tests {
             namespace: "hashicorp".to_string(),
             name: "consul".to_string(),
             provider: "aws".to_string(),
+            subdir: None,
+            params: Default::default(),
         };
         assert_eq!(
             source.canonical_id(),
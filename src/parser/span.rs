@@ -0,0 +1,211 @@
+//! Source span helpers.
+//!
+//! `hcl-rs` does not expose line information for parsed structures, so this
+//! module recovers it from the raw file text. The helpers are deliberately
+//! lexical: they understand strings and comments well enough to match braces,
+//! but make no attempt to fully parse HCL.
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+static REQUIRED_PROVIDERS_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*required_providers[ \t]*\{").expect("Invalid regex"));
+
+/// Convert a byte offset into a 1-based line number.
+pub(crate) fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())]
+        .bytes()
+        .filter(|b| *b == b'\n')
+        .count()
+        + 1
+}
+
+/// Byte offset of the first character of a 1-based line.
+pub(crate) fn line_offset(content: &str, line: usize) -> usize {
+    if line <= 1 {
+        return 0;
+    }
+    content
+        .match_indices('\n')
+        .nth(line - 2)
+        .map_or(content.len(), |(idx, _)| idx + 1)
+}
+
+/// Find the byte offset of the `}` closing the `{` at `open`.
+///
+/// Strings, line comments and block comments are skipped. Returns the end
+/// of the content when the block is never closed.
+pub(crate) fn matching_brace(content: &str, open: usize) -> usize {
+    let bytes = content.as_bytes();
+    let mut depth = 0usize;
+    let mut i = open;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            b'#' => i = skip_to_eol(bytes, i),
+            b'/' if bytes.get(i + 1) == Some(&b'/') => i = skip_to_eol(bytes, i),
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i + 1 < bytes.len() && !(bytes[i] == b'*' && bytes[i + 1] == b'/') {
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'{' => depth += 1,
+            b'}' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    content.len()
+}
+
+/// Return the byte offset where a comment starts on a single line, if any.
+///
+/// Recognises `#` and `//` comments outside of string literals.
+pub(crate) fn comment_start(line: &str) -> Option<usize> {
+    let bytes = line.as_bytes();
+    let mut in_string = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if in_string => i += 1,
+            b'"' => in_string = !in_string,
+            b'#' if !in_string => return Some(i),
+            b'/' if !in_string && bytes.get(i + 1) == Some(&b'/') => return Some(i),
+            _ => {}
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Last line of the block or attribute starting on `line`.
+///
+/// If the line opens a `{` that is not closed on the same line, the span
+/// extends to the matching `}`. Otherwise the span is the line itself.
+pub(crate) fn span_end(content: &str, line: usize) -> usize {
+    let start = line_offset(content, line);
+    let text = content[start..].lines().next().unwrap_or_default();
+    let code = comment_start(text).map_or(text, |idx| &text[..idx]);
+
+    match code.find('{') {
+        Some(idx) => line_of(content, matching_brace(content, start + idx)),
+        None => line,
+    }
+}
+
+/// Line of the `module "<name>"` block header.
+pub(crate) fn module_line(content: &str, name: &str) -> Option<usize> {
+    block_line(content, "module", name)
+}
+
+/// Line of the `<identifier> "<label>"` block header.
+pub(crate) fn block_line(content: &str, identifier: &str, label: &str) -> Option<usize> {
+    let pattern = format!(
+        r#"(?m)^[ \t]*{}[ \t]+"{}"[ \t]*\{{"#,
+        regex::escape(identifier),
+        regex::escape(label)
+    );
+    let re = Regex::new(&pattern).ok()?;
+    re.find(content).map(|m| line_of(content, m.start()))
+}
+
+/// Line of the `<name> = ...` entry inside a `required_providers` block.
+pub(crate) fn required_provider_line(content: &str, name: &str) -> Option<usize> {
+    let entry = Regex::new(&format!(r"(?m)^[ \t]*{}[ \t]*=", regex::escape(name))).ok()?;
+
+    REQUIRED_PROVIDERS_PATTERN.find_iter(content).find_map(|m| {
+        let open = m.end() - 1;
+        let close = matching_brace(content, open);
+        entry
+            .find(&content[open..close])
+            .map(|e| line_of(content, open + e.start()))
+    })
+}
+
+/// Line of the first top-level-looking `<key> = ...` attribute.
+pub(crate) fn attribute_line(content: &str, key: &str) -> Option<usize> {
+    let re = Regex::new(&format!(r"(?m)^[ \t]*{}[ \t]*=", regex::escape(key))).ok()?;
+    re.find(content).map(|m| line_of(content, m.start()))
+}
+
+fn skip_to_eol(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() && bytes[i] != b'\n' {
+        i += 1;
+    }
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = r#"terraform {
+  required_version = ">= 1.5"
+
+  required_providers {
+    aws = {
+      source  = "hashicorp/aws" # "}" in a comment
+      version = "~> 5.0"
+    }
+    random = "~> 3.0"
+  }
+}
+
+module "vpc" {
+  source = "terraform-aws-modules/vpc/aws"
+  tags = { Name = "}" }
+}
+"#;
+
+    #[test]
+    fn test_module_line() {
+        assert_eq!(module_line(CONTENT, "vpc"), Some(13));
+        assert_eq!(module_line(CONTENT, "missing"), None);
+    }
+
+    #[test]
+    fn test_required_provider_line() {
+        assert_eq!(required_provider_line(CONTENT, "aws"), Some(5));
+        assert_eq!(required_provider_line(CONTENT, "random"), Some(9));
+        assert_eq!(required_provider_line(CONTENT, "google"), None);
+    }
+
+    #[test]
+    fn test_attribute_line() {
+        assert_eq!(attribute_line(CONTENT, "required_version"), Some(2));
+    }
+
+    #[test]
+    fn test_span_end_skips_strings_and_comments() {
+        assert_eq!(span_end(CONTENT, 1), 11);
+        assert_eq!(span_end(CONTENT, 5), 8);
+        assert_eq!(span_end(CONTENT, 9), 9);
+        assert_eq!(span_end(CONTENT, 13), 16);
+    }
+
+    #[test]
+    fn test_comment_start() {
+        assert_eq!(comment_start("  # note"), Some(2));
+        assert_eq!(comment_start(r##"a = "#x" // note"##), Some(9));
+        assert_eq!(comment_start(r#"a = "b""#), None);
+    }
+}
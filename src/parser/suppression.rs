@@ -0,0 +1,236 @@
+//! Inline suppression directives.
+//!
+//! Findings can be silenced from within Terraform files using comments:
+//!
+//! ```hcl
+//! # monphare:ignore-file no-upper-bound reason="legacy stack, frozen"
+//!
+//! # monphare:ignore exact-version reason="pinned for FIPS"
+//! module "vpc" {
+//!   source  = "terraform-aws-modules/vpc/aws"
+//!   version = "5.1.2"
+//! }
+//!
+//! terraform {
+//!   required_providers {
+//!     aws = { source = "hashicorp/aws", version = ">= 5.0" } # monphare:ignore no-upper-bound
+//!   }
+//! }
+//! ```
+//!
+//! A directive on its own line applies to the next block or attribute; a
+//! trailing directive applies to the block or attribute on the same line.
+//! Multiple codes may be separated by commas. Omitting the codes (or using
+//! `*`) silences every finding in the span.
+
+use super::span;
+use crate::types::{ScanWarning, Suppression, SuppressionScope};
+use regex::Regex;
+use std::path::Path;
+use std::sync::LazyLock;
+
+static DIRECTIVE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    // Capture groups: 1=kind ("ignore" or "ignore-file"), 2=arguments
+    Regex::new(r"^monphare:(ignore-file|ignore)(?:\s+(.*?))?\s*$").expect("Invalid regex")
+});
+
+static REASON_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"reason\s*=\s*"((?:[^"\\]|\\.)*)""#).expect("Invalid regex"));
+
+/// Extract all suppression directives from a file's contents.
+///
+/// Malformed directives are reported as warnings and otherwise ignored.
+pub(crate) fn parse_suppressions(
+    content: &str,
+    file_path: &Path,
+    repository: Option<&str>,
+) -> (Vec<Suppression>, Vec<ScanWarning>) {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut suppressions = Vec::new();
+    let mut warnings = Vec::new();
+
+    for (idx, line) in lines.iter().enumerate() {
+        let Some(comment_idx) = span::comment_start(line) else {
+            continue;
+        };
+        let comment = line[comment_idx..]
+            .trim_start_matches('#')
+            .trim_start_matches('/')
+            .trim();
+        let Some(caps) = DIRECTIVE_PATTERN.captures(comment) else {
+            continue;
+        };
+
+        let line_number = idx + 1;
+        let args = caps.get(2).map_or("", |m| m.as_str());
+        let (codes, reason) = match parse_arguments(args) {
+            Ok(parsed) => parsed,
+            Err(message) => {
+                tracing::warn!(
+                    file = %file_path.display(),
+                    line = line_number,
+                    "Ignoring malformed suppression: {message}"
+                );
+                warnings.push(ScanWarning {
+                    code: "invalid-suppression".to_string(),
+                    message: format!("Malformed suppression directive: {message}"),
+                    file: file_path.to_path_buf(),
+                    line: Some(line_number),
+                    repository: repository.map(String::from),
+                });
+                continue;
+            }
+        };
+
+        let scope = if &caps[1] == "ignore-file" {
+            SuppressionScope::File
+        } else {
+            let target = if line[..comment_idx].trim().is_empty() {
+                next_code_line(&lines, idx + 1).unwrap_or(line_number)
+            } else {
+                line_number
+            };
+            SuppressionScope::Lines {
+                start: target,
+                end: span::span_end(content, target),
+            }
+        };
+
+        tracing::debug!(
+            file = %file_path.display(),
+            line = line_number,
+            codes = ?codes,
+            "Found suppression directive"
+        );
+
+        suppressions.push(Suppression {
+            codes,
+            reason,
+            file: file_path.to_path_buf(),
+            line: line_number,
+            scope,
+            repository: repository.map(String::from),
+        });
+    }
+
+    (suppressions, warnings)
+}
+
+/// Split directive arguments into finding codes and an optional reason.
+fn parse_arguments(args: &str) -> std::result::Result<(Vec<String>, Option<String>), String> {
+    let reason = REASON_PATTERN
+        .captures(args)
+        .map(|c| c[1].replace("\\\"", "\"").replace("\\\\", "\\"));
+    let rest = REASON_PATTERN.replace(args, "");
+
+    if rest.contains("reason") || rest.contains('"') {
+        return Err(format!("could not parse arguments '{args}'"));
+    }
+
+    let codes = rest
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|c| !c.is_empty() && *c != "*")
+        .map(String::from)
+        .collect();
+
+    Ok((codes, reason))
+}
+
+/// Find the first line at or after `from` (0-based) that contains code.
+///
+/// Returns a 1-based line number.
+fn next_code_line(lines: &[&str], from: usize) -> Option<usize> {
+    lines
+        .iter()
+        .enumerate()
+        .skip(from)
+        .find(|(_, line)| {
+            let code = span::comment_start(line).map_or(**line, |idx| &line[..idx]);
+            !code.trim().is_empty()
+        })
+        .map(|(idx, _)| idx + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> (Vec<Suppression>, Vec<ScanWarning>) {
+        parse_suppressions(content, Path::new("main.tf"), Some("repo"))
+    }
+
+    #[test]
+    fn test_block_directive_above_module() {
+        let content = r#"
+# monphare:ignore exact-version reason="pinned for FIPS"
+# another comment
+module "vpc" {
+  source  = "terraform-aws-modules/vpc/aws"
+  version = "5.1.2"
+}
+"#;
+        let (suppressions, warnings) = parse(content);
+
+        assert!(warnings.is_empty());
+        assert_eq!(suppressions.len(), 1);
+        let s = &suppressions[0];
+        assert_eq!(s.codes, vec!["exact-version"]);
+        assert_eq!(s.reason.as_deref(), Some("pinned for FIPS"));
+        assert_eq!(s.line, 2);
+        assert_eq!(s.scope, SuppressionScope::Lines { start: 4, end: 7 });
+    }
+
+    #[test]
+    fn test_trailing_directive_on_provider_entry() {
+        let content = r#"terraform {
+  required_providers {
+    aws = { source = "hashicorp/aws", version = ">= 5.0" } // monphare:ignore no-upper-bound, exact-version
+  }
+}
+"#;
+        let (suppressions, _) = parse(content);
+
+        assert_eq!(suppressions.len(), 1);
+        assert_eq!(
+            suppressions[0].codes,
+            vec!["no-upper-bound", "exact-version"]
+        );
+        assert_eq!(suppressions[0].reason, None);
+        assert_eq!(
+            suppressions[0].scope,
+            SuppressionScope::Lines { start: 3, end: 3 }
+        );
+    }
+
+    #[test]
+    fn test_file_directive_without_codes() {
+        let (suppressions, _) = parse("# monphare:ignore-file reason=\"vendored\"\n");
+
+        assert_eq!(suppressions.len(), 1);
+        assert!(suppressions[0].codes.is_empty());
+        assert_eq!(suppressions[0].scope, SuppressionScope::File);
+        assert_eq!(suppressions[0].repository.as_deref(), Some("repo"));
+    }
+
+    #[test]
+    fn test_malformed_directive_is_reported() {
+        let (suppressions, warnings) = parse("# monphare:ignore exact-version reason=\"open\n");
+
+        assert!(suppressions.is_empty());
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].code, "invalid-suppression");
+        assert_eq!(warnings[0].line, Some(1));
+    }
+
+    #[test]
+    fn test_ignores_unrelated_comments() {
+        let content = r##"
+# monphare is great
+locals { x = "# monphare:ignore exact-version" }
+"##;
+        let (suppressions, warnings) = parse(content);
+
+        assert!(suppressions.is_empty());
+        assert!(warnings.is_empty());
+    }
+}
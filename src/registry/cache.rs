@@ -0,0 +1,132 @@
+//! On-disk cache of registry responses.
+
+use super::{PackageAddress, PackageVersions};
+use crate::error::{MonPhareError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Cached versions of one package.
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedPackage {
+    fetched_at: DateTime<Utc>,
+    #[serde(flatten)]
+    versions: PackageVersions,
+}
+
+/// On-disk cache of package versions, one JSON file per package.
+#[derive(Debug, Clone)]
+pub struct RegistryCache {
+    dir: PathBuf,
+    ttl: Duration,
+}
+
+impl RegistryCache {
+    /// Create a cache in `dir` whose entries are fresh for `ttl`.
+    #[must_use]
+    pub fn new(dir: PathBuf, ttl: Duration) -> Self {
+        Self { dir, ttl }
+    }
+
+    /// Cache directory.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Load the cached versions of a package.
+    ///
+    /// Entries older than the TTL are ignored unless `allow_stale` is set.
+    /// Unreadable entries are treated as missing.
+    #[must_use]
+    pub fn load(&self, address: &PackageAddress, allow_stale: bool) -> Option<PackageVersions> {
+        let path = self.path_for(address);
+        let content = std::fs::read_to_string(&path).ok()?;
+        let cached: CachedPackage = match serde_json::from_str(&content) {
+            Ok(cached) => cached,
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Ignoring corrupt registry cache entry");
+                return None;
+            }
+        };
+
+        let age = (Utc::now() - cached.fetched_at)
+            .to_std()
+            .unwrap_or_default();
+        if age > self.ttl && !allow_stale {
+            tracing::debug!(package = %address, "Registry cache entry expired");
+            return None;
+        }
+
+        tracing::debug!(package = %address, "Registry cache hit");
+        Some(cached.versions)
+    }
+
+    /// Store the versions of a package.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory or file cannot be written.
+    pub fn save(&self, address: &PackageAddress, versions: &PackageVersions) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)
+            .map_err(|e| MonPhareError::io(&self.dir, e, file!(), line!()))?;
+
+        let cached = CachedPackage {
+            fetched_at: Utc::now(),
+            versions: versions.clone(),
+        };
+        let content = serde_json::to_string_pretty(&cached).map_err(|e| {
+            crate::err!(Internal {
+                message: format!("Failed to serialize registry cache entry: {e}"),
+            })
+        })?;
+
+        let path = self.path_for(address);
+        std::fs::write(&path, content).map_err(|e| MonPhareError::io(&path, e, file!(), line!()))
+    }
+
+    fn path_for(&self, address: &PackageAddress) -> PathBuf {
+        let name: String = format!("{}-{}", address.kind, address)
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.dir.join(format!("{name}.json"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::PackageKind;
+
+    #[test]
+    fn test_round_trip_and_expiry() {
+        let dir = tempfile::tempdir().unwrap();
+        let address = PackageAddress::new(
+            PackageKind::Provider,
+            "registry.terraform.io",
+            "hashicorp/aws",
+        );
+        let mut versions = PackageVersions::new(vec![semver::Version::new(5, 0, 0)]);
+        versions
+            .published
+            .insert(semver::Version::new(5, 0, 0), Utc::now());
+
+        let cache = RegistryCache::new(dir.path().to_path_buf(), Duration::from_secs(3600));
+        assert!(cache.load(&address, true).is_none());
+        cache.save(&address, &versions).unwrap();
+        assert_eq!(cache.load(&address, false), Some(versions.clone()));
+
+        let expired = RegistryCache::new(dir.path().to_path_buf(), Duration::ZERO);
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(expired.load(&address, false).is_none());
+        assert_eq!(expired.load(&address, true), Some(versions));
+    }
+}
@@ -0,0 +1,729 @@
+//! HTTP client for the Terraform registry protocols.
+
+use super::{
+    referenced_packages, PackageAddress, PackageKind, PackageVersions, RegistryCache,
+    RegistryCredentials, RegistryIndex,
+};
+use crate::config::RegistryOptions;
+use crate::error::Result;
+use crate::types::{ModuleRef, ProviderRef, ScanWarning};
+use chrono::{DateTime, Utc};
+use reqwest::{Client, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Client for the module and provider registry protocols.
+///
+/// Each registry host is asked for its service URLs through Terraform
+/// service discovery (`/.well-known/terraform.json`), so private registries
+/// and Terraform Enterprise work like the public registry. Hosts without a
+/// discovery document are assumed to serve the protocols under `/v1/`.
+///
+/// Module versions come from `{modules.v1}{namespace}/{name}/{provider}/versions`
+/// and provider versions from `{providers.v1}{namespace}/{type}/versions`.
+/// Publication dates are looked up per version, and only for the versions
+/// the analysis needs. Requests carry the host's token, if one is
+/// configured (see [`RegistryCredentials`]).
+#[derive(Debug, Clone)]
+pub struct RegistryClient {
+    http: Client,
+    cache: RegistryCache,
+    credentials: RegistryCredentials,
+    offline: bool,
+    base_url: Option<String>,
+    /// Discovered services by hostname
+    services: Arc<Mutex<HashMap<String, DiscoveredServices>>>,
+}
+
+impl RegistryClient {
+    /// Create a client from the registry options.
+    #[must_use]
+    pub fn new(options: &RegistryOptions) -> Self {
+        let http = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent(concat!("monphare/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            http,
+            cache: RegistryCache::new(
+                options.get_cache_dir(),
+                Duration::from_secs(options.cache_ttl_hours * 60 * 60),
+            ),
+            credentials: RegistryCredentials::load(options),
+            offline: options.offline,
+            base_url: None,
+            services: Arc::default(),
+        }
+    }
+
+    /// Send every request to this base URL instead of the package's registry
+    /// host (e.g., a mirror or a mock registry in tests).
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into().trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Use a different cache.
+    #[must_use]
+    pub fn with_cache(mut self, cache: RegistryCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Use these credentials instead of the Terraform CLI config files.
+    #[must_use]
+    pub fn with_credentials(mut self, credentials: RegistryCredentials) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    /// Returns true if the client only uses cached responses.
+    #[must_use]
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Versions published for a package.
+    ///
+    /// Returns `None` if the registry does not know the package, or, in
+    /// offline mode, if it is not cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the registry cannot be reached or answers with
+    /// something other than a version list.
+    pub async fn versions(&self, address: &PackageAddress) -> Result<Option<PackageVersions>> {
+        if let Some(cached) = self.cache.load(address, self.offline) {
+            return Ok(Some(cached));
+        }
+        if self.offline {
+            tracing::debug!(package = %address, "Offline and not cached, skipping");
+            return Ok(None);
+        }
+
+        let Some(mut versions) = self.fetch_versions(address).await? else {
+            return Ok(None);
+        };
+        // Publication dates never change, keep the ones already known
+        if let Some(stale) = self.cache.load(address, true) {
+            versions.published = stale.published;
+        }
+        self.save(address, &versions);
+        Ok(Some(versions))
+    }
+
+    /// Where to download a module version from.
+    ///
+    /// Asks the module registry's download endpoint and returns the
+    /// go-getter source address from its `X-Terraform-Get` header, resolved
+    /// against the endpoint URL when it is relative. Returns `None` if the
+    /// registry does not know the version, or in offline mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the registry cannot be reached or the response
+    /// has no download location.
+    pub async fn download_source(
+        &self,
+        address: &PackageAddress,
+        version: &semver::Version,
+    ) -> Result<Option<String>> {
+        if self.offline {
+            tracing::debug!(package = %address, "Offline, not asking for a download location");
+            return Ok(None);
+        }
+
+        let url = format!(
+            "{}{}/{version}/download",
+            self.service_url(address).await?,
+            address.path
+        );
+        let Some(response) = self.get(&address.hostname, &url).await? else {
+            return Ok(None);
+        };
+        let location = response
+            .headers()
+            .get("X-Terraform-Get")
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| {
+                crate::err!(Http {
+                    message: format!("Registry response from {url} has no X-Terraform-Get header"),
+                    status_code: Some(response.status().as_u16()),
+                })
+            })?;
+
+        // Relative locations are relative to the download endpoint
+        let is_relative = ["/", "./", "../"]
+            .iter()
+            .any(|prefix| location.starts_with(prefix));
+        let location = if is_relative {
+            url::Url::parse(&url)
+                .and_then(|base| base.join(location))
+                .map(|joined| joined.to_string())
+                .unwrap_or_else(|_| location.to_string())
+        } else {
+            location.to_string()
+        };
+        tracing::debug!(package = %address, version = %version, location = %location, "Resolved module download location");
+        Ok(Some(location))
+    }
+
+    /// Look up every registry module and provider of a scan.
+    ///
+    /// Also fetches the publication date of the newest version each
+    /// reference allows. Failures are reported as warnings and the package
+    /// is left out of the index.
+    pub async fn lookup(
+        &self,
+        modules: &[ModuleRef],
+        providers: &[ProviderRef],
+    ) -> (RegistryIndex, Vec<ScanWarning>) {
+        let mut index = RegistryIndex::default();
+        let mut warnings = Vec::new();
+
+        for package in referenced_packages(modules, providers) {
+            let warning = |message: String| ScanWarning {
+                code: "registry-unavailable".to_string(),
+                message,
+                file: package.file.to_path_buf(),
+                line: None,
+                repository: package.repository.map(str::to_string),
+            };
+
+            let mut versions = match self.versions(&package.address).await {
+                Ok(Some(versions)) => versions,
+                Ok(None) if self.offline => {
+                    warnings.push(warning(format!(
+                        "No cached registry data for {} {} (offline mode)",
+                        package.address.kind, package.address
+                    )));
+                    continue;
+                }
+                Ok(None) => {
+                    warnings.push(warning(format!(
+                        "{} {} was not found in its registry",
+                        package.address.kind, package.address
+                    )));
+                    continue;
+                }
+                Err(e) => {
+                    tracing::warn!(package = %package.address, error = %e, "Registry lookup failed");
+                    warnings.push(warning(format!(
+                        "Failed to query versions of {}: {e}",
+                        package.address
+                    )));
+                    continue;
+                }
+            };
+
+            let mut updated = false;
+            for constraint in &package.constraints {
+                let Some(version) = versions.newest_allowed(*constraint).cloned() else {
+                    continue;
+                };
+                if self.offline || versions.published.contains_key(&version) {
+                    continue;
+                }
+                match self.fetch_published_at(&package.address, &version).await {
+                    Ok(Some(date)) => {
+                        versions.published.insert(version, date);
+                        updated = true;
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        tracing::warn!(package = %package.address, version = %version, error = %e, "Failed to fetch publication date");
+                    }
+                }
+            }
+            if updated {
+                self.save(&package.address, &versions);
+            }
+
+            index.insert(package.address, versions);
+        }
+
+        tracing::info!(
+            packages = index.len(),
+            warnings = warnings.len(),
+            offline = self.offline,
+            "Registry lookup complete"
+        );
+
+        (index, warnings)
+    }
+
+    async fn fetch_versions(&self, address: &PackageAddress) -> Result<Option<PackageVersions>> {
+        let url = format!(
+            "{}{}/versions",
+            self.service_url(address).await?,
+            address.path
+        );
+        let versions: Vec<String> = match address.kind {
+            PackageKind::Module => {
+                let Some(response) = self
+                    .get_json::<ModuleVersionsResponse>(&address.hostname, &url)
+                    .await?
+                else {
+                    return Ok(None);
+                };
+                response
+                    .modules
+                    .into_iter()
+                    .flat_map(|m| m.versions)
+                    .map(|v| v.version)
+                    .collect()
+            }
+            PackageKind::Provider => {
+                let Some(response) = self
+                    .get_json::<ProviderVersionsResponse>(&address.hostname, &url)
+                    .await?
+                else {
+                    return Ok(None);
+                };
+                response.versions.into_iter().map(|v| v.version).collect()
+            }
+        };
+
+        let parsed = versions
+            .iter()
+            .filter_map(|v| match semver::Version::parse(v.trim_start_matches('v')) {
+                Ok(version) => Some(version),
+                Err(e) => {
+                    tracing::debug!(package = %address, version = %v, error = %e, "Skipping unparseable registry version");
+                    None
+                }
+            })
+            .collect();
+
+        tracing::debug!(package = %address, versions = versions.len(), "Fetched registry versions");
+        Ok(Some(PackageVersions::new(parsed)))
+    }
+
+    async fn fetch_published_at(
+        &self,
+        address: &PackageAddress,
+        version: &semver::Version,
+    ) -> Result<Option<DateTime<Utc>>> {
+        let url = format!(
+            "{}{}/{version}",
+            self.service_url(address).await?,
+            address.path
+        );
+        Ok(self
+            .get_json::<VersionDetailResponse>(&address.hostname, &url)
+            .await?
+            .and_then(|detail| detail.published_at))
+    }
+
+    /// Base URL of the registry service for a package, ending with `/`.
+    async fn service_url(&self, address: &PackageAddress) -> Result<String> {
+        let cached = self
+            .services
+            .lock()
+            .expect("registry services lock poisoned")
+            .get(&address.hostname)
+            .cloned();
+        let services = match cached {
+            Some(services) => services,
+            None => {
+                let services = self.discover(&address.hostname).await?;
+                self.services
+                    .lock()
+                    .expect("registry services lock poisoned")
+                    .insert(address.hostname.clone(), services.clone());
+                services
+            }
+        };
+
+        let (service, url) = match address.kind {
+            PackageKind::Module => ("modules.v1", services.modules),
+            PackageKind::Provider => ("providers.v1", services.providers),
+        };
+        url.ok_or_else(|| {
+            crate::err!(Http {
+                message: format!(
+                    "Registry host {} does not offer the {service} service",
+                    address.hostname
+                ),
+                status_code: None,
+            })
+        })
+    }
+
+    /// Run Terraform service discovery against a registry host.
+    async fn discover(&self, hostname: &str) -> Result<DiscoveredServices> {
+        let origin = self
+            .base_url
+            .clone()
+            .unwrap_or_else(|| format!("https://{hostname}"));
+        let discovery_url = format!("{origin}/.well-known/terraform.json");
+
+        let Some(document) = self
+            .get_json::<HashMap<String, serde_json::Value>>(hostname, &discovery_url)
+            .await?
+        else {
+            tracing::debug!(host = %hostname, "No service discovery document, assuming /v1/");
+            return Ok(DiscoveredServices {
+                modules: Some(format!("{origin}/v1/modules/")),
+                providers: Some(format!("{origin}/v1/providers/")),
+            });
+        };
+
+        let base = url::Url::parse(&discovery_url).map_err(|e| {
+            crate::err!(Http {
+                message: format!("Invalid registry URL {discovery_url}: {e}"),
+                status_code: None,
+            })
+        })?;
+        let resolve = |key: &str| {
+            let service = document.get(key)?.as_str()?;
+            let mut url = base.join(service).ok()?.to_string();
+            if !url.ends_with('/') {
+                url.push('/');
+            }
+            Some(url)
+        };
+
+        let services = DiscoveredServices {
+            modules: resolve("modules.v1"),
+            providers: resolve("providers.v1"),
+        };
+        tracing::debug!(host = %hostname, services = ?services, "Discovered registry services");
+        Ok(services)
+    }
+
+    /// GET a JSON document from a registry host, returning `None` on 404.
+    async fn get_json<T: DeserializeOwned>(&self, hostname: &str, url: &str) -> Result<Option<T>> {
+        let Some(response) = self.get(hostname, url).await? else {
+            return Ok(None);
+        };
+        let status = response.status();
+        response.json::<T>().await.map(Some).map_err(|e| {
+            crate::err!(Http {
+                message: format!("Invalid registry response from {url}: {e}"),
+                status_code: Some(status.as_u16()),
+            })
+        })
+    }
+
+    /// GET a URL on a registry host, returning `None` on 404 and an error
+    /// for any other unsuccessful status.
+    async fn get(&self, hostname: &str, url: &str) -> Result<Option<reqwest::Response>> {
+        tracing::trace!(url = %url, "Registry request");
+        let mut request = self.http.get(url);
+        if let Some(token) = self.credentials.token(hostname) {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await.map_err(|e| {
+            crate::err!(Http {
+                message: format!("Registry request to {url} failed: {e}"),
+                status_code: e.status().map(|s| s.as_u16()),
+            })
+        })?;
+
+        let status = response.status();
+        if status == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+            return Err(crate::err!(Http {
+                message: format!(
+                    "Registry request to {url} returned {status}; check the credentials for {hostname}"
+                ),
+                status_code: Some(status.as_u16()),
+            }));
+        }
+        if !status.is_success() {
+            return Err(crate::err!(Http {
+                message: format!("Registry request to {url} returned {status}"),
+                status_code: Some(status.as_u16()),
+            }));
+        }
+        Ok(Some(response))
+    }
+
+    fn save(&self, address: &PackageAddress, versions: &PackageVersions) {
+        if let Err(e) = self.cache.save(address, versions) {
+            tracing::warn!(package = %address, error = %e, "Failed to write registry cache");
+        }
+    }
+}
+
+/// Service URLs of a registry host, ending with `/`.
+#[derive(Debug, Clone)]
+struct DiscoveredServices {
+    modules: Option<String>,
+    providers: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModuleVersionsResponse {
+    modules: Vec<ModuleVersions>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModuleVersions {
+    versions: Vec<VersionEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProviderVersionsResponse {
+    versions: Vec<VersionEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VersionEntry {
+    version: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct VersionDetailResponse {
+    published_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Constraint, ModuleSource};
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use wiremock::{matchers::*, Mock, MockServer, ResponseTemplate};
+
+    async fn mock_registry() -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/modules/terraform-aws-modules/vpc/aws/versions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "modules": [{
+                    "source": "terraform-aws-modules/vpc/aws",
+                    "versions": [{"version": "4.0.0"}, {"version": "4.1.0"}, {"version": "5.0.0"}]
+                }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/modules/terraform-aws-modules/vpc/aws/4.1.0"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"published_at": "2023-01-10T12:00:00Z"})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/providers/hashicorp/aws/versions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "hashicorp/aws",
+                "versions": [{"version": "5.0.0", "protocols": ["5.0"]}, {"version": "5.1.0"}]
+            })))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    fn client(server: &MockServer, cache_dir: &std::path::Path, offline: bool) -> RegistryClient {
+        let options = RegistryOptions {
+            enabled: true,
+            offline,
+            cache_dir: Some(cache_dir.display().to_string()),
+            ..RegistryOptions::default()
+        };
+        RegistryClient::new(&options).with_base_url(server.uri())
+    }
+
+    fn vpc(version: &str) -> ModuleRef {
+        ModuleRef {
+            name: "vpc".to_string(),
+            source: ModuleSource::Registry {
+                hostname: "registry.terraform.io".to_string(),
+                namespace: "terraform-aws-modules".to_string(),
+                name: "vpc".to_string(),
+                provider: "aws".to_string(),
+                subdir: None,
+                params: Default::default(),
+            },
+            version_constraint: Some(Constraint::parse(version).unwrap()),
+            file_path: PathBuf::from("main.tf"),
+            line_number: 1,
+            repository: None,
+            attributes: HashMap::new(),
+            dependencies: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lookup_fetches_versions_and_dates() {
+        let server = mock_registry().await;
+        let cache_dir = tempfile::tempdir().unwrap();
+        let client = client(&server, cache_dir.path(), false);
+        let provider = ProviderRef {
+            name: "aws".to_string(),
+            source: Some("hashicorp/aws".to_string()),
+            version_constraint: None,
+            file_path: PathBuf::from("versions.tf"),
+            line_number: 1,
+            repository: None,
+        };
+
+        let (index, warnings) = client.lookup(&[vpc("~> 4.0")], &[provider]).await;
+
+        assert!(warnings.is_empty());
+        let module = PackageAddress::for_module(&vpc("~> 4.0").source).unwrap();
+        let versions = index.get(&module).unwrap();
+        assert_eq!(versions.versions.len(), 3);
+        let newest = semver::Version::new(4, 1, 0);
+        assert_eq!(
+            versions.published[&newest].to_rfc3339(),
+            "2023-01-10T12:00:00+00:00"
+        );
+        assert_eq!(index.len(), 2);
+
+        // Second lookup is served from the cache (the versions mock expects one call)
+        let (cached, _) = client.lookup(&[vpc("~> 4.0")], &[]).await;
+        assert_eq!(cached.get(&module), Some(versions));
+    }
+
+    #[tokio::test]
+    async fn test_offline_mode_never_uses_the_network() {
+        let server = MockServer::start().await;
+        let cache_dir = tempfile::tempdir().unwrap();
+        let client = client(&server, cache_dir.path(), true);
+
+        let (index, warnings) = client.lookup(&[vpc("~> 4.0")], &[]).await;
+
+        assert!(index.is_empty());
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("offline"));
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_package_and_server_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/providers/hashicorp/broken/versions"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+        let cache_dir = tempfile::tempdir().unwrap();
+        let client = client(&server, cache_dir.path(), false);
+
+        let missing = PackageAddress::new(PackageKind::Module, "registry.terraform.io", "a/b/c");
+        assert_eq!(client.versions(&missing).await.unwrap(), None);
+
+        let broken = PackageAddress::new(
+            PackageKind::Provider,
+            "registry.terraform.io",
+            "hashicorp/broken",
+        );
+        assert!(client.versions(&broken).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_download_source() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/modules/acme/vpc/aws/1.0.0/download"))
+            .respond_with(ResponseTemplate::new(204).insert_header(
+                "X-Terraform-Get",
+                "git::https://github.com/acme/terraform-aws-vpc?ref=v1.0.0",
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/modules/acme/vpc/aws/2.0.0/download"))
+            .respond_with(
+                ResponseTemplate::new(204).insert_header("X-Terraform-Get", "/archives/vpc.tgz"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/modules/acme/vpc/aws/3.0.0/download"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server)
+            .await;
+        let cache_dir = tempfile::tempdir().unwrap();
+        let client = client(&server, cache_dir.path(), false);
+        let address =
+            PackageAddress::new(PackageKind::Module, "registry.terraform.io", "acme/vpc/aws");
+        let version = |v: &str| semver::Version::parse(v).unwrap();
+
+        assert_eq!(
+            client
+                .download_source(&address, &version("1.0.0"))
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("git::https://github.com/acme/terraform-aws-vpc?ref=v1.0.0")
+        );
+        // Relative locations are resolved against the download endpoint
+        assert_eq!(
+            client
+                .download_source(&address, &version("2.0.0"))
+                .await
+                .unwrap(),
+            Some(format!("{}/archives/vpc.tgz", server.uri()))
+        );
+        assert!(client
+            .download_source(&address, &version("3.0.0"))
+            .await
+            .is_err());
+        assert_eq!(
+            client
+                .download_source(&address, &version("4.0.0"))
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_private_registry_discovery_and_credentials() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/.well-known/terraform.json"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"modules.v1": "/api/registry/v1/modules"})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/registry/v1/modules/acme/vpc/aws/versions"))
+            .and(header("authorization", "Bearer secret-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "modules": [{"versions": [{"version": "1.0.0"}, {"version": "1.2.0"}]}]
+            })))
+            .mount(&server)
+            .await;
+
+        let cache_dir = tempfile::tempdir().unwrap();
+        let credentials = RegistryCredentials::from_cli_config(
+            r#"credentials "tf.internal.example.com" { token = "secret-token" }"#,
+        )
+        .unwrap();
+        let client = client(&server, cache_dir.path(), false).with_credentials(credentials);
+
+        let module = PackageAddress::new(
+            PackageKind::Module,
+            "tf.internal.example.com",
+            "acme/vpc/aws",
+        );
+        let versions = client.versions(&module).await.unwrap().unwrap();
+        assert_eq!(versions.latest(), Some(&semver::Version::new(1, 2, 0)));
+
+        // Discovery is done once per host, and missing services are errors
+        let provider =
+            PackageAddress::new(PackageKind::Provider, "tf.internal.example.com", "acme/aws");
+        let err = client.versions(&provider).await.unwrap_err();
+        assert!(err.to_string().contains("providers.v1"));
+    }
+}
@@ -0,0 +1,217 @@
+//! Registry API tokens, read the way Terraform reads them.
+//!
+//! Tokens are looked up, in order, in:
+//!
+//! 1. `TF_TOKEN_<host>` environment variables, with `.` replaced by `_` and
+//!    `-` by `__` (e.g., `TF_TOKEN_tf_internal_example_com`)
+//! 2. `credentials "<host>" { token = "..." }` blocks in the Terraform CLI
+//!    config file (`.terraformrc` / `terraform.rc`)
+//! 3. `credentials.tfrc.json`, as written by `terraform login`
+
+use crate::config::RegistryOptions;
+use crate::error::{MonPhareError, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Registry API tokens by hostname.
+#[derive(Clone, Default)]
+pub struct RegistryCredentials {
+    tokens: HashMap<String, String>,
+}
+
+impl std::fmt::Debug for RegistryCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print the tokens themselves
+        f.debug_struct("RegistryCredentials")
+            .field("hosts", &self.tokens.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl RegistryCredentials {
+    /// Load the credentials files Terraform would use.
+    ///
+    /// Missing files are skipped; unreadable ones are logged and skipped.
+    #[must_use]
+    pub fn load(options: &RegistryOptions) -> Self {
+        let mut credentials = Self::default();
+
+        let tfrc_json = if cfg!(windows) {
+            dirs::config_dir()
+        } else {
+            dirs::home_dir()
+        }
+        .map(|dir| dir.join(".terraform.d").join("credentials.tfrc.json"));
+        if let Some(path) = tfrc_json.filter(|p| p.is_file()) {
+            match std::fs::read_to_string(&path)
+                .map_err(|e| MonPhareError::io(&path, e, file!(), line!()))
+                .and_then(|content| Self::from_tfrc_json(&content))
+            {
+                Ok(tokens) => credentials.tokens.extend(tokens.tokens),
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "Ignoring registry credentials file");
+                }
+            }
+        }
+
+        // The CLI config file wins over `terraform login` tokens
+        if let Some(path) = options.get_credentials_file().filter(|p| p.is_file()) {
+            match Self::from_cli_config_file(&path) {
+                Ok(tokens) => credentials.tokens.extend(tokens.tokens),
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "Ignoring Terraform CLI config file");
+                }
+            }
+        }
+
+        tracing::debug!(
+            hosts = credentials.tokens.len(),
+            "Loaded registry credentials"
+        );
+        credentials
+    }
+
+    /// Read the `credentials` blocks of a Terraform CLI config file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not valid HCL.
+    pub fn from_cli_config_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| MonPhareError::io(path, e, file!(), line!()))?;
+        Self::from_cli_config(&content).map_err(|e| {
+            crate::err!(ConfigParse {
+                message: format!("Invalid Terraform CLI config '{}': {e}", path.display()),
+                source: Some(Box::new(e)),
+            })
+        })
+    }
+
+    /// Read the `credentials` blocks of Terraform CLI config content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the content is not valid HCL.
+    pub fn from_cli_config(content: &str) -> std::result::Result<Self, hcl::Error> {
+        let body: hcl::Body = hcl::from_str(content)?;
+        let tokens = body
+            .blocks()
+            .filter(|block| block.identifier() == "credentials")
+            .filter_map(|block| {
+                let host = block.labels().first()?.as_str();
+                let token = block
+                    .body()
+                    .attributes()
+                    .find(|attr| attr.key() == "token")
+                    .and_then(|attr| match attr.expr() {
+                        hcl::Expression::String(token) => Some(token.clone()),
+                        _ => None,
+                    })?;
+                Some((host.to_ascii_lowercase(), token))
+            })
+            .collect();
+        Ok(Self { tokens })
+    }
+
+    /// Read a `credentials.tfrc.json` file's content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the content is not valid JSON.
+    pub fn from_tfrc_json(content: &str) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct TfrcJson {
+            #[serde(default)]
+            credentials: HashMap<String, TfrcToken>,
+        }
+        #[derive(Deserialize)]
+        struct TfrcToken {
+            token: String,
+        }
+
+        let parsed: TfrcJson = serde_json::from_str(content).map_err(|e| {
+            crate::err!(ConfigParse {
+                message: format!("Invalid credentials.tfrc.json: {e}"),
+                source: Some(Box::new(e)),
+            })
+        })?;
+        Ok(Self {
+            tokens: parsed
+                .credentials
+                .into_iter()
+                .map(|(host, t)| (host.to_ascii_lowercase(), t.token))
+                .collect(),
+        })
+    }
+
+    /// Token for a registry host, if any.
+    #[must_use]
+    pub fn token(&self, host: &str) -> Option<String> {
+        std::env::var(env_var_name(host))
+            .ok()
+            .filter(|token| !token.is_empty())
+            .or_else(|| self.tokens.get(&host.to_ascii_lowercase()).cloned())
+    }
+}
+
+/// Name of the `TF_TOKEN_*` variable Terraform reads for a host.
+fn env_var_name(host: &str) -> String {
+    format!(
+        "TF_TOKEN_{}",
+        host.to_ascii_lowercase()
+            .replace('-', "__")
+            .replace('.', "_")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_config_credentials() {
+        let credentials = RegistryCredentials::from_cli_config(
+            r#"
+            plugin_cache_dir = "$HOME/.terraform.d/plugin-cache"
+
+            credentials "TF.internal.example.com" {
+              token = "secret-token"
+            }
+
+            credentials "app.terraform.io" {
+              token = "other-token"
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            credentials.token("tf.internal.example.com").as_deref(),
+            Some("secret-token")
+        );
+        assert_eq!(
+            credentials.token("app.terraform.io").as_deref(),
+            Some("other-token")
+        );
+        assert!(credentials.token("registry.terraform.io").is_none());
+        assert!(!format!("{credentials:?}").contains("secret-token"));
+    }
+
+    #[test]
+    fn test_tfrc_json_credentials() {
+        let credentials = RegistryCredentials::from_tfrc_json(
+            r#"{"credentials": {"tf.example.com": {"token": "abc"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(credentials.token("tf.example.com").as_deref(), Some("abc"));
+    }
+
+    #[test]
+    fn test_env_var_name() {
+        assert_eq!(
+            env_var_name("tf-registry.example.com"),
+            "TF_TOKEN_tf__registry_example_com"
+        );
+    }
+}
@@ -0,0 +1,316 @@
+//! Terraform registry client.
+//!
+//! This module queries the Terraform module and provider registry protocols
+//! for the versions published for each registry module and provider found
+//! in a scan, so the analyzer can tell how far behind a constraint is.
+//!
+//! # Caching and offline mode
+//!
+//! Responses are cached on disk (by default under
+//! `~/.cache/monphare/registry`) and reused until `cache_ttl_hours` has
+//! passed. In offline mode the network is never used: cached responses are
+//! used whatever their age, and packages without one are skipped.
+//!
+//! # Private registries
+//!
+//! Any registry host is supported: its service URLs are found through
+//! Terraform service discovery, and requests are authenticated with the
+//! tokens Terraform itself would use (see [`RegistryCredentials`]). List
+//! private hosts in `registry.hosts` so module source URLs on them are
+//! recognised as registry sources.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use monphare::registry::{PackageAddress, PackageKind, RegistryClient};
+//! use monphare::Config;
+//!
+//! # async fn example() -> monphare::Result<()> {
+//! let client = RegistryClient::new(&Config::default().registry);
+//! let aws = PackageAddress::new(PackageKind::Provider, "registry.terraform.io", "hashicorp/aws");
+//! if let Some(versions) = client.versions(&aws).await? {
+//!     println!("latest: {:?}", versions.latest());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod cache;
+mod client;
+mod credentials;
+
+pub use cache::RegistryCache;
+pub use client::RegistryClient;
+pub use credentials::RegistryCredentials;
+
+use crate::types::{Constraint, ModuleRef, ModuleSource, ProviderRef};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Hostname of the public Terraform registry.
+pub const DEFAULT_REGISTRY_HOST: &str = "registry.terraform.io";
+
+/// Kind of registry package.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageKind {
+    /// A module (`namespace/name/provider`)
+    Module,
+    /// A provider (`namespace/type`)
+    Provider,
+}
+
+impl std::fmt::Display for PackageKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Module => write!(f, "module"),
+            Self::Provider => write!(f, "provider"),
+        }
+    }
+}
+
+/// A module or provider in a specific registry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PackageAddress {
+    /// Module or provider
+    pub kind: PackageKind,
+    /// Registry hostname (e.g., `registry.terraform.io`)
+    pub hostname: String,
+    /// Path within the registry (e.g., `hashicorp/aws`)
+    pub path: String,
+}
+
+impl PackageAddress {
+    /// Create a package address.
+    #[must_use]
+    pub fn new(kind: PackageKind, hostname: &str, path: &str) -> Self {
+        Self {
+            kind,
+            hostname: hostname.to_ascii_lowercase(),
+            path: path.to_ascii_lowercase(),
+        }
+    }
+
+    /// Registry address of a module, if it comes from a registry.
+    #[must_use]
+    pub fn for_module(source: &ModuleSource) -> Option<Self> {
+        match source {
+            ModuleSource::Registry {
+                hostname,
+                namespace,
+                name,
+                provider,
+                ..
+            } => Some(Self::new(
+                PackageKind::Module,
+                hostname,
+                &format!("{namespace}/{name}/{provider}"),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Registry address of a provider.
+    #[must_use]
+    pub fn for_provider(provider: &ProviderRef) -> Option<Self> {
+        let source = provider.qualified_source();
+        let parts: Vec<&str> = source.split('/').collect();
+        match parts.as_slice() {
+            [namespace, name] => Some(Self::new(
+                PackageKind::Provider,
+                DEFAULT_REGISTRY_HOST,
+                &format!("{namespace}/{name}"),
+            )),
+            [hostname, namespace, name] => Some(Self::new(
+                PackageKind::Provider,
+                hostname,
+                &format!("{namespace}/{name}"),
+            )),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for PackageAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.hostname, self.path)
+    }
+}
+
+/// Versions published for a package.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageVersions {
+    /// All published versions, oldest first
+    pub versions: Vec<semver::Version>,
+
+    /// Publication dates, for the versions they were looked up for
+    #[serde(default)]
+    pub published: BTreeMap<semver::Version, DateTime<Utc>>,
+}
+
+impl PackageVersions {
+    /// Build from unordered versions.
+    #[must_use]
+    pub fn new(mut versions: Vec<semver::Version>) -> Self {
+        versions.sort();
+        versions.dedup();
+        Self {
+            versions,
+            published: BTreeMap::new(),
+        }
+    }
+
+    /// The newest stable version (the newest pre-release if there is none).
+    #[must_use]
+    pub fn latest(&self) -> Option<&semver::Version> {
+        self.versions
+            .iter()
+            .rev()
+            .find(|v| v.pre.is_empty())
+            .or_else(|| self.versions.last())
+    }
+
+    /// The newest stable version a constraint allows.
+    ///
+    /// Pre-releases are only considered when the constraint names one.
+    #[must_use]
+    pub fn newest_allowed(&self, constraint: Option<&Constraint>) -> Option<&semver::Version> {
+        let allows_pre = constraint.is_some_and(|c| c.raw.contains('-'));
+        self.versions.iter().rev().find(|v| {
+            (allows_pre || v.pre.is_empty()) && constraint.is_none_or(|c| c.is_satisfied_by(v))
+        })
+    }
+
+    /// Number of stable versions newer than `version`.
+    #[must_use]
+    pub fn releases_after(&self, version: &semver::Version) -> usize {
+        self.versions
+            .iter()
+            .filter(|v| v.pre.is_empty() && *v > version)
+            .count()
+    }
+}
+
+/// Registry data for every package of a scan.
+#[derive(Debug, Clone, Default)]
+pub struct RegistryIndex {
+    packages: HashMap<PackageAddress, PackageVersions>,
+}
+
+impl RegistryIndex {
+    /// Versions known for a package.
+    #[must_use]
+    pub fn get(&self, address: &PackageAddress) -> Option<&PackageVersions> {
+        self.packages.get(address)
+    }
+
+    /// Record the versions of a package.
+    pub fn insert(&mut self, address: PackageAddress, versions: PackageVersions) {
+        self.packages.insert(address, versions);
+    }
+
+    /// Number of packages with registry data.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.packages.len()
+    }
+
+    /// Returns true if no package has registry data.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty()
+    }
+}
+
+/// A registry package and the references to it found in a scan.
+#[derive(Debug)]
+pub(crate) struct PackageReferences<'a> {
+    pub(crate) address: PackageAddress,
+    pub(crate) constraints: Vec<Option<&'a Constraint>>,
+    /// First file referencing the package, for warnings
+    pub(crate) file: &'a Path,
+    pub(crate) repository: Option<&'a str>,
+}
+
+/// Registry packages referenced by modules and providers.
+pub(crate) fn referenced_packages<'a>(
+    modules: &'a [ModuleRef],
+    providers: &'a [ProviderRef],
+) -> Vec<PackageReferences<'a>> {
+    let module_refs = modules.iter().filter_map(|m| {
+        PackageAddress::for_module(&m.source).map(|address| PackageReferences {
+            address,
+            constraints: vec![m.version_constraint.as_ref()],
+            file: &m.file_path,
+            repository: m.repository.as_deref(),
+        })
+    });
+    let provider_refs = providers.iter().filter_map(|p| {
+        PackageAddress::for_provider(p).map(|address| PackageReferences {
+            address,
+            constraints: vec![p.version_constraint.as_ref()],
+            file: &p.file_path,
+            repository: p.repository.as_deref(),
+        })
+    });
+
+    let mut packages: Vec<PackageReferences<'a>> = Vec::new();
+    for reference in module_refs.chain(provider_refs) {
+        match packages.iter_mut().find(|p| p.address == reference.address) {
+            Some(existing) => existing.constraints.extend(reference.constraints),
+            None => packages.push(reference),
+        }
+    }
+    packages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn v(s: &str) -> semver::Version {
+        semver::Version::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_package_versions() {
+        let versions = PackageVersions::new(vec![
+            v("5.1.0"),
+            v("4.0.0"),
+            v("6.0.0-beta.1"),
+            v("5.0.0"),
+            v("4.2.0"),
+        ]);
+        let constraint = Constraint::parse("~> 4.0").unwrap();
+
+        assert_eq!(versions.latest(), Some(&v("5.1.0")));
+        assert_eq!(
+            versions.newest_allowed(Some(&constraint)),
+            Some(&v("4.2.0"))
+        );
+        assert_eq!(versions.newest_allowed(None), Some(&v("5.1.0")));
+        assert_eq!(versions.releases_after(&v("4.2.0")), 2);
+    }
+
+    #[test]
+    fn test_provider_address() {
+        let provider = |source: &str| ProviderRef {
+            name: "aws".to_string(),
+            source: Some(source.to_string()),
+            version_constraint: None,
+            file_path: PathBuf::from("main.tf"),
+            line_number: 1,
+            repository: None,
+        };
+
+        let public = PackageAddress::for_provider(&provider("hashicorp/aws")).unwrap();
+        assert_eq!(public.to_string(), "registry.terraform.io/hashicorp/aws");
+        let private = PackageAddress::for_provider(&provider("tf.example.com/acme/aws")).unwrap();
+        assert_eq!(private.hostname, "tf.example.com");
+        assert_eq!(private.path, "acme/aws");
+        assert!(PackageAddress::for_provider(&provider("a/b/c/d")).is_none());
+    }
+}
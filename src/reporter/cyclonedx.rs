@@ -0,0 +1,392 @@
+//! CycloneDX JSON software bill of materials.
+//!
+//! Modules and providers are `library` components, graph edges become the
+//! `dependencies` section, and deprecation and advisory findings are listed
+//! as `vulnerabilities` affecting the components they were raised on.
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::reporter::sbom::{self, Component};
+use crate::reporter::ReportGenerator;
+use crate::types::ScanResult;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// CycloneDX specification version the BOM conforms to.
+const SPEC_VERSION: &str = "1.5";
+
+/// CycloneDX report generator.
+pub struct CycloneDxReporter {
+    /// Whether to pretty-print the output
+    pretty: bool,
+}
+
+impl CycloneDxReporter {
+    /// Create a new CycloneDX reporter.
+    #[must_use]
+    pub fn new(config: &Config) -> Self {
+        Self {
+            pretty: config.output.pretty,
+        }
+    }
+}
+
+impl ReportGenerator for CycloneDxReporter {
+    fn generate(&self, result: &ScanResult) -> Result<String> {
+        let bom = Bom::from(result);
+
+        let json = if self.pretty {
+            serde_json::to_string_pretty(&bom)
+        } else {
+            serde_json::to_string(&bom)
+        };
+
+        json.map_err(|e| {
+            crate::err!(ReportGeneration {
+                message: format!("Failed to serialize CycloneDX report: {e}"),
+            })
+        })
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Bom<'a> {
+    bom_format: &'static str,
+    spec_version: &'static str,
+    version: u32,
+    metadata: Metadata,
+    components: Vec<BomComponent<'a>>,
+    dependencies: Vec<Dependency<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    vulnerabilities: Vec<Vulnerability<'a>>,
+}
+
+#[derive(Serialize)]
+struct Metadata {
+    timestamp: String,
+    tools: Tools,
+}
+
+#[derive(Serialize)]
+struct Tools {
+    components: Vec<Tool>,
+}
+
+#[derive(Serialize)]
+struct Tool {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    name: &'static str,
+    version: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BomComponent<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    #[serde(rename = "bom-ref")]
+    bom_ref: &'a str,
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    purl: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    external_references: Vec<ExternalReference>,
+    properties: Vec<Property>,
+}
+
+#[derive(Serialize)]
+struct ExternalReference {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    url: String,
+}
+
+#[derive(Serialize)]
+struct Property {
+    name: &'static str,
+    value: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Dependency<'a> {
+    #[serde(rename = "ref")]
+    bom_ref: &'a str,
+    depends_on: Vec<&'a str>,
+}
+
+#[derive(Serialize)]
+struct Vulnerability<'a> {
+    #[serde(rename = "bom-ref")]
+    bom_ref: String,
+    id: &'a str,
+    source: VulnerabilitySource<'a>,
+    ratings: Vec<Rating>,
+    description: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recommendation: Option<&'a str>,
+    affects: Vec<Affects<'a>>,
+}
+
+#[derive(Serialize)]
+struct VulnerabilitySource<'a> {
+    name: &'a str,
+}
+
+#[derive(Serialize)]
+struct Rating {
+    severity: &'static str,
+    method: &'static str,
+}
+
+#[derive(Serialize)]
+struct Affects<'a> {
+    #[serde(rename = "ref")]
+    bom_ref: &'a str,
+}
+
+impl<'a> From<&'a ScanResult> for Bom<'a> {
+    fn from(result: &'a ScanResult) -> Self {
+        let (components, edges) = sbom::collect(result);
+
+        // Every component gets an entry, even without dependencies
+        let mut depends_on: BTreeMap<&str, Vec<&str>> = components
+            .iter()
+            .map(|c| (c.node.id(), Vec::new()))
+            .collect();
+        for (from, to) in edges {
+            depends_on.entry(from).or_default().push(to);
+        }
+
+        let vulnerabilities = components
+            .iter()
+            .flat_map(|c| c.vulnerabilities.iter().map(move |f| (c.node.id(), *f)))
+            .enumerate()
+            .map(|(i, (bom_ref, finding))| Vulnerability {
+                bom_ref: format!("vulnerability-{}", i + 1),
+                id: &finding.code,
+                source: VulnerabilitySource {
+                    name: finding.origin.as_deref().unwrap_or("monphare"),
+                },
+                ratings: vec![Rating {
+                    severity: sbom::severity_name(finding.severity),
+                    method: "other",
+                }],
+                description: &finding.message,
+                detail: finding.description.as_deref(),
+                recommendation: finding.suggestion.as_deref(),
+                affects: vec![Affects { bom_ref }],
+            })
+            .collect();
+
+        Self {
+            bom_format: "CycloneDX",
+            spec_version: SPEC_VERSION,
+            version: 1,
+            metadata: Metadata {
+                timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                tools: Tools {
+                    components: vec![Tool {
+                        kind: "application",
+                        name: "monphare",
+                        version: env!("CARGO_PKG_VERSION"),
+                    }],
+                },
+            },
+            components: components.iter().map(component).collect(),
+            dependencies: depends_on
+                .into_iter()
+                .map(|(bom_ref, depends_on)| Dependency {
+                    bom_ref,
+                    depends_on,
+                })
+                .collect(),
+            vulnerabilities,
+        }
+    }
+}
+
+fn component<'a>(c: &Component<'a>) -> BomComponent<'a> {
+    let mut external_references = Vec::new();
+    if let Some((_, url)) = &c.vcs_url {
+        external_references.push(ExternalReference {
+            kind: "vcs",
+            url: url.clone(),
+        });
+    }
+    if let Some(url) = &c.distribution_url {
+        external_references.push(ExternalReference {
+            kind: "distribution",
+            url: url.clone(),
+        });
+    }
+
+    let mut properties = vec![Property {
+        name: "monphare:kind",
+        value: c.kind().to_string(),
+    }];
+    if let Some(constraint) = c.constraint {
+        properties.push(Property {
+            name: "monphare:version-constraint",
+            value: constraint.to_string(),
+        });
+    }
+    if let Some(lock) = c.lock {
+        properties.push(Property {
+            name: "monphare:lock-file",
+            value: lock.file_path.display().to_string(),
+        });
+    }
+    if let Some(repository) = c.node.repository() {
+        properties.push(Property {
+            name: "monphare:repository",
+            value: repository.to_string(),
+        });
+    }
+    if let Some(vcs) = c.vcs {
+        properties.push(Property {
+            name: "monphare:vcs",
+            value: vcs.canonical.clone(),
+        });
+    }
+    properties.push(Property {
+        name: "monphare:source",
+        value: c.node.source(),
+    });
+
+    BomComponent {
+        kind: "library",
+        bom_ref: c.node.id(),
+        name: c.node.display_name(),
+        version: c.version.clone(),
+        purl: c.purl.clone(),
+        external_references,
+        properties,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DependencyGraph, EdgeType, RequirementOrigin};
+    use crate::types::{
+        Constraint, Finding, FindingCategory, Location, ModuleRef, ModuleSource, ProviderLock,
+        ProviderRef, Severity,
+    };
+    use std::path::PathBuf;
+
+    fn scan() -> ScanResult {
+        let module = ModuleRef {
+            name: "vpc".to_string(),
+            source: ModuleSource::Registry {
+                hostname: "registry.terraform.io".to_string(),
+                namespace: "terraform-aws-modules".to_string(),
+                name: "vpc".to_string(),
+                provider: "aws".to_string(),
+                subdir: None,
+                params: Default::default(),
+            },
+            version_constraint: Some(Constraint::parse("5.1.0").unwrap()),
+            file_path: PathBuf::from("infra/main.tf"),
+            line_number: 1,
+            repository: Some("infra".to_string()),
+            attributes: Default::default(),
+            dependencies: Default::default(),
+        };
+        let provider = ProviderRef {
+            name: "aws".to_string(),
+            source: Some("hashicorp/aws".to_string()),
+            version_constraint: Some(Constraint::parse("~> 5.0").unwrap()),
+            file_path: PathBuf::from("infra/versions.tf"),
+            line_number: 3,
+            repository: Some("infra".to_string()),
+        };
+
+        let mut graph = DependencyGraph::new();
+        let module_id = graph.add_module(&module);
+        let provider_id = graph.add_provider(&provider);
+        graph.add_edge(
+            &module_id,
+            &provider_id,
+            EdgeType::ModuleRequiresProvider(RequirementOrigin::Declared),
+        );
+
+        let mut result = ScanResult {
+            modules: vec![module],
+            providers: vec![provider],
+            graph,
+            locks: vec![ProviderLock {
+                source: "registry.terraform.io/hashicorp/aws".to_string(),
+                version: "5.31.0".to_string(),
+                constraints: Some("~> 5.0".to_string()),
+                file_path: PathBuf::from("infra/.terraform.lock.hcl"),
+                line_number: 4,
+                repository: Some("infra".to_string()),
+            }],
+            ..Default::default()
+        };
+        result.analysis.findings.push(Finding {
+            code: "deprecated-module".to_string(),
+            severity: Severity::Error,
+            message: "Module 'vpc' uses a deprecated version".to_string(),
+            description: None,
+            location: Some(Location {
+                file: PathBuf::from("infra/main.tf"),
+                line: 1,
+                column: None,
+                repository: Some("infra".to_string()),
+            }),
+            related_locations: vec![],
+            suggestion: Some("Upgrade to 6.0.0".to_string()),
+            category: FindingCategory::Deprecated,
+            suppressed_by: None,
+            resource: Some("module.vpc".to_string()),
+            source: Some("registry.terraform.io/terraform-aws-modules/vpc/aws".to_string()),
+            origin: None,
+        });
+        result
+    }
+
+    #[test]
+    fn test_cyclonedx_report() {
+        let json = CycloneDxReporter::new(&Config::default())
+            .generate(&scan())
+            .unwrap();
+        let bom: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(bom["bomFormat"], "CycloneDX");
+        assert_eq!(bom["specVersion"], SPEC_VERSION);
+
+        let components = bom["components"].as_array().unwrap();
+        assert_eq!(components.len(), 2);
+        let module = &components[0];
+        assert_eq!(
+            module["purl"],
+            "pkg:terraform/terraform-aws-modules/vpc/aws@5.1.0"
+        );
+        let provider = &components[1];
+        assert_eq!(provider["version"], "5.31.0");
+        assert_eq!(provider["purl"], "pkg:terraform/hashicorp/aws@5.31.0");
+        assert!(provider["properties"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|p| p["name"] == "monphare:version-constraint" && p["value"] == "~> 5.0"));
+
+        let dependencies = bom["dependencies"].as_array().unwrap();
+        assert_eq!(dependencies[0]["ref"], module["bom-ref"]);
+        assert_eq!(dependencies[0]["dependsOn"][0], provider["bom-ref"]);
+
+        let vulnerabilities = bom["vulnerabilities"].as_array().unwrap();
+        assert_eq!(vulnerabilities.len(), 1);
+        assert_eq!(vulnerabilities[0]["id"], "deprecated-module");
+        assert_eq!(vulnerabilities[0]["ratings"][0]["severity"], "high");
+        assert_eq!(vulnerabilities[0]["affects"][0]["ref"], module["bom-ref"]);
+    }
+}
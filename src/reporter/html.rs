@@ -6,10 +6,18 @@
 
 use crate::config::Config;
 use crate::error::Result;
-use crate::reporter::ReportGenerator;
-use crate::types::{Finding, ModuleRef, ProviderRef, ScanResult, ScanWarning, Severity};
+use crate::graph::{GraphNode, RequirementOrigin};
+use crate::reporter::{findings_by_node, ReportGenerator};
+use crate::types::{
+    BaselineResult, DeprecationNotice, DeprecationPhase, Finding, ModuleRef, ProviderRef,
+    ScanResult, ScanWarning, Severity, VersionStatus,
+};
+use serde::Serialize;
 use std::collections::HashMap;
 
+/// Number of shared modules ranked in the blast radius section.
+const STATS_TOP: usize = 10;
+
 /// HTML report generator.
 pub struct HtmlReporter {
     _config: Config,
@@ -40,21 +48,24 @@ fn generate_html_report(result: &ScanResult) -> String {
     // Count findings by severity
     let errors = result
         .analysis
-        .findings
-        .iter()
+        .active_findings()
         .filter(|f| matches!(f.severity, Severity::Error | Severity::Critical))
         .count();
     let warnings = result
         .analysis
-        .findings
-        .iter()
+        .active_findings()
         .filter(|f| f.severity == Severity::Warning)
         .count();
     let infos = result
+        .analysis
+        .active_findings()
+        .filter(|f| f.severity == Severity::Info)
+        .count();
+    let suppressed = result
         .analysis
         .findings
         .iter()
-        .filter(|f| f.severity == Severity::Info)
+        .filter(|f| f.is_suppressed())
         .count();
 
     // Determine status
@@ -84,7 +95,23 @@ fn generate_html_report(result: &ScanResult) -> String {
     let has_scan_warnings = !result.warnings.is_empty();
 
     // Generate findings HTML grouped by repository
-    let findings_html = generate_findings_html(result);
+    let findings_html = generate_findings_html(&result.analysis.findings);
+
+    // Generate retirement timeline HTML (if any dated deprecation matched)
+    let timeline = result.analysis.deprecations.timeline();
+    let timeline_html = generate_timeline_html(&timeline);
+
+    // Generate version status HTML (if registry lookups found stale references)
+    let behind: Vec<&VersionStatus> = result
+        .analysis
+        .versions
+        .iter()
+        .filter(|s| s.releases_behind > 0)
+        .collect();
+    let versions_html = generate_versions_html(&behind);
+
+    // Generate baseline HTML (if a baseline was given)
+    let baseline_html = result.baseline.as_ref().map(generate_baseline_html);
 
     // Generate modules HTML
     let modules_html = generate_modules_html(result);
@@ -92,6 +119,11 @@ fn generate_html_report(result: &ScanResult) -> String {
     // Generate providers HTML
     let providers_html = generate_providers_html(result);
 
+    // Generate the interactive graph (if the scan built one)
+    let graph_nodes = result.graph.node_count();
+    let graph_html = (graph_nodes > 0).then(|| generate_graph_html(result));
+    let stats_html = (graph_nodes > 0).then(|| generate_stats_html(result));
+
     format!(
         r##"<!DOCTYPE html>
 <html lang="en">
@@ -120,6 +152,9 @@ fn generate_html_report(result: &ScanResult) -> String {
                     <span class="nav-icon">⚑</span> Findings
                     {findings_badge}
                 </a>
+                {timeline_nav}
+                {versions_nav}
+                {baseline_nav}
                 <a href="#modules" class="nav-item">
                     <span class="nav-icon">◫</span> Modules
                     <span class="badge">{modules_count}</span>
@@ -128,6 +163,8 @@ fn generate_html_report(result: &ScanResult) -> String {
                     <span class="nav-icon">◩</span> Providers
                     <span class="badge">{providers_count}</span>
                 </a>
+                {graph_nav}
+                {stats_nav}
             </nav>
             <div class="sidebar-footer">
                 <div class="version">v{version}</div>
@@ -183,6 +220,10 @@ fn generate_html_report(result: &ScanResult) -> String {
                         <span class="finding-count">{infos}</span>
                         <span class="finding-label">Info</span>
                     </div>
+                    <div class="finding-stat suppressed">
+                        <span class="finding-count">{suppressed}</span>
+                        <span class="finding-label">Suppressed</span>
+                    </div>
                 </div>
             </section>
 
@@ -197,6 +238,12 @@ fn generate_html_report(result: &ScanResult) -> String {
                 {findings_html}
             </section>
 
+            {timeline_section}
+
+            {versions_section}
+
+            {baseline_section}
+
             <!-- Modules Section -->
             <section id="modules" class="section">
                 <h2 class="section-title">
@@ -214,6 +261,10 @@ fn generate_html_report(result: &ScanResult) -> String {
                 </h2>
                 {providers_html}
             </section>
+
+            {graph_section}
+
+            {stats_section}
         </main>
     </div>
 
@@ -246,6 +297,122 @@ fn generate_html_report(result: &ScanResult) -> String {
         } else {
             String::new()
         },
+        timeline_nav = if timeline.is_empty() {
+            String::new()
+        } else {
+            format!(
+                r##"<a href="#timeline" class="nav-item">
+                    <span class="nav-icon">⏳</span> Retirements
+                    <span class="badge">{}</span>
+                </a>"##,
+                timeline.len()
+            )
+        },
+        timeline_section = if timeline.is_empty() {
+            String::new()
+        } else {
+            format!(
+                r##"<!-- Retirement Timeline Section -->
+            <section id="timeline" class="section">
+                <h2 class="section-title">
+                    <span class="section-icon">⏳</span>
+                    Retirement Timeline
+                </h2>
+                {timeline_html}
+            </section>"##
+            )
+        },
+        versions_nav = if behind.is_empty() {
+            String::new()
+        } else {
+            format!(
+                r##"<a href="#versions" class="nav-item">
+                    <span class="nav-icon">↻</span> Version Status
+                    <span class="badge">{}</span>
+                </a>"##,
+                behind.len()
+            )
+        },
+        versions_section = if behind.is_empty() {
+            String::new()
+        } else {
+            format!(
+                r##"<!-- Version Status Section -->
+            <section id="versions" class="section">
+                <h2 class="section-title">
+                    <span class="section-icon">↻</span>
+                    Version Status
+                </h2>
+                {versions_html}
+            </section>"##
+            )
+        },
+        baseline_nav = match &result.baseline {
+            Some(baseline) => format!(
+                r##"<a href="#baseline" class="nav-item">
+                    <span class="nav-icon">◌</span> Baseline
+                    <span class="badge">{}</span>
+                </a>"##,
+                baseline.known.len()
+            ),
+            None => String::new(),
+        },
+        baseline_section = match baseline_html {
+            Some(baseline_html) => format!(
+                r##"<!-- Baseline Section -->
+            <section id="baseline" class="section">
+                <h2 class="section-title">
+                    <span class="section-icon">◌</span>
+                    Baseline
+                </h2>
+                {baseline_html}
+            </section>"##
+            ),
+            None => String::new(),
+        },
+        graph_nav = if graph_html.is_some() {
+            format!(
+                r##"<a href="#graph" class="nav-item">
+                    <span class="nav-icon">⬡</span> Graph
+                    <span class="badge">{graph_nodes}</span>
+                </a>"##
+            )
+        } else {
+            String::new()
+        },
+        graph_section = match graph_html {
+            Some(graph_html) => format!(
+                r##"<!-- Dependency Graph Section -->
+            <section id="graph" class="section">
+                <h2 class="section-title">
+                    <span class="section-icon">⬡</span>
+                    Dependency Graph
+                </h2>
+                {graph_html}
+            </section>"##
+            ),
+            None => String::new(),
+        },
+        stats_nav = if stats_html.is_some() {
+            r##"<a href="#stats" class="nav-item">
+                    <span class="nav-icon">◎</span> Blast Radius
+                </a>"##
+        } else {
+            ""
+        },
+        stats_section = match stats_html {
+            Some(stats_html) => format!(
+                r##"<!-- Blast Radius Section -->
+            <section id="stats" class="section">
+                <h2 class="section-title">
+                    <span class="section-icon">◎</span>
+                    Most Critical Shared Modules
+                </h2>
+                {stats_html}
+            </section>"##
+            ),
+            None => String::new(),
+        },
         scan_warnings_section = if has_scan_warnings {
             format!(
                 r##"<!-- Scan Warnings Section -->
@@ -263,6 +430,224 @@ fn generate_html_report(result: &ScanResult) -> String {
     )
 }
 
+/// Generate the interactive dependency graph.
+///
+/// The nodes and edges are embedded as JSON and laid out and drawn by the
+/// report's own script, so the report still works offline.
+fn generate_graph_html(result: &ScanResult) -> String {
+    #[derive(Serialize)]
+    struct ViewGraph<'a> {
+        nodes: Vec<ViewNode<'a>>,
+        edges: Vec<ViewEdge<'a>>,
+    }
+
+    #[derive(Serialize)]
+    struct ViewNode<'a> {
+        id: &'a str,
+        kind: &'static str,
+        name: &'a str,
+        source: String,
+        constraint: Option<&'a str>,
+        repository: Option<&'a str>,
+        file: String,
+        line: usize,
+        severity: Option<String>,
+    }
+
+    #[derive(Serialize)]
+    struct ViewEdge<'a> {
+        from: &'a str,
+        to: &'a str,
+        #[serde(rename = "type")]
+        edge_type: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        origin: Option<RequirementOrigin>,
+    }
+
+    let severities = worst_severities(result);
+    let nodes = result
+        .graph
+        .nodes()
+        .map(|node| {
+            let (kind, constraint, file, line) = match node {
+                GraphNode::Module(m) => (
+                    "module",
+                    m.version_constraint.as_ref().map(|c| c.raw.as_str()),
+                    &m.file_path,
+                    m.line_number,
+                ),
+                GraphNode::Provider(p) => (
+                    "provider",
+                    p.version_constraint.as_ref().map(|c| c.raw.as_str()),
+                    &p.file_path,
+                    p.line_number,
+                ),
+                GraphNode::Runtime(r) => (
+                    "runtime",
+                    Some(r.version.raw.as_str()),
+                    &r.file_path,
+                    r.line_number,
+                ),
+            };
+            ViewNode {
+                id: node.id(),
+                kind,
+                name: node.display_name(),
+                source: node.source(),
+                constraint,
+                repository: node.repository(),
+                file: extract_relative_path(&file.to_string_lossy(), node.repository()),
+                line,
+                severity: severities
+                    .get(node.id())
+                    .map(|s| s.to_string().to_lowercase()),
+            }
+        })
+        .collect();
+    let edges = result
+        .graph
+        .edges()
+        .map(|(from, to, edge_type)| ViewEdge {
+            from: from.id(),
+            to: to.id(),
+            edge_type: edge_type.to_string(),
+            origin: edge_type.requirement_origin(),
+        })
+        .collect();
+
+    // `<` is escaped so the data cannot close its script element
+    let data = serde_json::to_string(&ViewGraph { nodes, edges })
+        .unwrap_or_else(|_| r#"{"nodes":[],"edges":[]}"#.to_string())
+        .replace('<', "\\u003c");
+
+    let mut repositories: Vec<&str> = result
+        .graph
+        .nodes()
+        .filter_map(GraphNode::repository)
+        .collect();
+    repositories.sort_unstable();
+    repositories.dedup();
+    let repository_options: String = repositories
+        .iter()
+        .map(|repo| {
+            let repo = html_escape(repo);
+            format!(r#"<option value="{repo}">{repo}</option>"#)
+        })
+        .collect();
+
+    format!(
+        r#"<div class="graph-view">
+                    <div class="graph-toolbar">
+                        <input type="search" id="graph-search" class="graph-search" placeholder="Search name or source…">
+                        <label class="graph-toggle"><input type="checkbox" data-kind="module" checked> Modules</label>
+                        <label class="graph-toggle"><input type="checkbox" data-kind="provider" checked> Providers</label>
+                        <label class="graph-toggle"><input type="checkbox" data-kind="runtime" checked> Runtimes</label>
+                        <select id="graph-repository" class="graph-select">
+                            <option value="">All repositories</option>
+                            {repository_options}
+                        </select>
+                        <button type="button" id="graph-reset" class="graph-button">Reset view</button>
+                    </div>
+                    <div class="graph-body">
+                        <svg id="graph-canvas" class="graph-canvas" xmlns="http://www.w3.org/2000/svg"></svg>
+                        <aside id="graph-details" class="graph-details">
+                            <p class="graph-hint">Click a node to highlight what it depends on and what depends on it. Scroll to zoom, drag to pan.</p>
+                        </aside>
+                    </div>
+                    <div class="graph-legend">
+                        <span><i class="legend-dot sev-critical"></i> Critical</span>
+                        <span><i class="legend-dot sev-error"></i> Error</span>
+                        <span><i class="legend-dot sev-warning"></i> Warning</span>
+                        <span><i class="legend-dot sev-info"></i> Info</span>
+                        <span><i class="legend-dot sev-none"></i> No findings</span>
+                        <span>● Module &nbsp; ■ Provider &nbsp; ◆ Runtime</span>
+                    </div>
+                    <script type="application/json" id="graph-data">{data}</script>
+                </div>"#
+    )
+}
+
+/// Generate the ranking of shared modules by blast radius, and the
+/// providers no module requires.
+fn generate_stats_html(result: &ScanResult) -> String {
+    let stats = result.graph.stats(STATS_TOP);
+
+    let mut html = String::new();
+    if stats.critical_shared_modules.is_empty() {
+        html.push_str(
+            r#"<div class="empty-state">
+            <div class="empty-icon">◎</div>
+            <h3>No Shared Modules</h3>
+            <p>No module source is used by more than one module block or repository.</p>
+        </div>"#,
+        );
+    } else {
+        html.push_str(
+            r#"<table class="data-table">
+                    <thead>
+                        <tr>
+                            <th>Rank</th>
+                            <th>Source</th>
+                            <th>Blast Radius</th>
+                            <th>Direct</th>
+                            <th>Repositories</th>
+                            <th>Betweenness</th>
+                        </tr>
+                    </thead>
+                    <tbody>"#,
+        );
+        for (rank, module) in stats.critical_shared_modules.iter().enumerate() {
+            html.push_str(&format!(
+                r#"<tr>
+                    <td>{}</td>
+                    <td class="source-cell" title="{}">{}</td>
+                    <td class="name-cell">{}</td>
+                    <td>{}</td>
+                    <td>{}</td>
+                    <td>{:.1}</td>
+                </tr>"#,
+                rank + 1,
+                html_escape(&module.source),
+                html_escape(&shorten_source(&module.source, 60)),
+                module.blast_radius(),
+                module.direct_consumers,
+                module.repositories,
+                module.betweenness,
+            ));
+        }
+        html.push_str("</tbody></table>");
+    }
+
+    if !stats.orphaned_providers.is_empty() {
+        html.push_str(&format!(
+            r#"<div class="warnings-note stats-orphans">
+                    <strong>{} orphaned provider(s)</strong> that no module in the graph requires:
+                    {}
+                </div>"#,
+            stats.orphaned_providers.len(),
+            stats
+                .orphaned_providers
+                .iter()
+                .map(|p| format!(
+                    "<code>{}</code> ({})",
+                    html_escape(&p.source),
+                    html_escape(p.repository.as_deref().unwrap_or("Local"))
+                ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    html
+}
+
+/// Worst active finding severity of each graph node.
+fn worst_severities(result: &ScanResult) -> HashMap<&str, Severity> {
+    findings_by_node(result)
+        .into_iter()
+        .filter_map(|(id, findings)| Some((id, findings.iter().map(|f| f.severity).max()?)))
+        .collect()
+}
+
 /// Generate scan warnings HTML (for unparseable files, etc.)
 fn generate_scan_warnings_html(result: &ScanResult) -> String {
     if result.warnings.is_empty() {
@@ -330,6 +715,164 @@ fn generate_scan_warnings_html(result: &ScanResult) -> String {
     html
 }
 
+/// Generate the retirement timeline table, ordered by enforcement date.
+fn generate_timeline_html(timeline: &[&DeprecationNotice]) -> String {
+    if timeline.is_empty() {
+        return String::new();
+    }
+
+    let mut rows = String::new();
+    for notice in timeline {
+        let (status_class, status) = match (notice.phase, notice.days_until_enforcement) {
+            (DeprecationPhase::Enforced, _) => ("error", "enforced".to_string()),
+            (_, Some(days)) => ("warning", format!("in {days} days")),
+            (_, None) => ("warning", notice.phase.to_string()),
+        };
+        let file = extract_relative_path(
+            &notice.location.file.display().to_string(),
+            notice.location.repository.as_deref(),
+        );
+
+        rows.push_str(&format!(
+            r#"<tr>
+                <td>{date}</td>
+                <td><span class="severity-badge {status_class}">{status}</span></td>
+                <td><code>{resource}</code></td>
+                <td><code>{version}</code></td>
+                <td>{replacement}</td>
+                <td title="{reason}">{repo}{file}:{line}</td>
+            </tr>"#,
+            date = notice
+                .enforce_from
+                .map(|d| d.to_string())
+                .unwrap_or_default(),
+            resource = html_escape(&notice.resource),
+            version = html_escape(&notice.matched),
+            replacement = html_escape(&notice.replacement),
+            reason = html_escape(&notice.reason),
+            repo = notice
+                .location
+                .repository
+                .as_deref()
+                .map_or(String::new(), |r| format!("{}: ", html_escape(r))),
+            file = html_escape(&file),
+            line = notice.location.line,
+        ));
+    }
+
+    format!(
+        r#"<table class="data-table timeline-table">
+            <thead>
+                <tr>
+                    <th>Enforced</th>
+                    <th>Status</th>
+                    <th>Resource</th>
+                    <th>Version</th>
+                    <th>Replacement</th>
+                    <th>Location</th>
+                </tr>
+            </thead>
+            <tbody>{rows}</tbody>
+        </table>"#
+    )
+}
+
+/// Generate the table of registry references behind their latest release.
+fn generate_versions_html(statuses: &[&VersionStatus]) -> String {
+    if statuses.is_empty() {
+        return String::new();
+    }
+
+    let mut rows = String::new();
+    for status in statuses {
+        let file = extract_relative_path(
+            &status.location.file.display().to_string(),
+            status.location.repository.as_deref(),
+        );
+
+        rows.push_str(&format!(
+            r#"<tr>
+                <td><code>{resource}</code></td>
+                <td title="{source}"><code>{constraint}</code></td>
+                <td><code>{allowed}</code></td>
+                <td><code>{latest}</code></td>
+                <td>{behind}</td>
+                <td>{repo}{file}:{line}</td>
+            </tr>"#,
+            resource = html_escape(&status.resource),
+            source = html_escape(&status.source),
+            constraint = html_escape(status.constraint.as_deref().unwrap_or("-")),
+            allowed = html_escape(status.newest_allowed.as_deref().unwrap_or("-")),
+            latest = html_escape(&status.latest),
+            behind = status.releases_behind,
+            repo = status
+                .location
+                .repository
+                .as_deref()
+                .map_or(String::new(), |r| format!("{}: ", html_escape(r))),
+            file = html_escape(&file),
+            line = status.location.line,
+        ));
+    }
+
+    format!(
+        r#"<table class="data-table">
+            <thead>
+                <tr>
+                    <th>Resource</th>
+                    <th>Constraint</th>
+                    <th>Allowed</th>
+                    <th>Latest</th>
+                    <th>Behind</th>
+                    <th>Location</th>
+                </tr>
+            </thead>
+            <tbody>{rows}</tbody>
+        </table>"#
+    )
+}
+
+/// Generate baseline HTML: fixed entries, then known findings.
+fn generate_baseline_html(baseline: &BaselineResult) -> String {
+    let mut html = format!(
+        r#"<div class="warnings-note">
+        <strong>Note:</strong> {known} finding(s) were already recorded in the baseline and do not affect the result.
+        {fixed} baseline entry(ies) no longer occur; re-run with <code>--write-baseline</code> to drop them.
+    </div>"#,
+        known = baseline.known.len(),
+        fixed = baseline.fixed.len()
+    );
+
+    for entry in &baseline.fixed {
+        html.push_str(&format!(
+            r#"<div class="finding info baseline-fixed">
+                <div class="finding-header">
+                    <span class="severity-badge info">FIXED</span>
+                    <span class="finding-code">{code}</span>
+                </div>
+                <div class="finding-message">{resource}</div>
+                <div class="finding-location">
+                    <span class="location-icon">📄</span>
+                    <span class="location-file">{repo}{file}</span>
+                </div>
+            </div>"#,
+            code = html_escape(&entry.code),
+            resource = html_escape(entry.resource.as_deref().unwrap_or("-")),
+            repo = entry
+                .repository
+                .as_deref()
+                .map_or(String::new(), |r| format!("{}: ", html_escape(r))),
+            file = html_escape(&entry.file),
+        ));
+    }
+
+    if !baseline.known.is_empty() {
+        html.push_str(&generate_findings_html(&baseline.known));
+    }
+
+    html
+}
+
 /// Extract relative path from a full file path.
 fn extract_relative_path(full_path: &str, repo_name: Option<&str>) -> String {
     // Try to find the repo name in the path and return everything after it
@@ -349,8 +892,8 @@ fn extract_relative_path(full_path: &str, repo_name: Option<&str>) -> String {
 }
 
 /// Generate findings HTML grouped by repository.
-fn generate_findings_html(result: &ScanResult) -> String {
-    if result.analysis.findings.is_empty() {
+fn generate_findings_html(findings: &[Finding]) -> String {
+    if findings.is_empty() {
         return r#"<div class="empty-state">
             <div class="empty-icon">✔</div>
             <h3>No Issues Found</h3>
@@ -361,7 +904,7 @@ fn generate_findings_html(result: &ScanResult) -> String {
 
     // Group findings by repository
     let mut by_repo: HashMap<String, Vec<&Finding>> = HashMap::new();
-    for finding in &result.analysis.findings {
+    for finding in findings {
         let repo = finding
             .location
             .as_ref()
@@ -390,12 +933,15 @@ fn generate_findings_html(result: &ScanResult) -> String {
         let findings = &by_repo[&repo];
         let error_count = findings
             .iter()
+            .filter(|f| !f.is_suppressed())
             .filter(|f| matches!(f.severity, Severity::Error | Severity::Critical))
             .count();
         let warning_count = findings
             .iter()
+            .filter(|f| !f.is_suppressed())
             .filter(|f| f.severity == Severity::Warning)
             .count();
+        let suppressed_count = findings.iter().filter(|f| f.is_suppressed()).count();
 
         html.push_str(&format!(
             r#"<div class="repo-group">
@@ -405,6 +951,7 @@ fn generate_findings_html(result: &ScanResult) -> String {
                     <div class="repo-badges">
                         {}
                         {}
+                        {}
                     </div>
                     <span class="repo-toggle">▼</span>
                 </div>
@@ -420,6 +967,11 @@ fn generate_findings_html(result: &ScanResult) -> String {
             } else {
                 String::new()
             },
+            if suppressed_count > 0 {
+                format!(r#"<span class="badge">{suppressed_count} suppressed</span>"#)
+            } else {
+                String::new()
+            },
         ));
 
         // Group by file within repo
@@ -452,20 +1004,32 @@ fn generate_findings_html(result: &ScanResult) -> String {
                 };
 
                 let line = finding.location.as_ref().map(|l| l.line).unwrap_or(0);
+                let suppressed_class = if finding.is_suppressed() {
+                    " finding-suppressed"
+                } else {
+                    ""
+                };
 
                 html.push_str(&format!(
-                    r#"<div class="finding-card {severity_class}">
+                    r#"<div class="finding-card {severity_class}{suppressed_class}">
                         <div class="finding-header">
                             <span class="finding-severity">{}</span>
                             <span class="finding-code">{}</span>
+                            {}
                             <span class="finding-line">Line {}</span>
                         </div>
                         <div class="finding-message">{}</div>
                         {}
                         {}
+                        {}
                     </div>"#,
                     html_escape(&finding.severity.to_string()),
                     html_escape(&finding.code),
+                    if finding.is_suppressed() {
+                        r#"<span class="suppressed-badge">SUPPRESSED</span>"#
+                    } else {
+                        ""
+                    },
                     line,
                     html_escape(&finding.message),
                     finding
@@ -487,6 +1051,20 @@ fn generate_findings_html(result: &ScanResult) -> String {
                             html_escape(s)
                         ))
                         .unwrap_or_default(),
+                    finding
+                        .suppressed_by
+                        .as_ref()
+                        .map(|s| format!(
+                            r#"<div class="finding-suppression">
+                                <span class="suppression-icon">🔕</span>
+                                Suppressed at line {}: {}
+                            </div>"#,
+                            s.line,
+                            s.reason
+                                .as_deref()
+                                .map_or_else(|| "no reason given".to_string(), html_escape)
+                        ))
+                        .unwrap_or_default(),
                 ));
             }
 
@@ -529,8 +1107,7 @@ fn generate_modules_html(result: &ScanResult) -> String {
                 let pattern = format!("'{}'", m.name);
                 result
                     .analysis
-                    .findings
-                    .iter()
+                    .active_findings()
                     .any(|f| f.message.contains(&pattern))
             })
             .count();
@@ -570,8 +1147,7 @@ fn generate_modules_html(result: &ScanResult) -> String {
                 let pattern = format!("'{}'", module.name);
                 result
                     .analysis
-                    .findings
-                    .iter()
+                    .active_findings()
                     .any(|f| f.message.contains(&pattern))
             };
 
@@ -646,8 +1222,7 @@ fn generate_providers_html(result: &ScanResult) -> String {
                 let pattern = format!("'{}'", p.name);
                 result
                     .analysis
-                    .findings
-                    .iter()
+                    .active_findings()
                     .any(|f| f.message.contains(&pattern))
             })
             .count();
@@ -687,8 +1262,7 @@ fn generate_providers_html(result: &ScanResult) -> String {
                 let pattern = format!("'{}'", provider.name);
                 result
                     .analysis
-                    .findings
-                    .iter()
+                    .active_findings()
                     .any(|f| f.message.contains(&pattern))
             };
 
@@ -958,6 +1532,7 @@ body {
 .finding-stat.error .finding-count { color: var(--error); }
 .finding-stat.warning .finding-count { color: var(--warning); }
 .finding-stat.info .finding-count { color: var(--info); }
+.finding-stat.suppressed .finding-count { color: var(--text-muted); }
 
 .finding-label {
     font-size: 0.875rem;
@@ -1081,6 +1656,28 @@ body {
     border-radius: 4px;
 }
 
+/* Suppressed Findings */
+.finding-suppressed { opacity: 0.55; }
+
+.suppressed-badge {
+    font-size: 0.75rem;
+    font-weight: 600;
+    color: var(--text-muted);
+    border: 1px dashed var(--text-muted);
+    padding: 0.125rem 0.5rem;
+    border-radius: 4px;
+}
+
+.finding-suppression {
+    display: flex;
+    align-items: flex-start;
+    gap: 0.5rem;
+    margin-top: 0.5rem;
+    font-size: 0.875rem;
+    color: var(--text-secondary);
+    font-style: italic;
+}
+
 /* Warnings Note */
 .warnings-note {
     background: var(--warning-bg);
@@ -1096,6 +1693,8 @@ body {
     color: var(--warning);
 }
 
+.stats-orphans { margin-top: 1.5rem; }
+
 /* Data Tables */
 .data-table {
     width: 100%;
@@ -1142,6 +1741,104 @@ body {
 .empty-icon { font-size: 3rem; margin-bottom: 1rem; }
 .empty-state h3 { font-size: 1.25rem; margin-bottom: 0.5rem; color: var(--text-primary); }
 
+/* Dependency Graph */
+.graph-view {
+    background: var(--bg-secondary);
+    border: 1px solid var(--border);
+    border-radius: var(--radius);
+    overflow: hidden;
+}
+
+.graph-toolbar {
+    display: flex;
+    flex-wrap: wrap;
+    align-items: center;
+    gap: 0.75rem;
+    padding: 0.75rem 1rem;
+    background: var(--bg-tertiary);
+    border-bottom: 1px solid var(--border);
+    font-size: 0.875rem;
+}
+
+.graph-search,
+.graph-select,
+.graph-button {
+    background: var(--bg-primary);
+    color: var(--text-primary);
+    border: 1px solid var(--border);
+    border-radius: 4px;
+    padding: 0.375rem 0.625rem;
+    font: inherit;
+}
+
+.graph-search { flex: 1; min-width: 200px; }
+.graph-button { cursor: pointer; }
+.graph-button:hover { background: var(--bg-hover); }
+.graph-toggle { display: flex; align-items: center; gap: 0.375rem; color: var(--text-secondary); cursor: pointer; }
+
+.graph-body {
+    display: flex;
+    height: 560px;
+}
+
+.graph-canvas {
+    flex: 1;
+    cursor: grab;
+    background: var(--bg-primary);
+}
+
+.graph-canvas.panning { cursor: grabbing; }
+
+.graph-details {
+    width: 280px;
+    padding: 1rem;
+    border-left: 1px solid var(--border);
+    overflow-y: auto;
+    font-size: 0.875rem;
+}
+
+.graph-details h4 { font-size: 1rem; margin-bottom: 0.5rem; word-break: break-all; }
+.graph-details dt { color: var(--text-muted); font-size: 0.75rem; text-transform: uppercase; margin-top: 0.5rem; }
+.graph-details dd { font-family: var(--font-mono); font-size: 0.8rem; word-break: break-all; }
+.graph-hint { color: var(--text-secondary); }
+
+.graph-edge { stroke: var(--border); stroke-width: 1.25; fill: none; }
+.graph-edge.highlight { stroke: var(--accent); stroke-width: 2; }
+.graph-edge.inferred { stroke-dasharray: 4 3; }
+.graph-node { cursor: pointer; }
+.graph-node text { fill: var(--text-secondary); font-size: 11px; pointer-events: none; }
+.graph-node .shape { stroke: var(--bg-primary); stroke-width: 1.5; }
+.graph-node.selected .shape { stroke: var(--text-primary); stroke-width: 3; }
+.graph-node.match .shape { stroke: var(--accent); stroke-width: 3; }
+.graph-canvas.focused .graph-node,
+.graph-canvas.focused .graph-edge { opacity: 0.15; }
+.graph-canvas.focused .graph-node.highlight,
+.graph-canvas.focused .graph-edge.highlight { opacity: 1; }
+
+.sev-critical { fill: #dc2626; background: #dc2626; }
+.sev-error { fill: var(--error); background: var(--error); }
+.sev-warning { fill: var(--warning); background: var(--warning); }
+.sev-info { fill: var(--info); background: var(--info); }
+.sev-none { fill: var(--success); background: var(--success); }
+
+.graph-legend {
+    display: flex;
+    flex-wrap: wrap;
+    gap: 1.25rem;
+    padding: 0.625rem 1rem;
+    border-top: 1px solid var(--border);
+    font-size: 0.75rem;
+    color: var(--text-secondary);
+}
+
+.legend-dot {
+    display: inline-block;
+    width: 10px;
+    height: 10px;
+    border-radius: 50%;
+    vertical-align: middle;
+}
+
 /* Responsive */
 @media (max-width: 1024px) {
     .sidebar { display: none; }
@@ -1201,6 +1898,248 @@ window.addEventListener('scroll', () => {
         }
     });
 });
+
+// Interactive dependency graph
+(function() {
+    const dataEl = document.getElementById('graph-data');
+    if (!dataEl) return;
+
+    const data = JSON.parse(dataEl.textContent);
+    const svg = document.getElementById('graph-canvas');
+    const details = document.getElementById('graph-details');
+    const NS = 'http://www.w3.org/2000/svg';
+
+    const byId = new Map();
+    data.nodes.forEach((n, i) => {
+        const angle = (i / data.nodes.length) * Math.PI * 2;
+        n.x = Math.cos(angle) * 300;
+        n.y = Math.sin(angle) * 300;
+        n.vx = 0;
+        n.vy = 0;
+        n.out = [];
+        n.in = [];
+        byId.set(n.id, n);
+    });
+    data.edges = data.edges.filter(e => byId.has(e.from) && byId.has(e.to));
+    data.edges.forEach(e => {
+        byId.get(e.from).out.push(e);
+        byId.get(e.to).in.push(e);
+    });
+
+    // Force-directed layout, run once up front
+    for (let step = 0; step < 300; step++) {
+        const cooling = 1 - step / 300;
+        for (let i = 0; i < data.nodes.length; i++) {
+            const a = data.nodes[i];
+            for (let j = i + 1; j < data.nodes.length; j++) {
+                const b = data.nodes[j];
+                let dx = a.x - b.x;
+                let dy = a.y - b.y;
+                const dist2 = Math.max(dx * dx + dy * dy, 1);
+                const force = 4000 / dist2;
+                const dist = Math.sqrt(dist2);
+                dx = dx / dist * force;
+                dy = dy / dist * force;
+                a.vx += dx; a.vy += dy;
+                b.vx -= dx; b.vy -= dy;
+            }
+        }
+        data.edges.forEach(e => {
+            const a = byId.get(e.from);
+            const b = byId.get(e.to);
+            const dx = b.x - a.x;
+            const dy = b.y - a.y;
+            const dist = Math.max(Math.sqrt(dx * dx + dy * dy), 1);
+            const force = (dist - 90) * 0.02;
+            a.vx += dx / dist * force; a.vy += dy / dist * force;
+            b.vx -= dx / dist * force; b.vy -= dy / dist * force;
+        });
+        data.nodes.forEach(n => {
+            n.vx -= n.x * 0.002;
+            n.vy -= n.y * 0.002;
+            n.x += Math.max(-20, Math.min(20, n.vx)) * cooling;
+            n.y += Math.max(-20, Math.min(20, n.vy)) * cooling;
+            n.vx *= 0.5;
+            n.vy *= 0.5;
+        });
+    }
+
+    const viewport = document.createElementNS(NS, 'g');
+    svg.appendChild(viewport);
+
+    data.edges.forEach(e => {
+        const a = byId.get(e.from);
+        const b = byId.get(e.to);
+        const line = document.createElementNS(NS, 'line');
+        line.setAttribute('class', e.origin === 'inferred' ? 'graph-edge inferred' : 'graph-edge');
+        line.setAttribute('x1', a.x); line.setAttribute('y1', a.y);
+        line.setAttribute('x2', b.x); line.setAttribute('y2', b.y);
+        const title = document.createElementNS(NS, 'title');
+        title.textContent = e.origin ? `${e.type} (${e.origin})` : e.type;
+        line.appendChild(title);
+        viewport.appendChild(line);
+        e.el = line;
+    });
+
+    data.nodes.forEach(n => {
+        const g = document.createElementNS(NS, 'g');
+        g.setAttribute('class', 'graph-node');
+        g.setAttribute('transform', `translate(${n.x},${n.y})`);
+        let shape;
+        if (n.kind === 'provider') {
+            shape = document.createElementNS(NS, 'rect');
+            shape.setAttribute('x', -8); shape.setAttribute('y', -8);
+            shape.setAttribute('width', 16); shape.setAttribute('height', 16);
+        } else if (n.kind === 'runtime') {
+            shape = document.createElementNS(NS, 'polygon');
+            shape.setAttribute('points', '0,-10 10,0 0,10 -10,0');
+        } else {
+            shape = document.createElementNS(NS, 'circle');
+            shape.setAttribute('r', 9);
+        }
+        shape.setAttribute('class', 'shape sev-' + (n.severity || 'none'));
+        const label = document.createElementNS(NS, 'text');
+        label.setAttribute('x', 13);
+        label.setAttribute('y', 4);
+        label.textContent = n.name;
+        const title = document.createElementNS(NS, 'title');
+        title.textContent = n.source;
+        g.appendChild(shape);
+        g.appendChild(label);
+        g.appendChild(title);
+        g.addEventListener('click', ev => {
+            ev.stopPropagation();
+            select(n);
+        });
+        viewport.appendChild(g);
+        n.el = g;
+    });
+
+    // Zoom and pan
+    let view = { x: 0, y: 0, k: 1 };
+    function applyView() {
+        viewport.setAttribute('transform', `translate(${view.x},${view.y}) scale(${view.k})`);
+    }
+    function resetView() {
+        const visible = data.nodes.filter(n => n.el.style.display !== 'none');
+        const rect = svg.getBoundingClientRect();
+        if (!visible.length || !rect.width) {
+            view = { x: rect.width / 2, y: rect.height / 2, k: 1 };
+        } else {
+            const xs = visible.map(n => n.x);
+            const ys = visible.map(n => n.y);
+            const minX = Math.min(...xs) - 40, maxX = Math.max(...xs) + 120;
+            const minY = Math.min(...ys) - 40, maxY = Math.max(...ys) + 40;
+            const k = Math.min(2, rect.width / (maxX - minX), rect.height / (maxY - minY));
+            view = { k, x: (rect.width - (minX + maxX) * k) / 2, y: (rect.height - (minY + maxY) * k) / 2 };
+        }
+        applyView();
+    }
+    svg.addEventListener('wheel', ev => {
+        ev.preventDefault();
+        const rect = svg.getBoundingClientRect();
+        const px = ev.clientX - rect.left;
+        const py = ev.clientY - rect.top;
+        const factor = ev.deltaY < 0 ? 1.15 : 1 / 1.15;
+        const k = Math.max(0.1, Math.min(8, view.k * factor));
+        view.x = px - (px - view.x) * (k / view.k);
+        view.y = py - (py - view.y) * (k / view.k);
+        view.k = k;
+        applyView();
+    }, { passive: false });
+    let drag = null;
+    svg.addEventListener('mousedown', ev => {
+        drag = { x: ev.clientX - view.x, y: ev.clientY - view.y, moved: false };
+        svg.classList.add('panning');
+    });
+    window.addEventListener('mousemove', ev => {
+        if (!drag) return;
+        drag.moved = true;
+        view.x = ev.clientX - drag.x;
+        view.y = ev.clientY - drag.y;
+        applyView();
+    });
+    window.addEventListener('mouseup', () => {
+        svg.classList.remove('panning');
+        setTimeout(() => { drag = null; }, 0);
+    });
+    svg.addEventListener('click', () => {
+        if (drag && drag.moved) return;
+        select(null);
+    });
+
+    // Click to highlight transitive dependencies and dependents
+    function walk(start, next) {
+        const seen = new Set();
+        const stack = [start];
+        while (stack.length) {
+            const n = stack.pop();
+            next(n).forEach(e => {
+                e.el.classList.add('highlight');
+                const other = byId.get(e.from === n.id ? e.to : e.from);
+                if (!seen.has(other.id)) {
+                    seen.add(other.id);
+                    other.el.classList.add('highlight');
+                    stack.push(other);
+                }
+            });
+        }
+        return seen;
+    }
+    function escapeText(s) {
+        const div = document.createElement('div');
+        div.textContent = s == null ? '' : String(s);
+        return div.innerHTML;
+    }
+    function select(node) {
+        svg.querySelectorAll('.highlight, .selected').forEach(el => el.classList.remove('highlight', 'selected'));
+        if (!node) {
+            svg.classList.remove('focused');
+            details.innerHTML = '<p class=\'graph-hint\'>Click a node to highlight what it depends on and what depends on it. Scroll to zoom, drag to pan.</p>';
+            return;
+        }
+        svg.classList.add('focused');
+        node.el.classList.add('highlight', 'selected');
+        const deps = walk(node, n => n.out);
+        const dependents = walk(node, n => n.in);
+        details.innerHTML =
+            `<h4>${escapeText(node.name)}</h4><dl>` +
+            `<dt>Kind</dt><dd>${escapeText(node.kind)}</dd>` +
+            `<dt>Source</dt><dd>${escapeText(node.source)}</dd>` +
+            `<dt>Constraint</dt><dd>${escapeText(node.constraint || 'none')}</dd>` +
+            `<dt>Repository</dt><dd>${escapeText(node.repository || '-')}</dd>` +
+            `<dt>Location</dt><dd>${escapeText(node.file)}:${node.line}</dd>` +
+            `<dt>Worst finding</dt><dd>${escapeText(node.severity || 'none')}</dd>` +
+            `<dt>Dependencies</dt><dd>${deps.size}</dd>` +
+            `<dt>Dependents</dt><dd>${dependents.size}</dd></dl>`;
+    }
+
+    // Search and filters
+    const search = document.getElementById('graph-search');
+    const repository = document.getElementById('graph-repository');
+    const kinds = document.querySelectorAll('.graph-toggle input');
+    function applyFilters() {
+        const query = search.value.trim().toLowerCase();
+        const repo = repository.value;
+        const shownKinds = new Set([...kinds].filter(k => k.checked).map(k => k.dataset.kind));
+        data.nodes.forEach(n => {
+            const visible = shownKinds.has(n.kind) && (!repo || n.repository === repo);
+            n.el.style.display = visible ? '' : 'none';
+            const match = query && (n.name.toLowerCase().includes(query) || n.source.toLowerCase().includes(query));
+            n.el.classList.toggle('match', Boolean(visible && match));
+        });
+        data.edges.forEach(e => {
+            const visible = byId.get(e.from).el.style.display !== 'none' && byId.get(e.to).el.style.display !== 'none';
+            e.el.style.display = visible ? '' : 'none';
+        });
+    }
+    search.addEventListener('input', applyFilters);
+    repository.addEventListener('change', () => { applyFilters(); resetView(); });
+    kinds.forEach(k => k.addEventListener('change', applyFilters));
+    document.getElementById('graph-reset').addEventListener('click', resetView);
+
+    resetView();
+})();
 "
 }
 
@@ -1248,12 +2187,15 @@ mod tests {
                     namespace: "terraform-aws-modules".to_string(),
                     name: "vpc".to_string(),
                     provider: "aws".to_string(),
+                    subdir: None,
+                    params: Default::default(),
                 },
                 version_constraint: None,
                 file_path: PathBuf::from("main.tf"),
                 line_number: 1,
                 repository: Some("test-repo".to_string()),
                 attributes: Default::default(),
+                dependencies: Default::default(),
             }],
             providers: vec![ProviderRef {
                 name: "aws".to_string(),
@@ -1275,6 +2217,9 @@ mod tests {
             graph: Default::default(),
             analysis: AnalysisResult::default(),
             warnings: Vec::new(),
+            baseline: None,
+            locks: Vec::new(),
+            roots: Vec::new(),
         }
     }
 
@@ -1292,6 +2237,172 @@ mod tests {
         assert!(html.contains("vpc"));
     }
 
+    #[test]
+    fn test_html_report_baseline_section() {
+        let mut result = create_test_result();
+        result.baseline = Some(BaselineResult {
+            known: vec![],
+            fixed: vec![crate::types::BaselineEntry {
+                code: "missing-version".to_string(),
+                repository: Some("test-repo".to_string()),
+                file: "main.tf".to_string(),
+                resource: Some("module.old".to_string()),
+                source: None,
+            }],
+        });
+
+        let html = HtmlReporter::new(&Config::default())
+            .generate(&result)
+            .unwrap();
+
+        assert!(html.contains(r#"id="baseline""#));
+        assert!(html.contains("module.old"));
+    }
+
+    #[test]
+    fn test_html_report_graph_section() {
+        use crate::graph::{DependencyGraph, EdgeType};
+        use crate::types::{FindingCategory, Location};
+
+        let mut result = create_test_result();
+        let html = HtmlReporter::new(&Config::default())
+            .generate(&result)
+            .unwrap();
+        assert!(!html.contains(r#"id="graph""#));
+
+        let mut graph = DependencyGraph::new();
+        let module = graph.add_module(&result.modules[0]);
+        let provider = graph.add_provider(&result.providers[0]);
+        graph.add_edge(
+            &module,
+            &provider,
+            EdgeType::ModuleRequiresProvider(RequirementOrigin::Declared),
+        );
+        result.graph = graph;
+        result.analysis.findings.push(Finding {
+            code: "missing-version".to_string(),
+            severity: Severity::Error,
+            message: "Module 'vpc' has no version constraint".to_string(),
+            description: None,
+            location: Some(Location {
+                file: PathBuf::from("main.tf"),
+                line: 1,
+                column: None,
+                repository: Some("test-repo".to_string()),
+            }),
+            related_locations: vec![],
+            suggestion: None,
+            category: FindingCategory::MissingConstraint,
+            suppressed_by: None,
+            resource: Some("module.vpc".to_string()),
+            source: Some(result.modules[0].source.canonical_id()),
+            origin: None,
+        });
+
+        let html = HtmlReporter::new(&Config::default())
+            .generate(&result)
+            .unwrap();
+
+        assert!(html.contains(r#"id="graph""#));
+        assert!(html.contains(r#"<option value="test-repo">"#));
+        assert!(html.contains(r#""kind":"module""#));
+        assert!(html.contains(r#""severity":"error""#));
+        assert!(html.contains(r#""type":"requires""#));
+        assert!(!html.contains("<script src="));
+        assert!(html.contains(r#"id="stats""#));
+        assert!(html.contains("No Shared Modules"));
+    }
+
+    /// The graph data embedded in a report.
+    fn graph_data(html: &str) -> serde_json::Value {
+        let start = html
+            .find(r#"<script type="application/json" id="graph-data">"#)
+            .expect("graph data");
+        let data = &html[start..];
+        let data = &data[data.find('>').unwrap() + 1..data.find("</script>").unwrap()];
+        serde_json::from_str(data).unwrap()
+    }
+
+    #[test]
+    fn test_graph_data_nodes_and_edges() {
+        use crate::graph::{DependencyGraph, EdgeType};
+
+        let mut result = create_test_result();
+        result.modules[0].version_constraint = Some(Constraint::parse("~> 5.0").unwrap());
+        let mut graph = DependencyGraph::new();
+        let module = graph.add_module(&result.modules[0]);
+        let provider = graph.add_provider(&result.providers[0]);
+        graph.add_edge(
+            &module,
+            &provider,
+            EdgeType::ModuleRequiresProvider(RequirementOrigin::Inferred),
+        );
+        result.graph = graph;
+
+        let data = graph_data(&generate_graph_html(&result));
+
+        let nodes = data["nodes"].as_array().unwrap();
+        assert_eq!(nodes.len(), 2);
+        let vpc = nodes.iter().find(|n| n["kind"] == "module").unwrap();
+        assert_eq!(vpc["id"], module.as_str());
+        assert_eq!(vpc["name"], "vpc");
+        assert_eq!(vpc["constraint"], "~> 5.0");
+        assert_eq!(vpc["repository"], "test-repo");
+        assert_eq!(vpc["file"], "main.tf");
+        assert_eq!(vpc["line"], 1);
+        assert!(vpc["severity"].is_null());
+        let aws = nodes.iter().find(|n| n["kind"] == "provider").unwrap();
+        assert_eq!(aws["id"], provider.as_str());
+        assert!(aws["constraint"].is_null());
+
+        assert_eq!(
+            data["edges"],
+            serde_json::json!([{
+                "from": module,
+                "to": provider,
+                "type": "requires",
+                "origin": "inferred",
+            }])
+        );
+    }
+
+    #[test]
+    fn test_graph_data_escapes_markup() {
+        use crate::graph::DependencyGraph;
+
+        let mut result = create_test_result();
+        let name = "</script><script>alert(1)</script>";
+        result.modules[0].name = name.to_string();
+        result.modules[0].repository = Some("<repo>".to_string());
+        let mut graph = DependencyGraph::new();
+        graph.add_module(&result.modules[0]);
+        result.graph = graph;
+
+        let html = generate_graph_html(&result);
+
+        // Only the closing tag of the data element itself
+        assert_eq!(html.matches("</script>").count(), 1);
+        assert!(!html.contains("<script>alert"));
+        assert!(html.contains(r#"<option value="&lt;repo&gt;">&lt;repo&gt;</option>"#));
+        let data = graph_data(&html);
+        assert_eq!(data["nodes"][0]["name"], name);
+        assert_eq!(data["nodes"][0]["repository"], "<repo>");
+    }
+
+    #[test]
+    fn test_graph_data_empty_graph() {
+        let result = create_test_result();
+
+        let html = generate_graph_html(&result);
+
+        assert_eq!(
+            graph_data(&html),
+            serde_json::json!({ "nodes": [], "edges": [] })
+        );
+        assert!(html.contains(r#"<option value="">All repositories</option>"#));
+        assert!(!html.contains(r#"<option value="test-repo">"#));
+    }
+
     #[test]
     fn test_html_escape() {
         assert_eq!(html_escape("<script>"), "&lt;script&gt;");
@@ -6,7 +6,9 @@
 use crate::config::Config;
 use crate::error::Result;
 use crate::reporter::ReportGenerator;
-use crate::types::{ScanResult, ScanWarning, Severity};
+use crate::types::{
+    BaselineEntry, DeprecationNotice, Finding, ScanResult, ScanWarning, Severity, VersionStatus,
+};
 use serde::Serialize;
 use std::collections::HashMap;
 
@@ -74,6 +76,18 @@ pub struct JsonReport {
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub findings: Vec<RepoFindings>,
 
+    /// Comparison against the baseline file, if one was given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<JsonBaseline>,
+
+    /// Upcoming and past retirements from dated deprecation rules, by date
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub deprecation_timeline: Vec<JsonTimelineEntry>,
+
+    /// How far registry modules and providers are behind their latest release
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub versions: Vec<JsonVersionStatus>,
+
     /// Full inventory of modules and providers
     pub inventory: Inventory,
 }
@@ -82,25 +96,28 @@ impl From<&ScanResult> for JsonReport {
     fn from(result: &ScanResult) -> Self {
         let errors = result
             .analysis
-            .findings
-            .iter()
+            .active_findings()
             .filter(|f| matches!(f.severity, Severity::Error | Severity::Critical))
             .count();
         let warnings = result
             .analysis
-            .findings
-            .iter()
+            .active_findings()
             .filter(|f| f.severity == Severity::Warning)
             .count();
         let infos = result
+            .analysis
+            .active_findings()
+            .filter(|f| f.severity == Severity::Info)
+            .count();
+        let suppressed = result
             .analysis
             .findings
             .iter()
-            .filter(|f| f.severity == Severity::Info)
+            .filter(|f| f.is_suppressed())
             .count();
 
         // Group findings by repository
-        let findings = group_findings_by_repo(result);
+        let findings = group_findings_by_repo(&result.analysis.findings);
 
         Self {
             meta: ReportMeta {
@@ -127,10 +144,11 @@ impl From<&ScanResult> for JsonReport {
                 },
             },
             summary: ReportSummary {
-                total_findings: result.analysis.findings.len(),
+                total_findings: result.analysis.findings.len() - suppressed,
                 errors,
                 warnings,
                 infos,
+                suppressed,
                 modules: ModuleSummary {
                     total: result.modules.len(),
                     with_issues: result
@@ -140,8 +158,7 @@ impl From<&ScanResult> for JsonReport {
                             let pattern = format!("'{}'", m.name);
                             result
                                 .analysis
-                                .findings
-                                .iter()
+                                .active_findings()
                                 .any(|f| f.message.contains(&pattern))
                         })
                         .count(),
@@ -160,8 +177,7 @@ impl From<&ScanResult> for JsonReport {
                             let pattern = format!("'{}'", p.name);
                             result
                                 .analysis
-                                .findings
-                                .iter()
+                                .active_findings()
                                 .any(|f| f.message.contains(&pattern))
                         })
                         .count(),
@@ -181,6 +197,25 @@ impl From<&ScanResult> for JsonReport {
             },
             scan_warnings: result.warnings.iter().map(JsonScanWarning::from).collect(),
             findings,
+            baseline: result.baseline.as_ref().map(|baseline| JsonBaseline {
+                known: baseline.known.len(),
+                fixed: baseline.fixed.len(),
+                known_findings: group_findings_by_repo(&baseline.known),
+                fixed_entries: baseline.fixed.clone(),
+            }),
+            deprecation_timeline: result
+                .analysis
+                .deprecations
+                .timeline()
+                .into_iter()
+                .map(JsonTimelineEntry::from)
+                .collect(),
+            versions: result
+                .analysis
+                .versions
+                .iter()
+                .map(JsonVersionStatus::from)
+                .collect(),
             inventory: Inventory {
                 modules: result.modules.iter().map(JsonModule::from).collect(),
                 providers: result.providers.iter().map(JsonProvider::from).collect(),
@@ -217,6 +252,143 @@ pub struct ReportStatus {
     pub message: String,
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// BASELINE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Comparison against a baseline file.
+///
+/// Known findings are excluded from `summary`, `status` and `findings`.
+#[derive(Debug, Serialize)]
+pub struct JsonBaseline {
+    /// Number of findings already recorded in the baseline
+    pub known: usize,
+    /// Number of baseline entries that no longer occur
+    pub fixed: usize,
+    /// Known findings grouped by repository
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub known_findings: Vec<RepoFindings>,
+    /// Baseline entries that can be removed from the file
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fixed_entries: Vec<BaselineEntry>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DEPRECATION TIMELINE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A dated deprecation rule matched by a module, provider or runtime.
+#[derive(Debug, Serialize)]
+pub struct JsonTimelineEntry {
+    /// Resource address (e.g., "module.eks")
+    pub resource: String,
+    /// Rule key the resource matched
+    pub source: String,
+    /// Where the rule was defined
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+    /// Deprecated version or git ref
+    pub version: String,
+    /// Current phase ("announced", "warning" or "enforced")
+    pub phase: String,
+    /// Severity once enforced
+    pub severity: String,
+    /// Enforcement date (YYYY-MM-DD)
+    pub enforce_from: String,
+    /// Start of the warning period (YYYY-MM-DD)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warn_from: Option<String>,
+    /// Days until enforcement (negative once enforced)
+    pub days_until_enforcement: i64,
+    /// Reason for the deprecation
+    pub reason: String,
+    /// Suggested replacement
+    pub replacement: String,
+    /// Repository name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    /// File where the resource is declared
+    pub file: String,
+    /// Line number
+    pub line: usize,
+}
+
+impl From<&DeprecationNotice> for JsonTimelineEntry {
+    fn from(notice: &DeprecationNotice) -> Self {
+        Self {
+            resource: notice.resource.clone(),
+            source: notice.source.clone(),
+            origin: notice.origin.clone(),
+            version: notice.matched.clone(),
+            phase: notice.phase.to_string(),
+            severity: notice.severity.to_string().to_lowercase(),
+            enforce_from: notice
+                .enforce_from
+                .map(|d| d.to_string())
+                .unwrap_or_default(),
+            warn_from: notice.warn_from.map(|d| d.to_string()),
+            days_until_enforcement: notice.days_until_enforcement.unwrap_or_default(),
+            reason: notice.reason.clone(),
+            replacement: notice.replacement.clone(),
+            repository: notice.location.repository.clone(),
+            file: notice.location.file.to_string_lossy().to_string(),
+            line: notice.location.line,
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// VERSION STATUS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Registry version status of a module or provider reference.
+#[derive(Debug, Serialize)]
+pub struct JsonVersionStatus {
+    /// Resource address (e.g., "module.vpc")
+    pub resource: String,
+    /// Registry address (e.g., "registry.terraform.io/hashicorp/aws")
+    pub source: String,
+    /// Version constraint, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constraint: Option<String>,
+    /// Newest published version the constraint allows
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub newest_allowed: Option<String>,
+    /// Publication date of the newest allowed version (YYYY-MM-DD)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub newest_allowed_published: Option<String>,
+    /// Latest published version
+    pub latest: String,
+    /// Number of releases newer than the newest allowed version
+    pub releases_behind: usize,
+    /// Repository name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    /// File where the resource is declared
+    pub file: String,
+    /// Line number
+    pub line: usize,
+}
+
+impl From<&VersionStatus> for JsonVersionStatus {
+    fn from(status: &VersionStatus) -> Self {
+        Self {
+            resource: status.resource.clone(),
+            source: status.source.clone(),
+            constraint: status.constraint.clone(),
+            newest_allowed: status.newest_allowed.clone(),
+            newest_allowed_published: status
+                .newest_allowed_published
+                .map(|d| d.date_naive().to_string()),
+            latest: status.latest.clone(),
+            releases_behind: status.releases_behind,
+            repository: status.location.repository.clone(),
+            file: status.location.file.to_string_lossy().to_string(),
+            line: status.location.line,
+        }
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // SCAN WARNINGS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -265,6 +437,8 @@ pub struct ReportSummary {
     pub warnings: usize,
     /// Info count
     pub infos: usize,
+    /// Findings silenced by inline suppressions (not counted above)
+    pub suppressed: usize,
     /// Module statistics
     pub modules: ModuleSummary,
     /// Provider statistics
@@ -341,6 +515,15 @@ pub struct JsonFinding {
     /// Related locations for context (e.g., conflicting constraints)
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub related: Vec<RelatedLocation>,
+    /// Whether an inline `# monphare:ignore` comment silenced this finding
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub suppressed: bool,
+    /// Reason given on the suppression comment (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suppression_reason: Option<String>,
+    /// Where the rule behind this finding was defined (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
 }
 
 /// A related location (for cross-references).
@@ -402,9 +585,11 @@ impl From<&crate::types::ModuleRef> for JsonModule {
             crate::types::ModuleSource::Registry { .. } => "registry",
             crate::types::ModuleSource::Git { .. } => "git",
             crate::types::ModuleSource::Local { .. } => "local",
+            crate::types::ModuleSource::Hg { .. } => "hg",
             crate::types::ModuleSource::Http { .. } => "http",
             crate::types::ModuleSource::S3 { .. } => "s3",
             crate::types::ModuleSource::Gcs { .. } => "gcs",
+            crate::types::ModuleSource::Oci { .. } => "oci",
             crate::types::ModuleSource::Unknown(_) => "unknown",
         };
 
@@ -475,10 +660,10 @@ pub struct JsonLocation {
 // ═══════════════════════════════════════════════════════════════════════════════
 
 /// Group findings by repository, then by file.
-fn group_findings_by_repo(result: &ScanResult) -> Vec<RepoFindings> {
+fn group_findings_by_repo(findings: &[Finding]) -> Vec<RepoFindings> {
     let mut by_repo: HashMap<String, HashMap<String, Vec<JsonFinding>>> = HashMap::new();
 
-    for finding in &result.analysis.findings {
+    for finding in findings {
         let (repo, file, line) = if let Some(loc) = &finding.location {
             (
                 loc.repository
@@ -508,6 +693,12 @@ fn group_findings_by_repo(result: &ScanResult) -> Vec<RepoFindings> {
                     line: loc.line,
                 })
                 .collect(),
+            suppressed: finding.is_suppressed(),
+            suppression_reason: finding
+                .suppressed_by
+                .as_ref()
+                .and_then(|s| s.reason.clone()),
+            origin: finding.origin.clone(),
         };
 
         by_repo
@@ -529,11 +720,13 @@ fn group_findings_by_repo(result: &ScanResult) -> Vec<RepoFindings> {
             let errors = file_findings
                 .iter()
                 .flat_map(|f| &f.findings)
+                .filter(|f| !f.suppressed)
                 .filter(|f| f.severity == "error" || f.severity == "critical")
                 .count();
             let warnings = file_findings
                 .iter()
                 .flat_map(|f| &f.findings)
+                .filter(|f| !f.suppressed)
                 .filter(|f| f.severity == "warning")
                 .count();
 
@@ -573,12 +766,15 @@ mod tests {
                     namespace: "terraform-aws-modules".to_string(),
                     name: "vpc".to_string(),
                     provider: "aws".to_string(),
+                    subdir: None,
+                    params: Default::default(),
                 },
                 version_constraint: None,
                 file_path: PathBuf::from("main.tf"),
                 line_number: 1,
                 repository: Some("test-repo".to_string()),
                 attributes: Default::default(),
+                dependencies: Default::default(),
             }],
             providers: vec![ProviderRef {
                 name: "aws".to_string(),
@@ -600,6 +796,9 @@ mod tests {
             graph: Default::default(),
             analysis: AnalysisResult::default(),
             warnings: Vec::new(),
+            baseline: None,
+            locks: Vec::new(),
+            roots: Vec::new(),
         }
     }
 
@@ -652,4 +851,136 @@ mod tests {
         assert!(parsed["inventory"]["modules"].is_array());
         assert!(parsed["inventory"]["providers"].is_array());
     }
+
+    #[test]
+    fn test_json_report_suppressed_finding() {
+        use crate::types::{Finding, FindingCategory, Location, Suppression, SuppressionScope};
+
+        let mut result = create_test_result();
+        result.analysis.findings.push(Finding {
+            code: "missing-version".to_string(),
+            severity: Severity::Error,
+            message: "Module 'vpc' has no version constraint".to_string(),
+            description: None,
+            location: Some(Location {
+                file: PathBuf::from("main.tf"),
+                line: 1,
+                column: None,
+                repository: Some("test-repo".to_string()),
+            }),
+            related_locations: vec![],
+            suggestion: None,
+            category: FindingCategory::MissingConstraint,
+            suppressed_by: Some(Suppression {
+                codes: vec!["missing-version".to_string()],
+                reason: Some("tracked upstream".to_string()),
+                file: PathBuf::from("main.tf"),
+                line: 1,
+                scope: SuppressionScope::File,
+                repository: Some("test-repo".to_string()),
+            }),
+            resource: Some("module.vpc".to_string()),
+            source: None,
+            origin: None,
+        });
+
+        let json = JsonReporter::new(&Config::default())
+            .generate(&result)
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert!(parsed["status"]["passed"].as_bool().unwrap());
+        assert_eq!(parsed["summary"]["errors"].as_u64().unwrap(), 0);
+        assert_eq!(parsed["summary"]["suppressed"].as_u64().unwrap(), 1);
+
+        let finding = &parsed["findings"][0]["files"][0]["findings"][0];
+        assert!(finding["suppressed"].as_bool().unwrap());
+        assert_eq!(finding["suppression_reason"], "tracked upstream");
+    }
+
+    #[test]
+    fn test_json_report_baseline_section() {
+        use crate::types::{BaselineResult, FindingCategory, Location};
+
+        let mut result = create_test_result();
+        result.baseline = Some(BaselineResult {
+            known: vec![Finding {
+                code: "exact-version".to_string(),
+                severity: Severity::Info,
+                message: "Module 'vpc' is pinned".to_string(),
+                description: None,
+                location: Some(Location {
+                    file: PathBuf::from("main.tf"),
+                    line: 1,
+                    column: None,
+                    repository: Some("test-repo".to_string()),
+                }),
+                related_locations: vec![],
+                suggestion: None,
+                category: FindingCategory::BestPractice,
+                suppressed_by: None,
+                resource: Some("module.vpc".to_string()),
+                source: None,
+                origin: None,
+            }],
+            fixed: vec![BaselineEntry {
+                code: "missing-version".to_string(),
+                repository: Some("test-repo".to_string()),
+                file: "main.tf".to_string(),
+                resource: Some("module.old".to_string()),
+                source: None,
+            }],
+        });
+
+        let json = JsonReporter::new(&Config::default())
+            .generate(&result)
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed["baseline"]["known"].as_u64().unwrap(), 1);
+        assert_eq!(parsed["baseline"]["fixed"].as_u64().unwrap(), 1);
+        assert_eq!(
+            parsed["baseline"]["fixed_entries"][0]["resource"],
+            "module.old"
+        );
+        assert_eq!(parsed["summary"]["infos"].as_u64().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_json_report_deprecation_timeline() {
+        use crate::types::{DeprecationNotice, DeprecationPhase, Location};
+
+        let mut result = create_test_result();
+        result.analysis.deprecations.notices = vec![DeprecationNotice {
+            resource: "module.vpc".to_string(),
+            source: "terraform-aws-modules/vpc/aws".to_string(),
+            origin: None,
+            matched: "< 5.0.0".to_string(),
+            reason: "v4 retired".to_string(),
+            replacement: ">= 5.0.0".to_string(),
+            severity: Severity::Error,
+            phase: DeprecationPhase::Warning,
+            announced: None,
+            warn_from: chrono::NaiveDate::from_ymd_opt(2025, 3, 1),
+            enforce_from: chrono::NaiveDate::from_ymd_opt(2025, 6, 30),
+            days_until_enforcement: Some(12),
+            location: Location {
+                file: PathBuf::from("main.tf"),
+                line: 1,
+                column: None,
+                repository: Some("test-repo".to_string()),
+            },
+        }];
+
+        let json = JsonReporter::new(&Config::default())
+            .generate(&result)
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        let entry = &parsed["deprecation_timeline"][0];
+        assert_eq!(entry["resource"], "module.vpc");
+        assert_eq!(entry["phase"], "warning");
+        assert_eq!(entry["enforce_from"], "2025-06-30");
+        assert_eq!(entry["days_until_enforcement"].as_i64().unwrap(), 12);
+    }
 }
@@ -4,6 +4,7 @@
 //! - JSON: Machine-readable structured output
 //! - Text: Human-readable CLI output
 //! - HTML: Self-contained visual reports
+//! - CycloneDX and SPDX: Software bills of materials
 //!
 //! # Example
 //!
@@ -20,16 +21,24 @@
 //! // let html = reporter.generate(&result, ReportFormat::Html)?;
 //! ```
 
+mod cyclonedx;
 mod html;
 mod json;
+mod sbom;
+mod spdx;
 mod text;
 
 use crate::config::Config;
 use crate::error::Result;
-use crate::types::{ReportFormat, ScanResult};
+use crate::graph::GraphNode;
+use crate::types::{Finding, ReportFormat, ScanResult};
+use std::collections::HashMap;
+use std::path::Path;
 
+pub use cyclonedx::CycloneDxReporter;
 pub use html::HtmlReporter;
 pub use json::JsonReporter;
+pub use spdx::SpdxReporter;
 pub use text::TextReporter;
 
 /// Report generator that supports multiple output formats.
@@ -56,6 +65,8 @@ impl Reporter {
             ReportFormat::Json => JsonReporter::new(&self.config).generate(result),
             ReportFormat::Text => TextReporter::new(&self.config).generate(result),
             ReportFormat::Html => HtmlReporter::new(&self.config).generate(result),
+            ReportFormat::Cyclonedx => CycloneDxReporter::new(&self.config).generate(result),
+            ReportFormat::Spdx => SpdxReporter::new(&self.config).generate(result),
         }
     }
 }
@@ -69,3 +80,52 @@ pub trait ReportGenerator {
     /// Returns an error if generation fails.
     fn generate(&self, result: &ScanResult) -> Result<String>;
 }
+
+/// Active findings of each graph node, keyed by node ID.
+///
+/// Module findings are matched by repository, name and source, provider
+/// findings by repository and source, and anything else by file and line.
+pub(crate) fn findings_by_node(result: &ScanResult) -> HashMap<&str, Vec<&Finding>> {
+    let mut by_resource: HashMap<(Option<&str>, String), &str> = HashMap::new();
+    let mut by_location: HashMap<(&Path, usize), &str> = HashMap::new();
+    for node in result.graph.nodes() {
+        let (resource, file, line) = match node {
+            GraphNode::Module(m) => (
+                format!("module.{}|{}", m.name, m.source.canonical_id()),
+                &m.file_path,
+                m.line_number,
+            ),
+            GraphNode::Provider(p) => (
+                format!("provider|{}", p.source),
+                &p.file_path,
+                p.line_number,
+            ),
+            GraphNode::Runtime(r) => (format!("runtime|{}", r.name), &r.file_path, r.line_number),
+        };
+        by_resource.insert((node.repository(), resource), node.id());
+        by_location.insert((file.as_path(), line), node.id());
+    }
+
+    let mut findings: HashMap<&str, Vec<&Finding>> = HashMap::new();
+    for finding in result.analysis.active_findings() {
+        let Some(location) = &finding.location else {
+            continue;
+        };
+        let resource = match (finding.resource.as_deref(), finding.source.as_deref()) {
+            (Some(resource), Some(source)) if resource.starts_with("module.") => {
+                Some(format!("{resource}|{source}"))
+            }
+            (Some(resource), Some(source)) if resource.starts_with("provider.") => {
+                Some(format!("provider|{source}"))
+            }
+            _ => None,
+        };
+        let node = resource
+            .and_then(|key| by_resource.get(&(location.repository.as_deref(), key)))
+            .or_else(|| by_location.get(&(location.file.as_path(), location.line)));
+        if let Some(&id) = node {
+            findings.entry(id).or_default().push(finding);
+        }
+    }
+    findings
+}
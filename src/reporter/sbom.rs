@@ -0,0 +1,375 @@
+//! Shared model for the CycloneDX and SPDX software bill of materials.
+//!
+//! Every module and provider node of the dependency graph becomes a
+//! component, identified by its node ID. Graph edges between them become
+//! dependency relationships, and deprecation and advisory findings on a node
+//! are attached to its component.
+
+use crate::graph::GraphNode;
+use crate::reporter::findings_by_node;
+use crate::types::{
+    Finding, FindingCategory, ModuleSource, ProviderLock, ScanResult, Severity, VersionRange,
+};
+use crate::vcs::VcsIdentifier;
+use std::path::Path;
+
+/// Hostname of the public Terraform registry, left out of package URLs.
+const DEFAULT_REGISTRY: &str = "registry.terraform.io";
+
+/// A module or provider in the bill of materials.
+pub(crate) struct Component<'a> {
+    /// The graph node the component describes
+    pub node: &'a GraphNode,
+    /// Package URL (`pkg:terraform/...`), if the source has a stable address
+    pub purl: Option<String>,
+    /// Resolved version: the locked version, an exact pin, or a tag
+    pub version: Option<String>,
+    /// Version constraint as written
+    pub constraint: Option<&'a str>,
+    /// Lock file the version was read from
+    pub lock: Option<&'a ProviderLock>,
+    /// Version control tool (`git` or `hg`) and URL the source is fetched from
+    pub vcs_url: Option<(&'static str, String)>,
+    /// Where the source is downloaded from (HTTP or OCI)
+    pub distribution_url: Option<String>,
+    /// VCS namespace of the repository the dependency was found in
+    pub vcs: Option<&'a VcsIdentifier>,
+    /// Deprecation and advisory findings on the component
+    pub vulnerabilities: Vec<&'a Finding>,
+}
+
+impl Component<'_> {
+    /// `module` or `provider`.
+    pub const fn kind(&self) -> &'static str {
+        match self.node {
+            GraphNode::Provider(_) => "provider",
+            _ => "module",
+        }
+    }
+}
+
+/// Collect the components and dependency relationships of a scan.
+///
+/// Relationships are `(dependent, dependency)` node ID pairs; edges to
+/// runtime nodes are left out.
+pub(crate) fn collect(result: &ScanResult) -> (Vec<Component<'_>>, Vec<(&str, &str)>) {
+    let mut findings = findings_by_node(result);
+
+    let mut components: Vec<Component<'_>> = result
+        .graph
+        .nodes()
+        .filter(|node| !node.is_runtime())
+        .map(|node| {
+            let vulnerabilities = findings
+                .remove(node.id())
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|f| {
+                    matches!(
+                        f.category,
+                        FindingCategory::Deprecated | FindingCategory::Security
+                    )
+                })
+                .collect();
+            let vcs = result.graph.get_vcs_metadata(&node.id().to_string());
+            match node {
+                GraphNode::Module(m) => {
+                    let version = m
+                        .version_constraint
+                        .as_ref()
+                        .and_then(exact_version)
+                        .or_else(|| source_version(&m.source));
+                    let (vcs_url, distribution_url) = source_urls(&m.source);
+                    Component {
+                        node,
+                        purl: module_purl(&m.source, version.as_deref()),
+                        version,
+                        constraint: m.version_constraint.as_ref().map(|c| c.raw.as_str()),
+                        lock: None,
+                        vcs_url,
+                        distribution_url,
+                        vcs,
+                        vulnerabilities,
+                    }
+                }
+                GraphNode::Provider(p) => {
+                    let lock = find_lock(&result.locks, &p.source, &p.file_path, node.repository());
+                    let version = lock
+                        .map(|l| l.version.clone())
+                        .or_else(|| p.version_constraint.as_ref().and_then(exact_version));
+                    Component {
+                        node,
+                        purl: provider_purl(&p.source, version.as_deref()),
+                        version,
+                        constraint: p.version_constraint.as_ref().map(|c| c.raw.as_str()),
+                        lock,
+                        vcs_url: None,
+                        distribution_url: None,
+                        vcs,
+                        vulnerabilities,
+                    }
+                }
+                GraphNode::Runtime(_) => unreachable!("runtime nodes are filtered out"),
+            }
+        })
+        .collect();
+    components.sort_by(|a, b| a.node.id().cmp(b.node.id()));
+
+    let mut dependencies: Vec<(&str, &str)> = result
+        .graph
+        .edges()
+        .filter(|(from, to, _)| !from.is_runtime() && !to.is_runtime())
+        .map(|(from, to, _)| (from.id(), to.id()))
+        .collect();
+    dependencies.sort_unstable();
+    dependencies.dedup();
+
+    (components, dependencies)
+}
+
+/// Lowercase severity name shared by both formats' ratings.
+pub(crate) const fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "critical",
+        Severity::Error => "high",
+        Severity::Warning => "medium",
+        Severity::Info => "info",
+    }
+}
+
+/// Find the lock entry of a provider, preferring the lock file next to the
+/// file that requires it.
+fn find_lock<'a>(
+    locks: &'a [ProviderLock],
+    source: &str,
+    file_path: &Path,
+    repository: Option<&str>,
+) -> Option<&'a ProviderLock> {
+    let mut candidates = locks
+        .iter()
+        .filter(|l| l.repository.as_deref() == repository && l.locks(source));
+    let directory = file_path.parent();
+    candidates
+        .clone()
+        .find(|l| l.file_path.parent() == directory)
+        .or_else(|| candidates.next())
+}
+
+/// The version a constraint pins, if it allows exactly one.
+fn exact_version(constraint: &crate::types::Constraint) -> Option<String> {
+    match constraint.ranges.as_slice() {
+        [VersionRange::Exact(v)] => Some(v.to_string()),
+        _ => None,
+    }
+}
+
+/// The version a source selects itself (git ref, hg revision, OCI tag).
+fn source_version(source: &ModuleSource) -> Option<String> {
+    match source {
+        ModuleSource::Git { ref_, .. } => ref_.clone(),
+        ModuleSource::Hg { rev, .. } => rev.clone(),
+        ModuleSource::Oci { tag, digest, .. } => digest.clone().or_else(|| tag.clone()),
+        _ => None,
+    }
+}
+
+/// VCS and distribution URLs of a module source.
+fn source_urls(source: &ModuleSource) -> (Option<(&'static str, String)>, Option<String>) {
+    match source {
+        ModuleSource::Git { url, .. } => (Some(("git", url.clone())), None),
+        ModuleSource::Hg { url, .. } => (Some(("hg", url.clone())), None),
+        ModuleSource::Http { url, .. } => (None, Some(url.clone())),
+        ModuleSource::Oci {
+            registry,
+            repository,
+            ..
+        } => (None, Some(format!("oci://{registry}/{repository}"))),
+        _ => (None, None),
+    }
+}
+
+/// Package URL of a module.
+///
+/// Registry modules use `namespace/name/provider`, git and OCI modules the
+/// segments of their address. Local, archive and bucket sources have no
+/// package URL.
+fn module_purl(source: &ModuleSource, version: Option<&str>) -> Option<String> {
+    match source {
+        ModuleSource::Registry {
+            hostname,
+            namespace,
+            name,
+            provider,
+            subdir,
+            ..
+        } => {
+            let qualifiers = registry_qualifier(hostname);
+            Some(purl(
+                &[namespace, name, provider],
+                version,
+                &qualifiers,
+                subdir.as_deref(),
+            ))
+        }
+        ModuleSource::Git {
+            host, url, subdir, ..
+        } => {
+            let segments: Vec<&str> = host.split('/').filter(|s| !s.is_empty()).collect();
+            let vcs_url = format!("git+{url}");
+            Some(purl(
+                &segments,
+                version,
+                &[("vcs_url", vcs_url.as_str())],
+                subdir.as_deref(),
+            ))
+        }
+        ModuleSource::Oci {
+            registry,
+            repository,
+            subdir,
+            ..
+        } => {
+            let segments: Vec<&str> = std::iter::once(registry.as_str())
+                .chain(repository.split('/'))
+                .collect();
+            Some(purl(&segments, version, &[], subdir.as_deref()))
+        }
+        _ => None,
+    }
+}
+
+/// Package URL of a provider: `namespace/type`, plus the registry host
+/// when it is not the public registry.
+fn provider_purl(source: &str, version: Option<&str>) -> Option<String> {
+    let parts: Vec<&str> = source.split('/').collect();
+    match parts.as_slice() {
+        [namespace, name] => Some(purl(&[namespace, name], version, &[], None)),
+        [hostname, namespace, name] => {
+            let qualifiers = registry_qualifier(hostname);
+            Some(purl(&[namespace, name], version, &qualifiers, None))
+        }
+        _ => None,
+    }
+}
+
+fn registry_qualifier(hostname: &str) -> Vec<(&'static str, &str)> {
+    if hostname.eq_ignore_ascii_case(DEFAULT_REGISTRY) {
+        Vec::new()
+    } else {
+        vec![("repository_url", hostname)]
+    }
+}
+
+/// Assemble a `pkg:terraform/...` package URL.
+fn purl(
+    segments: &[&str],
+    version: Option<&str>,
+    qualifiers: &[(&str, &str)],
+    subpath: Option<&str>,
+) -> String {
+    let path: Vec<String> = segments.iter().map(|s| percent_encode(s, "")).collect();
+    let mut purl = format!("pkg:terraform/{}", path.join("/"));
+    if let Some(version) = version {
+        purl.push('@');
+        purl.push_str(&percent_encode(version, ""));
+    }
+    if !qualifiers.is_empty() {
+        let query: Vec<String> = qualifiers
+            .iter()
+            .map(|(k, v)| format!("{k}={}", percent_encode(v, ":/")))
+            .collect();
+        purl.push('?');
+        purl.push_str(&query.join("&"));
+    }
+    if let Some(subpath) = subpath.filter(|s| !s.is_empty()) {
+        let parts: Vec<String> = subpath
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(|s| percent_encode(s, ""))
+            .collect();
+        purl.push('#');
+        purl.push_str(&parts.join("/"));
+    }
+    purl
+}
+
+/// Percent-encode everything but unreserved characters and `keep`.
+fn percent_encode(s: &str, keep: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for b in s.bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~".contains(&b) || keep.as_bytes().contains(&b) {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{b:02X}"));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_module_purl() {
+        let registry = ModuleSource::Registry {
+            hostname: "registry.terraform.io".to_string(),
+            namespace: "terraform-aws-modules".to_string(),
+            name: "vpc".to_string(),
+            provider: "aws".to_string(),
+            subdir: None,
+            params: Default::default(),
+        };
+        assert_eq!(
+            module_purl(&registry, Some("5.1.0")).unwrap(),
+            "pkg:terraform/terraform-aws-modules/vpc/aws@5.1.0"
+        );
+
+        let private = ModuleSource::Registry {
+            hostname: "app.terraform.io".to_string(),
+            namespace: "acme".to_string(),
+            name: "network".to_string(),
+            provider: "azurerm".to_string(),
+            subdir: None,
+            params: Default::default(),
+        };
+        assert_eq!(
+            module_purl(&private, None).unwrap(),
+            "pkg:terraform/acme/network/azurerm?repository_url=app.terraform.io"
+        );
+
+        let git = ModuleSource::Git {
+            host: "github.com/acme/modules".to_string(),
+            url: "https://github.com/acme/modules.git".to_string(),
+            ref_: Some("v1.2.0".to_string()),
+            subdir: Some("vpc".to_string()),
+            params: BTreeMap::new(),
+        };
+        assert_eq!(
+            module_purl(&git, source_version(&git).as_deref()).unwrap(),
+            "pkg:terraform/github.com/acme/modules@v1.2.0?vcs_url=git%2Bhttps://github.com/acme/modules.git#vpc"
+        );
+
+        let local = ModuleSource::Local {
+            path: "../vpc".to_string(),
+        };
+        assert_eq!(module_purl(&local, None), None);
+    }
+
+    #[test]
+    fn test_provider_purl() {
+        assert_eq!(
+            provider_purl("hashicorp/aws", Some("5.31.0")).unwrap(),
+            "pkg:terraform/hashicorp/aws@5.31.0"
+        );
+        assert_eq!(
+            provider_purl("registry.terraform.io/hashicorp/aws", None).unwrap(),
+            "pkg:terraform/hashicorp/aws"
+        );
+        assert_eq!(
+            provider_purl("tf.acme.io/acme/internal", None).unwrap(),
+            "pkg:terraform/acme/internal?repository_url=tf.acme.io"
+        );
+    }
+}
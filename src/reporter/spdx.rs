@@ -0,0 +1,357 @@
+//! SPDX 2.3 JSON software bill of materials.
+//!
+//! Modules and providers are packages with a `purl` external reference,
+//! graph edges become `DEPENDS_ON` relationships, and deprecation and
+//! advisory findings are attached as `REVIEW` annotations, since SPDX 2.3
+//! has no vulnerability section.
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::reporter::sbom::{self, Component};
+use crate::reporter::ReportGenerator;
+use crate::types::ScanResult;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// SPDX specification version the document conforms to.
+const SPDX_VERSION: &str = "SPDX-2.3";
+
+/// Placeholder for values the scan cannot determine.
+const NOASSERTION: &str = "NOASSERTION";
+
+/// SPDX report generator.
+pub struct SpdxReporter {
+    /// Whether to pretty-print the output
+    pretty: bool,
+}
+
+impl SpdxReporter {
+    /// Create a new SPDX reporter.
+    #[must_use]
+    pub fn new(config: &Config) -> Self {
+        Self {
+            pretty: config.output.pretty,
+        }
+    }
+}
+
+impl ReportGenerator for SpdxReporter {
+    fn generate(&self, result: &ScanResult) -> Result<String> {
+        let document = Document::from(result);
+
+        let json = if self.pretty {
+            serde_json::to_string_pretty(&document)
+        } else {
+            serde_json::to_string(&document)
+        };
+
+        json.map_err(|e| {
+            crate::err!(ReportGeneration {
+                message: format!("Failed to serialize SPDX report: {e}"),
+            })
+        })
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Document {
+    spdx_version: &'static str,
+    data_license: &'static str,
+    #[serde(rename = "SPDXID")]
+    spdx_id: &'static str,
+    name: String,
+    document_namespace: String,
+    creation_info: CreationInfo,
+    packages: Vec<Package>,
+    relationships: Vec<Relationship>,
+}
+
+#[derive(Serialize)]
+struct CreationInfo {
+    created: String,
+    creators: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Package {
+    #[serde(rename = "SPDXID")]
+    spdx_id: String,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version_info: Option<String>,
+    download_location: String,
+    files_analyzed: bool,
+    license_concluded: &'static str,
+    license_declared: &'static str,
+    copyright_text: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_info: Option<String>,
+    comment: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    external_refs: Vec<ExternalRef>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    annotations: Vec<Annotation>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExternalRef {
+    reference_category: &'static str,
+    reference_type: &'static str,
+    reference_locator: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Annotation {
+    annotation_date: String,
+    annotation_type: &'static str,
+    annotator: String,
+    comment: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Relationship {
+    spdx_element_id: String,
+    relationship_type: &'static str,
+    related_spdx_element: String,
+}
+
+impl From<&ScanResult> for Document {
+    fn from(result: &ScanResult) -> Self {
+        let now = chrono::Utc::now();
+        let created = now.to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        let tool = format!("Tool: monphare-{}", env!("CARGO_PKG_VERSION"));
+        let (components, edges) = sbom::collect(result);
+
+        let ids: HashMap<&str, String> = components
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (c.node.id(), spdx_id(c, i + 1)))
+            .collect();
+
+        let mut relationships: Vec<Relationship> = components
+            .iter()
+            .map(|c| Relationship {
+                spdx_element_id: "SPDXRef-DOCUMENT".to_string(),
+                relationship_type: "DESCRIBES",
+                related_spdx_element: ids[c.node.id()].clone(),
+            })
+            .collect();
+        relationships.extend(edges.into_iter().map(|(from, to)| Relationship {
+            spdx_element_id: ids[from].clone(),
+            relationship_type: "DEPENDS_ON",
+            related_spdx_element: ids[to].clone(),
+        }));
+
+        Self {
+            spdx_version: SPDX_VERSION,
+            data_license: "CC0-1.0",
+            spdx_id: "SPDXRef-DOCUMENT",
+            name: "monphare-terraform-dependencies".to_string(),
+            document_namespace: format!(
+                "https://spdx.org/spdxdocs/monphare-{}",
+                now.format("%Y%m%dT%H%M%S%.fZ")
+            ),
+            creation_info: CreationInfo {
+                created: created.clone(),
+                creators: vec![tool.clone()],
+            },
+            packages: components
+                .iter()
+                .map(|c| package(c, ids[c.node.id()].clone(), &created, &tool))
+                .collect(),
+            relationships,
+        }
+    }
+}
+
+fn package(c: &Component<'_>, spdx_id: String, created: &str, tool: &str) -> Package {
+    let mut external_refs = Vec::new();
+    if let Some(purl) = &c.purl {
+        external_refs.push(ExternalRef {
+            reference_category: "PACKAGE-MANAGER",
+            reference_type: "purl",
+            reference_locator: purl.clone(),
+            comment: None,
+        });
+    }
+
+    let annotations = c
+        .vulnerabilities
+        .iter()
+        .map(|finding| Annotation {
+            annotation_date: created.to_string(),
+            annotation_type: "REVIEW",
+            annotator: tool.to_string(),
+            comment: format!(
+                "[{}] {} ({}): {}",
+                sbom::severity_name(finding.severity),
+                finding.code,
+                finding.category,
+                finding.message
+            ),
+        })
+        .collect();
+
+    let mut comment = vec![format!("{} {}", c.kind(), c.node.source())];
+    if let Some(constraint) = c.constraint {
+        comment.push(format!("version constraint: {constraint}"));
+    }
+    if let Some(lock) = c.lock {
+        comment.push(format!("locked in {}", lock.file_path.display()));
+    }
+    if let Some(repository) = c.node.repository() {
+        comment.push(format!("declared in repository {repository}"));
+    }
+
+    Package {
+        spdx_id,
+        name: c.node.display_name().to_string(),
+        version_info: c.version.clone(),
+        download_location: c
+            .vcs_url
+            .as_ref()
+            .map(|(tool, url)| format!("{tool}+{url}"))
+            .or_else(|| c.distribution_url.clone())
+            .unwrap_or_else(|| NOASSERTION.to_string()),
+        files_analyzed: false,
+        license_concluded: NOASSERTION,
+        license_declared: NOASSERTION,
+        copyright_text: NOASSERTION,
+        source_info: c.vcs.map(|vcs| format!("found in {}", vcs.canonical)),
+        comment: comment.join("; "),
+        external_refs,
+        annotations,
+    }
+}
+
+/// Build an `SPDXRef-` identifier, which may only contain letters,
+/// digits, `.` and `-`.
+fn spdx_id(c: &Component<'_>, index: usize) -> String {
+    let name: String = c
+        .node
+        .display_name()
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '.' {
+                ch
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("SPDXRef-{}-{index}-{name}", c.kind())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DependencyGraph, EdgeType, RequirementOrigin};
+    use crate::types::{
+        Finding, FindingCategory, Location, ModuleRef, ModuleSource, ProviderRef, Severity,
+    };
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_spdx_report() {
+        let module = ModuleRef {
+            name: "network_core".to_string(),
+            source: ModuleSource::Git {
+                host: "github.com/acme/network".to_string(),
+                url: "https://github.com/acme/network.git".to_string(),
+                ref_: Some("v2.0.0".to_string()),
+                subdir: None,
+                params: BTreeMap::new(),
+            },
+            version_constraint: None,
+            file_path: PathBuf::from("main.tf"),
+            line_number: 1,
+            repository: Some("infra".to_string()),
+            attributes: Default::default(),
+            dependencies: Default::default(),
+        };
+        let provider = ProviderRef {
+            name: "aws".to_string(),
+            source: Some("hashicorp/aws".to_string()),
+            version_constraint: None,
+            file_path: PathBuf::from("versions.tf"),
+            line_number: 3,
+            repository: Some("infra".to_string()),
+        };
+        let mut graph = DependencyGraph::new();
+        let module_id = graph.add_module(&module);
+        let provider_id = graph.add_provider(&provider);
+        graph.add_edge(
+            &module_id,
+            &provider_id,
+            EdgeType::ModuleRequiresProvider(RequirementOrigin::Declared),
+        );
+        let mut result = ScanResult {
+            modules: vec![module],
+            providers: vec![provider],
+            graph,
+            ..Default::default()
+        };
+        result.analysis.findings.push(Finding {
+            code: "security-advisory".to_string(),
+            severity: Severity::Critical,
+            message: "Provider 'aws' allows versions affected by TFSEC-1".to_string(),
+            description: None,
+            location: Some(Location {
+                file: PathBuf::from("versions.tf"),
+                line: 3,
+                column: None,
+                repository: Some("infra".to_string()),
+            }),
+            related_locations: vec![],
+            suggestion: None,
+            category: FindingCategory::Security,
+            suppressed_by: None,
+            resource: Some("provider.aws".to_string()),
+            source: Some("hashicorp/aws".to_string()),
+            origin: None,
+        });
+
+        let json = SpdxReporter::new(&Config::default())
+            .generate(&result)
+            .unwrap();
+        let document: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(document["spdxVersion"], SPDX_VERSION);
+        let packages = document["packages"].as_array().unwrap();
+        assert_eq!(packages.len(), 2);
+
+        let module = &packages[0];
+        assert_eq!(module["SPDXID"], "SPDXRef-module-1-network-core");
+        assert_eq!(module["versionInfo"], "v2.0.0");
+        assert_eq!(
+            module["downloadLocation"],
+            "git+https://github.com/acme/network.git"
+        );
+
+        let provider = &packages[1];
+        assert_eq!(
+            provider["externalRefs"][0]["referenceLocator"],
+            "pkg:terraform/hashicorp/aws"
+        );
+        assert!(provider["annotations"][0]["comment"]
+            .as_str()
+            .unwrap()
+            .contains("security-advisory"));
+
+        assert!(document["relationships"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|r| r["relationshipType"] == "DEPENDS_ON"
+                && r["spdxElementId"] == module["SPDXID"]
+                && r["relatedSpdxElement"] == provider["SPDXID"]));
+    }
+}
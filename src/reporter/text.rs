@@ -4,7 +4,10 @@
 use crate::config::Config;
 use crate::error::Result;
 use crate::reporter::ReportGenerator;
-use crate::types::{Finding, ScanResult, ScanWarning, Severity};
+use crate::types::{
+    BaselineResult, DeprecationNotice, DeprecationPhase, Finding, ScanResult, ScanWarning,
+    Severity, VersionStatus,
+};
 use colored::Colorize;
 use comfy_table::{presets, Attribute, Cell, Color, ContentArrangement, Table};
 use std::collections::HashMap;
@@ -42,12 +45,34 @@ impl ReportGenerator for TextReporter {
         }
 
         // Issues grouped by repository
-        if !result.analysis.findings.is_empty() {
+        if result.analysis.active_findings().next().is_some() {
             output.push_str(&self.format_issues_by_repo(result));
         } else {
             output.push_str(&self.format_no_issues());
         }
 
+        // Upcoming retirements from dated deprecation rules
+        let timeline = result.analysis.deprecations.timeline();
+        if !timeline.is_empty() {
+            output.push_str(&self.format_timeline(&timeline));
+        }
+
+        // Registry references behind their latest release
+        let behind: Vec<&VersionStatus> = result
+            .analysis
+            .versions
+            .iter()
+            .filter(|s| s.releases_behind > 0)
+            .collect();
+        if !behind.is_empty() {
+            output.push_str(&self.format_version_status(&behind));
+        }
+
+        // Findings already recorded in the baseline
+        if let Some(baseline) = &result.baseline {
+            output.push_str(&self.format_baseline(baseline));
+        }
+
         // Footer
         output.push_str(&self.format_footer(result));
 
@@ -94,8 +119,20 @@ impl TextReporter {
             format!("{warnings} warnings")
         };
 
+        let suppressed = result
+            .analysis
+            .findings
+            .iter()
+            .filter(|f| f.is_suppressed())
+            .count();
+        let suppressed_str = if suppressed > 0 {
+            format!(", {suppressed} suppressed")
+        } else {
+            String::new()
+        };
+
         format!(
-            "\nMonPhare v{version}  [{status}]  {errors_str}, {warnings_str}\n\
+            "\nMonPhare v{version}  [{status}]  {errors_str}, {warnings_str}{suppressed_str}\n\
              Scanned: {} files, {} modules, {} providers\n\n",
             result.files_scanned.len(),
             result.modules.len(),
@@ -109,7 +146,7 @@ impl TextReporter {
 
         // Group findings by repository
         let mut by_repo: HashMap<String, Vec<&Finding>> = HashMap::new();
-        for finding in &result.analysis.findings {
+        for finding in result.analysis.active_findings() {
             let repo = finding
                 .location
                 .as_ref()
@@ -171,7 +208,7 @@ impl TextReporter {
 
             // Sort findings: errors first, then warnings, then info
             let mut sorted_findings = findings.clone();
-            sorted_findings.sort_by(|a, b| b.severity.cmp(&a.severity));
+            sorted_findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
 
             for finding in sorted_findings {
                 let (sev_text, sev_color) = match finding.severity {
@@ -190,7 +227,10 @@ impl TextReporter {
                 };
 
                 // Extract resource name from message (e.g., "Module 'vpc' has no...")
-                let resource = extract_resource_name(&finding.message, &finding.category);
+                let resource = finding
+                    .resource
+                    .clone()
+                    .unwrap_or_else(|| extract_resource_name(&finding.message, &finding.category));
 
                 // Short issue description
                 let issue = short_issue_description(&finding.code, &finding.category);
@@ -294,6 +334,181 @@ impl TextReporter {
         output
     }
 
+    /// Format the retirement timeline of dated deprecation rules.
+    fn format_timeline(&self, timeline: &[&DeprecationNotice]) -> String {
+        let mut output = String::new();
+
+        let header = if self.use_colors {
+            format!("  {}\n", "RETIREMENT TIMELINE".magenta().bold())
+        } else {
+            "  RETIREMENT TIMELINE\n".to_string()
+        };
+        output.push_str(&header);
+
+        let mut table = Table::new();
+        table
+            .load_preset(presets::UTF8_FULL_CONDENSED)
+            .set_content_arrangement(ContentArrangement::Dynamic)
+            .set_header(vec![
+                Cell::new("Enforced").add_attribute(Attribute::Bold),
+                Cell::new("Status").add_attribute(Attribute::Bold),
+                Cell::new("Resource").add_attribute(Attribute::Bold),
+                Cell::new("Version").add_attribute(Attribute::Bold),
+                Cell::new("Replacement").add_attribute(Attribute::Bold),
+                Cell::new("File").add_attribute(Attribute::Bold),
+            ]);
+
+        for notice in timeline {
+            let date = notice
+                .enforce_from
+                .map_or_else(|| "-".to_string(), |d| d.to_string());
+            let (status, color) = match (notice.phase, notice.days_until_enforcement) {
+                (DeprecationPhase::Enforced, _) => ("enforced".to_string(), Color::Red),
+                (_, Some(days)) => (format!("in {days} days"), Color::Yellow),
+                (_, None) => (notice.phase.to_string(), Color::Yellow),
+            };
+            let status_cell = if self.use_colors {
+                Cell::new(status).fg(color)
+            } else {
+                Cell::new(status)
+            };
+            let file = format!(
+                "{}:{}",
+                extract_relative_path(
+                    &notice.location.file.to_string_lossy(),
+                    notice.location.repository.as_deref()
+                ),
+                notice.location.line
+            );
+
+            table.add_row(vec![
+                Cell::new(date),
+                status_cell,
+                Cell::new(&notice.resource),
+                Cell::new(shorten_str(&notice.matched, 15)),
+                Cell::new(shorten_str(&notice.replacement, 25)),
+                Cell::new(file),
+            ]);
+        }
+
+        output.push_str(&table.to_string());
+        output.push_str("\n\n");
+
+        output
+    }
+
+    /// Format registry references that are behind their latest release.
+    fn format_version_status(&self, statuses: &[&VersionStatus]) -> String {
+        let mut output = String::new();
+
+        let header = if self.use_colors {
+            format!("  {}\n", "VERSION STATUS".blue().bold())
+        } else {
+            "  VERSION STATUS\n".to_string()
+        };
+        output.push_str(&header);
+
+        let mut table = Table::new();
+        table
+            .load_preset(presets::UTF8_FULL_CONDENSED)
+            .set_content_arrangement(ContentArrangement::Dynamic)
+            .set_header(vec![
+                Cell::new("Resource").add_attribute(Attribute::Bold),
+                Cell::new("Constraint").add_attribute(Attribute::Bold),
+                Cell::new("Allowed").add_attribute(Attribute::Bold),
+                Cell::new("Latest").add_attribute(Attribute::Bold),
+                Cell::new("Behind").add_attribute(Attribute::Bold),
+                Cell::new("File").add_attribute(Attribute::Bold),
+            ]);
+
+        for status in statuses {
+            let file = format!(
+                "{}:{}",
+                extract_relative_path(
+                    &status.location.file.to_string_lossy(),
+                    status.location.repository.as_deref()
+                ),
+                status.location.line
+            );
+
+            table.add_row(vec![
+                Cell::new(&status.resource),
+                Cell::new(shorten_str(status.constraint.as_deref().unwrap_or("-"), 15)),
+                Cell::new(status.newest_allowed.as_deref().unwrap_or("-")),
+                Cell::new(&status.latest),
+                Cell::new(status.releases_behind),
+                Cell::new(file),
+            ]);
+        }
+
+        output.push_str(&table.to_string());
+        output.push_str("\n\n");
+
+        output
+    }
+
+    /// Format the baseline comparison: known counts and fixed entries.
+    ///
+    /// Known findings are only listed in verbose mode; fixed entries are always
+    /// listed so the baseline file can be tightened.
+    fn format_baseline(&self, baseline: &BaselineResult) -> String {
+        let mut output = String::new();
+
+        let counts = format!(
+            "({} known, {} fixed)",
+            baseline.known.len(),
+            baseline.fixed.len()
+        );
+        let header = if self.use_colors {
+            format!("  {} {}\n", "BASELINE".cyan().bold(), counts.dimmed())
+        } else {
+            format!("  BASELINE {counts}\n")
+        };
+        output.push_str(&header);
+
+        if self.verbose {
+            for finding in &baseline.known {
+                let file = finding.location.as_ref().map_or(String::new(), |l| {
+                    extract_relative_path(&l.file.display().to_string(), l.repository.as_deref())
+                });
+                let msg = if self.use_colors {
+                    format!(
+                        "    {} {}: {}\n",
+                        "KNOWN".dimmed(),
+                        file.dimmed(),
+                        finding.message
+                    )
+                } else {
+                    format!("    KNOWN {file}: {}\n", finding.message)
+                };
+                output.push_str(&msg);
+            }
+        }
+
+        for entry in &baseline.fixed {
+            let target = entry.resource.as_deref().unwrap_or("-");
+            let msg = if self.use_colors {
+                format!(
+                    "    {} {}: {} {}\n",
+                    "FIXED".green(),
+                    entry.file.dimmed(),
+                    target,
+                    entry.code.dimmed()
+                )
+            } else {
+                format!("    FIXED {}: {} {}\n", entry.file, target, entry.code)
+            };
+            output.push_str(&msg);
+        }
+
+        if !baseline.fixed.is_empty() {
+            output.push_str("  Re-run with --write-baseline to drop fixed entries.\n");
+        }
+        output.push('\n');
+
+        output
+    }
+
     /// Format the footer.
     fn format_footer(&self, result: &ScanResult) -> String {
         let errors = count_by_severity(result, |s| {
@@ -331,15 +546,14 @@ impl TextReporter {
 // Helper functions
 // ─────────────────────────────────────────────────────────────────────────────
 
-/// Count findings by severity.
+/// Count unsuppressed findings by severity.
 fn count_by_severity<F>(result: &ScanResult, predicate: F) -> usize
 where
     F: Fn(&Severity) -> bool,
 {
     result
         .analysis
-        .findings
-        .iter()
+        .active_findings()
         .filter(|f| predicate(&f.severity))
         .count()
 }
@@ -464,6 +678,15 @@ fn short_issue_description(code: &str, category: &crate::types::FindingCategory)
         "prerelease-version" => "Pre-release".to_string(),
         "exact-version" => "Exact version".to_string(),
         "no-upper-bound" => "No upper bound".to_string(),
+        "stale-suppression" => "Stale ignore".to_string(),
+        "deprecated-module" | "deprecated-provider" | "deprecated-runtime" => {
+            "Deprecated".to_string()
+        }
+        "outdated-major" => "Old major".to_string(),
+        "outdated-version" => "Outdated".to_string(),
+        "circular-dependency" => "Cycle".to_string(),
+        "unsatisfiable-provider-constraint" => "Unsatisfiable".to_string(),
+        "deprecated-provider-pinned" => "Pins deprecated".to_string(),
         _ => category.to_string(),
     }
 }
@@ -547,12 +770,15 @@ mod tests {
                     namespace: "terraform-aws-modules".to_string(),
                     name: "vpc".to_string(),
                     provider: "aws".to_string(),
+                    subdir: None,
+                    params: Default::default(),
                 },
                 version_constraint: None,
                 file_path: PathBuf::from("main.tf"),
                 line_number: 1,
                 repository: Some("test-repo".to_string()),
                 attributes: Default::default(),
+                dependencies: Default::default(),
             }],
             providers: vec![ProviderRef {
                 name: "aws".to_string(),
@@ -574,6 +800,9 @@ mod tests {
             graph: Default::default(),
             analysis: AnalysisResult::default(),
             warnings: Vec::new(),
+            baseline: None,
+            locks: Vec::new(),
+            roots: Vec::new(),
         }
     }
 
@@ -590,6 +819,28 @@ mod tests {
         assert!(text.contains("No issues found"));
     }
 
+    #[test]
+    fn test_text_report_lists_fixed_baseline_entries() {
+        let mut result = create_test_result();
+        result.baseline = Some(BaselineResult {
+            known: vec![],
+            fixed: vec![crate::types::BaselineEntry {
+                code: "missing-version".to_string(),
+                repository: Some("test-repo".to_string()),
+                file: "main.tf".to_string(),
+                resource: Some("module.old".to_string()),
+                source: None,
+            }],
+        });
+        let mut config = Config::default();
+        config.output.colored = false;
+
+        let text = TextReporter::new(&config).generate(&result).unwrap();
+
+        assert!(text.contains("BASELINE (0 known, 1 fixed)"));
+        assert!(text.contains("FIXED main.tf: module.old missing-version"));
+    }
+
     #[test]
     fn test_extract_resource_name() {
         let category = crate::types::FindingCategory::MissingConstraint;
@@ -0,0 +1,598 @@
+//! Transitive module resolution.
+//!
+//! A module block with a remote source is a leaf of the scanned
+//! configuration. The [`ModuleResolver`] fetches those sources, parses the
+//! fetched module with [`HclParser`] and follows the module calls it makes
+//! in turn, so the graph shows the whole tree a root module pulls in and
+//! every provider requirement it inherits.
+//!
+//! Git sources are cloned with [`GitClient`]. Registry modules are resolved
+//! to the newest version their constraint allows, then downloaded from the
+//! location the registry's download endpoint returns. Local calls inside a
+//! fetched module are read from the same checkout. Each fetched module is
+//! identified by its source and version, and fetched once however many
+//! modules call it.
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::git::GitClient;
+use crate::graph::normalize_path;
+use crate::parser::{parse_module_source_with_registries, HclParser};
+use crate::registry::{PackageAddress, RegistryClient};
+use crate::types::{ModuleRef, ModuleSource, ProviderRef, RuntimeRef, ScanWarning};
+use std::collections::{HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+/// A remote module fetched during resolution.
+#[derive(Debug, Clone)]
+pub struct ResolvedPackage {
+    /// Source of the module without its version (e.g.,
+    /// `registry.terraform.io/terraform-aws-modules/vpc/aws`)
+    pub source: String,
+    /// Version fetched: a registry version, a Git ref, or `HEAD`
+    pub version: String,
+    /// Levels below the scanned configuration (1 for modules it calls directly)
+    pub depth: usize,
+    /// Module blocks of the fetched module, located relative to its checkout
+    pub modules: Vec<ModuleRef>,
+    /// Provider requirements of the fetched module
+    pub providers: Vec<ProviderRef>,
+    /// `required_version` constraints of the fetched module
+    pub runtimes: Vec<RuntimeRef>,
+}
+
+impl ResolvedPackage {
+    /// Identifier of the package, `source@version`.
+    ///
+    /// Also used as the repository of its modules and providers, which
+    /// keeps their graph nodes apart from the scanned configuration.
+    #[must_use]
+    pub fn key(&self) -> String {
+        package_key(&self.source, &self.version)
+    }
+}
+
+/// A module block whose source resolved to a fetched package.
+#[derive(Debug, Clone)]
+pub struct ResolvedCall {
+    /// The calling module block
+    pub caller: ModuleRef,
+    /// Key of the package it calls (see [`ResolvedPackage::key`])
+    pub package: String,
+}
+
+/// Everything found by transitive resolution.
+#[derive(Debug, Clone, Default)]
+pub struct Resolution {
+    /// Fetched packages, in the order they were first reached
+    pub packages: Vec<ResolvedPackage>,
+    /// Module blocks and the packages they call
+    pub calls: Vec<ResolvedCall>,
+    /// Sources that could not be fetched or parsed
+    pub warnings: Vec<ScanWarning>,
+}
+
+impl Resolution {
+    /// Find a package by key.
+    #[must_use]
+    pub fn package(&self, key: &str) -> Option<&ResolvedPackage> {
+        self.packages.iter().find(|p| p.key() == key)
+    }
+}
+
+/// Where a fetched module lives on disk.
+#[derive(Debug, Clone)]
+struct Checkout {
+    /// Root of the clone
+    root: PathBuf,
+    /// Directory of the package root, relative to `root`
+    base_dir: PathBuf,
+    /// Source of the package root
+    base_source: String,
+    /// Version of the clone
+    version: String,
+}
+
+/// A module directory to parse.
+#[derive(Debug, Clone)]
+struct Target {
+    checkout: Checkout,
+    /// Module directory, relative to the checkout root
+    dir: PathBuf,
+}
+
+impl Target {
+    fn source(&self) -> String {
+        match self.dir.strip_prefix(&self.checkout.base_dir) {
+            Ok(rel) if rel.as_os_str().is_empty() => self.checkout.base_source.clone(),
+            Ok(rel) => format!("{}//{}", self.checkout.base_source, slash_path(rel)),
+            Err(_) => format!("{}//{}", self.checkout.base_source, slash_path(&self.dir)),
+        }
+    }
+
+    fn key(&self) -> String {
+        package_key(&self.source(), &self.checkout.version)
+    }
+}
+
+/// A module block waiting to be resolved.
+struct Pending {
+    module: ModuleRef,
+    depth: usize,
+    /// Package the module block was found in, `None` for the scanned configuration
+    within: Option<Target>,
+}
+
+/// Fetches remote module sources and follows their module calls.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use monphare::git::GitClient;
+/// use monphare::resolver::ModuleResolver;
+/// use monphare::Config;
+///
+/// # async fn example(modules: Vec<monphare::ModuleRef>) {
+/// let config = Config::default();
+/// let git = GitClient::new(config.clone());
+/// let resolution = ModuleResolver::new(&config, &git).resolve(&modules).await;
+/// println!("Fetched {} modules", resolution.packages.len());
+/// # }
+/// ```
+pub struct ModuleResolver<'a> {
+    git: &'a GitClient,
+    registry: RegistryClient,
+    parser: HclParser,
+    registry_hosts: Vec<String>,
+    max_depth: usize,
+}
+
+impl<'a> ModuleResolver<'a> {
+    /// Create a resolver using the `resolve` and `registry` options of `config`.
+    #[must_use]
+    pub fn new(config: &Config, git: &'a GitClient) -> Self {
+        Self {
+            git,
+            registry: RegistryClient::new(&config.registry),
+            parser: HclParser::new(config),
+            registry_hosts: config.registry.hosts.clone(),
+            max_depth: config.resolve.max_depth,
+        }
+    }
+
+    /// Resolve registry modules with this client.
+    #[must_use]
+    pub fn with_registry(mut self, client: RegistryClient) -> Self {
+        self.registry = client;
+        self
+    }
+
+    /// Fetch the remote modules called by `modules`, and theirs, down to the
+    /// configured depth.
+    ///
+    /// Sources that cannot be fetched or parsed are reported as
+    /// `unresolved-module` warnings and left as leaves.
+    pub async fn resolve(&self, modules: &[ModuleRef]) -> Resolution {
+        let queue = modules
+            .iter()
+            .map(|module| Pending {
+                module: module.clone(),
+                depth: 1,
+                within: None,
+            })
+            .collect();
+        self.resolve_queue(queue).await
+    }
+
+    /// Resolve module blocks breadth first, queueing the calls of every
+    /// module fetched.
+    async fn resolve_queue(&self, mut queue: VecDeque<Pending>) -> Resolution {
+        let mut resolution = Resolution::default();
+        let mut fetched = HashSet::new();
+
+        while let Some(pending) = queue.pop_front() {
+            if pending.depth > self.max_depth {
+                tracing::debug!(
+                    module = %pending.module.name,
+                    depth = pending.depth,
+                    "Maximum resolution depth reached"
+                );
+                continue;
+            }
+
+            let target = match self.locate(&pending.module, pending.within.as_ref()).await {
+                Ok(Some(target)) => target,
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::warn!(module = %pending.module.name, error = %e, "Failed to resolve module");
+                    resolution.warnings.push(unresolved(
+                        &pending.module,
+                        format!(
+                            "Could not fetch module '{}' ({}): {e}",
+                            pending.module.name,
+                            pending.module.source.to_source_string()
+                        ),
+                    ));
+                    continue;
+                }
+            };
+
+            let key = target.key();
+            resolution.calls.push(ResolvedCall {
+                caller: pending.module,
+                package: key.clone(),
+            });
+            if !fetched.insert(key.clone()) {
+                continue;
+            }
+
+            let dir = target.checkout.root.join(&target.dir);
+            let parsed = match self.parser.parse_module_directory(&dir, Some(&key)).await {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    tracing::warn!(package = %key, error = %e, "Failed to parse fetched module");
+                    let caller = &resolution.calls[resolution.calls.len() - 1].caller;
+                    resolution.warnings.push(unresolved(
+                        caller,
+                        format!("Could not parse module {key}: {e}"),
+                    ));
+                    continue;
+                }
+            };
+
+            let root = &target.checkout.root;
+            let mut package_modules = parsed.modules;
+            for module in &mut package_modules {
+                module.file_path = relative_to(&module.file_path, root);
+                queue.push_back(Pending {
+                    module: module.clone(),
+                    depth: pending.depth + 1,
+                    within: Some(target.clone()),
+                });
+            }
+            let mut package_providers = parsed.providers;
+            for provider in &mut package_providers {
+                provider.file_path = relative_to(&provider.file_path, root);
+            }
+            let mut package_runtimes = parsed.runtimes;
+            for runtime in &mut package_runtimes {
+                runtime.file_path = relative_to(&runtime.file_path, root);
+            }
+
+            tracing::debug!(
+                package = %key,
+                modules = package_modules.len(),
+                providers = package_providers.len(),
+                "Resolved module"
+            );
+            resolution.packages.push(ResolvedPackage {
+                source: target.source(),
+                version: target.checkout.version.clone(),
+                depth: pending.depth,
+                modules: package_modules,
+                providers: package_providers,
+                runtimes: package_runtimes,
+            });
+        }
+
+        tracing::info!(
+            packages = resolution.packages.len(),
+            warnings = resolution.warnings.len(),
+            "Module resolution complete"
+        );
+        resolution
+    }
+
+    /// Find the directory a module block's source points to, fetching it if
+    /// needed. Returns `None` for sources that are not resolved.
+    async fn locate(&self, module: &ModuleRef, within: Option<&Target>) -> Result<Option<Target>> {
+        match &module.source {
+            // Local calls of the scanned configuration are scanned already
+            ModuleSource::Local { path } => Ok(within.map(|parent| Target {
+                checkout: parent.checkout.clone(),
+                dir: normalize_path(&parent.dir.join(path)),
+            })),
+            ModuleSource::Git {
+                host,
+                url,
+                ref_,
+                subdir,
+                ..
+            } => {
+                let checkout = self
+                    .clone_git(url, ref_.as_deref(), subdir.as_deref(), host.clone())
+                    .await?;
+                Ok(Some(Target {
+                    dir: checkout.base_dir.clone(),
+                    checkout,
+                }))
+            }
+            ModuleSource::Registry { .. } => self.download_registry_module(module).await,
+            ModuleSource::Unknown(_) => Ok(None),
+            other => Err(crate::err!(ModuleSourceParse {
+                module_source: other.to_source_string(),
+                message: "only Git and registry sources can be fetched".to_string(),
+            })),
+        }
+    }
+
+    /// Resolve a registry module to a version and fetch it from the location
+    /// the registry gives for that version.
+    async fn download_registry_module(&self, module: &ModuleRef) -> Result<Option<Target>> {
+        let Some(address) = PackageAddress::for_module(&module.source) else {
+            return Ok(None);
+        };
+        let not_found = |message: String| {
+            crate::err!(Http {
+                message: message,
+                status_code: None,
+            })
+        };
+
+        let versions = self.registry.versions(&address).await?.ok_or_else(|| {
+            not_found(if self.registry.is_offline() {
+                format!("no cached registry data for {address} (offline mode)")
+            } else {
+                format!("{address} was not found in its registry")
+            })
+        })?;
+        let version = versions
+            .newest_allowed(module.version_constraint.as_ref())
+            .cloned()
+            .ok_or_else(|| not_found(format!("no version of {address} matches the constraint")))?;
+        let location = self
+            .registry
+            .download_source(&address, &version)
+            .await?
+            .ok_or_else(|| not_found(format!("no download location for {address} {version}")))?;
+
+        match parse_module_source_with_registries(&location, &self.registry_hosts)? {
+            ModuleSource::Git {
+                url, ref_, subdir, ..
+            } => {
+                let checkout = self
+                    .clone_git(&url, ref_.as_deref(), subdir.as_deref(), String::new())
+                    .await?;
+                // The download location is an implementation detail, the
+                // package is known by its registry address; a `//subdir`
+                // selects a submodule within it
+                let mut package = module.source.clone();
+                let mut dir = checkout.base_dir.clone();
+                if let ModuleSource::Registry { subdir, .. } = &mut package {
+                    if let Some(subdir) = subdir.take() {
+                        dir = dir.join(normalize_path(Path::new(&subdir)));
+                    }
+                }
+                Ok(Some(Target {
+                    dir,
+                    checkout: Checkout {
+                        base_source: package.canonical_id(),
+                        version: version.to_string(),
+                        ..checkout
+                    },
+                }))
+            }
+            other => Err(crate::err!(ModuleSourceParse {
+                module_source: other.to_source_string(),
+                message: format!(
+                    "{address} {version} is downloaded from a location that cannot be fetched"
+                ),
+            })),
+        }
+    }
+
+    async fn clone_git(
+        &self,
+        url: &str,
+        git_ref: Option<&str>,
+        subdir: Option<&str>,
+        base_source: String,
+    ) -> Result<Checkout> {
+        let root = self.git.clone_repository_at(url, git_ref).await?;
+        let base_dir = subdir
+            .map(|subdir| normalize_path(Path::new(subdir)))
+            .unwrap_or_default();
+        let base_source = match subdir {
+            Some(subdir) if !base_dir.as_os_str().is_empty() => format!("{base_source}//{subdir}"),
+            _ => base_source,
+        };
+        Ok(Checkout {
+            root,
+            base_dir,
+            base_source,
+            version: git_ref.unwrap_or("HEAD").to_string(),
+        })
+    }
+}
+
+fn package_key(source: &str, version: &str) -> String {
+    format!("{source}@{version}")
+}
+
+fn unresolved(module: &ModuleRef, message: String) -> ScanWarning {
+    ScanWarning {
+        code: "unresolved-module".to_string(),
+        message,
+        file: module.file_path.clone(),
+        line: Some(module.line_number),
+        repository: module.repository.clone(),
+    }
+}
+
+fn relative_to(path: &Path, root: &Path) -> PathBuf {
+    path.strip_prefix(root).unwrap_or(path).to_path_buf()
+}
+
+/// Render a relative path with `/` separators, as module sources use.
+fn slash_path(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RegistryOptions;
+    use crate::types::Constraint;
+    use std::fs;
+    use wiremock::{matchers::*, Mock, MockServer, ResponseTemplate};
+
+    fn local(name: &str, path: &str) -> ModuleRef {
+        ModuleRef {
+            name: name.to_string(),
+            source: ModuleSource::Local {
+                path: path.to_string(),
+            },
+            version_constraint: None,
+            file_path: PathBuf::from("main.tf"),
+            line_number: 1,
+            repository: Some("infra".to_string()),
+            attributes: Default::default(),
+            dependencies: Default::default(),
+        }
+    }
+
+    fn checkout(root: &Path) -> Target {
+        Target {
+            checkout: Checkout {
+                root: root.to_path_buf(),
+                base_dir: PathBuf::new(),
+                base_source: "github.com/acme/network".to_string(),
+                version: "v1.2.0".to_string(),
+            },
+            dir: PathBuf::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_local_calls_inside_a_checkout_are_followed() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("modules/subnets")).unwrap();
+        fs::create_dir_all(dir.path().join("modules/routes")).unwrap();
+        fs::write(
+            dir.path().join("modules/subnets/main.tf"),
+            r#"
+terraform {
+  required_providers {
+    aws = { source = "hashicorp/aws", version = ">= 5.0" }
+  }
+}
+module "routes" {
+  source = "../routes"
+}
+"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("modules/routes/main.tf"), "").unwrap();
+
+        let config = Config::default();
+        let git = GitClient::new(config.clone());
+        let resolver = ModuleResolver::new(&config, &git);
+        let queue = VecDeque::from([
+            Pending {
+                module: local("subnets", "./modules/subnets"),
+                depth: 1,
+                within: Some(checkout(dir.path())),
+            },
+            // Calling the same module twice fetches it once
+            Pending {
+                module: local("subnets_again", "modules/subnets"),
+                depth: 1,
+                within: Some(checkout(dir.path())),
+            },
+        ]);
+        let resolution = resolver.resolve_queue(queue).await;
+
+        assert!(resolution.warnings.is_empty());
+        let keys: Vec<_> = resolution
+            .packages
+            .iter()
+            .map(ResolvedPackage::key)
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                "github.com/acme/network//modules/subnets@v1.2.0",
+                "github.com/acme/network//modules/routes@v1.2.0",
+            ]
+        );
+        assert_eq!(resolution.calls.len(), 3);
+
+        let subnets = &resolution.packages[0];
+        assert_eq!(subnets.depth, 1);
+        assert_eq!(subnets.providers[0].qualified_source(), "hashicorp/aws");
+        assert_eq!(
+            subnets.modules[0].file_path,
+            PathBuf::from("modules/subnets/main.tf")
+        );
+        assert_eq!(
+            subnets.modules[0].repository.as_deref(),
+            Some(keys[0].as_str())
+        );
+        assert_eq!(resolution.packages[1].depth, 2);
+    }
+
+    #[tokio::test]
+    async fn test_depth_limit_and_unfetchable_sources() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/modules/acme/bucket/aws/versions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "modules": [{"versions": [{"version": "1.0.0"}, {"version": "2.0.0"}]}]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/modules/acme/bucket/aws/1.0.0/download"))
+            .respond_with(
+                ResponseTemplate::new(204)
+                    .insert_header("X-Terraform-Get", "https://example.com/bucket.zip"),
+            )
+            .mount(&server)
+            .await;
+
+        let cache = tempfile::tempdir().unwrap();
+        let mut config = Config {
+            registry: RegistryOptions {
+                cache_dir: Some(cache.path().display().to_string()),
+                ..RegistryOptions::default()
+            },
+            ..Config::default()
+        };
+        let git = GitClient::new(config.clone());
+        let registry = RegistryClient::new(&config.registry).with_base_url(server.uri());
+        let bucket = ModuleRef {
+            name: "bucket".to_string(),
+            source: ModuleSource::Registry {
+                hostname: "registry.terraform.io".to_string(),
+                namespace: "acme".to_string(),
+                name: "bucket".to_string(),
+                provider: "aws".to_string(),
+                subdir: None,
+                params: Default::default(),
+            },
+            version_constraint: Some(Constraint::parse("~> 1.0").unwrap()),
+            ..local("bucket", "unused")
+        };
+
+        let resolver = ModuleResolver::new(&config, &git).with_registry(registry.clone());
+        let resolution = resolver
+            .resolve(&[bucket.clone(), local("app", "./app")])
+            .await;
+        assert!(resolution.packages.is_empty());
+        assert!(resolution.calls.is_empty());
+        // Local calls of the scanned configuration are not resolved
+        assert_eq!(resolution.warnings.len(), 1);
+        assert_eq!(resolution.warnings[0].code, "unresolved-module");
+        assert!(resolution.warnings[0].message.contains(
+            "acme/bucket/aws 1.0.0 is downloaded from a location that cannot be fetched"
+        ));
+
+        config.resolve.max_depth = 0;
+        let resolver = ModuleResolver::new(&config, &git).with_registry(registry);
+        let resolution = resolver.resolve(&[bucket]).await;
+        assert!(resolution.warnings.is_empty());
+    }
+}
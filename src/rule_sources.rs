@@ -0,0 +1,516 @@
+//! Deprecation rule includes.
+//!
+//! Resolves `deprecations.include` entries into the rule maps of a
+//! [`Config`], so that many teams can consume one centrally maintained rule
+//! set:
+//!
+//! ```yaml
+//! deprecations:
+//!   include:
+//!     - path: ./deprecations/*.yaml        # file, directory or glob
+//!     - git: https://github.com/my-org/terraform-deprecations
+//!       ref: main                          # branch to check out
+//!       path: rules/                       # optional, defaults to all YAML files
+//!   modules:
+//!     "terraform-aws-modules/vpc/aws": [...]  # overrides any include
+//! ```
+//!
+//! # Precedence
+//!
+//! Rules are merged per key (module source, provider source or runtime
+//! name). Includes are applied in order, so a later include replaces the
+//! rules of an earlier one for the same key, and rules written directly in
+//! the configuration file replace every include. When a replaced rule list
+//! differs from the one replacing it, a `deprecation-conflict` warning is
+//! reported. Every rule remembers where it came from, and that origin is
+//! copied into the findings it produces.
+
+use crate::config::{DeprecationInclude, DeprecationRef, DeprecationsOptions};
+use crate::error::{MonPhareError, Result};
+use crate::git::GitClient;
+use crate::types::ScanWarning;
+use crate::Config;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Configuration key reported in include errors.
+const INCLUDE_KEY: &str = "deprecations.include.path";
+
+/// Rules loaded from one file, with their origin.
+#[derive(Debug)]
+struct RuleSource {
+    origin: String,
+    file: PathBuf,
+    rules: DeprecationsOptions,
+}
+
+/// The winning rules for one key and where they came from.
+struct MergedRules {
+    origin: String,
+    rules: Vec<DeprecationRef>,
+}
+
+/// Load every include of `config` and merge it into its deprecation rules.
+///
+/// Relative include paths are resolved against the directory of
+/// `config_path` (the current directory when `None`). Returns warnings for
+/// conflicting rules.
+///
+/// # Errors
+///
+/// Returns an error if an include cannot be cloned, matches no files, or
+/// contains invalid rules.
+pub async fn resolve_includes(
+    config: &mut Config,
+    config_path: Option<&Path>,
+    git_client: &GitClient,
+) -> Result<Vec<ScanWarning>> {
+    if config.deprecations.include.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let base_dir = config_path
+        .and_then(Path::parent)
+        .filter(|p| !p.as_os_str().is_empty())
+        .map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+
+    let mut sources = Vec::new();
+    let mut warnings = Vec::new();
+    for include in &config.deprecations.include {
+        let loaded = load_include(include, &base_dir, git_client).await?;
+        for source in &loaded {
+            if !source.rules.include.is_empty() {
+                tracing::warn!(origin = %source.origin, "Nested deprecation includes are ignored");
+                warnings.push(ScanWarning {
+                    code: "deprecation-include".to_string(),
+                    message: format!("Nested 'include' entries in {} are ignored", source.origin),
+                    file: source.file.clone(),
+                    line: None,
+                    repository: None,
+                });
+            }
+        }
+        sources.extend(loaded);
+    }
+
+    // Rules from the configuration file itself have the highest precedence
+    let inline_origin =
+        config_path.map_or_else(|| "configuration".to_string(), |p| p.display().to_string());
+    sources.push(RuleSource {
+        origin: inline_origin,
+        file: config_path.map(Path::to_path_buf).unwrap_or_default(),
+        rules: std::mem::take(&mut config.deprecations),
+    });
+
+    // Settings other than the rules (e.g. `enforce_undated`) come from the
+    // configuration file
+    let inline = &mut sources.last_mut().expect("inline source").rules;
+    let mut merged = DeprecationsOptions {
+        modules: HashMap::new(),
+        providers: HashMap::new(),
+        runtime: HashMap::new(),
+        include: std::mem::take(&mut inline.include),
+        ..inline.clone()
+    };
+    let mut modules = HashMap::new();
+    let mut providers = HashMap::new();
+    let mut runtime = HashMap::new();
+    for source in sources {
+        merge_rules(
+            "module",
+            &mut modules,
+            &source,
+            source.rules.modules.clone(),
+            &mut warnings,
+        );
+        merge_rules(
+            "provider",
+            &mut providers,
+            &source,
+            source.rules.providers.clone(),
+            &mut warnings,
+        );
+        merge_rules(
+            "runtime",
+            &mut runtime,
+            &source,
+            source.rules.runtime.clone(),
+            &mut warnings,
+        );
+    }
+
+    merged.modules = into_rules(modules);
+    merged.providers = into_rules(providers);
+    merged.runtime = into_rules(runtime);
+
+    tracing::info!(
+        modules = merged.modules.len(),
+        providers = merged.providers.len(),
+        runtimes = merged.runtime.len(),
+        conflicts = warnings.len(),
+        "Deprecation rules resolved"
+    );
+
+    config.deprecations = merged;
+    Ok(warnings)
+}
+
+/// Load the rule files selected by one include entry.
+async fn load_include(
+    include: &DeprecationInclude,
+    base_dir: &Path,
+    git_client: &GitClient,
+) -> Result<Vec<RuleSource>> {
+    let (root, files) = match &include.git {
+        Some(url) => {
+            tracing::info!(url = %url, git_ref = ?include.git_ref, "Fetching deprecation rules");
+            let root = git_client
+                .clone_repository_at(url, include.git_ref.as_deref())
+                .await?;
+            let files = collect_files(&root, include.path.as_deref().unwrap_or("."), INCLUDE_KEY)?;
+            (Some(root), files)
+        }
+        None => {
+            let path = include.path.as_deref().unwrap_or(".");
+            (None, collect_files(base_dir, path, INCLUDE_KEY)?)
+        }
+    };
+
+    files
+        .into_iter()
+        .map(|file| {
+            let origin = match (&include.git, &root) {
+                (Some(url), Some(root)) => {
+                    let relative = relative_path(&file, root);
+                    match &include.git_ref {
+                        Some(git_ref) => format!("{url}@{git_ref}:{relative}"),
+                        None => format!("{url}:{relative}"),
+                    }
+                }
+                _ => file.display().to_string(),
+            };
+            tracing::debug!(origin = %origin, "Loading deprecation rule file");
+            Ok(RuleSource {
+                rules: load_rule_file(&file)?,
+                origin,
+                file,
+            })
+        })
+        .collect()
+}
+
+/// Expand a file, directory or glob pattern into YAML files, sorted by path.
+///
+/// `key` names the configuration setting in error messages.
+pub(crate) fn collect_files(base: &Path, pattern: &str, key: &str) -> Result<Vec<PathBuf>> {
+    let target = base.join(pattern);
+
+    let mut files: Vec<PathBuf> = if pattern.contains(['*', '?', '[']) {
+        let pattern_str = target.to_string_lossy();
+        glob::glob(&pattern_str)
+            .map_err(|e| {
+                crate::err!(ConfigValue {
+                    key: key.to_string(),
+                    message: format!("invalid glob '{pattern}': {e}"),
+                })
+            })?
+            .filter_map(std::result::Result::ok)
+            .filter(|p| p.is_file())
+            .collect()
+    } else if target.is_dir() {
+        walkdir::WalkDir::new(&target)
+            .into_iter()
+            .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+            .filter_map(std::result::Result::ok)
+            .map(walkdir::DirEntry::into_path)
+            .filter(|p| p.is_file() && is_yaml(p))
+            .collect()
+    } else if target.is_file() {
+        vec![target.clone()]
+    } else {
+        return Err(crate::err!(FileNotFound { path: target }));
+    };
+
+    if files.is_empty() {
+        return Err(crate::err!(ConfigValue {
+            key: key.to_string(),
+            message: format!("'{}' matched no rule files", target.display()),
+        }));
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Parse a rule file.
+///
+/// The file may be a full configuration (rules under `deprecations:`) or
+/// contain the `modules`/`providers`/`runtime` maps at the top level.
+fn load_rule_file(path: &Path) -> Result<DeprecationsOptions> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| MonPhareError::io(path, e, file!(), line!()))?;
+
+    let parse_error = |e: serde_yaml::Error| {
+        crate::err!(ConfigParse {
+            message: format!("Invalid deprecation rule file '{}': {e}", path.display()),
+            source: Some(Box::new(e)),
+        })
+    };
+
+    let value: serde_yaml::Value = serde_yaml::from_str(&content).map_err(parse_error)?;
+    let value = match value.get("deprecations") {
+        Some(rules) => rules.clone(),
+        None => value,
+    };
+    if value.is_null() {
+        return Ok(DeprecationsOptions::default());
+    }
+    let rules: DeprecationsOptions = serde_yaml::from_value(value).map_err(parse_error)?;
+
+    for (kind, map) in [
+        ("modules", &rules.modules),
+        ("providers", &rules.providers),
+        ("runtime", &rules.runtime),
+    ] {
+        for (name, refs) in map {
+            for rule in refs {
+                rule.validate_schedule(&format!("{kind}.{name}"))?;
+            }
+        }
+    }
+
+    Ok(rules)
+}
+
+/// Merge one source's rules for a kind, replacing earlier rules per key.
+fn merge_rules(
+    kind: &str,
+    merged: &mut HashMap<String, MergedRules>,
+    source: &RuleSource,
+    rules: HashMap<String, Vec<DeprecationRef>>,
+    warnings: &mut Vec<ScanWarning>,
+) {
+    for (key, mut rules) in rules {
+        for rule in &mut rules {
+            rule.origin = Some(source.origin.clone());
+        }
+
+        if let Some(previous) = merged.get(&key) {
+            if !same_rules(&previous.rules, &rules) {
+                tracing::warn!(
+                    kind = kind,
+                    key = %key,
+                    origin = %source.origin,
+                    overridden = %previous.origin,
+                    "Conflicting deprecation rules"
+                );
+                warnings.push(ScanWarning {
+                    code: "deprecation-conflict".to_string(),
+                    message: format!(
+                        "Deprecation rules for {kind} '{key}' from {} override different rules from {}",
+                        source.origin, previous.origin
+                    ),
+                    file: source.file.clone(),
+                    line: None,
+                    repository: None,
+                });
+            }
+        }
+
+        merged.insert(
+            key,
+            MergedRules {
+                origin: source.origin.clone(),
+                rules,
+            },
+        );
+    }
+}
+
+/// Compare rule lists, ignoring where they were loaded from.
+fn same_rules(a: &[DeprecationRef], b: &[DeprecationRef]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(x, y)| {
+            DeprecationRef {
+                origin: None,
+                ..x.clone()
+            } == DeprecationRef {
+                origin: None,
+                ..y.clone()
+            }
+        })
+}
+
+fn into_rules(merged: HashMap<String, MergedRules>) -> HashMap<String, Vec<DeprecationRef>> {
+    merged.into_iter().map(|(k, v)| (k, v.rules)).collect()
+}
+
+fn is_yaml(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("yaml" | "yml")
+    )
+}
+
+fn relative_path(file: &Path, root: &Path) -> String {
+    file.strip_prefix(root)
+        .unwrap_or(file)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHARED: &str = r#"
+deprecations:
+  modules:
+    "terraform-aws-modules/vpc/aws":
+      - version: "< 5.0.0"
+        reason: "security team: v4 retired"
+        severity: error
+        replacement: ">= 5.0.0"
+    "terraform-aws-modules/eks/aws":
+      - version: "< 19.0.0"
+        reason: "security team: v18 retired"
+        severity: error
+        replacement: ">= 19.0.0"
+"#;
+
+    const TEAM: &str = r#"
+modules:
+  "terraform-aws-modules/eks/aws":
+    - version: "< 18.0.0"
+      reason: "team: v17 retired"
+      severity: warning
+      replacement: ">= 18.0.0"
+providers:
+  "hashicorp/aws":
+    - version: "< 4.0.0"
+      reason: "team: v3 retired"
+      severity: warning
+      replacement: ">= 4.0.0"
+"#;
+
+    fn write(dir: &Path, name: &str, content: &str) {
+        let path = dir.join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn config_with(dir: &Path, yaml: &str) -> (Config, PathBuf) {
+        let path = dir.join("monphare.yaml");
+        std::fs::write(&path, yaml).unwrap();
+        (Config::from_yaml(yaml).unwrap(), path)
+    }
+
+    #[tokio::test]
+    async fn test_later_includes_and_inline_rules_take_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "rules/00-shared.yaml", SHARED);
+        write(dir.path(), "rules/10-team.yml", TEAM);
+        let (mut config, path) = config_with(
+            dir.path(),
+            r#"
+deprecations:
+  include:
+    - path: rules/*.y*ml
+  providers:
+    "hashicorp/aws":
+      - version: "< 4.0.0"
+        reason: "team: v3 retired"
+        severity: warning
+        replacement: ">= 4.0.0"
+"#,
+        );
+        let git_client = GitClient::new(config.clone());
+
+        let warnings = resolve_includes(&mut config, Some(&path), &git_client)
+            .await
+            .unwrap();
+
+        let rules = &config.deprecations;
+        assert_eq!(rules.modules.len(), 2);
+        let eks = &rules.modules["terraform-aws-modules/eks/aws"][0];
+        assert_eq!(eks.reason, "team: v17 retired");
+        assert!(eks.origin.as_deref().unwrap().ends_with("10-team.yml"));
+        let vpc = &rules.modules["terraform-aws-modules/vpc/aws"][0];
+        assert!(vpc.origin.as_deref().unwrap().ends_with("00-shared.yaml"));
+        let aws = &rules.providers["hashicorp/aws"][0];
+        assert!(aws.origin.as_deref().unwrap().ends_with("monphare.yaml"));
+
+        // eks differs between the two includes; the provider rule is identical
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].code, "deprecation-conflict");
+        assert!(warnings[0]
+            .message
+            .contains("module 'terraform-aws-modules/eks/aws'"));
+    }
+
+    #[tokio::test]
+    async fn test_inline_settings_survive_includes() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "rules/00-shared.yaml", SHARED);
+        let (mut config, path) = config_with(
+            dir.path(),
+            r#"
+deprecations:
+  include:
+    - path: rules/00-shared.yaml
+  enforce_undated: true
+"#,
+        );
+        let git_client = GitClient::new(config.clone());
+
+        resolve_includes(&mut config, Some(&path), &git_client)
+            .await
+            .unwrap();
+
+        assert!(config.deprecations.enforce_undated);
+        assert_eq!(config.deprecations.include.len(), 1);
+        assert!(!config.deprecations.modules.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_directory_include() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "rules/shared.yaml", SHARED);
+        write(dir.path(), "rules/README.md", "not rules");
+        let (mut config, path) =
+            config_with(dir.path(), "deprecations:\n  include:\n    - path: rules\n");
+        let git_client = GitClient::new(config.clone());
+
+        resolve_includes(&mut config, Some(&path), &git_client)
+            .await
+            .unwrap();
+
+        assert_eq!(config.deprecations.modules.len(), 2);
+        assert_eq!(config.deprecations.include.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_missing_include_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut config, path) = config_with(
+            dir.path(),
+            "deprecations:\n  include:\n    - path: missing.yaml\n",
+        );
+        let git_client = GitClient::new(config.clone());
+
+        let result = resolve_includes(&mut config, Some(&path), &git_client).await;
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_invalid_include_entries_rejected() {
+        assert!(Config::from_yaml("deprecations:\n  include:\n    - {}\n").is_err());
+        assert!(Config::from_yaml(
+            "deprecations:\n  include:\n    - path: a.yaml\n      ref: main\n"
+        )
+        .is_err());
+    }
+}
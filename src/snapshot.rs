@@ -0,0 +1,210 @@
+//! Scan snapshots.
+//!
+//! A snapshot stores a complete [`ScanResult`], including the dependency
+//! graph and analysis, so commands can run against a slow org-wide scan
+//! without cloning and parsing every repository again.
+//!
+//! # Example
+//!
+//! ```bash
+//! # Scan once and keep the result
+//! monphare scan --github my-org --snapshot org.json
+//!
+//! # Later: report, graph or query without rescanning
+//! monphare scan --from-snapshot org.json --format html --output report.html
+//! monphare graph --from-snapshot org.json --filter terraform-aws-modules/vpc
+//! monphare impact hashicorp/aws --from-snapshot org.json --candidate 6.0.0
+//! ```
+
+use crate::error::{MonPhareError, Result};
+use crate::types::ScanResult;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Current snapshot file format version.
+pub const SNAPSHOT_VERSION: u32 = 2;
+
+/// A scan result, as stored on disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanSnapshot {
+    /// File format version
+    pub version: u32,
+
+    /// Version of MonPhare that wrote the snapshot
+    pub monphare_version: String,
+
+    /// When the snapshot was written
+    pub created_at: DateTime<Utc>,
+
+    /// The scan result
+    pub result: ScanResult,
+}
+
+impl ScanSnapshot {
+    /// Wrap a scan result in a snapshot taken now.
+    #[must_use]
+    pub fn new(result: ScanResult) -> Self {
+        Self {
+            version: SNAPSHOT_VERSION,
+            monphare_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: Utc::now(),
+            result,
+        }
+    }
+
+    /// Load a snapshot from a JSON file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, is not a valid snapshot,
+    /// or uses an unsupported format version.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Err(crate::err!(FileNotFound {
+                path: path.to_path_buf(),
+            }));
+        }
+
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| MonPhareError::io(path, e, file!(), line!()))?;
+
+        // Check the version first so an incompatible layout gets a clear error
+        #[derive(Deserialize)]
+        struct Header {
+            version: u32,
+        }
+        let header: Header = serde_json::from_str(&content).map_err(|e| invalid(path, e))?;
+        if header.version != SNAPSHOT_VERSION {
+            return Err(crate::err!(ConfigValue {
+                key: "snapshot.version".to_string(),
+                message: format!(
+                    "unsupported snapshot version {} (expected {SNAPSHOT_VERSION})",
+                    header.version
+                ),
+            }));
+        }
+
+        let snapshot: Self = serde_json::from_str(&content).map_err(|e| invalid(path, e))?;
+        tracing::debug!(
+            path = %path.display(),
+            created_at = %snapshot.created_at,
+            modules = snapshot.result.modules.len(),
+            nodes = snapshot.result.graph.node_count(),
+            "Snapshot loaded"
+        );
+        Ok(snapshot)
+    }
+
+    /// Write the snapshot to a JSON file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization or writing fails.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string(self).map_err(|e| {
+            crate::err!(ReportGeneration {
+                message: format!("Failed to serialize snapshot: {e}"),
+            })
+        })?;
+
+        std::fs::write(path, json + "\n").map_err(|e| MonPhareError::io(path, e, file!(), line!()))
+    }
+}
+
+fn invalid(path: &Path, e: serde_json::Error) -> MonPhareError {
+    crate::err!(ConfigParse {
+        message: format!("Invalid snapshot file '{}': {e}", path.display()),
+        source: Some(Box::new(e)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::GraphBuilder;
+    use crate::types::{Constraint, ModuleRef, ModuleSource, ProviderRef};
+    use std::path::PathBuf;
+
+    fn scan_result() -> ScanResult {
+        let modules = vec![
+            ModuleRef {
+                name: "vpc".to_string(),
+                source: ModuleSource::Registry {
+                    hostname: "registry.terraform.io".to_string(),
+                    namespace: "terraform-aws-modules".to_string(),
+                    name: "vpc".to_string(),
+                    provider: "aws".to_string(),
+                    subdir: None,
+                    params: Default::default(),
+                },
+                version_constraint: Some(Constraint::parse("~> 5.0").unwrap()),
+                file_path: PathBuf::from("modules/network/main.tf"),
+                line_number: 1,
+                repository: Some("infra".to_string()),
+                attributes: Default::default(),
+                dependencies: Default::default(),
+            },
+            ModuleRef {
+                name: "network".to_string(),
+                source: ModuleSource::Local {
+                    path: "../../modules/network".to_string(),
+                },
+                version_constraint: None,
+                file_path: PathBuf::from("envs/prod/main.tf"),
+                line_number: 4,
+                repository: Some("infra".to_string()),
+                attributes: Default::default(),
+                dependencies: Default::default(),
+            },
+        ];
+        let providers = vec![ProviderRef {
+            name: "aws".to_string(),
+            source: Some("hashicorp/aws".to_string()),
+            version_constraint: Some(Constraint::parse(">= 5.0").unwrap()),
+            file_path: PathBuf::from("modules/network/versions.tf"),
+            line_number: 3,
+            repository: Some("infra".to_string()),
+        }];
+        let graph = GraphBuilder::new()
+            .build(&modules, &providers, &[])
+            .unwrap();
+        ScanResult {
+            modules,
+            providers,
+            graph,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshot.json");
+        let result = scan_result();
+        ScanSnapshot::new(result.clone()).save(&path).unwrap();
+
+        let loaded = ScanSnapshot::load(&path).unwrap();
+        assert_eq!(loaded.version, SNAPSHOT_VERSION);
+        assert_eq!(loaded.result.modules, result.modules);
+        assert_eq!(loaded.result.graph.node_count(), result.graph.node_count());
+        assert_eq!(loaded.result.graph.edge_count(), result.graph.edge_count());
+        let edges = |graph: &crate::graph::DependencyGraph| {
+            graph
+                .edges()
+                .map(|(from, to, edge)| (from.id().to_string(), to.id().to_string(), edge.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(edges(&loaded.result.graph), edges(&result.graph));
+    }
+
+    #[test]
+    fn test_snapshot_version_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshot.json");
+        std::fs::write(&path, r#"{"version": 99, "result": {}}"#).unwrap();
+
+        let err = ScanSnapshot::load(&path).unwrap_err();
+        assert!(err.to_string().contains("unsupported snapshot version 99"));
+    }
+}
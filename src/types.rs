@@ -10,7 +10,9 @@ use crate::graph::DependencyGraph;
 use crate::MonPhareError;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Bound;
 use std::path::PathBuf;
 
 /// Represents a Terraform/OpenTofu module reference.
@@ -49,6 +51,61 @@ pub struct ModuleRef {
     /// Additional attributes from the module block
     #[serde(default)]
     pub attributes: HashMap<String, String>,
+
+    /// Other modules and provider configurations this block refers to
+    #[serde(default, skip_serializing_if = "ModuleDependencies::is_empty")]
+    pub dependencies: ModuleDependencies,
+}
+
+/// References from a module block to sibling modules and providers.
+///
+/// # Example HCL
+///
+/// ```hcl
+/// module "eks" {
+///   source     = "terraform-aws-modules/eks/aws"
+///   vpc_id     = module.vpc.vpc_id    # references: ["vpc"]
+///   depends_on = [module.iam]         # depends_on: ["iam"]
+///   providers  = { aws = aws.east }   # providers: {"aws": "aws.east"}
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleDependencies {
+    /// Modules whose outputs are used in arguments (`module.<name>.<output>`)
+    #[serde(default)]
+    pub references: Vec<String>,
+
+    /// Modules listed in `depends_on`
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+
+    /// Provider configurations passed in, from the child's provider address
+    /// to the caller's (e.g., `aws` -> `aws.east`)
+    #[serde(default)]
+    pub providers: BTreeMap<String, String>,
+}
+
+impl ModuleDependencies {
+    /// Returns true if the block refers to nothing.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.references.is_empty() && self.depends_on.is_empty() && self.providers.is_empty()
+    }
+
+    /// Names of every module this block depends on, explicitly or through
+    /// output references, without duplicates.
+    #[must_use]
+    pub fn modules(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .references
+            .iter()
+            .chain(&self.depends_on)
+            .map(String::as_str)
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
 }
 
 /// Represents a runtime environment (Terraform or OpenTofu) and its version.
@@ -110,6 +167,9 @@ impl std::fmt::Display for RuntimeSource {
 }
 
 /// Represents the source of a Terraform module.
+///
+/// Query arguments without a dedicated field (e.g., git `depth` or `sshkey`,
+/// S3 `version`, `archive`) are kept in `params`.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "type", content = "value")]
 pub enum ModuleSource {
@@ -123,6 +183,11 @@ pub enum ModuleSource {
         name: String,
         /// Provider (e.g., "aws")
         provider: String,
+        /// Subdirectory within the module package (e.g., "modules/vpc-endpoints")
+        subdir: Option<String>,
+        /// Other query arguments
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        params: BTreeMap<String, String>,
     },
 
     /// Git repository source
@@ -135,6 +200,22 @@ pub enum ModuleSource {
         ref_: Option<String>,
         /// Subdirectory within the repository
         subdir: Option<String>,
+        /// Other query arguments (e.g., `depth`, `sshkey`)
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        params: BTreeMap<String, String>,
+    },
+
+    /// Mercurial repository source
+    Hg {
+        /// Repository URL
+        url: String,
+        /// Revision (branch, tag, or changeset)
+        rev: Option<String>,
+        /// Subdirectory within the repository
+        subdir: Option<String>,
+        /// Other query arguments
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        params: BTreeMap<String, String>,
     },
 
     /// Local file path
@@ -145,8 +226,14 @@ pub enum ModuleSource {
 
     /// HTTP/HTTPS URL
     Http {
-        /// URL to the module archive
+        /// URL to the module archive, without query arguments
         url: String,
+        /// Subdirectory within the archive
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        subdir: Option<String>,
+        /// Query arguments (e.g., `archive`)
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        params: BTreeMap<String, String>,
     },
 
     /// S3 bucket source
@@ -157,6 +244,12 @@ pub enum ModuleSource {
         key: String,
         /// AWS region
         region: Option<String>,
+        /// Subdirectory within the archive
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        subdir: Option<String>,
+        /// Query arguments (e.g., `version`, `archive`)
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        params: BTreeMap<String, String>,
     },
 
     /// GCS bucket source
@@ -165,6 +258,29 @@ pub enum ModuleSource {
         bucket: String,
         /// Object path
         path: String,
+        /// Subdirectory within the archive
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        subdir: Option<String>,
+        /// Query arguments (e.g., `archive`)
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        params: BTreeMap<String, String>,
+    },
+
+    /// OCI registry artifact (OpenTofu `oci://` source)
+    Oci {
+        /// Registry hostname, with port if any (e.g., "ghcr.io")
+        registry: String,
+        /// Repository within the registry (e.g., "acme/modules/vpc")
+        repository: String,
+        /// Tag (e.g., "1.2.0")
+        tag: Option<String>,
+        /// Manifest digest (e.g., "sha256:...")
+        digest: Option<String>,
+        /// Subdirectory within the artifact
+        subdir: Option<String>,
+        /// Other query arguments
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        params: BTreeMap<String, String>,
     },
 
     /// Unknown or unparseable source
@@ -173,6 +289,10 @@ pub enum ModuleSource {
 
 impl ModuleSource {
     /// Returns a canonical identifier for this source.
+    ///
+    /// Identifiers include what selects the content (git ref, hg revision,
+    /// S3 version, OCI tag or digest) but not transport options such as
+    /// `depth` or `sshkey`.
     #[must_use]
     pub fn canonical_id(&self) -> String {
         match self {
@@ -181,12 +301,19 @@ impl ModuleSource {
                 namespace,
                 name,
                 provider,
-            } => format!("{hostname}/{namespace}/{name}/{provider}"),
+                subdir,
+                ..
+            } => {
+                let mut id = format!("{hostname}/{namespace}/{name}/{provider}");
+                push_subdir(&mut id, subdir.as_deref());
+                id
+            }
             Self::Git {
                 host,
                 url,
                 ref_,
                 subdir,
+                ..
             } => {
                 let mut id = host.clone();
                 if let Some(r) = ref_ {
@@ -201,10 +328,172 @@ impl ModuleSource {
                 }
                 id
             }
+            Self::Hg {
+                url, rev, subdir, ..
+            } => {
+                let mut id = format!("hg::{url}");
+                if let Some(r) = rev {
+                    id.push_str(&format!("?rev={r}"));
+                }
+                push_subdir(&mut id, subdir.as_deref());
+                id
+            }
             Self::Local { path } => format!("local://{path}"),
-            Self::Http { url } => url.clone(),
-            Self::S3 { bucket, key, .. } => format!("s3://{bucket}/{key}"),
-            Self::Gcs { bucket, path } => format!("gcs://{bucket}/{path}"),
+            Self::Http {
+                url,
+                subdir,
+                params,
+            } => {
+                let mut id = url.clone();
+                push_subdir(&mut id, subdir.as_deref());
+                if !params.is_empty() {
+                    let query: Vec<String> =
+                        params.iter().map(|(k, v)| format!("{k}={v}")).collect();
+                    id.push_str(&format!("?{}", query.join("&")));
+                }
+                id
+            }
+            Self::S3 {
+                bucket,
+                key,
+                subdir,
+                params,
+                ..
+            } => {
+                let mut id = format!("s3://{bucket}/{key}");
+                if let Some(version) = params.get("version") {
+                    id.push_str(&format!("?version={version}"));
+                }
+                push_subdir(&mut id, subdir.as_deref());
+                id
+            }
+            Self::Gcs {
+                bucket,
+                path,
+                subdir,
+                ..
+            } => {
+                let mut id = format!("gcs://{bucket}/{path}");
+                push_subdir(&mut id, subdir.as_deref());
+                id
+            }
+            Self::Oci {
+                registry,
+                repository,
+                tag,
+                digest,
+                subdir,
+                ..
+            } => {
+                let mut id = format!("oci://{registry}/{repository}");
+                // A digest identifies the artifact even when a tag is also given
+                if let Some(d) = digest {
+                    id.push_str(&format!("@{d}"));
+                } else if let Some(t) = tag {
+                    id.push_str(&format!(":{t}"));
+                }
+                push_subdir(&mut id, subdir.as_deref());
+                id
+            }
+            Self::Unknown(s) => s.clone(),
+        }
+    }
+
+    /// Renders this source as a module `source` string.
+    ///
+    /// The result uses forced getters (`git::`, `hg::`, `s3::`, `gcs::`) and
+    /// parses back to the same source.
+    #[must_use]
+    pub fn to_source_string(&self) -> String {
+        match self {
+            Self::Registry {
+                hostname,
+                namespace,
+                name,
+                provider,
+                subdir,
+                params,
+            } => {
+                let base = if hostname == "registry.terraform.io" {
+                    format!("{namespace}/{name}/{provider}")
+                } else {
+                    format!("{hostname}/{namespace}/{name}/{provider}")
+                };
+                with_subdir_and_query(&base, subdir.as_deref(), std::iter::empty(), params)
+            }
+            Self::Git {
+                url,
+                ref_,
+                subdir,
+                params,
+                ..
+            } => with_subdir_and_query(
+                &format!("git::{url}"),
+                subdir.as_deref(),
+                ref_.iter().map(|r| ("ref", r.as_str())),
+                params,
+            ),
+            Self::Hg {
+                url,
+                rev,
+                subdir,
+                params,
+            } => with_subdir_and_query(
+                &format!("hg::{url}"),
+                subdir.as_deref(),
+                rev.iter().map(|r| ("rev", r.as_str())),
+                params,
+            ),
+            Self::Local { path } => path.clone(),
+            Self::Http {
+                url,
+                subdir,
+                params,
+            } => with_subdir_and_query(url, subdir.as_deref(), std::iter::empty(), params),
+            Self::S3 {
+                bucket,
+                key,
+                region,
+                subdir,
+                params,
+            } => {
+                let endpoint = match region {
+                    Some(region) => format!("s3-{region}.amazonaws.com"),
+                    None => "s3.amazonaws.com".to_string(),
+                };
+                with_subdir_and_query(
+                    &format!("s3::https://{endpoint}/{bucket}/{key}"),
+                    subdir.as_deref(),
+                    std::iter::empty(),
+                    params,
+                )
+            }
+            Self::Gcs {
+                bucket,
+                path,
+                subdir,
+                params,
+            } => with_subdir_and_query(
+                &format!("gcs::https://www.googleapis.com/storage/v1/{bucket}/{path}"),
+                subdir.as_deref(),
+                std::iter::empty(),
+                params,
+            ),
+            Self::Oci {
+                registry,
+                repository,
+                tag,
+                digest,
+                subdir,
+                params,
+            } => with_subdir_and_query(
+                &format!("oci://{registry}/{repository}"),
+                subdir.as_deref(),
+                tag.iter()
+                    .map(|t| ("tag", t.as_str()))
+                    .chain(digest.iter().map(|d| ("digest", d.as_str()))),
+                params,
+            ),
             Self::Unknown(s) => s.clone(),
         }
     }
@@ -220,6 +509,42 @@ impl ModuleSource {
     pub const fn is_registry(&self) -> bool {
         matches!(self, Self::Registry { .. })
     }
+
+    /// Returns true if this is an OCI artifact source.
+    #[must_use]
+    pub const fn is_oci(&self) -> bool {
+        matches!(self, Self::Oci { .. })
+    }
+}
+
+/// Append a non-empty `//subdir` to a canonical identifier.
+fn push_subdir(id: &mut String, subdir: Option<&str>) {
+    if let Some(s) = subdir.filter(|s| !s.is_empty()) {
+        id.push_str(&format!("//{s}"));
+    }
+}
+
+/// Build `base//subdir?query` with URL-encoded query arguments.
+fn with_subdir_and_query<'a>(
+    base: &str,
+    subdir: Option<&str>,
+    leading: impl Iterator<Item = (&'a str, &'a str)>,
+    params: &'a BTreeMap<String, String>,
+) -> String {
+    let mut source = base.to_string();
+    push_subdir(&mut source, subdir);
+
+    let mut query = url::form_urlencoded::Serializer::new(String::new());
+    let mut has_query = false;
+    for (key, value) in leading.chain(params.iter().map(|(k, v)| (k.as_str(), v.as_str()))) {
+        query.append_pair(key, value);
+        has_query = true;
+    }
+    if has_query {
+        source.push('?');
+        source.push_str(&query.finish());
+    }
+    source
 }
 
 /// Represents a Terraform provider requirement.
@@ -269,6 +594,57 @@ impl ProviderRef {
     }
 }
 
+/// A provider version selected in a `.terraform.lock.hcl` file.
+///
+/// # Example HCL
+///
+/// ```hcl
+/// provider "registry.terraform.io/hashicorp/aws" {
+///   version     = "5.31.0"
+///   constraints = ">= 4.0, < 6.0"
+///   hashes      = ["h1:..."]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderLock {
+    /// Fully qualified provider address (e.g., "registry.terraform.io/hashicorp/aws")
+    pub source: String,
+
+    /// Version the lock file selected
+    pub version: String,
+
+    /// Constraints the version was selected against, as recorded
+    pub constraints: Option<String>,
+
+    /// Lock file the entry was read from
+    pub file_path: PathBuf,
+
+    /// Line number in the file
+    pub line_number: usize,
+
+    /// The repository/project the lock file belongs to
+    pub repository: Option<String>,
+}
+
+impl ProviderLock {
+    /// Check if this entry locks the given provider source.
+    ///
+    /// Sources without a hostname are on the public registry, so
+    /// `hashicorp/aws` matches `registry.terraform.io/hashicorp/aws`.
+    #[must_use]
+    pub fn locks(&self, provider_source: &str) -> bool {
+        let normalize = |source: &str| {
+            let source = source.to_ascii_lowercase();
+            if source.split('/').count() == 2 {
+                format!("registry.terraform.io/{source}")
+            } else {
+                source
+            }
+        };
+        normalize(&self.source) == normalize(provider_source)
+    }
+}
+
 /// Represents a version constraint expression.
 ///
 /// Supports Terraform's constraint syntax:
@@ -407,6 +783,251 @@ impl Constraint {
         }
         false
     }
+
+    /// Check if at least one version satisfies both constraints.
+    ///
+    /// Unlike [`Self::has_overlap_with`], this is exact: every range
+    /// including `!=` exclusions is taken into account.
+    #[must_use]
+    pub fn intersects(&self, other: &Self) -> bool {
+        !VersionSet::from(self)
+            .intersect(&VersionSet::from(other))
+            .is_empty()
+    }
+}
+
+/// A set of versions, stored as sorted, disjoint intervals.
+///
+/// Used for exact reasoning about constraints, where a single
+/// min/max pair is not enough (e.g., `!= 1.2.0` or unions of ranges).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct VersionSet {
+    intervals: Vec<Interval>,
+}
+
+/// A contiguous range of versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Interval {
+    lower: Bound<semver::Version>,
+    upper: Bound<semver::Version>,
+}
+
+impl Interval {
+    fn is_empty(&self) -> bool {
+        match (&self.lower, &self.upper) {
+            (Bound::Included(a), Bound::Included(b)) => a > b,
+            (Bound::Included(a) | Bound::Excluded(a), Bound::Excluded(b))
+            | (Bound::Excluded(a), Bound::Included(b)) => a >= b,
+            _ => false,
+        }
+    }
+
+    fn intersect(&self, other: &Self) -> Self {
+        Self {
+            lower: max_lower(&self.lower, &other.lower),
+            upper: min_upper(&self.upper, &other.upper),
+        }
+    }
+}
+
+impl VersionSet {
+    /// Every version.
+    pub(crate) fn all() -> Self {
+        Self {
+            intervals: vec![Interval {
+                lower: Bound::Unbounded,
+                upper: Bound::Unbounded,
+            }],
+        }
+    }
+
+    /// No version at all.
+    pub(crate) fn empty() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    fn from_intervals(intervals: Vec<Interval>) -> Self {
+        let mut intervals: Vec<_> = intervals.into_iter().filter(|i| !i.is_empty()).collect();
+        intervals.sort_by(|a, b| cmp_lower(&a.lower, &b.lower));
+
+        let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if !has_gap(&last.upper, &interval.lower) => {
+                    last.upper = max_upper(&last.upper, &interval.upper);
+                }
+                _ => merged.push(interval),
+            }
+        }
+        Self { intervals: merged }
+    }
+
+    fn from_range(range: &VersionRange) -> Self {
+        use Bound::{Excluded, Included, Unbounded};
+
+        let interval = |lower, upper| Interval { lower, upper };
+        let intervals = match range {
+            VersionRange::Exact(v) => vec![interval(Included(v.clone()), Included(v.clone()))],
+            VersionRange::GreaterThan(v) => vec![interval(Excluded(v.clone()), Unbounded)],
+            VersionRange::GreaterThanOrEqual(v) => vec![interval(Included(v.clone()), Unbounded)],
+            VersionRange::LessThan(v) => vec![interval(Unbounded, Excluded(v.clone()))],
+            VersionRange::LessThanOrEqual(v) => vec![interval(Unbounded, Included(v.clone()))],
+            VersionRange::NotEqual(v) => vec![
+                interval(Unbounded, Excluded(v.clone())),
+                interval(Excluded(v.clone()), Unbounded),
+            ],
+            VersionRange::Pessimistic { version, parts } => {
+                let upper = if *parts >= 2 {
+                    Excluded(pessimistic_upper_bound(version, *parts))
+                } else {
+                    Unbounded
+                };
+                vec![interval(Included(version.clone()), upper)]
+            }
+        };
+        Self::from_intervals(intervals)
+    }
+
+    /// Versions in both sets.
+    pub(crate) fn intersect(&self, other: &Self) -> Self {
+        let intervals = self
+            .intervals
+            .iter()
+            .flat_map(|a| other.intervals.iter().map(|b| a.intersect(b)))
+            .collect();
+        Self::from_intervals(intervals)
+    }
+
+    /// Versions in either set.
+    pub(crate) fn union(&self, other: &Self) -> Self {
+        Self::from_intervals(
+            self.intervals
+                .iter()
+                .chain(&other.intervals)
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Versions not in this set.
+    pub(crate) fn complement(&self) -> Self {
+        let mut intervals = Vec::new();
+        let mut lower = Some(Bound::Unbounded);
+        for interval in &self.intervals {
+            if let (Some(start), Some(end)) = (lower.take(), flip(&interval.lower)) {
+                intervals.push(Interval {
+                    lower: start,
+                    upper: end,
+                });
+            }
+            lower = flip(&interval.upper);
+        }
+        if let Some(start) = lower {
+            intervals.push(Interval {
+                lower: start,
+                upper: Bound::Unbounded,
+            });
+        }
+        Self::from_intervals(intervals)
+    }
+
+    /// Check if the set contains no version.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The lowest version in the set, or `None` if it is empty or has no
+    /// lower bound. An excluded bound is returned as is.
+    pub(crate) fn lowest(&self) -> Option<&semver::Version> {
+        match &self.intervals.first()?.lower {
+            Bound::Included(v) | Bound::Excluded(v) => Some(v),
+            Bound::Unbounded => None,
+        }
+    }
+}
+
+impl From<&Constraint> for VersionSet {
+    fn from(constraint: &Constraint) -> Self {
+        constraint.ranges.iter().fold(Self::all(), |set, range| {
+            set.intersect(&Self::from_range(range))
+        })
+    }
+}
+
+/// Order lower bounds: unbounded first, then `>= v` before `> v`.
+fn cmp_lower(a: &Bound<semver::Version>, b: &Bound<semver::Version>) -> Ordering {
+    match (a, b) {
+        (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+        (Bound::Unbounded, _) => Ordering::Less,
+        (_, Bound::Unbounded) => Ordering::Greater,
+        (Bound::Included(x), Bound::Included(y)) | (Bound::Excluded(x), Bound::Excluded(y)) => {
+            x.cmp(y)
+        }
+        (Bound::Included(x), Bound::Excluded(y)) => x.cmp(y).then(Ordering::Less),
+        (Bound::Excluded(x), Bound::Included(y)) => x.cmp(y).then(Ordering::Greater),
+    }
+}
+
+/// Order upper bounds: `< v` before `<= v`, unbounded last.
+fn cmp_upper(a: &Bound<semver::Version>, b: &Bound<semver::Version>) -> Ordering {
+    match (a, b) {
+        (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+        (Bound::Unbounded, _) => Ordering::Greater,
+        (_, Bound::Unbounded) => Ordering::Less,
+        (Bound::Included(x), Bound::Included(y)) | (Bound::Excluded(x), Bound::Excluded(y)) => {
+            x.cmp(y)
+        }
+        (Bound::Included(x), Bound::Excluded(y)) => x.cmp(y).then(Ordering::Greater),
+        (Bound::Excluded(x), Bound::Included(y)) => x.cmp(y).then(Ordering::Less),
+    }
+}
+
+fn max_lower(a: &Bound<semver::Version>, b: &Bound<semver::Version>) -> Bound<semver::Version> {
+    if cmp_lower(a, b) == Ordering::Less {
+        b
+    } else {
+        a
+    }
+    .clone()
+}
+
+fn min_upper(a: &Bound<semver::Version>, b: &Bound<semver::Version>) -> Bound<semver::Version> {
+    if cmp_upper(a, b) == Ordering::Greater {
+        b
+    } else {
+        a
+    }
+    .clone()
+}
+
+fn max_upper(a: &Bound<semver::Version>, b: &Bound<semver::Version>) -> Bound<semver::Version> {
+    if cmp_upper(a, b) == Ordering::Less {
+        b
+    } else {
+        a
+    }
+    .clone()
+}
+
+/// Check if there are versions between an upper bound and a later lower bound.
+fn has_gap(upper: &Bound<semver::Version>, lower: &Bound<semver::Version>) -> bool {
+    match (upper, lower) {
+        (Bound::Unbounded, _) | (_, Bound::Unbounded) => false,
+        (Bound::Excluded(u), Bound::Excluded(l)) => l >= u,
+        (Bound::Included(u) | Bound::Excluded(u), Bound::Included(l))
+        | (Bound::Included(u), Bound::Excluded(l)) => l > u,
+    }
+}
+
+/// The bound on the other side of a boundary (`< v` becomes `>= v`).
+fn flip(bound: &Bound<semver::Version>) -> Option<Bound<semver::Version>> {
+    match bound {
+        Bound::Included(v) => Some(Bound::Excluded(v.clone())),
+        Bound::Excluded(v) => Some(Bound::Included(v.clone())),
+        Bound::Unbounded => None,
+    }
 }
 
 /// Represents a single version range component.
@@ -581,6 +1202,10 @@ pub enum ReportFormat {
     Text,
     /// Self-contained HTML report
     Html,
+    /// CycloneDX JSON software bill of materials
+    Cyclonedx,
+    /// SPDX JSON software bill of materials
+    Spdx,
 }
 
 /// Graph output format.
@@ -593,6 +1218,34 @@ pub enum GraphFormat {
     Json,
     /// Mermaid diagram format
     Mermaid,
+    /// GraphML with typed attributes (Gephi, yEd, NetworkX)
+    Graphml,
+    /// GEXF with typed attributes (Gephi)
+    Gexf,
+    /// Cypher script that creates the graph in Neo4j
+    Cypher,
+}
+
+/// How to group graph nodes into clusters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+pub enum ClusterBy {
+    /// One cluster per repository
+    Repository,
+    /// Clusters per VCS namespace (organization, group or project), with
+    /// one nested cluster per repository
+    Namespace,
+}
+
+/// Output format of the `impact` and `diff` commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+pub enum QueryFormat {
+    /// Plain text format
+    #[default]
+    Text,
+    /// JSON format
+    Json,
+    /// Markdown tables
+    Markdown,
 }
 
 /// Severity level for findings.
@@ -635,7 +1288,7 @@ pub struct ScanResult {
     pub files_scanned: Vec<PathBuf>,
 
     /// The dependency graph
-    #[serde(skip)]
+    #[serde(default)]
     pub graph: DependencyGraph,
 
     /// Analysis results
@@ -643,6 +1296,18 @@ pub struct ScanResult {
 
     /// Warnings encountered during scanning (e.g., unparseable constraints)
     pub warnings: Vec<ScanWarning>,
+
+    /// Comparison against a baseline file, if one was given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<BaselineResult>,
+
+    /// Provider versions selected by dependency lock files
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locks: Vec<ProviderLock>,
+
+    /// Absolute paths of the local directories that were scanned
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roots: Vec<PathBuf>,
 }
 
 impl ScanResult {
@@ -655,6 +1320,13 @@ impl ScanResult {
         self.graph.merge(other.graph);
         self.analysis.merge(other.analysis);
         self.warnings.extend(other.warnings);
+        if let Some(other_baseline) = other.baseline {
+            let baseline = self.baseline.get_or_insert_with(BaselineResult::default);
+            baseline.known.extend(other_baseline.known);
+            baseline.fixed.extend(other_baseline.fixed);
+        }
+        self.locks.extend(other.locks);
+        self.roots.extend(other.roots);
     }
 
     /// Generate a report in the specified format.
@@ -681,6 +1353,10 @@ pub struct AnalysisResult {
     /// Deprecation results
     pub deprecations: DeprecationResult,
 
+    /// How far each registry reference is behind the latest release
+    #[serde(default)]
+    pub versions: Vec<VersionStatus>,
+
     /// Timestamp of the analysis
     pub timestamp: Option<DateTime<Utc>>,
 }
@@ -689,24 +1365,26 @@ impl AnalysisResult {
     /// Check if there are any error-level findings.
     #[must_use]
     pub fn has_errors(&self) -> bool {
-        self.findings
-            .iter()
+        self.active_findings()
             .any(|f| matches!(f.severity, Severity::Error | Severity::Critical))
     }
 
     /// Check if there are any warning-level findings.
     #[must_use]
     pub fn has_warnings(&self) -> bool {
-        self.findings
-            .iter()
+        self.active_findings()
             .any(|f| matches!(f.severity, Severity::Warning))
     }
 
+    /// Iterate over findings that were not silenced by a suppression.
+    pub fn active_findings(&self) -> impl Iterator<Item = &Finding> {
+        self.findings.iter().filter(|f| !f.is_suppressed())
+    }
+
     /// Get findings filtered by severity.
     #[must_use]
     pub fn findings_by_severity(&self, severity: Severity) -> Vec<&Finding> {
-        self.findings
-            .iter()
+        self.active_findings()
             .filter(|f| f.severity == severity)
             .collect()
     }
@@ -745,6 +1423,123 @@ pub struct Finding {
 
     /// Category of the finding
     pub category: FindingCategory,
+
+    /// Inline suppression that silenced this finding, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suppressed_by: Option<Suppression>,
+
+    /// Resource the finding is about (e.g., `module.vpc`, `provider.aws`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource: Option<String>,
+
+    /// Canonical source of that resource (e.g., `hashicorp/aws`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// Where the rule behind this finding was defined (e.g., an included
+    /// deprecation file)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+}
+
+impl Finding {
+    /// Check if this finding was silenced by an inline suppression.
+    #[must_use]
+    pub fn is_suppressed(&self) -> bool {
+        self.suppressed_by.is_some()
+    }
+}
+
+/// An inline `# monphare:ignore` directive found in a Terraform file.
+///
+/// # Example HCL
+///
+/// ```hcl
+/// # monphare:ignore exact-version reason="pinned for FIPS"
+/// module "vpc" {
+///   source  = "terraform-aws-modules/vpc/aws"
+///   version = "5.1.2"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Suppression {
+    /// Finding codes this directive silences (empty means all codes)
+    pub codes: Vec<String>,
+    /// Justification given with `reason="..."`
+    pub reason: Option<String>,
+    /// File containing the directive
+    pub file: PathBuf,
+    /// Line of the directive itself (1-based)
+    pub line: usize,
+    /// What part of the file the directive covers
+    pub scope: SuppressionScope,
+    /// The repository this directive belongs to
+    pub repository: Option<String>,
+}
+
+impl Suppression {
+    /// Check if this suppression silences the given finding.
+    #[must_use]
+    pub fn matches(&self, finding: &Finding) -> bool {
+        let Some(location) = &finding.location else {
+            return false;
+        };
+        if location.file != self.file || location.repository != self.repository {
+            return false;
+        }
+        if !self.codes.is_empty() && !self.codes.iter().any(|c| c == &finding.code) {
+            return false;
+        }
+        match self.scope {
+            SuppressionScope::File => true,
+            SuppressionScope::Lines { start, end } => (start..=end).contains(&location.line),
+        }
+    }
+}
+
+/// Span covered by a suppression directive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SuppressionScope {
+    /// The whole file (`# monphare:ignore-file`)
+    File,
+    /// A block or attribute spanning these lines (1-based, inclusive)
+    Lines {
+        /// First line of the span
+        start: usize,
+        /// Last line of the span
+        end: usize,
+    },
+}
+
+/// A finding recorded in a baseline file.
+///
+/// Entries identify findings without line numbers so that unrelated edits
+/// to a file don't turn known findings into new ones.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// Finding code (e.g., "missing-version")
+    pub code: String,
+    /// Repository name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    /// File path relative to the repository root
+    pub file: String,
+    /// Resource the finding is about (e.g., `module.vpc`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource: Option<String>,
+    /// Canonical source of that resource
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// Outcome of comparing a scan against a baseline file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BaselineResult {
+    /// Findings already present in the baseline (excluded from `analysis`)
+    pub known: Vec<Finding>,
+    /// Baseline entries that no longer match any finding
+    pub fixed: Vec<BaselineEntry>,
 }
 
 /// Location in a file.
@@ -773,6 +1568,35 @@ impl std::fmt::Display for Location {
     }
 }
 
+/// How a registry module or provider reference compares to the releases
+/// published in its registry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionStatus {
+    /// Resource the status is about (e.g., `module.vpc`, `provider.aws`)
+    pub resource: String,
+
+    /// Registry address (e.g., `registry.terraform.io/hashicorp/aws`)
+    pub source: String,
+
+    /// Version constraint as written, if any
+    pub constraint: Option<String>,
+
+    /// Newest release the constraint allows
+    pub newest_allowed: Option<String>,
+
+    /// When that release was published, if known
+    pub newest_allowed_published: Option<DateTime<Utc>>,
+
+    /// Newest stable release
+    pub latest: String,
+
+    /// Number of stable releases newer than `newest_allowed`
+    pub releases_behind: usize,
+
+    /// Where the reference is
+    pub location: Location,
+}
+
 /// Category of findings.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FindingCategory {
@@ -790,6 +1614,8 @@ pub enum FindingCategory {
     BestPractice,
     /// Configuration issue
     Configuration,
+    /// Problem in the dependency graph (e.g., a cycle)
+    Dependency,
 }
 
 impl std::fmt::Display for FindingCategory {
@@ -802,6 +1628,7 @@ impl std::fmt::Display for FindingCategory {
             Self::Security => write!(f, "Security"),
             Self::BestPractice => write!(f, "Best Practice"),
             Self::Configuration => write!(f, "Configuration"),
+            Self::Dependency => write!(f, "Dependency"),
         }
     }
 }
@@ -823,6 +1650,9 @@ pub struct AnalysisSummary {
     pub findings_by_severity: HashMap<String, usize>,
     /// Counts by category
     pub findings_by_category: HashMap<String, usize>,
+    /// Findings silenced by inline suppressions (not counted above)
+    #[serde(default)]
+    pub suppressed: usize,
 }
 
 /// Deprecation results. Used to track deprecated runtimes, modules, and providers.
@@ -845,6 +1675,88 @@ pub struct DeprecationResult {
 
     /// Number of unique provider sources
     pub unique_provider_sources: HashSet<String>,
+
+    /// Every matched deprecation rule with its retirement phase
+    #[serde(default)]
+    pub notices: Vec<DeprecationNotice>,
+}
+
+impl DeprecationResult {
+    /// Notices with an enforcement date, ordered by that date.
+    ///
+    /// This is the retirement timeline shown in reports.
+    #[must_use]
+    pub fn timeline(&self) -> Vec<&DeprecationNotice> {
+        let mut timeline: Vec<_> = self
+            .notices
+            .iter()
+            .filter(|n| n.enforce_from.is_some())
+            .collect();
+        timeline.sort_by(|a, b| {
+            a.enforce_from
+                .cmp(&b.enforce_from)
+                .then_with(|| a.resource.cmp(&b.resource))
+        });
+        timeline
+    }
+}
+
+/// Where a deprecation rule is in its retirement schedule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeprecationPhase {
+    /// Announced, reported as info
+    Announced,
+    /// Warning period before enforcement
+    Warning,
+    /// Enforced at the configured severity
+    Enforced,
+}
+
+impl std::fmt::Display for DeprecationPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Announced => write!(f, "announced"),
+            Self::Warning => write!(f, "warning"),
+            Self::Enforced => write!(f, "enforced"),
+        }
+    }
+}
+
+/// A deprecation rule matched by a module, provider or runtime.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeprecationNotice {
+    /// Resource address (e.g. "module.vpc", "provider.aws", "runtime.terraform")
+    pub resource: String,
+    /// Rule key the resource matched (source or runtime name)
+    pub source: String,
+    /// Deprecated version or git ref from the rule
+    pub matched: String,
+    /// Reason for the deprecation
+    pub reason: String,
+    /// Suggested replacement
+    pub replacement: String,
+    /// Severity once enforced
+    pub severity: Severity,
+    /// Current phase
+    pub phase: DeprecationPhase,
+    /// Announcement date
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub announced: Option<chrono::NaiveDate>,
+    /// Start of the warning period
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warn_from: Option<chrono::NaiveDate>,
+    /// Enforcement date
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enforce_from: Option<chrono::NaiveDate>,
+    /// Days until enforcement (negative once enforced)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub days_until_enforcement: Option<i64>,
+    /// Where the rule was defined
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+    /// Where the resource is declared
+    pub location: Location,
 }
 
 impl AnalysisSummary {
@@ -860,6 +1772,7 @@ impl AnalysisSummary {
         for (k, v) in other.findings_by_category {
             *self.findings_by_category.entry(k).or_insert(0) += v;
         }
+        self.suppressed += other.suppressed;
     }
 }
 
@@ -1015,6 +1928,10 @@ pub struct ParsedHcl {
     pub runtimes: Vec<RuntimeRef>,
     /// Warnings encountered during parsing (e.g., unparseable constraints)
     pub warnings: Vec<ScanWarning>,
+    /// Inline suppression directives found in this file
+    pub suppressions: Vec<Suppression>,
+    /// Provider versions selected by dependency lock files
+    pub locks: Vec<ProviderLock>,
 }
 
 impl ParsedHcl {
@@ -1025,6 +1942,8 @@ impl ParsedHcl {
         self.files.extend(other.files);
         self.runtimes.extend(other.runtimes);
         self.warnings.extend(other.warnings);
+        self.suppressions.extend(other.suppressions);
+        self.locks.extend(other.locks);
     }
 }
 
@@ -1082,6 +2001,34 @@ mod tests {
         assert!(!c.is_satisfied_by(&semver::Version::new(2, 0, 0)));
     }
 
+    #[test]
+    fn test_constraint_intersects_exactly() {
+        let c = |s| Constraint::parse(s).unwrap();
+
+        assert!(c("~> 4.0").intersects(&c("< 4.2.0")));
+        assert!(!c("~> 4.0").intersects(&c(">= 5.0.0")));
+        // Touching bounds only overlap when both are inclusive
+        assert!(c(">= 1.0.0").intersects(&c("<= 1.0.0")));
+        assert!(!c("> 1.0.0").intersects(&c("<= 1.0.0")));
+        // Exclusions are honoured
+        assert!(!c("1.2.0").intersects(&c("!= 1.2.0")));
+        assert!(c(">= 1.2.0, <= 1.3.0").intersects(&c("!= 1.2.0")));
+        assert!(c("").intersects(&c("1.0.0")));
+    }
+
+    #[test]
+    fn test_version_set_complement() {
+        let set = VersionSet::from(&Constraint::parse(">= 1.0.0, < 2.0.0").unwrap());
+        let outside = set.complement();
+
+        assert!(set.intersect(&outside).is_empty());
+        assert_eq!(set.union(&outside), VersionSet::all());
+        assert_eq!(VersionSet::all().complement(), VersionSet::empty());
+        assert!(!outside
+            .intersect(&VersionSet::from(&Constraint::parse("2.0.0").unwrap()))
+            .is_empty());
+    }
+
     #[test]
     fn test_constraint_conflict_detection() {
         let c1 = Constraint::parse(">= 5.0.0").unwrap();
@@ -1110,6 +2057,8 @@ mod tests {
             namespace: "hashicorp".to_string(),
             name: "consul".to_string(),
             provider: "aws".to_string(),
+            subdir: None,
+            params: Default::default(),
         };
         assert_eq!(
             source.canonical_id(),
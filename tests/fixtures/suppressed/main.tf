@@ -0,0 +1,22 @@
+# monphare:ignore-file no-upper-bound reason="legacy stack, frozen until decommission"
+
+terraform {
+  required_providers {
+    aws = {
+      source  = "hashicorp/aws"
+      version = ">= 5.0"
+    }
+  }
+}
+
+# monphare:ignore exact-version reason="pinned for FIPS"
+module "vpc" {
+  source  = "terraform-aws-modules/vpc/aws"
+  version = "5.1.2"
+}
+
+# monphare:ignore missing-version reason="version added since"
+module "eks" {
+  source  = "terraform-aws-modules/eks/aws"
+  version = "~> 19.0"
+}
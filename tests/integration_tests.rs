@@ -136,6 +136,414 @@ mod scanner_tests {
         // Should have modules from both directories
         assert!(result.modules.len() > 2);
     }
+
+    #[tokio::test]
+    async fn test_inline_suppressions() {
+        let config = Config::default();
+        let scanner = Scanner::new(config);
+
+        let fixture_path = fixtures_path().join("suppressed");
+        let result = scanner.scan_paths(vec![fixture_path]).await.unwrap();
+        let findings = &result.analysis.findings;
+
+        let vpc = result.modules.iter().find(|m| m.name == "vpc").unwrap();
+        assert_eq!(vpc.line_number, 13);
+
+        let exact = findings
+            .iter()
+            .find(|f| f.code == "exact-version")
+            .expect("exact-version finding should still be reported");
+        let suppression = exact.suppressed_by.as_ref().unwrap();
+        assert_eq!(suppression.reason.as_deref(), Some("pinned for FIPS"));
+
+        assert!(findings
+            .iter()
+            .filter(|f| f.code == "no-upper-bound")
+            .all(|f| f.is_suppressed()));
+
+        let stale: Vec<_> = findings
+            .iter()
+            .filter(|f| f.code == "stale-suppression")
+            .collect();
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].location.as_ref().unwrap().line, 18);
+
+        assert!(!result.analysis.has_errors());
+        assert_eq!(
+            result.analysis.summary.suppressed,
+            findings.iter().filter(|f| f.is_suppressed()).count()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_baseline_round_trip() {
+        use monphare::analyzer::Baseline;
+
+        let scanner = Scanner::new(Config::default());
+        let fixture_path = fixtures_path().join("risky");
+
+        let first = scanner
+            .scan_paths(vec![fixture_path.clone()])
+            .await
+            .unwrap();
+        assert!(!first.analysis.findings.is_empty());
+
+        let dir = tempfile::tempdir().unwrap();
+        let baseline_path = dir.path().join("baseline.json");
+        Baseline::from_findings(&first.analysis.findings, &first.roots)
+            .save(&baseline_path)
+            .unwrap();
+
+        let mut second = scanner.scan_paths(vec![fixture_path]).await.unwrap();
+        Baseline::load(&baseline_path).unwrap().apply(&mut second);
+
+        let baseline = second.baseline.as_ref().unwrap();
+        assert!(second.analysis.findings.is_empty());
+        assert!(!second.analysis.has_errors());
+        assert!(!second.analysis.has_warnings());
+        assert_eq!(baseline.known.len(), first.analysis.findings.len());
+        assert!(baseline.fixed.is_empty());
+    }
+
+    #[test]
+    fn test_baseline_matches_across_working_directories() {
+        use std::process::Command;
+
+        let dir = tempfile::tempdir().unwrap();
+        let baseline_path = dir.path().join("baseline.json");
+        let monphare = env!("CARGO_BIN_EXE_monphare");
+
+        let write = Command::new(monphare)
+            .current_dir(fixtures_path())
+            .args(["scan", "risky", "--baseline"])
+            .arg(&baseline_path)
+            .arg("--write-baseline")
+            .output()
+            .unwrap();
+        assert!(write.status.success(), "{write:?}");
+
+        let compare = Command::new(monphare)
+            .current_dir(fixtures_path().join("risky"))
+            .args(["scan", ".", "--format", "json", "--baseline"])
+            .arg(&baseline_path)
+            .output()
+            .unwrap();
+        let report: serde_json::Value = serde_json::from_slice(&compare.stdout).unwrap();
+
+        assert!(report["baseline"]["known"].as_u64().unwrap() > 0);
+        assert_eq!(report["baseline"]["fixed"].as_u64(), Some(0));
+        assert!(compare.status.success(), "{compare:?}");
+    }
+
+    #[tokio::test]
+    async fn test_included_deprecation_rules() {
+        use monphare::git::GitClient;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("shared.yaml"),
+            r#"
+modules:
+  "terraform-aws-modules/eks/aws":
+    - version: ">= 19.0.0, < 20.0.0"
+      reason: "EKS module v19 is retired"
+      severity: error
+      replacement: ">= 20.0.0"
+"#,
+        )
+        .unwrap();
+        let config_path = dir.path().join("monphare.yaml");
+        let mut config =
+            Config::from_yaml("deprecations:\n  include:\n    - path: shared.yaml\n").unwrap();
+
+        let git_client = GitClient::new(config.clone());
+        let warnings =
+            monphare::rule_sources::resolve_includes(&mut config, Some(&config_path), &git_client)
+                .await
+                .unwrap();
+        assert!(warnings.is_empty());
+
+        let scanner = Scanner::new(config);
+        let result = scanner
+            .scan_paths(vec![fixtures_path().join("simple")])
+            .await
+            .unwrap();
+
+        let finding = result
+            .analysis
+            .findings
+            .iter()
+            .find(|f| f.code == "deprecated-module")
+            .expect("deprecated EKS module should be reported");
+        assert!(finding.origin.as_deref().unwrap().ends_with("shared.yaml"));
+    }
+
+    #[test]
+    fn test_diff_uses_included_rules_and_advisories() {
+        use std::process::Command;
+
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().join("repo");
+        std::fs::create_dir(&repo).unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .current_dir(&repo)
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {args:?}");
+        };
+        git(&["init", "-q"]);
+        std::fs::write(repo.join("main.tf"), "# empty\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "empty"]);
+        std::fs::copy(fixtures_path().join("simple/main.tf"), repo.join("main.tf")).unwrap();
+        git(&["commit", "-q", "-am", "modules"]);
+
+        std::fs::write(
+            dir.path().join("shared.yaml"),
+            r#"
+modules:
+  "terraform-aws-modules/eks/aws":
+    - version: ">= 19.0.0, < 20.0.0"
+      reason: "EKS module v19 is retired"
+      severity: error
+      replacement: ">= 20.0.0"
+"#,
+        )
+        .unwrap();
+        let config_path = dir.path().join("monphare.yaml");
+        std::fs::write(
+            &config_path,
+            format!(
+                "deprecations:\n  include:\n    - path: shared.yaml\nadvisories:\n  path: {}\n",
+                fixtures_path().join("advisories").display()
+            ),
+        )
+        .unwrap();
+
+        let output = Command::new(env!("CARGO_BIN_EXE_monphare"))
+            .arg("--config")
+            .arg(&config_path)
+            .args(["diff", "HEAD~1", "HEAD", "--format", "json", "--path"])
+            .arg(&repo)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+        let diff: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let introduced: Vec<&str> = diff["introduced"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|f| f["code"].as_str())
+            .collect();
+
+        assert!(introduced.contains(&"deprecated-module"), "{introduced:?}");
+        assert!(introduced.contains(&"security-advisory"), "{introduced:?}");
+    }
+
+    /// Create a git repository at `repo` with `files` committed.
+    fn git_repo(repo: &std::path::Path, files: &[(&str, &str)]) {
+        for (path, content) in files {
+            let path = repo.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        for args in [
+            &["init", "-q"][..],
+            &["add", "."],
+            &["commit", "-q", "-m", "initial"],
+        ] {
+            let status = std::process::Command::new("git")
+                .current_dir(repo)
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {args:?}");
+        }
+    }
+
+    const VPC: &str = "module \"vpc\" {\n  source  = \"terraform-aws-modules/vpc/aws\"\n  version = \"~> 5.0\"\n}\n";
+    const VPC_NO_VERSION: &str =
+        "module \"vpc\" {\n  source  = \"terraform-aws-modules/vpc/aws\"\n}\n";
+
+    #[tokio::test]
+    async fn test_changed_since_parses_affected_directories() {
+        use monphare::git::ChangeSet;
+
+        let dir = tempfile::tempdir().unwrap();
+        let repo = &dir.path().join("repo");
+        git_repo(
+            repo,
+            &[
+                (
+                    "envs/prod/main.tf",
+                    "module \"network\" {\n  source = \"../../modules/network\"\n}\n",
+                ),
+                ("modules/network/main.tf", VPC),
+                ("modules/dns/main.tf", "# dns\n"),
+                (
+                    "envs/dev/main.tf",
+                    "module \"eks\" {\n  source = \"terraform-aws-modules/eks/aws\"\n}\n",
+                ),
+            ],
+        );
+        std::fs::write(
+            repo.join("modules/network/main.tf"),
+            format!("{VPC_NO_VERSION}module \"dns\" {{\n  source = \"../dns\"\n}}\n"),
+        )
+        .unwrap();
+
+        let changes = ChangeSet::since(repo, "HEAD").unwrap();
+        let result = Scanner::new(Config::default())
+            .with_changes(changes)
+            .scan_paths(vec![repo.to_path_buf()])
+            .await
+            .unwrap();
+
+        // The changed module, its caller and the module it calls; not envs/dev
+        let mut scanned: Vec<String> = result
+            .files_scanned
+            .iter()
+            .map(|f| {
+                f.strip_prefix(repo)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+        scanned.sort();
+        assert_eq!(
+            scanned,
+            [
+                "envs/prod/main.tf",
+                "modules/dns/main.tf",
+                "modules/network/main.tf"
+            ]
+        );
+        let findings: Vec<_> = result
+            .analysis
+            .findings
+            .iter()
+            .map(|f| (f.code.as_str(), f.resource.as_deref().unwrap_or_default()))
+            .collect();
+        assert_eq!(findings, [("missing-version", "module.vpc")]);
+    }
+
+    #[test]
+    fn test_changed_since_covers_every_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let (first, second) = (dir.path().join("first"), dir.path().join("second"));
+        for repo in [&first, &second] {
+            git_repo(repo, &[("main.tf", VPC)]);
+            std::fs::write(repo.join("main.tf"), VPC_NO_VERSION).unwrap();
+        }
+
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_monphare"))
+            .args(["scan", "--changed-since", "HEAD", "--format", "json"])
+            .arg(&first)
+            .arg(&second)
+            .output()
+            .unwrap();
+        let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let mut repositories: Vec<&str> = report["findings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|r| r["repository"].as_str())
+            .collect();
+        repositories.sort_unstable();
+
+        assert_eq!(repositories, ["first", "second"], "{output:?}");
+    }
+
+    #[tokio::test]
+    async fn test_security_advisories() {
+        use monphare::analyzer::AdvisoryDatabase;
+        use monphare::types::FindingCategory;
+
+        let database = AdvisoryDatabase::load_dir(&fixtures_path().join("advisories")).unwrap();
+        assert_eq!(database.len(), 2);
+
+        let scanner = Scanner::new(Config::default()).with_advisories(database);
+        let result = scanner
+            .scan_paths(vec![fixtures_path().join("simple")])
+            .await
+            .unwrap();
+
+        let security: Vec<_> = result
+            .analysis
+            .findings
+            .iter()
+            .filter(|f| f.category == FindingCategory::Security)
+            .collect();
+        // vpc "~> 5.0" allows 5.0.x; random "~> 3.0" is entirely patched
+        assert_eq!(security.len(), 1);
+        assert_eq!(security[0].resource.as_deref(), Some("module.vpc"));
+        assert!(security[0].message.contains("TFSEC-2024-0001"));
+        assert!(result.analysis.has_errors());
+    }
+
+    #[tokio::test]
+    async fn test_outdated_registry_versions() {
+        use monphare::config::RegistryOptions;
+        use monphare::registry::RegistryClient;
+        use serde_json::json;
+        use wiremock::{matchers::*, Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/modules/terraform-aws-modules/vpc/aws/versions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "modules": [{
+                    "versions": [{"version": "5.0.0"}, {"version": "5.1.0"}, {"version": "6.0.0"}]
+                }]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/modules/terraform-aws-modules/vpc/aws/5.1.0"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"published_at": "2020-01-10T12:00:00Z"})),
+            )
+            .mount(&server)
+            .await;
+
+        let cache_dir = tempfile::tempdir().unwrap();
+        let options = RegistryOptions {
+            enabled: true,
+            cache_dir: Some(cache_dir.path().display().to_string()),
+            ..RegistryOptions::default()
+        };
+        let client = RegistryClient::new(&options).with_base_url(server.uri());
+        let scanner = Scanner::new(Config::default()).with_registry(client);
+        let result = scanner
+            .scan_paths(vec![fixtures_path().join("simple")])
+            .await
+            .unwrap();
+
+        let codes: Vec<_> = result
+            .analysis
+            .findings
+            .iter()
+            .filter(|f| f.resource.as_deref() == Some("module.vpc"))
+            .map(|f| f.code.as_str())
+            .collect();
+        assert!(codes.contains(&"outdated-major"));
+        assert!(codes.contains(&"outdated-version"));
+
+        let vpc = result
+            .analysis
+            .versions
+            .iter()
+            .find(|s| s.resource == "module.vpc")
+            .unwrap();
+        assert_eq!(vpc.newest_allowed.as_deref(), Some("5.1.0"));
+        assert_eq!(vpc.releases_behind, 1);
+    }
 }
 
 mod reporter_tests {
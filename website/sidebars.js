@@ -29,6 +29,8 @@ const sidebars = {
       items: [
         'commands/scan',
         'commands/graph',
+        'commands/impact',
+        'commands/diff',
         'commands/init',
         'commands/validate',
       ],